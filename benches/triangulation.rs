@@ -0,0 +1,207 @@
+//! Compares the insertion strategies on the same inputs, and measures locating points and cutting holes on their own.
+//!
+//! Run with `cargo bench --features test-util`. Before the timings of every input, the work counters of the
+//! triangulation are printed, so that a regression in flips or walk steps shows even when the timings are noisy.
+use std::hint::black_box;
+
+use constrained_denaulay_triangulation::{
+    test_util::random_points, triangulate_into, triangulate_with_options, InsertionAlgorithm,
+    InsertionOrder, Triangle, TriangulateOptions, TriangulationAlgorithm, Vector,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// The inputs of the benchmarks, with their names.
+fn corpora() -> Vec<(String, Vec<Vector>)> {
+    let mut corpora: Vec<(String, Vec<Vector>)> = [1_000, 10_000, 100_000]
+        .into_iter()
+        .map(|n| (format!("uniform {n}"), random_points(n, 1)))
+        .collect();
+    corpora.push(("grid 100x100".to_string(), grid(100)));
+    corpora.push(("spiral 10000".to_string(), spiral(10_000)));
+    corpora
+}
+
+/// The corners of a square grid with `side` points per side, where many points are on a common circle.
+fn grid(side: usize) -> Vec<Vector> {
+    (0..side * side)
+        .map(|i| Vector::new((i % side) as f32, (i / side) as f32))
+        .collect()
+}
+
+/// Points along a widening spiral, which are inserted in a bad order by the spatial sort.
+fn spiral(n: usize) -> Vec<Vector> {
+    (0..n)
+        .map(|i| {
+            let angle = i as f32 * 0.05;
+            Vector::from_polar(1. + angle, angle)
+        })
+        .collect()
+}
+
+/// A square from 0 to `side` with a small counter-clockwise square hole in every unit cell but the outer ring.
+fn square_with_holes(side: usize) -> (Vec<Vector>, Vec<Vec<Vector>>) {
+    let outline = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)]
+        .iter()
+        .map(|corner| Vector::from(corner) * side as f32)
+        .collect();
+    let holes = (1..side - 1)
+        .flat_map(|row| (1..side - 1).map(move |column| (row, column)))
+        .map(|(row, column)| {
+            let center = Vector::new(column as f32 + 0.5, row as f32 + 0.5);
+            [(-0.2, -0.2), (0.2, -0.2), (0.2, 0.2), (-0.2, 0.2)]
+                .iter()
+                .map(|offset| center + Vector::from(offset))
+                .collect()
+        })
+        .collect();
+    (outline, holes)
+}
+
+/// The strategies to build the delaunay triangulation of the input points, with their names.
+fn strategies() -> Vec<(&'static str, TriangulateOptions)> {
+    vec![
+        ("spatial", TriangulateOptions::new()),
+        (
+            "brio",
+            TriangulateOptions::new().with_insertion_order(InsertionOrder::Brio { seed: 1 }),
+        ),
+        (
+            "bowyer-watson",
+            TriangulateOptions::new().with_insertion_algorithm(InsertionAlgorithm::BowyerWatson),
+        ),
+        (
+            "divide-and-conquer",
+            TriangulateOptions::new().with_algorithm(TriangulationAlgorithm::DivideAndConquer),
+        ),
+    ]
+}
+
+/// Prints the work counters of a triangulation, which do not depend on the machine.
+fn report_counters(
+    name: &str,
+    points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) {
+    let result = triangulate_with_options(points, holes, options).expect("the inputs are valid");
+    let statistics = result.statistics;
+    println!(
+        "{name}: {} triangles, {} walk steps, {} flips, {} cavity triangles, {} orientation and {} circumcircle fallbacks",
+        result.len(),
+        statistics.walk_steps,
+        statistics.flips,
+        statistics.cavity_triangles,
+        statistics.orientation_fallbacks,
+        statistics.circumcircle_fallbacks,
+    );
+}
+
+fn bench_triangulate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("triangulate");
+    group.sample_size(10);
+    // the output buffer is reused, so that the timings are not dominated by growing it
+    let mut triangles: Vec<Triangle> = Vec::new();
+    for (corpus, points) in corpora() {
+        group.throughput(Throughput::Elements(points.len() as u64));
+        for (strategy, options) in strategies() {
+            let name = format!("{strategy}/{corpus}");
+            report_counters(&name, &points, None, &options);
+            group.bench_function(BenchmarkId::new(strategy, &corpus), |b| {
+                b.iter(|| {
+                    triangulate_into(black_box(&points), None, &options, &mut triangles)
+                        .expect("the inputs are valid");
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_refinement(c: &mut Criterion) {
+    let mut group = c.benchmark_group("refinement");
+    group.sample_size(10);
+    let square = grid(2);
+    let mut triangles: Vec<Triangle> = Vec::new();
+    for maximum_triangle_area in [1e-3, 1e-4] {
+        let options = TriangulateOptions::new().with_maximum_triangle_area(maximum_triangle_area);
+        let name = format!("maximum area {maximum_triangle_area}");
+        report_counters(&name, &square, None, &options);
+        group.bench_function(&name, |b| {
+            b.iter(|| {
+                triangulate_into(black_box(&square), None, &options, &mut triangles)
+                    .expect("the inputs are valid");
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_holes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("holes");
+    group.sample_size(10);
+    let mut triangles: Vec<Triangle> = Vec::new();
+    for side in [10, 30] {
+        let (outline, holes) = square_with_holes(side);
+        // the hole vertices as plain points, so that the difference is the cost of cutting the holes
+        let points: Vec<Vector> = outline
+            .iter()
+            .chain(holes.iter().flatten())
+            .copied()
+            .collect();
+        let options = TriangulateOptions::new();
+        let corpus = format!("{} holes", holes.len());
+        report_counters(&format!("with {corpus}"), &outline, Some(&holes), &options);
+        group.bench_function(BenchmarkId::new("points only", &corpus), |b| {
+            b.iter(|| {
+                triangulate_into(black_box(&points), None, &options, &mut triangles)
+                    .expect("the inputs are valid");
+            })
+        });
+        group.bench_function(BenchmarkId::new("with holes", &corpus), |b| {
+            b.iter(|| {
+                triangulate_into(black_box(&outline), Some(&holes), &options, &mut triangles)
+                    .expect("the inputs are valid");
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_locate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("locate");
+    let points = random_points(10_000, 1);
+    let result = triangulate_with_options(&points, None, &TriangulateOptions::new())
+        .expect("the inputs are valid");
+    let view = result.view();
+    let queries = random_points(1_000, 2);
+    group.throughput(Throughput::Elements(queries.len() as u64));
+    group.bench_function("without hint", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(view.locate(*query, None));
+            }
+        })
+    });
+    // nearby queries after each other, like tracing a path through the mesh
+    let mut path = queries.clone();
+    path.sort_by(|a, b| a.x.total_cmp(&b.x));
+    group.bench_function("with the previous triangle as hint", |b| {
+        b.iter(|| {
+            let mut hint = None;
+            for query in &path {
+                let triangle = view.locate(*query, hint);
+                hint = black_box(triangle).or(hint);
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_triangulate,
+    bench_refinement,
+    bench_holes,
+    bench_locate
+);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+//! Triangulates a square with a hole in the background, while the vertices of the hole are dragged with the mouse.
+//!
+//! Run with `cargo run --example drag_holes --features bevy-example`.
+use bevy::prelude::*;
+use constrained_denaulay_triangulation::{
+    TriangulateOptions, TriangulationPlugin, TriangulationReady, TriangulationRequest, Vector,
+};
+
+/// How close to a hole vertex the cursor has to be to grab it.
+const GRAB_DISTANCE: f32 = 15.;
+
+/// The index of the hole vertex that is being dragged.
+#[derive(Resource, Default)]
+struct Dragged(Option<usize>);
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TriangulationPlugin))
+        .init_resource::<Dragged>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, (drag_hole_vertices, draw_triangles))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands.spawn(Camera2d);
+
+    let square = [(-300., -300.), (300., -300.), (300., 300.), (-300., 300.)];
+    let hole = [(-100., -100.), (100., -100.), (100., 100.), (-100., 100.)];
+    commands.spawn((
+        TriangulationRequest {
+            points: square.iter().map(Vector::from).collect(),
+            holes: vec![hole.iter().map(Vector::from).collect()],
+            options: TriangulateOptions::new().with_maximum_triangle_area(2000.),
+        },
+        MeshMaterial2d(materials.add(Color::srgb(0.2, 0.3, 0.5))),
+    ));
+}
+
+fn drag_hole_vertices(
+    mut dragged: ResMut<Dragged>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut requests: Query<&mut TriangulationRequest>,
+    mut gizmos: Gizmos,
+) {
+    let (camera, camera_transform) = *camera;
+    let cursor = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok());
+
+    for mut request in &mut requests {
+        let hole = &request.holes[0];
+        for vertex in hole {
+            gizmos.circle_2d(Vec2::new(vertex.x, vertex.y), 6., Color::WHITE);
+        }
+        let Some(cursor) = cursor else {
+            continue;
+        };
+
+        if buttons.just_pressed(MouseButton::Left) {
+            dragged.0 = hole
+                .iter()
+                .position(|vertex| Vec2::new(vertex.x, vertex.y).distance(cursor) < GRAB_DISTANCE);
+        }
+        if buttons.just_released(MouseButton::Left) {
+            dragged.0 = None;
+        }
+        // Changing the request starts a new triangulation and cancels the running one
+        if let Some(index) = dragged.0 {
+            request.holes[0][index] = Vector::new(cursor.x, cursor.y);
+        }
+    }
+}
+
+fn draw_triangles(results: Query<&TriangulationReady>, mut gizmos: Gizmos) {
+    for TriangulationReady(result) in &results {
+        for triangle in result.to_triangles() {
+            let corners = [0, 1, 2, 0].map(|i| Vec2::new(triangle.p(i).x, triangle.p(i).y));
+            gizmos.linestrip_2d(corners, Color::srgb(0.8, 0.8, 0.8));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Triangulates the example of the documentation and writes it as a legacy VTK file, to be opened in ParaView.
+//!
+//! Run with `cargo run --example export_vtk -- triangulation.vtk`.
+use std::{fs::File, io::BufWriter};
+
+use constrained_denaulay_triangulation::{
+    export::to_vtk, triangulate_with_options, TriangulateOptions, Vector,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "triangulation.vtk".to_string());
+
+    let input_points: Vec<Vector> = [
+        (0., 7.),
+        (-5., 5.),
+        (5., 5.),
+        (-1., 3.),
+        (3., 1.),
+        (-4., -1.),
+        (1., -2.),
+        (-6., -4.),
+        (5., -4.),
+    ]
+    .iter()
+    .map(Vector::from)
+    .collect();
+    let hole: Vec<Vector> = [(-4., 4.), (0., -2.), (4., 4.)]
+        .iter()
+        .map(Vector::from)
+        .collect();
+    let result = triangulate_with_options(&input_points, Some(&[hole]), &TriangulateOptions::new())
+        .map_err(|error| format!("triangulation failed: {error:?}"))?;
+
+    to_vtk(&result, BufWriter::new(File::create(&path)?))?;
+    println!("wrote {} triangles to {path}", result.triangles.len());
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Triangulates a `.poly` file of Shewchuk's Triangle, with its segments and holes, and writes the mesh as `.node` and `.ele` files
+//! next to it, so that the output can be compared with Triangle's or shown with its viewer.
+//!
+//! Run with `cargo run --example triangulate_poly -- path/to/file.poly`.
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+use constrained_denaulay_triangulation::{
+    io::triangle_fmt::{read_poly, write_ele, write_node},
+    triangulate_pslg,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(path) = std::env::args().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: triangulate_poly <file.poly>");
+        std::process::exit(2);
+    };
+    let (points, segments, holes) = read_poly(BufReader::new(File::open(&path)?))?;
+    let result = triangulate_pslg(&points, &segments, Some(&holes))
+        .map_err(|error| format!("triangulation failed: {error:?}"))?;
+    write_node(
+        BufWriter::new(File::create(path.with_extension("node"))?),
+        &result,
+    )?;
+    write_ele(
+        BufWriter::new(File::create(path.with_extension("ele"))?),
+        &result,
+    )?;
+    println!(
+        "wrote {} vertices and {} triangles",
+        result.vertices.len(),
+        result.triangles.len()
+    );
+    Ok(())
+}
@@ -22,9 +22,8 @@ fn main() {
     bighole.push(Vector::new(0., -2.) * 10.);
     bighole.push(Vector::new(4., 4.) * 10.);
     holes.push(bighole);
-    let input_hole = Some(&mut holes);
 
-    let a = match triangulate(&mut input_points, input_hole, None) {
+    let a = match triangulate(&input_points, Some(&holes), None) {
         Ok(result) => result,
         Err(err) => panic!("triangulation failed!{:?}", err),
     };
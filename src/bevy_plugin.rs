@@ -0,0 +1,238 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{Assets, RenderAssetUsages},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Changed,
+        schedule::IntoScheduleConfigs,
+        system::{Commands, Query, ResMut},
+    },
+    log::warn,
+    render::mesh::{Indices, Mesh, Mesh2d, PrimitiveTopology},
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+
+use crate::{
+    data_structures::{
+        error::CustomError, triangulate_options::TriangulateOptions,
+        triangulation_result::TriangulationResult, vector::Vector,
+    },
+    triangulate_with_options,
+};
+
+/// Triangulates the [`TriangulationRequest`]s of entities on the [`AsyncComputeTaskPool`], off the main thread.
+///
+/// Once a triangulation is done, the entity gets a [`TriangulationReady`] with the result and a [`Mesh2d`] of it.
+/// Changing the request starts a new triangulation and cancels the one still running.
+/// Despawning the entity or removing the request cancels it as well.
+/// ```no_run
+/// use bevy::prelude::*;
+/// use constrained_denaulay_triangulation::{TriangulationPlugin, TriangulationRequest, Vector};
+///
+/// fn setup(mut commands: Commands) {
+///     let square = [(0., 0.), (100., 0.), (100., 100.), (0., 100.)];
+///     commands.spawn(TriangulationRequest {
+///         points: square.iter().map(Vector::from).collect(),
+///         ..Default::default()
+///     });
+/// }
+///
+/// App::new()
+///     .add_plugins((MinimalPlugins, AssetPlugin::default(), TriangulationPlugin))
+///     .init_asset::<Mesh>()
+///     .add_systems(Startup, setup)
+///     .run();
+/// ```
+pub struct TriangulationPlugin;
+
+impl Plugin for TriangulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                start_triangulation_tasks,
+                poll_triangulation_tasks,
+                create_triangulation_meshes,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// The input of a triangulation, which the [`TriangulationPlugin`] triangulates whenever it changes.
+#[derive(Component, Clone, Debug, Default)]
+pub struct TriangulationRequest {
+    /// The points to triangulate.
+    pub points: Vec<Vector>,
+    /// The holes, each as a closed polygon.
+    pub holes: Vec<Vec<Vector>>,
+    /// The options passed on to [`triangulate_with_options`].
+    pub options: TriangulateOptions,
+}
+
+/// The triangulation of the [`TriangulationRequest`] of the same entity, that is still running.
+/// Dropping it cancels the triangulation.
+#[derive(Component)]
+pub struct TriangulationTask(Task<Result<TriangulationResult, CustomError>>);
+
+/// The finished triangulation of the [`TriangulationRequest`] of the same entity.
+#[derive(Component, Debug)]
+pub struct TriangulationReady(pub TriangulationResult);
+
+impl From<&TriangulationResult> for Mesh {
+    /// A flat mesh in the xy plane facing towards z, with the uvs spanning the bounds of the vertices.
+    fn from(result: &TriangulationResult) -> Self {
+        let min = result
+            .vertices
+            .iter()
+            .fold(Vector::new(f32::INFINITY, f32::INFINITY), |min, vertex| {
+                min.min(*vertex)
+            });
+        let max = result.vertices.iter().fold(
+            Vector::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+            |max, vertex| max.max(*vertex),
+        );
+        let size = max - min;
+
+        let positions: Vec<[f32; 3]> = result
+            .vertices
+            .iter()
+            .map(|vertex| [vertex.x, vertex.y, 0.])
+            .collect();
+        let normals = vec![[0., 0., 1.]; result.vertices.len()];
+        let uvs: Vec<[f32; 2]> = result
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let uv = (*vertex - min) / size;
+                [uv.x, 1. - uv.y]
+            })
+            .collect();
+        let indices = result
+            .triangles
+            .iter()
+            .flatten()
+            .map(|vertex| *vertex as u32)
+            .collect();
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+/// Starts a triangulation for every new or changed request, replacing the task of the previous one.
+fn start_triangulation_tasks(
+    mut commands: Commands,
+    requests: Query<(Entity, &TriangulationRequest), Changed<TriangulationRequest>>,
+) {
+    let task_pool = AsyncComputeTaskPool::get();
+    for (entity, request) in &requests {
+        let request = request.clone();
+        let task = task_pool.spawn(async move {
+            let holes = (!request.holes.is_empty()).then_some(request.holes.as_slice());
+            triangulate_with_options(&request.points, holes, &request.options)
+        });
+        commands.entity(entity).insert(TriangulationTask(task));
+    }
+}
+
+/// Moves the results of finished triangulations into [`TriangulationReady`].
+fn poll_triangulation_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut TriangulationTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        let Some(result) = block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<TriangulationTask>();
+        match result {
+            Ok(result) => {
+                entity_commands.insert(TriangulationReady(result));
+            }
+            Err(error) => warn!("Triangulation of {entity} failed: {error:?}"),
+        }
+    }
+}
+
+/// Creates a [`Mesh2d`] for every new result.
+fn create_triangulation_meshes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    results: Query<(Entity, &TriangulationReady), Changed<TriangulationReady>>,
+) {
+    for (entity, ready) in &results {
+        let mesh = meshes.add(Mesh::from(&ready.0));
+        commands.entity(entity).insert(Mesh2d(mesh));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        prelude::*,
+        render::mesh::{Mesh, Mesh2d},
+    };
+
+    use super::{TriangulationPlugin, TriangulationReady, TriangulationRequest, TriangulationTask};
+    use crate::Vector;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), TriangulationPlugin))
+            .init_asset::<Mesh>();
+        app
+    }
+
+    fn square_request() -> TriangulationRequest {
+        TriangulationRequest {
+            points: [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+                .iter()
+                .map(Vector::from)
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn request_produces_result_and_mesh() {
+        let mut app = app();
+        let entity = app.world_mut().spawn(square_request()).id();
+
+        for _ in 0..1000 {
+            app.update();
+            if app.world().get::<Mesh2d>(entity).is_some() {
+                break;
+            }
+            std::thread::yield_now();
+        }
+
+        let world = app.world();
+        let ready = world.get::<TriangulationReady>(entity).unwrap();
+        assert_eq!(ready.0.len(), 2);
+        assert!(world.get::<TriangulationTask>(entity).is_none());
+        let mesh_handle = &world.get::<Mesh2d>(entity).unwrap().0;
+        let mesh = world.resource::<Assets<Mesh>>().get(mesh_handle).unwrap();
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.indices().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn despawning_cancels_the_task() {
+        let mut app = app();
+        let entity = app.world_mut().spawn(square_request()).id();
+        app.update();
+        app.world_mut().despawn(entity);
+        app.update();
+        assert!(app.world().get_entity(entity).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    data_structures::{
+        error::CustomError,
+        found_or_added::FoundOrAdded,
+        ids::{TriangleId, VertexId},
+        triangle_info::TriangleInfo,
+        triangle_set::TriangleSet,
+        vector::Vector,
+    },
+    math_utils::is_point_strictly_inside_circumcircle,
+};
+
+/// Inserts a point with the Bowyer-Watson algorithm, as an alternative to
+/// [`triangulate_point`](crate::triangulation::triangulate_point).
+///
+/// The triangles whose circumcircle contains the point form a cavity around it, which is retriangulated by
+/// connecting the point to every edge of the cavity's outline. The triangle set must not have constrained edges yet.
+pub fn triangulate_point_bowyer_watson(
+    triangle_set: &mut TriangleSet,
+    point_to_insert: Vector,
+) -> Result<FoundOrAdded, CustomError> {
+    let inserted_point_index = match triangle_set.add_point(point_to_insert) {
+        FoundOrAdded::Found(idx) => return Ok(FoundOrAdded::Found(idx)),
+        FoundOrAdded::Added(idx) => idx,
+    };
+    // Start at the last added triangle
+    let start_triangle = TriangleId(triangle_set.triangle_count() - 1);
+    let (containing_triangle_index, walk_steps) = triangle_set
+        .walk_to_point(point_to_insert, start_triangle)
+        .map_err(|_| CustomError::PointNotInTriangle)?;
+    triangle_set.statistics.walk_steps += walk_steps;
+
+    let cavity = find_cavity(triangle_set, point_to_insert, containing_triangle_index);
+    triangle_set.statistics.cavity_triangles += cavity.len();
+    fill_cavity(triangle_set, inserted_point_index, &cavity);
+    Ok(FoundOrAdded::Added(inserted_point_index))
+}
+
+/// The connected triangles around the containing one, whose circumcircle contains the point.
+///
+/// Rounding can leave the point on the wrong side of an edge of the cavity's outline,
+/// the triangle behind such an edge is added as well, so that the cavity is star-shaped around the point.
+fn find_cavity(
+    triangle_set: &TriangleSet,
+    point: Vector,
+    containing_triangle_index: TriangleId,
+) -> Vec<TriangleId> {
+    let mut cavity = vec![containing_triangle_index];
+    let mut in_cavity = HashSet::from([containing_triangle_index]);
+    let mut to_check = vec![containing_triangle_index];
+    while let Some(triangle_index) = to_check.pop() {
+        let triangle_info = triangle_set.get_triangle_info(triangle_index);
+        for edge_index in 0..3 {
+            let Some(adjacent) = triangle_info.adjacent_triangle_indices[edge_index] else {
+                continue;
+            };
+            if in_cavity.contains(&adjacent) {
+                continue;
+            }
+            let edge_start = triangle_set.get_point_from_index(triangle_index, edge_index);
+            let edge_end = triangle_set.get_point_from_index(triangle_index, (edge_index + 1) % 3);
+            let is_point_behind_edge =
+                (*edge_end - *edge_start).cross_product(point - *edge_start) <= 0.;
+            if is_point_behind_edge
+                || is_point_strictly_inside_circumcircle(triangle_set.get_triangle(adjacent), point)
+            {
+                cavity.push(adjacent);
+                in_cavity.insert(adjacent);
+                to_check.push(adjacent);
+            }
+        }
+    }
+    cavity
+}
+
+/// Replaces the triangles of the cavity with a fan of triangles from the inserted vertex to the cavity's outline.
+///
+/// The outline has two edges more than the cavity has triangles, the two additional triangles are appended.
+fn fill_cavity(
+    triangle_set: &mut TriangleSet,
+    inserted_point_index: VertexId,
+    cavity: &[TriangleId],
+) {
+    let in_cavity: HashSet<TriangleId> = cavity.iter().copied().collect();
+    // every edge of the outline as start, end and the triangle behind it, which is never part of the cavity
+    let mut outline = Vec::with_capacity(cavity.len() + 2);
+    for triangle_index in cavity {
+        let triangle_info = triangle_set.get_triangle_info(*triangle_index);
+        for edge_index in 0..3 {
+            let adjacent = triangle_info.adjacent_triangle_indices[edge_index];
+            if adjacent.is_some_and(|adjacent| in_cavity.contains(&adjacent)) {
+                continue;
+            }
+            outline.push((
+                triangle_info.vertex_indices[edge_index],
+                triangle_info.vertex_indices[(edge_index + 1) % 3],
+                adjacent,
+            ));
+        }
+    }
+
+    // the triangles of the cavity are reused for the first triangles of the fan
+    let mut new_triangle_indices = cavity.to_vec();
+    while new_triangle_indices.len() < outline.len() {
+        new_triangle_indices
+            .push(triangle_set.add_triangle_info(TriangleInfo::new([inserted_point_index; 3])));
+    }
+    let triangle_starting_at: HashMap<VertexId, TriangleId> = outline
+        .iter()
+        .zip(&new_triangle_indices)
+        .map(|((start, ..), triangle_index)| (*start, *triangle_index))
+        .collect();
+    let triangle_ending_at: HashMap<VertexId, TriangleId> = outline
+        .iter()
+        .zip(&new_triangle_indices)
+        .map(|((_, end, ..), triangle_index)| (*end, *triangle_index))
+        .collect();
+
+    // all the triangles take the inserted point as their vertex 0, so that the outline is edge 1
+    for ((start, end, adjacent), triangle_index) in outline.iter().zip(&new_triangle_indices) {
+        let new_triangle = TriangleInfo::new([inserted_point_index, *start, *end]).with_adjacent(
+            triangle_ending_at.get(start).copied(),
+            *adjacent,
+            triangle_starting_at.get(end).copied(),
+        );
+        triangle_set.replace_triangle(*triangle_index, &new_triangle);
+        // the triangle behind the outline may have had several triangles of the cavity as neighbors,
+        // which are reused, so it is updated by its edge instead of by the old neighbor
+        if let Some(adjacent) = adjacent {
+            let adjacent_info = &mut triangle_set.triangle_infos[adjacent.0];
+            if let Some(edge_index) = (0..3).find(|edge_index| {
+                adjacent_info.vertex_indices[*edge_index] == *end
+                    && adjacent_info.vertex_indices[(edge_index + 1) % 3] == *start
+            }) {
+                adjacent_info.adjacent_triangle_indices[edge_index] = Some(*triangle_index);
+            }
+        }
+    }
+}
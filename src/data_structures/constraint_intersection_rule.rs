@@ -0,0 +1,12 @@
+/// What happens to a constrained edge that crosses a constrained edge inserted before it,
+/// like the two lobes of a figure-eight hole or two overlapping holes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConstraintIntersectionRule {
+    /// Both edges are split at their intersection point, which becomes a vertex of both.
+    /// A hole polygon that crosses itself is cut out as the separate loops between its crossings.
+    #[default]
+    Split,
+    /// The triangulation fails with
+    /// [`CustomError::ConstraintIntersection`](crate::CustomError::ConstraintIntersection).
+    Reject,
+}
@@ -0,0 +1,42 @@
+use super::ids::VertexId;
+
+/// The input edge a [`ConstraintTrace`] was created for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstraintSource {
+    /// The edge from the vertex `edge` of the hole `hole` to the next vertex of that hole.
+    HoleEdge {
+        /// The index of the hole in the input.
+        hole: usize,
+        /// The index of the first vertex of the edge in the hole polygon.
+        edge: usize,
+    },
+    /// The segment with this index, see [`triangulate_pslg`](crate::triangulate_pslg).
+    Segment(usize),
+    /// The edge from the input point with this index to the next one,
+    /// see [`TriangulateOptions::assume_closed_boundary`](crate::TriangulateOptions::assume_closed_boundary).
+    Boundary(usize),
+}
+
+/// How one constrained input edge ended up in the mesh.
+///
+/// Points that lie on the edge split it, so it may be made up of several constrained edges of the output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintTrace {
+    /// The input edge.
+    pub source: ConstraintSource,
+    /// The output indices of the vertices along the edge, from its start to its end.
+    /// Every two consecutive vertices are a constrained edge of the output.
+    pub vertices: Vec<usize>,
+    /// The triangle set vertices along the edge.
+    pub(crate) vertex_ids: Vec<VertexId>,
+}
+
+impl ConstraintTrace {
+    pub(crate) fn new(source: ConstraintSource, vertex_ids: Vec<VertexId>) -> Self {
+        ConstraintTrace {
+            source,
+            vertices: Vec::new(),
+            vertex_ids,
+        }
+    }
+}
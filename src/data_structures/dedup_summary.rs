@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use super::{triangle_set::TriangleSet, vector::Vector};
+
+/// Which input points became which output vertices, so that duplicates in the input can be counted.
+///
+/// Points at the same position, see [`TriangulateOptions::dedup_input`](crate::TriangulateOptions::dedup_input),
+/// and points only a few ulps apart after the normalization share one vertex.
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let points = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(0., 0.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(result.dedup_summary.input_count, 4);
+/// assert_eq!(result.dedup_summary.unique_count, 3);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DedupSummary {
+    /// How many points were passed to the triangulation.
+    pub input_count: usize,
+    /// How many different vertices the input points became.
+    /// These are the first output vertices, the vertices of holes and of the tesselation follow them.
+    pub unique_count: usize,
+    /// For every output vertex `k` below `unique_count`, the first input index with its coordinates.
+    /// Editing the mesh afterwards does not update this.
+    pub first_occurrence: Vec<usize>,
+}
+
+impl DedupSummary {
+    /// Matches the normalized input points with the vertices of the triangle set, before any other point is added to it.
+    pub(crate) fn new(normalized_input_points: &[Vector], triangle_set: &TriangleSet) -> Self {
+        // The first three points are the vertices of the supertriangle
+        let input_vertices = &triangle_set.points[3..];
+        let vertex_of_bits: HashMap<(u32, u32), usize> = input_vertices
+            .iter()
+            .enumerate()
+            .map(|(vertex, point)| ((point.x.to_bits(), point.y.to_bits()), vertex))
+            .collect();
+
+        let mut first_occurrence = vec![usize::MAX; input_vertices.len()];
+        for (input_index, point) in normalized_input_points.iter().enumerate() {
+            // points that were merged with a point a few ulps away are not found exactly
+            let vertex = vertex_of_bits
+                .get(&(point.x.to_bits(), point.y.to_bits()))
+                .copied()
+                .or_else(|| {
+                    input_vertices
+                        .iter()
+                        .position(|vertex| vertex.is_same_point(*point))
+                });
+            if let Some(vertex) = vertex {
+                first_occurrence[vertex] = first_occurrence[vertex].min(input_index);
+            }
+        }
+        DedupSummary {
+            input_count: normalized_input_points.len(),
+            unique_count: input_vertices.len(),
+            first_occurrence,
+        }
+    }
+}
@@ -1,22 +1,86 @@
-#[derive(Debug, PartialEq)]
+use super::ids::VertexId;
+
+/// An edge between two vertices.
+///
+/// Equality and hashing follow the direction of the edge: `Edge::new(a, b)` and `Edge::new(b, a)` are different.
+/// Edges that walk along an outline, like the edges of a hole, keep their direction with [`Edge::new`],
+/// because it tells which side of the outline is inside. Sets and maps that only care about
+/// which vertices are connected store edges made with [`Edge::undirected`], or compare them with [`Edge::eq_undirected`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Edge {
-    edge_vertex_a: usize,
-    edge_vertex_b: usize,
+    edge_vertex_a: VertexId,
+    edge_vertex_b: VertexId,
 }
 
 impl Edge {
-    pub fn new(edge_vertex_a: usize, edge_vertex_b: usize) -> Self {
+    /// The edge from `edge_vertex_a` to `edge_vertex_b`.
+    pub fn new(edge_vertex_a: VertexId, edge_vertex_b: VertexId) -> Self {
         Edge {
             edge_vertex_a,
             edge_vertex_b,
         }
     }
 
-    pub fn vertex_a(&self) -> usize {
+    /// The edge between both vertices with the smaller vertex first, the same for both directions.
+    pub fn undirected(edge_vertex_a: VertexId, edge_vertex_b: VertexId) -> Self {
+        Edge {
+            edge_vertex_a: edge_vertex_a.min(edge_vertex_b),
+            edge_vertex_b: edge_vertex_a.max(edge_vertex_b),
+        }
+    }
+
+    /// The vertex the edge starts at.
+    pub fn vertex_a(&self) -> VertexId {
         self.edge_vertex_a
     }
 
-    pub fn vertex_b(&self) -> usize {
+    /// The vertex the edge ends at.
+    pub fn vertex_b(&self) -> VertexId {
         self.edge_vertex_b
     }
+
+    /// The vertices with the smaller one first, the same for both directions of the edge.
+    pub fn canonical(&self) -> (VertexId, VertexId) {
+        (
+            self.edge_vertex_a.min(self.edge_vertex_b),
+            self.edge_vertex_a.max(self.edge_vertex_b),
+        )
+    }
+
+    /// Whether both edges connect the same vertices, in any direction.
+    pub fn eq_undirected(&self, other: &Edge) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Edge;
+    use crate::VertexId;
+
+    #[test]
+    fn both_directions_share_the_canonical_form() {
+        let forward = Edge::new(VertexId(7), VertexId(3));
+        let backward = Edge::new(VertexId(3), VertexId(7));
+        assert_eq!(forward.canonical(), (VertexId(3), VertexId(7)));
+        assert_eq!(forward.canonical(), backward.canonical());
+        assert!(forward.eq_undirected(&backward));
+        assert_ne!(forward, backward);
+        assert!(!forward.eq_undirected(&Edge::new(VertexId(3), VertexId(8))));
+    }
+
+    #[test]
+    fn undirected_edges_are_equal_and_hash_alike_in_both_directions() {
+        let forward = Edge::undirected(VertexId(7), VertexId(3));
+        let backward = Edge::undirected(VertexId(3), VertexId(7));
+        assert_eq!(forward, backward);
+        assert_eq!(forward, Edge::new(VertexId(3), VertexId(7)));
+        let registry: HashSet<Edge> = [forward, backward].into_iter().collect();
+        assert_eq!(registry.len(), 1);
+        // a directed edge only matches the registry in the order of the smaller vertex first
+        assert!(registry.contains(&Edge::new(VertexId(3), VertexId(7))));
+        assert!(!registry.contains(&Edge::new(VertexId(7), VertexId(3))));
+    }
 }
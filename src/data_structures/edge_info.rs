@@ -1,31 +1,96 @@
-#[derive(Debug)]
+use super::{
+    edge::Edge,
+    ids::{TriangleId, VertexId},
+};
+
+/// Where a directed edge is stored: in which triangle and at which of its three edges,
+/// see [`TriangleSet::find_edge_info_for_vertices`](super::triangle_set::TriangleSet::find_edge_info_for_vertices).
+///
+/// The edge `edge_index` of a triangle runs from its vertex `edge_index` to the next one counter-clockwise,
+/// and the adjacent triangle across it is the one at the same index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct EdgeInfo {
-    pub triangle_index: usize,
-    pub edge_index: usize,
-    edge_vertex_a: usize,
-    edge_vertex_b: usize,
+    triangle_index: TriangleId,
+    edge_index: usize,
+    vertex_indices: [VertexId; 3],
 }
 
 impl EdgeInfo {
+    /// The edge `edge_index` of the triangle with these vertices, in their counter-clockwise order.
     pub fn new(
-        triangle_index: usize,
+        triangle_index: TriangleId,
         edge_index: usize,
-        edge_vertex_a: usize,
-        edge_vertex_b: usize,
+        vertex_indices: [VertexId; 3],
     ) -> Self {
         EdgeInfo {
             triangle_index,
             edge_index,
-            edge_vertex_a,
-            edge_vertex_b,
+            vertex_indices,
+        }
+    }
+
+    /// The triangle that contains the edge.
+    pub fn triangle_index(&self) -> TriangleId {
+        self.triangle_index
+    }
+
+    /// The position of the edge in its triangle, from 0 to 2.
+    pub fn edge_index(&self) -> usize {
+        self.edge_index
+    }
+
+    /// The vertex the edge starts at.
+    pub fn vertex_a(&self) -> VertexId {
+        self.vertex_indices[self.edge_index]
+    }
+
+    /// The vertex the edge ends at.
+    pub fn vertex_b(&self) -> VertexId {
+        self.vertex_indices[(self.edge_index + 1) % 3]
+    }
+
+    /// The position in the triangle of the vertex that is not part of the edge.
+    pub fn opposite_vertex_index(&self) -> usize {
+        (self.edge_index + 2) % 3
+    }
+
+    /// The vertex of the triangle that is not part of the edge.
+    pub fn opposite_vertex(&self) -> VertexId {
+        self.vertex_indices[self.opposite_vertex_index()]
+    }
+
+    /// The edge of the same triangle that starts where this one ends.
+    pub fn next_edge(&self) -> EdgeInfo {
+        EdgeInfo {
+            edge_index: (self.edge_index + 1) % 3,
+            ..*self
         }
     }
 
-    pub fn vertex_a(&self) -> usize {
-        self.edge_vertex_a
+    /// The edge from its start to its end vertex.
+    pub fn as_edge(&self) -> Edge {
+        Edge::new(self.vertex_a(), self.vertex_b())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdgeInfo;
+    use crate::{data_structures::edge::Edge, TriangleId, VertexId};
+
+    #[test]
+    fn next_edges_go_around_the_triangle() {
+        let vertices = [VertexId(4), VertexId(7), VertexId(9)];
+        let edge_info = EdgeInfo::new(TriangleId(2), 1, vertices);
+        assert_eq!(edge_info.as_edge(), Edge::new(VertexId(7), VertexId(9)));
+        assert_eq!(edge_info.opposite_vertex_index(), 0);
+        assert_eq!(edge_info.opposite_vertex(), VertexId(4));
 
-    pub fn vertex_b(&self) -> usize {
-        self.edge_vertex_b
+        let next_edge = edge_info.next_edge();
+        assert_eq!(next_edge.triangle_index(), TriangleId(2));
+        assert_eq!(next_edge.edge_index(), 2);
+        assert_eq!(next_edge.vertex_a(), edge_info.vertex_b());
+        assert_eq!(next_edge.vertex_b(), edge_info.opposite_vertex());
+        assert_eq!(next_edge.next_edge().next_edge(), edge_info);
     }
 }
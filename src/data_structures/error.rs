@@ -1,9 +1,52 @@
+use super::{hole_problem::HoleProblem, ids::VertexId, vector::Vector};
+
+/// Everything that can go wrong while triangulating.
 #[derive(Debug)]
 pub enum CustomError {
+    /// A point could not be located in any triangle of the triangulation.
     PointNotInTriangle,
+    /// Swapping the shared edge of two triangles failed.
     SwappingFailed,
+    /// Two triangles that were expected to be adjacent do not share a vertex.
     TrianglesDontShareIndex,
+    /// Inserting the points of the tesselation failed.
     TesselationFailed,
-    EdgeNotFoundInTriangles(usize, usize),
+    /// The edge between the two vertices is not part of any triangle.
+    EdgeNotFoundInTriangles(VertexId, VertexId),
+    /// A hole polygon is not closed by triangles.
     PolygonIsOpen,
+    /// The named option has a value that can not be used, e.g. a non-positive or non-finite area.
+    InvalidParameter(&'static str, f32),
+    /// The tesselation would have to add more points than the given limit.
+    SteinerPointLimitExceeded(usize),
+    /// There is no hole with this index in the result.
+    HoleNotFound(usize),
+    /// There is no vertex with this index in the result.
+    VertexNotFound(usize),
+    /// The vertex is part of a constrained edge or of the border of the mesh, so it can not be taken out of the triangulation.
+    VertexIsFixed(VertexId),
+    /// A segment connects a vertex to itself or refers to a vertex that is not in the input.
+    InvalidSegment(usize, usize),
+    /// A point lies on an edge of a hole and [`PointOnConstraintRule::Reject`](crate::PointOnConstraintRule::Reject)
+    /// was asked for. Contains the position of the point.
+    PointOnConstraint(Vector),
+    /// A constrained edge crosses an earlier one and
+    /// [`ConstraintIntersectionRule::Reject`](crate::ConstraintIntersectionRule::Reject) was asked for.
+    /// Contains the intersection point.
+    ConstraintIntersection(Vector),
+    /// An input point is outside of [`TriangulateOptions::bounds`](crate::TriangulateOptions::bounds).
+    /// Contains the position of the point.
+    PointOutsideBounds(Vector),
+    /// A point of the input or of a hole has a coordinate that is NaN or infinite. Contains the point.
+    NonFinitePoint(Vector),
+    /// There are less than three input points. Contains their number.
+    TooFewPoints(usize),
+    /// All input points are at the same position.
+    CoincidentPoints,
+    /// All input points are on one line, so they do not span a triangle.
+    CollinearPoints,
+    /// A hole polygon is malformed, see [`validate_holes`](crate::validate_holes). Contains the first problem.
+    InvalidHole(HoleProblem),
+    /// A vertex of the hole with this index is outside of the convex hull of the input points.
+    HoleOutsideOfPoints(usize),
 }
@@ -0,0 +1,74 @@
+use std::mem::size_of;
+
+use super::{
+    ids::{TriangleId, VertexId},
+    triangle_info::TriangleInfo,
+    triangulate_options::TriangulateOptions,
+    vector::Vector,
+};
+
+/// Splitting triangles at their edge midpoints leaves them at about 40% of the maximum area on average.
+const REFINED_TRIANGLES_PER_MAXIMUM_AREA: f64 = 2.6;
+/// A vec that grew by doubling has on average this much more capacity than elements.
+const VEC_GROWTH_SLACK: f64 = 1.5;
+
+/// The expected size of a triangulation, see [`estimate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Estimate {
+    /// The expected number of output triangles.
+    pub triangles: usize,
+    /// The expected largest amount of heap memory the triangulation uses at once, in bytes.
+    pub peak_bytes: usize,
+}
+
+/// Predicts how many triangles a triangulation of `points_len` points will have and how much memory it will need,
+/// without running it.
+///
+/// A point cloud gets about two triangles per point, and the tesselation fills `polygon_area`
+/// (the area inside of the outline without the holes, in the units of the input) with triangles
+/// of about 40% of [`TriangulateOptions::maximum_triangle_area`], up to the point limits of the options.
+/// The memory is counted from the sizes of the internal and the output buffers, with the spare capacity of grown vecs.
+///
+/// For point clouds in general position and for tesselated polygons, both numbers are within a factor of two
+/// of the real ones. Points in convex position have only one triangle per point, so the estimate is twice too high.
+/// The memory of the constrained edges of holes and of the input itself is not included.
+/// ```
+/// use constrained_denaulay_triangulation::{estimate, TriangulateOptions};
+///
+/// let coarse = estimate(1000, 100., &TriangulateOptions::new());
+/// assert_eq!(coarse.triangles, 2000);
+/// let fine = estimate(1000, 100., &TriangulateOptions::new().with_maximum_triangle_area(0.01));
+/// assert!(fine.triangles > 10 * coarse.triangles);
+/// assert!(fine.peak_bytes > 10 * coarse.peak_bytes);
+/// ```
+pub fn estimate(points_len: usize, polygon_area: f32, options: &TriangulateOptions) -> Estimate {
+    let refined_triangles = match options.maximum_triangle_area {
+        Some(maximum_triangle_area) if maximum_triangle_area > 0. && polygon_area > 0. => {
+            REFINED_TRIANGLES_PER_MAXIMUM_AREA * polygon_area as f64 / maximum_triangle_area as f64
+        }
+        _ => 0.,
+    };
+    // every point added by the tesselation adds two triangles
+    let steiner_points = (refined_triangles / 2.).min(options.steiner_point_limit as f64);
+    let vertices = points_len as f64 + steiner_points.ceil();
+    let triangles = 2. * vertices;
+
+    // the internal triangle set, which also holds the triangles of the supertriangle
+    let triangle_set = VEC_GROWTH_SLACK
+        * (triangles * size_of::<TriangleInfo>() as f64 + vertices * size_of::<Vector>() as f64);
+    // the normalized and the sorted copy of the input points
+    let input_copies = 2. * points_len as f64 * size_of::<Vector>() as f64;
+    // the output arrays and the lookups that build them from the triangle set
+    let output_triangle_bytes = size_of::<[usize; 3]>()
+        + size_of::<[Option<usize>; 3]>()
+        + size_of::<TriangleId>()
+        + size_of::<Option<usize>>();
+    let output_vertex_bytes =
+        size_of::<Vector>() + size_of::<VertexId>() + size_of::<Option<usize>>();
+    let output = triangles * output_triangle_bytes as f64 + vertices * output_vertex_bytes as f64;
+
+    Estimate {
+        triangles: triangles as usize,
+        peak_bytes: (triangle_set + input_copies + output) as usize,
+    }
+}
@@ -0,0 +1,81 @@
+use super::triangulation_result::TriangulationResult;
+
+/// A triangulation as flat arrays, to hand it over to C and other languages.
+///
+/// The same mesh as a [`TriangulationResult`], with the same vertex and triangle order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FfiMesh {
+    /// The vertices as `x, y` pairs.
+    pub vertices: Vec<f32>,
+    /// The counter-clockwise vertex indices, three per triangle.
+    pub indices: Vec<u32>,
+    /// The adjacent triangle across the edge from vertex `i` to vertex `i + 1`, three per triangle.
+    /// `-1` if there is no triangle on the other side.
+    pub adjacency: Vec<i32>,
+}
+
+impl From<&TriangulationResult> for FfiMesh {
+    fn from(result: &TriangulationResult) -> Self {
+        FfiMesh {
+            vertices: result
+                .vertices
+                .iter()
+                .flat_map(|vertex| [vertex.x, vertex.y])
+                .collect(),
+            indices: result
+                .triangles
+                .iter()
+                .flatten()
+                .map(|vertex| *vertex as u32)
+                .collect(),
+            adjacency: result
+                .adjacency
+                .iter()
+                .flatten()
+                .map(|adjacent| adjacent.map_or(-1, |adjacent| adjacent as i32))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{triangulate_ffi, CustomError, TriangulateOptions, Vector};
+
+    #[test]
+    fn adjacency_is_symmetric_and_indices_are_in_range() -> Result<(), CustomError> {
+        let outline = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let hole = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(2.);
+        let mesh = triangulate_ffi(&outline, Some(&[hole]), &options)?;
+
+        let vertex_count = mesh.vertices.len() / 2;
+        let triangle_count = mesh.indices.len() / 3;
+        assert_eq!(mesh.vertices.len() % 2, 0);
+        assert_eq!(mesh.adjacency.len(), mesh.indices.len());
+        assert!(mesh
+            .indices
+            .iter()
+            .all(|vertex| (*vertex as usize) < vertex_count));
+        let mut boundary_edges = 0;
+        for (edge, adjacent) in mesh.adjacency.iter().enumerate() {
+            let triangle = edge / 3;
+            if *adjacent == -1 {
+                boundary_edges += 1;
+                continue;
+            }
+            assert!(*adjacent >= 0 && (*adjacent as usize) < triangle_count);
+            let neighbor = *adjacent as usize;
+            assert!(mesh.adjacency[3 * neighbor..3 * neighbor + 3].contains(&(triangle as i32)));
+        }
+        // the outline and the hole
+        assert!(boundary_edges >= 8);
+        Ok(())
+    }
+}
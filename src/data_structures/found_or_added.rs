@@ -1,16 +1,50 @@
+use super::ids::VertexId;
 
-// TODO find better name
-#[derive(PartialEq)]
+/// The vertex of a point that was inserted into a triangulation,
+/// and whether the point was already a vertex or had to be added as a new one.
+/// ```
+/// use constrained_denaulay_triangulation::{FoundOrAdded, VertexId};
+///
+/// let added = FoundOrAdded::Added(VertexId(5));
+/// assert!(added.is_added());
+/// assert_eq!(added.value(), 5);
+/// let index: usize = FoundOrAdded::Found(VertexId(3)).into();
+/// assert_eq!(index, 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum FoundOrAdded {
-    Found(usize),
-    Added(usize),
+    /// The point was already a vertex at the same position, up to rounding.
+    Found(VertexId),
+    /// The point was added as a new vertex.
+    Added(VertexId),
 }
 
-impl FoundOrAdded{
-    pub fn value(self)->usize{
+impl FoundOrAdded {
+    /// The vertex of the point, no matter whether it was found or added.
+    pub fn vertex(&self) -> VertexId {
         match self {
-            FoundOrAdded::Found(idx) => idx,
-            FoundOrAdded::Added(idx) => idx,
+            FoundOrAdded::Found(vertex) | FoundOrAdded::Added(vertex) => *vertex,
         }
     }
-}
\ No newline at end of file
+
+    /// The index of the vertex of the point, see [`VertexId::index`].
+    pub fn value(&self) -> usize {
+        self.vertex().index()
+    }
+
+    /// Whether the point was already a vertex.
+    pub fn is_found(&self) -> bool {
+        matches!(self, FoundOrAdded::Found(_))
+    }
+
+    /// Whether the point was added as a new vertex.
+    pub fn is_added(&self) -> bool {
+        matches!(self, FoundOrAdded::Added(_))
+    }
+}
+
+impl From<FoundOrAdded> for usize {
+    fn from(found_or_added: FoundOrAdded) -> Self {
+        found_or_added.value()
+    }
+}
@@ -0,0 +1,33 @@
+/// Something wrong with an input hole, found by [`validate_holes`](crate::validate_holes).
+///
+/// Every problem names the index of its hole in the input. Edge `i` of a hole is the edge from its vertex `i` to its vertex `i + 1`,
+/// the last edge closes the polygon back to the first vertex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HoleProblem {
+    /// The hole has less than three vertices, so it does not enclose anything.
+    TooFewVertices {
+        /// The index of the hole.
+        hole: usize,
+        /// How many vertices the hole has.
+        vertices: usize,
+    },
+    /// A vertex is at the same position as the next one, which includes repeating the first vertex at the end.
+    DuplicateConsecutiveVertices {
+        /// The index of the hole.
+        hole: usize,
+        /// The index of the first of both vertices.
+        vertex: usize,
+    },
+    /// The vertices are not in counter-clockwise order, so the hole would be cut out of the outside of its polygon.
+    NotCounterClockwise {
+        /// The index of the hole.
+        hole: usize,
+    },
+    /// Two edges of the hole cross or touch each other, apart from the vertex shared by consecutive edges.
+    SelfIntersection {
+        /// The index of the hole.
+        hole: usize,
+        /// The indices of both edges, the smaller one first.
+        edges: [usize; 2],
+    },
+}
@@ -0,0 +1,22 @@
+use super::ids::{TriangleId, VertexId};
+
+/// What happened to one of the input holes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HoleReport {
+    /// The output indices of the vertices of the hole polygon, in the order of the input.
+    /// Points that lie on an edge of the polygon split it and are listed between its endpoints.
+    pub vertices: Vec<usize>,
+    /// How many edges of the hole polygon had to be forced into the triangulation, counting split edges per part.
+    pub inserted_edges: usize,
+    /// How many edges of the hole polygon were already part of the triangulation.
+    pub existing_edges: usize,
+    /// How many triangles were removed for this hole.
+    /// Triangles that an earlier hole already removed are not counted again.
+    pub removed_triangles: usize,
+    /// Whether the hole was filled again after the triangulation, see [`fill_hole`](crate::fill_hole).
+    pub filled: bool,
+    /// The triangle set vertices of the hole polygon.
+    pub(crate) vertex_ids: Vec<VertexId>,
+    /// The sorted triangle set triangles that were removed for this hole.
+    pub(crate) triangle_ids: Vec<TriangleId>,
+}
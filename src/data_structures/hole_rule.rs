@@ -0,0 +1,15 @@
+/// Which triangles the hole polygons remove, when they are nested inside of each other.
+///
+/// The mesh is solid, inside of a hole it is empty, inside of a polygon in that hole it is solid again, and so on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HoleRule {
+    /// Every triangle inside of any hole is removed, so a polygon inside of a hole changes nothing.
+    #[default]
+    Union,
+    /// A triangle is removed if it is inside of an odd number of hole polygons.
+    ///
+    /// A polygon inside of a hole is an island that keeps its triangles,
+    /// a polygon inside of that island is a hole again. The polygons must not cross each other.
+    /// The removed triangles are reported for the innermost polygon around them.
+    EvenOdd,
+}
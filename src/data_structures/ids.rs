@@ -0,0 +1,65 @@
+/// Identifies a vertex (a point) stored in the triangulation.
+///
+/// Vertex ids and triangle ids are both plain indices internally, but they index different buffers.
+/// Keeping them as separate types makes it impossible to pass one where the other is expected.
+/// ```compile_fail
+/// use constrained_denaulay_triangulation::{TriangleId, VertexId};
+///
+/// let vertex: VertexId = TriangleId(0);
+/// ```
+/// ```compile_fail
+/// use constrained_denaulay_triangulation::VertexId;
+///
+/// let vertex: VertexId = 0usize;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertexId(pub usize);
+
+impl VertexId {
+    /// The position of the vertex in the point buffer.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Identifies a triangle stored in the triangulation.
+///
+/// ```compile_fail
+/// use constrained_denaulay_triangulation::{TriangleId, VertexId};
+///
+/// let triangle: TriangleId = VertexId(0);
+/// ```
+/// ```compile_fail
+/// use constrained_denaulay_triangulation::TriangleId;
+///
+/// let triangle: TriangleId = 0usize;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TriangleId(pub usize);
+
+impl TriangleId {
+    /// The position of the triangle in the triangle buffer.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TriangleId, VertexId};
+
+    #[test]
+    fn ids_expose_their_index() {
+        assert_eq!(VertexId(4).index(), 4);
+        assert_eq!(TriangleId(7).index(), 7);
+    }
+
+    #[test]
+    fn ids_are_ordered_by_index() {
+        let mut vertices = vec![VertexId(3), VertexId(1), VertexId(2)];
+        vertices.sort();
+        assert_eq!(vertices, vec![VertexId(1), VertexId(2), VertexId(3)]);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::normalize::Bounds;
+
+use super::{vector::Vector, warning::Warning};
+
+/// Above this ratio between the largest and the smallest distance of the input,
+/// the points can not be told apart anymore after normalization.
+pub const MAX_DYNAMIC_RANGE: f32 = 1e6;
+
+/// The smallest distance is searched among at most this many points, so the diagnostics stay cheap.
+const DISTANCE_SAMPLE_SIZE: usize = 64;
+
+/// Cheap statistics about the scale of the input points, to explain bad results of badly scaled input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InputDiagnostics {
+    /// The smallest x and y coordinate of the input,
+    /// after the [`TriangulateOptions::pre_transform`](crate::TriangulateOptions::pre_transform) if there is one.
+    pub min: Vector,
+    /// The biggest x and y coordinate of the input, after the transform like `min`.
+    pub max: Vector,
+    /// The diagonal of the bounds divided by the smallest distance between two different points of a sample of the input.
+    /// `None` if the sample has no two different points.
+    pub distance_ratio: Option<f32>,
+}
+
+impl InputDiagnostics {
+    /// Measures the input points, which were normalized with the given bounds.
+    pub(crate) fn new(input_points: &[Vector], bounds: &Bounds) -> Self {
+        let step = input_points.len().div_ceil(DISTANCE_SAMPLE_SIZE).max(1);
+        Self::from_sample(input_points.iter().step_by(step).copied().collect(), bounds)
+    }
+
+    /// Measures the input points from their normalized copies, without denormalizing more than the sample.
+    pub(crate) fn from_normalized(normalized_points: &[Vector], bounds: &Bounds) -> Self {
+        let step = normalized_points
+            .len()
+            .div_ceil(DISTANCE_SAMPLE_SIZE)
+            .max(1);
+        let sample = normalized_points
+            .iter()
+            .step_by(step)
+            .map(|point| bounds.denormalize(*point))
+            .collect();
+        Self::from_sample(sample, bounds)
+    }
+
+    fn from_sample(sample: Vec<Vector>, bounds: &Bounds) -> Self {
+        let mut smallest_distance = f32::INFINITY;
+        // the distances are compared with the diagonal of the bounds, in the same frame
+        let sample: Vec<Vector> = sample
+            .into_iter()
+            .map(|point| bounds.transform(point))
+            .collect();
+        for (i, a) in sample.iter().enumerate() {
+            for b in &sample[i + 1..] {
+                let difference = *b - *a;
+                let distance = difference.x.hypot(difference.y);
+                if distance > 0. && distance < smallest_distance {
+                    smallest_distance = distance;
+                }
+            }
+        }
+        let diagonal = bounds.max() - bounds.min();
+        let distance_ratio = smallest_distance
+            .is_finite()
+            .then(|| diagonal.x.hypot(diagonal.y) / smallest_distance);
+
+        InputDiagnostics {
+            min: bounds.min(),
+            max: bounds.max(),
+            distance_ratio,
+        }
+    }
+
+    /// A warning about the input, if its dynamic range is too big.
+    pub(crate) fn warning(&self) -> Option<Warning> {
+        self.distance_ratio
+            .filter(|ratio| *ratio > MAX_DYNAMIC_RANGE)
+            .map(|ratio| Warning::LargeDynamicRange { ratio })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{triangulate_with_options, CustomError, TriangulateOptions, Vector, Warning};
+
+    fn screen_points() -> Vec<Vector> {
+        [
+            (0., 0.),
+            (1920., 0.),
+            (1920., 1080.),
+            (0., 1080.),
+            (960., 540.),
+            (400., 300.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect()
+    }
+
+    #[test]
+    fn well_scaled_input_has_no_warning() -> Result<(), CustomError> {
+        let result = triangulate_with_options(&screen_points(), None, &TriangulateOptions::new())?;
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.diagnostics.min, Vector::new(0., 0.));
+        assert_eq!(result.diagnostics.max, Vector::new(1920., 1080.));
+        assert!(result
+            .diagnostics
+            .distance_ratio
+            .is_some_and(|ratio| ratio < 10.));
+        Ok(())
+    }
+
+    #[test]
+    fn outlier_triggers_a_warning() -> Result<(), CustomError> {
+        let mut points = screen_points();
+        points.push(Vector::new(1e9, 1e9));
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        assert!(matches!(
+            result.warnings.as_slice(),
+            [Warning::LargeDynamicRange { ratio }] if *ratio > 1e6
+        ));
+        Ok(())
+    }
+}
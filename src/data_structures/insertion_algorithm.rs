@@ -0,0 +1,39 @@
+/// How a point is inserted into the triangulation and the delaunay constraint restored around it.
+///
+/// Both give the same triangles, as long as no four points are on a common circle.
+/// ```
+/// use constrained_denaulay_triangulation::{
+///     edges_iter, triangulate_with_options, InsertionAlgorithm, TriangulateOptions, TriangulationResult, Vector,
+/// };
+///
+/// // pseudo random points, so that no four of them are on a common circle
+/// let mut state = 7u32;
+/// let mut next_coordinate = || {
+///     state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+///     (state >> 8) as f32 / (1 << 24) as f32
+/// };
+/// let points: Vec<Vector> = (0..500).map(|_| Vector::new(next_coordinate(), next_coordinate())).collect();
+/// let edges = |algorithm: InsertionAlgorithm| {
+///     let options = TriangulateOptions::new().with_insertion_algorithm(algorithm);
+///     let result: TriangulationResult = triangulate_with_options(&points, None, &options).unwrap();
+///     let mut edges: Vec<(Vector, Vector)> = edges_iter(&result)
+///         .map(|(start, end)| (result.vertices[start], result.vertices[end]))
+///         .map(|(a, b)| if (a.x, a.y) < (b.x, b.y) { (a, b) } else { (b, a) })
+///         .collect();
+///     edges.sort_by(|a, b| (a.0.x, a.0.y, a.1.x, a.1.y).partial_cmp(&(b.0.x, b.0.y, b.1.x, b.1.y)).unwrap());
+///     edges
+/// };
+/// assert_eq!(edges(InsertionAlgorithm::Flips), edges(InsertionAlgorithm::BowyerWatson));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InsertionAlgorithm {
+    /// The triangle containing the point is split into three and the edges around it are swapped
+    /// until they are delaunay again.
+    #[default]
+    Flips,
+    /// Bowyer-Watson: all triangles whose circumcircle contains the point are removed
+    /// and the cavity they leave is filled with triangles fanning out from the point.
+    ///
+    /// This is faster when inserting a point would swap many edges.
+    BowyerWatson,
+}
@@ -0,0 +1,19 @@
+/// The order in which the input points are inserted into the triangulation.
+///
+/// The triangulation does not depend on the order, only the time it takes does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InsertionOrder {
+    /// The points are sorted along the cells of a [`PointBinGrid`](crate::PointBinGrid),
+    /// so consecutive points are close to each other.
+    #[default]
+    Spatial,
+    /// Biased randomized insertion order: the points are shuffled into rounds that double in size
+    /// and every round is sorted like [`InsertionOrder::Spatial`].
+    ///
+    /// This keeps the walks to the next point and the swaps after inserting it short for adversarial inputs,
+    /// like points along a spiral. The same seed always gives the same order.
+    Brio {
+        /// The seed of the shuffle.
+        seed: u64,
+    },
+}
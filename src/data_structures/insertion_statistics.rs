@@ -0,0 +1,31 @@
+/// How much work inserting the points into the triangulation took.
+///
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert!(result.statistics.walk_steps >= square.len());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InsertionStatistics {
+    /// How many triangles were visited while searching the triangles containing the inserted points.
+    pub walk_steps: usize,
+    /// How many edges were swapped to restore the delaunay constraint after inserting the points,
+    /// with [`InsertionAlgorithm::Flips`](crate::InsertionAlgorithm::Flips).
+    pub flips: usize,
+    /// How many triangles were removed from the cavities of the inserted points,
+    /// with [`InsertionAlgorithm::BowyerWatson`](crate::InsertionAlgorithm::BowyerWatson).
+    pub cavity_triangles: usize,
+    /// How often the side of an edge a point is on was too close to call in f32 and was recomputed in f64,
+    /// during the whole triangulation.
+    pub orientation_fallbacks: usize,
+    /// How often a point was too close to a circumcircle to decide in f32 whether it is inside,
+    /// and the test was recomputed in f64, during the whole triangulation.
+    pub circumcircle_fallbacks: usize,
+}
@@ -0,0 +1,148 @@
+use std::f32::consts::FRAC_PI_3;
+
+use crate::math_utils::calculate_triangle_area;
+
+use super::{triangle::Triangle, triangulation_result::TriangulationResult};
+
+/// The distribution of the smallest angles and the areas of the triangles of a mesh, to find bad triangles.
+///
+/// The indices are the indices of the triangles that the statistics were computed from,
+/// for a [`TriangulationResult`] the output indices.
+/// ```
+/// use constrained_denaulay_triangulation::{MeshStatistics, Triangle, Vector};
+///
+/// let triangles = [
+///     Triangle::new(Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(0.5, 0.9)),
+///     Triangle::new(Vector::new(0., 0.), Vector::new(4., 0.), Vector::new(2., 0.1)),
+/// ];
+/// let statistics = MeshStatistics::new(&triangles);
+/// assert_eq!(statistics.worst_min_angle_triangle().unwrap().0, 1);
+/// assert_eq!(statistics.angle_histogram(2), vec![1, 1]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshStatistics {
+    /// The smallest angle of every triangle in radians.
+    min_angles: Vec<f32>,
+    /// The area of every triangle.
+    areas: Vec<f32>,
+}
+
+impl MeshStatistics {
+    /// Measures the triangles.
+    pub fn new(triangles: &[Triangle]) -> Self {
+        MeshStatistics {
+            min_angles: triangles.iter().map(Triangle::min_angle).collect(),
+            areas: triangles.iter().map(calculate_triangle_area).collect(),
+        }
+    }
+
+    /// Measures the triangles of the result, in the order of [`TriangulationResult::triangles`].
+    pub fn from_result(result: &TriangulationResult) -> Self {
+        Self::new(&result.to_triangles())
+    }
+
+    /// The smallest angle of every triangle in radians.
+    pub fn min_angles(&self) -> &[f32] {
+        &self.min_angles
+    }
+
+    /// The area of every triangle.
+    pub fn areas(&self) -> &[f32] {
+        &self.areas
+    }
+
+    /// How many triangles have their smallest angle in each of `bins` equally wide ranges from 0 to 60 degrees,
+    /// the most a smallest angle can be.
+    pub fn angle_histogram(&self, bins: usize) -> Vec<usize> {
+        histogram(&self.min_angles, FRAC_PI_3, bins)
+    }
+
+    /// How many triangles have their area in each of `bins` equally wide ranges from 0 to the largest area.
+    pub fn area_histogram(&self, bins: usize) -> Vec<usize> {
+        let largest_area = self.largest_triangle().map_or(0., |(_, area)| area);
+        histogram(&self.areas, largest_area, bins)
+    }
+
+    /// The index of the triangle with the smallest angle and that angle in radians, `None` without triangles.
+    pub fn worst_min_angle_triangle(&self) -> Option<(usize, f32)> {
+        self.min_angles
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// The index of the triangle with the largest area and that area, `None` without triangles.
+    pub fn largest_triangle(&self) -> Option<(usize, f32)> {
+        self.areas
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Counts the values in `bins` equally wide ranges from 0 to `maximum`, the maximum itself is in the last bin.
+fn histogram(values: &[f32], maximum: f32, bins: usize) -> Vec<usize> {
+    let mut counts = vec![0; bins];
+    if bins == 0 {
+        return counts;
+    }
+    for value in values {
+        let bin = if maximum > 0. {
+            (value / maximum * bins as f32).max(0.) as usize
+        } else {
+            0
+        };
+        counts[bin.min(bins - 1)] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeshStatistics;
+    use crate::{triangulate_with_options, CustomError, TriangulateOptions, Vector};
+
+    #[test]
+    fn worst_and_largest_triangle_of_a_square_with_a_point_near_its_bottom(
+    ) -> Result<(), CustomError> {
+        let points = [(0., 0.), (4., 0.), (4., 4.), (0., 4.), (2., 0.2)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        assert_eq!(result.len(), 4);
+        let statistics = MeshStatistics::from_result(&result);
+        let has_vertices = |index: usize, expected: [(f32, f32); 3]| {
+            let triangle = result.triangle(index);
+            expected
+                .iter()
+                .all(|point| (0..3).any(|i| triangle.p(i) == Vector::from(point)))
+        };
+
+        // the flat triangle along the bottom edge
+        let (worst, angle) = statistics.worst_min_angle_triangle().unwrap();
+        assert!(has_vertices(worst, [(0., 0.), (4., 0.), (2., 0.2)]));
+        assert!((angle - 0.1f32.atan()).abs() < 1e-4);
+        // the triangle from the top edge down to the point
+        let (largest, area) = statistics.largest_triangle().unwrap();
+        assert!(has_vertices(largest, [(4., 4.), (0., 4.), (2., 0.2)]));
+        assert!((area - 7.6).abs() < 1e-4);
+
+        assert_eq!(statistics.angle_histogram(6).iter().sum::<usize>(), 4);
+        assert_eq!(statistics.angle_histogram(6)[0], 1);
+        // 0.4, 4, 4 and 7.6 in bins of 1.9
+        assert_eq!(statistics.area_histogram(4), vec![1, 0, 2, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_mesh_has_no_worst_triangle() {
+        let statistics = MeshStatistics::new(&[]);
+        assert_eq!(statistics.worst_min_angle_triangle(), None);
+        assert_eq!(statistics.largest_triangle(), None);
+        assert_eq!(statistics.angle_histogram(3), vec![0, 0, 0]);
+        assert!(statistics.area_histogram(0).is_empty());
+    }
+}
@@ -1,9 +1,34 @@
+pub mod constraint_intersection_rule;
+pub mod constraint_trace;
+pub mod dedup_summary;
+pub mod edge;
+pub mod edge_info;
 pub mod error;
+pub mod estimate;
+pub mod ffi_mesh;
 pub mod found_or_added;
+pub mod hole_problem;
+pub mod hole_report;
+pub mod hole_rule;
+pub mod ids;
+pub mod input_diagnostics;
+pub mod insertion_algorithm;
+pub mod insertion_order;
+pub mod insertion_statistics;
+pub mod mesh_statistics;
+pub mod point2;
 pub mod point_bin_grid;
+pub mod point_on_constraint_rule;
+pub mod triangle;
 pub mod triangle_info;
+pub mod triangle_ref;
 pub mod triangle_set;
-pub mod triangle;
+pub mod triangulate_options;
+pub mod triangulation_algorithm;
+pub mod triangulation_result;
+pub mod triangulation_snapshot;
+pub mod triangulation_view;
 pub mod vector;
-pub mod edge_info;
-pub mod edge;
\ No newline at end of file
+pub mod vertex_marker;
+pub mod vertex_ring;
+pub mod warning;
@@ -0,0 +1,60 @@
+use super::vector::Vector;
+
+/// A point in the plane, that can be triangulated by [`triangulate_generic`](crate::triangulate_generic)
+/// without converting it into a [`Vector`] first.
+///
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_generic, Point2};
+///
+/// struct Node {
+///     position: (f64, f64),
+/// }
+///
+/// impl Point2 for Node {
+///     fn x(&self) -> f32 {
+///         self.position.0 as f32
+///     }
+///     fn y(&self) -> f32 {
+///         self.position.1 as f32
+///     }
+/// }
+///
+/// let nodes = [(0., 0.), (1., 0.), (0., 1.)].map(|position| Node { position });
+/// assert_eq!(triangulate_generic(&nodes, None).unwrap().len(), 1);
+/// ```
+pub trait Point2 {
+    /// The x coordinate.
+    fn x(&self) -> f32;
+    /// The y coordinate.
+    fn y(&self) -> f32;
+}
+
+impl Point2 for Vector {
+    fn x(&self) -> f32 {
+        self.x
+    }
+
+    fn y(&self) -> f32 {
+        self.y
+    }
+}
+
+impl Point2 for (f32, f32) {
+    fn x(&self) -> f32 {
+        self.0
+    }
+
+    fn y(&self) -> f32 {
+        self.1
+    }
+}
+
+impl Point2 for [f32; 2] {
+    fn x(&self) -> f32 {
+        self[0]
+    }
+
+    fn y(&self) -> f32 {
+        self[1]
+    }
+}
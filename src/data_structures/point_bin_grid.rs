@@ -1,44 +1,351 @@
 use super::vector::Vector;
 
+/// A square grid of bins over the unit square, that sorts points so that consecutive points are close to each other.
+///
+/// The triangulation inserts the points in this order, so the search for the triangle containing the next point stays short.
+/// The cells are numbered row by row from the bottom, alternating the direction in every row (serpentine order),
+/// so that the last cell of a row is next to the first cell of the following row:
+/// ```text
+/// 6 7 8 ->
+/// 5 4 3 <-
+/// 0 1 2 ->
+/// ```
+/// The points have to be normalized to `[0, 1]`, like the triangulation does with the input points.
+/// ```
+/// use constrained_denaulay_triangulation::{PointBinGrid, Vector};
+///
+/// let mut grid = PointBinGrid::new(3);
+/// grid.add_point(Vector::new(0.9, 0.5));
+/// grid.add_point(Vector::new(0.1, 0.1));
+/// assert_eq!(grid.cell_index_of(Vector::new(0.9, 0.5)), 3);
+/// let sorted: Vec<Vector> = grid.iter_points_in_insertion_order().collect();
+/// assert_eq!(sorted, vec![Vector::new(0.1, 0.1), Vector::new(0.9, 0.5)]);
+/// ```
+/// With [`with_max_points_per_cell`](Self::with_max_points_per_cell), [`rebin`](Self::rebin) splits the cells
+/// that hold too many points like a quadtree, so that a dense cluster does not end up in a single cell.
+/// ```
+/// use constrained_denaulay_triangulation::{PointBinGrid, Vector};
+///
+/// let mut grid = PointBinGrid::new(2).with_max_points_per_cell(2);
+/// for point in [(0.1, 0.1), (0.4, 0.4), (0.1, 0.4), (0.4, 0.1), (0.9, 0.9)] {
+///     grid.add_point(Vector::new(point.0, point.1));
+/// }
+/// grid.rebin();
+/// assert_eq!(grid.cells()[0].len(), 4);
+/// assert!(grid.leaves().all(|leaf| leaf.len() <= 2));
+/// ```
 #[derive(Debug)]
 pub struct PointBinGrid {
     cells: Vec<Vec<Vector>>,
     grid_size: Vector,
     cells_per_side: usize,
+    max_points_per_cell: Option<usize>,
+    /// The lengths of the parts that [`rebin`](Self::rebin) split every cell into, empty for cells that were not split.
+    leaf_lengths: Vec<Vec<usize>>,
 }
 
+/// How often [`PointBinGrid::rebin`] halves a cell at most. Below this, the points are about as close as f32 allows
+/// inside of the unit square, so cells of points at the same position stay overfull.
+const MAX_SUBDIVISION_DEPTH: usize = 20;
+
 impl PointBinGrid {
+    /// Creates an empty grid with `cells_per_side * cells_per_side` cells.
     pub fn new(cells_per_side: usize) -> Self {
         let grid_size = Vector::new(1., 1.);
-        let cells = vec![vec![]; (cells_per_side * cells_per_side) as usize];
+        let cells = vec![vec![]; cells_per_side * cells_per_side];
 
         PointBinGrid {
             cells,
             grid_size,
             cells_per_side,
+            max_points_per_cell: None,
+            leaf_lengths: vec![vec![]; cells_per_side * cells_per_side],
         }
     }
+
+    /// Lets [`rebin`](Self::rebin) split the cells that hold more than `max_points_per_cell` points.
+    pub fn with_max_points_per_cell(mut self, max_points_per_cell: usize) -> Self {
+        self.max_points_per_cell = Some(max_points_per_cell.max(1));
+        self
+    }
+
+    /// Adds a point to the end of the cell it falls into.
+    ///
+    /// If [`rebin`](Self::rebin) split that cell before, the split is undone and the cell has to be rebinned again.
     pub fn add_point(&mut self, new_point: Vector) {
+        let bin_index = self.cell_index_of(new_point);
+        self.cells[bin_index].push(new_point);
+        self.leaf_lengths[bin_index].clear();
+    }
+
+    /// Splits every cell with more than [`with_max_points_per_cell`](Self::with_max_points_per_cell) points
+    /// into four quarters, and those again, until no part holds too many points.
+    ///
+    /// The points of a split cell are reordered part by part, with the quarters of every part in the order
+    /// of a two by two grid, so consecutive points stay close to each other. The points stay in their cells,
+    /// [`leaves`](Self::leaves) lists the parts. Without a maximum, nothing is split.
+    pub fn rebin(&mut self) {
+        let Some(max_points_per_cell) = self.max_points_per_cell else {
+            return;
+        };
+        let cell_size = Vector::new(
+            self.grid_size.x / self.cells_per_side as f32,
+            self.grid_size.y / self.cells_per_side as f32,
+        );
+        for cell_index in 0..self.cells.len() {
+            if self.cells[cell_index].len() <= max_points_per_cell {
+                self.leaf_lengths[cell_index].clear();
+                continue;
+            }
+            let (row_index, column_index) = self.row_and_column_of_cell(cell_index);
+            let min = Vector::new(
+                column_index as f32 * cell_size.x,
+                row_index as f32 * cell_size.y,
+            );
+            let points = std::mem::take(&mut self.cells[cell_index]);
+            let mut sorted = Vec::with_capacity(points.len());
+            let mut leaf_lengths = Vec::new();
+            subdivide(
+                points,
+                min,
+                min + cell_size,
+                max_points_per_cell,
+                MAX_SUBDIVISION_DEPTH,
+                &mut sorted,
+                &mut leaf_lengths,
+            );
+            self.cells[cell_index] = sorted;
+            self.leaf_lengths[cell_index] = leaf_lengths;
+        }
+    }
+
+    /// The parts of the cells in insertion order: the parts that [`rebin`](Self::rebin) split a cell into,
+    /// or the whole cell, if it was not split.
+    pub fn leaves(&self) -> impl Iterator<Item = &[Vector]> + '_ {
+        self.cells
+            .iter()
+            .zip(&self.leaf_lengths)
+            .flat_map(|(cell, leaf_lengths)| {
+                let whole_cell = leaf_lengths.is_empty().then_some(cell.as_slice());
+                let mut start = 0;
+                let parts = leaf_lengths.iter().map(move |length| {
+                    start += length;
+                    &cell[start - length..start]
+                });
+                whole_cell.into_iter().chain(parts)
+            })
+    }
+
+    /// The row and the column of the cell with the index, undoing the serpentine order.
+    fn row_and_column_of_cell(&self, cell_index: usize) -> (usize, usize) {
+        let row_index = cell_index / self.cells_per_side;
+        let column_index = if row_index.is_multiple_of(2) {
+            cell_index % self.cells_per_side
+        } else {
+            self.cells_per_side - 1 - cell_index % self.cells_per_side
+        };
+        (row_index, column_index)
+    }
+
+    /// The index of the cell in [`cells`](Self::cells) that the point falls into.
+    ///
+    /// A point on the border between cells falls into the cell above or to the right of it.
+    /// Two nearby points on both sides of a border only end up further apart in the insertion order,
+    /// both are still inserted as separate vertices, unless they are the same point for the triangulation.
+    pub fn cell_index_of(&self, point: Vector) -> usize {
         // grid size should be one
-        let row_index =
-            (0.99 * self.cells_per_side as f32 * new_point.y / self.grid_size.y) as usize;
+        let row_index = (0.99 * self.cells_per_side as f32 * point.y / self.grid_size.y) as usize;
         let column_index =
-            (0.99 * self.cells_per_side as f32 * new_point.x / self.grid_size.x) as usize;
+            (0.99 * self.cells_per_side as f32 * point.x / self.grid_size.x) as usize;
 
         // it will be filled like so:
         // 6 7 8 ->
         // 5 4 3 <-
         // 0 1 2 ->
-        let bin_index = if row_index % 2 == 0 {
-            (row_index * self.cells_per_side + column_index) as usize
+        if row_index.is_multiple_of(2) {
+            row_index * self.cells_per_side + column_index
         } else {
-            ((row_index + 1) * self.cells_per_side - column_index - 1) as usize
-        };
-
-        self.cells[bin_index as usize].push(new_point);
+            (row_index + 1) * self.cells_per_side - column_index - 1
+        }
     }
 
+    /// The cells in serpentine order, each with its points in the order they were added.
     pub fn cells(&self) -> &Vec<Vec<Vector>> {
         &self.cells
     }
+
+    /// All points in the order the triangulation inserts them: cell by cell, in the order they were added to their cell.
+    pub fn iter_points_in_insertion_order(&self) -> impl Iterator<Item = Vector> + '_ {
+        self.cells.iter().flatten().copied()
+    }
+}
+
+/// Appends the points to `sorted` quarter by quarter of the square from `min` to `max`,
+/// splitting every quarter with more than `max_points` points again, and the lengths of the parts to `leaf_lengths`.
+fn subdivide(
+    points: Vec<Vector>,
+    min: Vector,
+    max: Vector,
+    max_points: usize,
+    remaining_depth: usize,
+    sorted: &mut Vec<Vector>,
+    leaf_lengths: &mut Vec<usize>,
+) {
+    if points.len() <= max_points || remaining_depth == 0 {
+        if !points.is_empty() {
+            leaf_lengths.push(points.len());
+            sorted.extend(points);
+        }
+        return;
+    }
+    let center = (min + max) / 2.;
+    // the quarters in the order of a two by two grid, like the cells: lower left, lower right, upper right, upper left
+    let mut quarters: [Vec<Vector>; 4] = Default::default();
+    for point in points {
+        let quarter = match (point.x >= center.x, point.y >= center.y) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (true, true) => 2,
+            (false, true) => 3,
+        };
+        quarters[quarter].push(point);
+    }
+    let bounds = [
+        (min, center),
+        (Vector::new(center.x, min.y), Vector::new(max.x, center.y)),
+        (center, max),
+        (Vector::new(min.x, center.y), Vector::new(center.x, max.y)),
+    ];
+    for (quarter, (min, max)) in quarters.into_iter().zip(bounds) {
+        subdivide(
+            quarter,
+            min,
+            max,
+            max_points,
+            remaining_depth - 1,
+            sorted,
+            leaf_lengths,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PointBinGrid;
+    use crate::Vector;
+
+    /// A uniform cloud in the unit square from a small linear congruential generator.
+    fn random_cloud(count: usize) -> Vec<Vector> {
+        let mut state: u32 = 12345;
+        let mut next = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        (0..count).map(|_| Vector::new(next(), next())).collect()
+    }
+
+    #[test]
+    fn iterator_yields_every_point_once() {
+        let points = random_cloud(500);
+        let mut grid = PointBinGrid::new(5);
+        for point in &points {
+            grid.add_point(*point);
+        }
+
+        let mut sorted: Vec<Vector> = grid.iter_points_in_insertion_order().collect();
+        let mut expected = points.clone();
+        let by_coordinates = |a: &Vector, b: &Vector| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y));
+        sorted.sort_by(by_coordinates);
+        expected.sort_by(by_coordinates);
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn consecutive_points_are_close() {
+        let points = random_cloud(1000);
+        let mut grid = PointBinGrid::new(10);
+        for point in &points {
+            grid.add_point(*point);
+        }
+
+        let sorted: Vec<Vector> = grid.iter_points_in_insertion_order().collect();
+        let mean_step = |points: &[Vector]| {
+            points
+                .windows(2)
+                .map(|pair| {
+                    let step = pair[1] - pair[0];
+                    step.x.hypot(step.y)
+                })
+                .sum::<f32>()
+                / (points.len() - 1) as f32
+        };
+        // two random points of the unit square are about 0.52 apart
+        assert!(mean_step(&points) > 0.4);
+        assert!(mean_step(&sorted) < 0.1);
+    }
+
+    /// Most of the points in a tiny cluster, the others spread over the unit square.
+    fn clustered_cloud() -> Vec<Vector> {
+        let outliers = random_cloud(50);
+        let cluster = random_cloud(1950)
+            .into_iter()
+            .map(|point| Vector::new(0.3, 0.3) + point * 0.01);
+        outliers.into_iter().chain(cluster).collect()
+    }
+
+    #[test]
+    fn rebinning_bounds_the_points_per_cell_of_a_cluster() {
+        let points = clustered_cloud();
+        let mut grid = PointBinGrid::new(5).with_max_points_per_cell(32);
+        for point in &points {
+            grid.add_point(*point);
+        }
+        let largest_leaf = |grid: &PointBinGrid| grid.leaves().map(<[Vector]>::len).max();
+        assert!(largest_leaf(&grid) > Some(1950));
+
+        grid.rebin();
+        assert!(largest_leaf(&grid) <= Some(32));
+        assert_eq!(
+            grid.leaves().map(<[Vector]>::len).sum::<usize>(),
+            points.len()
+        );
+        let mut sorted: Vec<Vector> = grid.iter_points_in_insertion_order().collect();
+        let by_coordinates = |a: &Vector, b: &Vector| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y));
+        sorted.sort_by(by_coordinates);
+        let mut expected = points.clone();
+        expected.sort_by(by_coordinates);
+        assert_eq!(sorted, expected);
+
+        // adding a point undoes the split of its cell
+        grid.add_point(Vector::new(0.3, 0.3));
+        assert!(largest_leaf(&grid) > Some(1950));
+    }
+
+    #[test]
+    fn rebinning_keeps_the_points_of_a_cluster_close() {
+        let points = clustered_cloud();
+        let mean_step_in_cluster = |rebin: bool| {
+            let mut grid = PointBinGrid::new(5);
+            if rebin {
+                grid = grid.with_max_points_per_cell(32);
+            }
+            for point in &points {
+                grid.add_point(*point);
+            }
+            grid.rebin();
+            let sorted: Vec<Vector> = grid.iter_points_in_insertion_order().collect();
+            let steps: Vec<f32> = sorted
+                .windows(2)
+                .filter(|pair| {
+                    pair.iter()
+                        .all(|point| (*point - Vector::new(0.305, 0.305)).length() < 0.01)
+                })
+                .map(|pair| (pair[1] - pair[0]).length())
+                .collect();
+            steps.iter().sum::<f32>() / steps.len() as f32
+        };
+        // two random points of the cluster are about 0.005 apart
+        assert!(mean_step_in_cluster(false) > 0.004);
+        assert!(mean_step_in_cluster(true) < 0.001);
+    }
 }
@@ -0,0 +1,10 @@
+/// What happens to a point that lies on an edge of a hole, when the edge is forced into the triangulation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PointOnConstraintRule {
+    /// The edge is split at the point into two constrained edges, so the point becomes a vertex of the hole outline.
+    #[default]
+    Split,
+    /// The triangulation fails with [`CustomError::PointOnConstraint`](crate::CustomError::PointOnConstraint).
+    /// Points added by the tesselation are still split at, because they are not part of the input.
+    Reject,
+}
@@ -1,17 +1,157 @@
 use crate::data_structures::vector::Vector;
-#[derive(Default, Debug,Clone,Copy)]
+
+/// A triangle given by its three vertices, sorted counter-clockwise.
+#[derive(Default, Debug, Clone, Copy)]
 pub struct Triangle {
     vertices: [Vector; 3],
 }
 
 impl Triangle {
+    /// Creates a triangle from its three vertices.
     pub fn new(vertex0: Vector, vertex1: Vector, vertex2: Vector) -> Self {
         Triangle {
             vertices: [vertex0, vertex1, vertex2],
         }
     }
 
+    /// Returns the vertex at the given index (0, 1 or 2).
     pub fn p(&self, index: usize) -> Vector {
         self.vertices[index]
     }
+
+    /// Whether the point is inside of the triangle or on one of its edges, no matter how the vertices are sorted.
+    pub fn contains(&self, point: Vector) -> bool {
+        let sides =
+            [0, 1, 2].map(|i| (self.p((i + 1) % 3) - self.p(i)).cross_product(point - self.p(i)));
+        sides.iter().all(|side| *side >= 0.) || sides.iter().all(|side| *side <= 0.)
+    }
+
+    /// The interior angles in radians, the angle at vertex `i` at index `i`.
+    pub fn angles(&self) -> [f32; 3] {
+        [0, 1, 2].map(|i| {
+            let to_next = self.p((i + 1) % 3) - self.p(i);
+            let to_previous = self.p((i + 2) % 3) - self.p(i);
+            to_next.angle_between(to_previous).abs()
+        })
+    }
+
+    /// The smallest interior angle in radians.
+    pub fn min_angle(&self) -> f32 {
+        self.angles().into_iter().fold(f32::INFINITY, f32::min)
+    }
+
+    /// The center of the circle through all three vertices, `None` if the vertices are collinear.
+    pub fn circumcenter(&self) -> Option<Vector> {
+        let b = self.p(1) - self.p(0);
+        let c = self.p(2) - self.p(0);
+        let denominator = 2. * b.cross_product(c);
+        if denominator == 0. {
+            return None;
+        }
+        let b_squared = b.x * b.x + b.y * b.y;
+        let c_squared = c.x * c.x + c.y * c.y;
+        let center = Vector::new(
+            (c.y * b_squared - b.y * c_squared) / denominator,
+            (b.x * c_squared - c.x * b_squared) / denominator,
+        ) + self.p(0);
+        (center.x.is_finite() && center.y.is_finite()).then_some(center)
+    }
+
+    /// The radius of the circle through all three vertices, `None` if the vertices are collinear.
+    pub fn circumradius(&self) -> Option<f32> {
+        self.circumcenter().map(|center| {
+            let offset = self.p(0) - center;
+            offset.x.hypot(offset.y)
+        })
+    }
+}
+
+impl std::fmt::Display for Triangle {
+    /// Formats the triangle as `Triangle[(x0, y0), (x1, y1), (x2, y2)]`, passing the precision on to the vertices.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Triangle[")?;
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            std::fmt::Display::fmt(vertex, f)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Triangle;
+    use crate::Vector;
+
+    #[test]
+    fn contains_points_inside_and_on_edges() {
+        let (a, b, c) = (
+            Vector::new(0., 0.),
+            Vector::new(4., 0.),
+            Vector::new(0., 4.),
+        );
+        for triangle in [Triangle::new(a, b, c), Triangle::new(a, c, b)] {
+            assert!(triangle.contains(Vector::new(1., 1.)));
+            assert!(triangle.contains(Vector::new(2., 2.)));
+            assert!(triangle.contains(a));
+            assert!(!triangle.contains(Vector::new(3., 3.)));
+            assert!(!triangle.contains(Vector::new(-0.1, 1.)));
+        }
+    }
+
+    #[test]
+    fn display_lists_the_vertices() {
+        let triangle = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(2.5, 0.),
+            Vector::new(1., 1. / 3.),
+        );
+        assert_eq!(
+            triangle.to_string(),
+            "Triangle[(0, 0), (2.5, 0), (1, 0.33333334)]"
+        );
+        assert_eq!(
+            format!("{triangle:.1}"),
+            "Triangle[(0.0, 0.0), (2.5, 0.0), (1.0, 0.3)]"
+        );
+    }
+
+    #[test]
+    fn circumcircle_of_a_right_triangle() {
+        let triangle = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(4., 0.),
+            Vector::new(0., 2.),
+        );
+        // the hypotenuse is the diameter
+        assert_eq!(triangle.circumcenter(), Some(Vector::new(2., 1.)));
+        assert_eq!(triangle.circumradius(), Some(5f32.sqrt()));
+    }
+
+    #[test]
+    fn angles_of_a_right_triangle() {
+        let triangle = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(1., 0.),
+            Vector::new(0., 1.),
+        );
+        let [right, first, second] = triangle.angles();
+        assert!((right - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((first - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+        assert!((second - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+        assert_eq!(triangle.min_angle(), first.min(second));
+    }
+
+    #[test]
+    fn collinear_triangle_has_no_circumcircle() {
+        let triangle = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(1., 1.),
+            Vector::new(2., 2.),
+        );
+        assert_eq!(triangle.circumcenter(), None);
+        assert_eq!(triangle.circumradius(), None);
+    }
 }
@@ -1,25 +1,32 @@
-#[derive(Clone,Copy, Debug, PartialEq)]
+use super::ids::{TriangleId, VertexId};
+
+/// A triangle of a [`TriangleSet`](super::triangle_set::TriangleSet), given by its vertices and its neighbors.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct TriangleInfo {
-    pub vertex_indices: [usize; 3],
-    pub adjacent_triangle_indices: [Option<usize>; 3],
+    /// The vertices, sorted counter-clockwise.
+    pub vertex_indices: [VertexId; 3],
+    /// The triangle across the edge from vertex `i` to vertex `i + 1` at index `i`, `None` at the border of the set.
+    pub adjacent_triangle_indices: [Option<TriangleId>; 3],
 }
 impl TriangleInfo {
-    pub fn new(index_vertices: [usize; 3]) -> Self {
+    /// The triangle with these vertices and without neighbors.
+    pub fn new(index_vertices: [VertexId; 3]) -> Self {
         TriangleInfo {
             vertex_indices: index_vertices,
             adjacent_triangle_indices: [None, None, None],
         }
     }
 
+    /// The same triangle with these neighbors, in the order of [`TriangleInfo::adjacent_triangle_indices`].
     pub fn with_adjacent(
         mut self,
-        adjacent0: Option<usize>,
-        adjacent1: Option<usize>,
-        adjacent2: Option<usize>,
+        adjacent0: Option<TriangleId>,
+        adjacent1: Option<TriangleId>,
+        adjacent2: Option<TriangleId>,
     ) -> TriangleInfo {
         self.adjacent_triangle_indices[0] = adjacent0;
         self.adjacent_triangle_indices[1] = adjacent1;
         self.adjacent_triangle_indices[2] = adjacent2;
         self
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,186 @@
+use std::{collections::HashMap, iter::FusedIterator, ops::Range};
+
+use super::{
+    constraint_trace::ConstraintSource, triangulation_result::TriangulationResult, vector::Vector,
+};
+
+/// Where an output triangle lies in the mesh, like [`VertexMarker`](super::vertex_marker::VertexMarker) for vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriangleKind {
+    /// Every edge of the triangle has a neighbor.
+    Interior,
+    /// An edge of the triangle is on the outline of the mesh, but none on a hole.
+    Hull,
+    /// An edge of the triangle is on the outline of the hole with this index in
+    /// [`TriangulationResult::hole_reports`].
+    /// This takes precedence over [`TriangleKind::Hull`], and the hole with the lowest index over the others.
+    HoleBoundary(usize),
+}
+
+/// An output triangle with everything about it in one place, see [`TriangulationResult::iter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleRef {
+    /// The output index of the triangle.
+    pub index: usize,
+    /// The counter-clockwise indices into [`TriangulationResult::vertices`].
+    pub vertices: [usize; 3],
+    /// The positions of the vertices.
+    pub points: [Vector; 3],
+    /// The adjacent triangle across the edge from vertex `i` to vertex `i + 1`, see [`TriangulationResult::adjacency`].
+    pub neighbors: [Option<usize>; 3],
+    /// Whether the triangle borders the outline or a hole.
+    pub kind: TriangleKind,
+}
+
+/// The iterator over the triangles of a [`TriangulationResult`] as [`TriangleRef`]s,
+/// see [`TriangulationResult::iter`].
+#[derive(Clone, Debug)]
+pub struct TriangleRefs<'a> {
+    result: &'a TriangulationResult,
+    indices: Range<usize>,
+    /// The hole of every constrained edge on the outline of a hole that was not filled again,
+    /// with the vertices of the edge in ascending order.
+    hole_edges: HashMap<(usize, usize), usize>,
+}
+
+impl<'a> TriangleRefs<'a> {
+    pub(crate) fn new(result: &'a TriangulationResult) -> Self {
+        let mut hole_edges = HashMap::new();
+        for trace in &result.constrained_edges {
+            let ConstraintSource::HoleEdge { hole, .. } = trace.source else {
+                continue;
+            };
+            if result.hole_reports[hole].filled {
+                continue;
+            }
+            for edge in trace.vertices.windows(2) {
+                let hole_of_edge = hole_edges
+                    .entry((edge[0].min(edge[1]), edge[0].max(edge[1])))
+                    .or_insert(hole);
+                *hole_of_edge = (*hole_of_edge).min(hole);
+            }
+        }
+        TriangleRefs {
+            result,
+            indices: 0..result.len(),
+            hole_edges,
+        }
+    }
+
+    fn triangle_ref(&self, index: usize) -> TriangleRef {
+        let vertices = self.result.triangles[index];
+        let neighbors = self.result.adjacency[index];
+        let mut kind = TriangleKind::Interior;
+        for edge in (0..3).filter(|edge| neighbors[*edge].is_none()) {
+            let (start, end) = (vertices[edge], vertices[(edge + 1) % 3]);
+            kind = match (self.hole_edges.get(&(start.min(end), start.max(end))), kind) {
+                (Some(hole), TriangleKind::HoleBoundary(other)) => {
+                    TriangleKind::HoleBoundary((*hole).min(other))
+                }
+                (Some(hole), _) => TriangleKind::HoleBoundary(*hole),
+                (None, TriangleKind::Interior) => TriangleKind::Hull,
+                (None, kind) => kind,
+            };
+        }
+        TriangleRef {
+            index,
+            vertices,
+            points: vertices.map(|vertex| self.result.vertices[vertex]),
+            neighbors,
+            kind,
+        }
+    }
+}
+
+impl Iterator for TriangleRefs<'_> {
+    type Item = TriangleRef;
+
+    fn next(&mut self) -> Option<TriangleRef> {
+        self.indices.next().map(|index| self.triangle_ref(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<TriangleRef> {
+        self.indices.nth(n).map(|index| self.triangle_ref(index))
+    }
+}
+
+impl DoubleEndedIterator for TriangleRefs<'_> {
+    fn next_back(&mut self) -> Option<TriangleRef> {
+        self.indices
+            .next_back()
+            .map(|index| self.triangle_ref(index))
+    }
+}
+
+impl ExactSizeIterator for TriangleRefs<'_> {}
+
+impl FusedIterator for TriangleRefs<'_> {}
+
+impl<'a> IntoIterator for &'a TriangulationResult {
+    type Item = TriangleRef;
+    type IntoIter = TriangleRefs<'a>;
+
+    fn into_iter(self) -> TriangleRefs<'a> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TriangleKind;
+    use crate::{triangulate_with_options, CustomError, TriangulateOptions, Vector};
+
+    #[test]
+    fn iterator_has_every_triangle_with_its_kind_in_both_directions() -> Result<(), CustomError> {
+        let square = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)].map(|p| Vector::new(p.0, p.1));
+        let hole = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .map(|p| Vector::new(p.0, p.1))
+            .to_vec();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(2.);
+        let result = triangulate_with_options(&square, Some(&[hole]), &options)?;
+
+        let mut triangles = result.iter();
+        assert_eq!(triangles.len(), result.triangles.len());
+        triangles.next();
+        triangles.next_back();
+        assert_eq!(triangles.len(), result.triangles.len() - 2);
+        let backwards: Vec<usize> = result.iter().rev().map(|triangle| triangle.index).collect();
+        assert_eq!(backwards, (0..result.len()).rev().collect::<Vec<usize>>());
+
+        for triangle in &result {
+            assert_eq!(triangle.vertices, result.triangles[triangle.index]);
+            assert_eq!(triangle.neighbors, result.adjacency[triangle.index]);
+            assert_eq!(
+                triangle.points,
+                triangle.vertices.map(|vertex| result.vertices[vertex])
+            );
+            // the outline of the hole is the only boundary away from the outer square
+            let on_hole =
+                |point: Vector| (4. ..=6.).contains(&point.x) && (4. ..=6.).contains(&point.y);
+            let boundary_edges: Vec<usize> = (0..3)
+                .filter(|edge| triangle.neighbors[*edge].is_none())
+                .collect();
+            let expected = if boundary_edges.is_empty() {
+                TriangleKind::Interior
+            } else if boundary_edges.iter().any(|edge| {
+                on_hole(triangle.points[*edge]) && on_hole(triangle.points[(edge + 1) % 3])
+            }) {
+                TriangleKind::HoleBoundary(0)
+            } else {
+                TriangleKind::Hull
+            };
+            assert_eq!(triangle.kind, expected, "triangle {}", triangle.index);
+        }
+        assert!(result
+            .iter()
+            .any(|triangle| triangle.kind == TriangleKind::Hull));
+        assert!(result
+            .iter()
+            .any(|triangle| triangle.kind == TriangleKind::HoleBoundary(0)));
+        Ok(())
+    }
+}
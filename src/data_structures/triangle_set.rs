@@ -1,18 +1,61 @@
-use std::collections::VecDeque;
-
-use crate::math_utils::{
-    intersection_between_lines, is_point_to_the_left_of_edge, is_point_to_the_right_of_edge,
+use std::collections::{HashSet, VecDeque};
+
+use crate::{
+    math_utils::{
+        calculate_triangle_area, do_segments_cross, is_point_inside_triangle,
+        is_point_to_the_left_of_edge, is_point_to_the_right_of_edge,
+    },
+    triangulation::triangulate_point,
 };
 
 use super::{
-    edge::Edge, edge_info::EdgeInfo, error::CustomError, found_or_added::FoundOrAdded,
-    triangle::Triangle, triangle_info::TriangleInfo, vector::Vector,
+    edge::Edge,
+    edge_info::EdgeInfo,
+    error::CustomError,
+    found_or_added::FoundOrAdded,
+    ids::{TriangleId, VertexId},
+    insertion_statistics::InsertionStatistics,
+    triangle::Triangle,
+    triangle_info::TriangleInfo,
+    vector::Vector,
+    vertex_ring::{walk_vertex_ring, VertexRing},
 };
 
-#[derive(Debug)]
+/// The triangles of a triangulation with their adjacency, the structure every algorithm of the crate works on.
+///
+/// Triangles store their vertices counter-clockwise. The adjacent triangle at index `i` of a [`TriangleInfo`]
+/// is the one across the edge from vertex `i` to vertex `i + 1`. The triangulations of the crate start with a
+/// supertriangle, see [`TriangleSet::add_supertriangle`], and work on points normalized into the unit square.
+///
+/// Reading the set, walking it and inserting points with [`TriangleSet::insert_point`] keep it consistent.
+/// The methods marked as low level change single triangles or points without updating their neighbors;
+/// they are meant for algorithms that restore the adjacency themselves, and so is writing the fields directly.
+/// ```
+/// use constrained_denaulay_triangulation::{Triangle, TriangleSet, Vector};
+///
+/// let mut triangle_set = TriangleSet::new(8);
+/// triangle_set.add_triangle(&Triangle::new(
+///     Vector::new(-10., -10.),
+///     Vector::new(10., -10.),
+///     Vector::new(0., 10.),
+/// ));
+/// let vertex = triangle_set.insert_point(Vector::new(0., 0.)).unwrap().vertex();
+/// assert_eq!(triangle_set.triangle_count(), 3);
+/// assert_eq!(triangle_set.get_triangle_ring(vertex).unwrap().len(), 3);
+/// assert!(triangle_set.insert_point(Vector::new(0., 0.)).unwrap().is_found());
+/// ```
+#[derive(Clone, Debug)]
 pub struct TriangleSet {
+    /// The position of every vertex, indexed by [`VertexId`].
     pub points: Vec<Vector>,
+    /// The vertices and neighbors of every triangle, indexed by [`TriangleId`].
     pub triangle_infos: Vec<TriangleInfo>,
+    /// Edges that must not be swapped away, stored as [`Edge::undirected`].
+    pub constrained_edges: HashSet<Edge>,
+    /// The work done by inserting points so far.
+    pub statistics: InsertionStatistics,
+    /// The vertices of the supertriangle, see [`TriangleSet::add_supertriangle`].
+    pub(crate) supertriangle_vertices: Option<[VertexId; 3]>,
 }
 
 impl TriangleSet {
@@ -21,67 +64,172 @@ impl TriangleSet {
         TriangleSet {
             points: Vec::with_capacity(expected_triangles),
             triangle_infos: Vec::with_capacity(expected_triangles * 3),
+            constrained_edges: HashSet::new(),
+            statistics: InsertionStatistics::default(),
+            supertriangle_vertices: None,
         }
     }
 
+    /// Registers the edge between both vertices as constrained, regardless of its direction.
+    pub fn add_constrained_edge(&mut self, edge_vertex_a: VertexId, edge_vertex_b: VertexId) {
+        self.constrained_edges
+            .insert(Edge::undirected(edge_vertex_a, edge_vertex_b));
+    }
+
+    /// Removes the constraint from the edge between both vertices, regardless of its direction.
+    pub fn remove_constrained_edge(&mut self, edge_vertex_a: VertexId, edge_vertex_b: VertexId) {
+        self.constrained_edges
+            .remove(&Edge::undirected(edge_vertex_a, edge_vertex_b));
+    }
+
+    /// Whether the edge between both vertices is constrained, regardless of its direction.
+    pub fn is_constrained_edge(&self, edge_vertex_a: VertexId, edge_vertex_b: VertexId) -> bool {
+        self.constrained_edges
+            .contains(&Edge::undirected(edge_vertex_a, edge_vertex_b))
+    }
+
+    /// Adds the point, unless there is already one at the same position up to a few ulps of rounding.
     pub fn add_point(&mut self, point_to_add: Vector) -> FoundOrAdded {
         for (idx, point) in self.points.iter().enumerate() {
-            if *point == point_to_add {
-                return FoundOrAdded::Found(idx);
+            if point.is_same_point(point_to_add) {
+                return FoundOrAdded::Found(VertexId(idx));
             }
         }
         self.points.push(point_to_add);
-        FoundOrAdded::Added(self.points.len() - 1)
+        FoundOrAdded::Added(VertexId(self.points.len() - 1))
+    }
+
+    /// Inserts the point into the triangle that contains it and flips the edges around it until the triangulation
+    /// is Delaunay again, without flipping constrained edges. The walk to the point starts at the last triangle.
+    ///
+    /// Returns the existing vertex instead if there already is a point at the same position.
+    /// Fails with [`CustomError::PointNotInTriangle`] if no triangle contains the point.
+    pub fn insert_point(&mut self, point: Vector) -> Result<FoundOrAdded, CustomError> {
+        triangulate_point(self, point)
+    }
+
+    /// Adds the triangle with its points. A clockwise triangle is turned around by swapping its last two vertices,
+    /// because every predicate of the triangulation expects counter-clockwise triangles.
+    pub fn add_triangle(&mut self, triangle: &Triangle) -> TriangleId {
+        let (second, third) = if calculate_triangle_area(triangle) < 0. {
+            (2, 1)
+        } else {
+            (1, 2)
+        };
+        let p0 = self.add_point(triangle.p(0)).vertex();
+        let p1 = self.add_point(triangle.p(second)).vertex();
+        let p2 = self.add_point(triangle.p(third)).vertex();
+        self.add_triangle_info(TriangleInfo::new([p0, p1, p2]))
+    }
+
+    /// Adds the triangle that contains every point to be inserted, like [`TriangleSet::add_triangle`],
+    /// and remembers its vertices, whose triangles are not part of the final mesh.
+    pub fn add_supertriangle(&mut self, supertriangle: &Triangle) -> TriangleId {
+        let triangle = self.add_triangle(supertriangle);
+        self.supertriangle_vertices = Some(self.triangle_infos[triangle.0].vertex_indices);
+        triangle
     }
 
-    pub fn add_triangle(&mut self, triangle: &Triangle) {
-        let p0 = self.add_point(triangle.p(0)).value();
-        let p1 = self.add_point(triangle.p(1)).value();
-        let p2 = self.add_point(triangle.p(2)).value();
-        self.triangle_infos.push(TriangleInfo::new([p0, p1, p2]));
+    /// The vertices of the supertriangle, `None` if none was added.
+    pub fn supertriangle_vertices(&self) -> Option<[VertexId; 3]> {
+        self.supertriangle_vertices
     }
 
-    pub fn add_triangle_info(&mut self, triangle_info_to_add: TriangleInfo) -> usize {
+    /// Whether the vertex is one of the vertices of the supertriangle.
+    pub fn is_supertriangle_vertex(&self, vertex: VertexId) -> bool {
+        self.supertriangle_vertices
+            .is_some_and(|vertices| vertices.contains(&vertex))
+    }
+
+    /// Whether the triangle uses a vertex of the supertriangle.
+    pub fn is_supertriangle_triangle(&self, triangle_index: TriangleId) -> bool {
+        self.triangle_infos[triangle_index.0]
+            .vertex_indices
+            .iter()
+            .any(|vertex| self.is_supertriangle_vertex(*vertex))
+    }
+
+    /// Every vertex that is not a vertex of the supertriangle, in ascending order.
+    pub fn mesh_vertices(&self) -> impl Iterator<Item = VertexId> + '_ {
+        (0..self.points.len())
+            .map(VertexId)
+            .filter(|vertex| !self.is_supertriangle_vertex(*vertex))
+    }
+
+    /// Adds the triangle between points that are already in the set, which have to be sorted counter-clockwise.
+    ///
+    /// Low level: the adjacency of the new triangle and of its neighbors is left to the caller.
+    pub fn add_triangle_info(&mut self, triangle_info_to_add: TriangleInfo) -> TriangleId {
+        debug_assert!(
+            !is_clearly_clockwise(
+                triangle_info_to_add
+                    .vertex_indices
+                    .map(|vertex| self.points[vertex.0])
+            ),
+            "the triangle {:?} is clockwise",
+            triangle_info_to_add.vertex_indices
+        );
         self.triangle_infos.push(triangle_info_to_add);
-        self.triangle_infos.len() - 1
+        TriangleId(self.triangle_infos.len() - 1)
     }
 
+    /// The number of triangles, including removed ones that were not taken out of the set.
     pub fn triangle_count(&self) -> usize {
         self.triangle_infos.len()
     }
 
-    pub fn get_triangle(&self, index: usize) -> Triangle {
-        let p0 = self.points[self.triangle_infos[index].vertex_indices[0]];
-        let p1 = self.points[self.triangle_infos[index].vertex_indices[1]];
-        let p2 = self.points[self.triangle_infos[index].vertex_indices[2]];
-        Triangle::new(p0, p1, p2)
+    /// The positions of the vertices of the triangle.
+    pub fn get_triangle(&self, index: TriangleId) -> Triangle {
+        let vertices = self.triangle_infos[index.0].vertex_indices;
+        Triangle::new(
+            self.points[vertices[0].0],
+            self.points[vertices[1].0],
+            self.points[vertices[2].0],
+        )
     }
 
-    pub fn get_triangle_info(&self, index: usize) -> TriangleInfo {
-        self.triangle_infos[index]
+    /// The vertices and neighbors of the triangle.
+    pub fn get_triangle_info(&self, index: TriangleId) -> TriangleInfo {
+        self.triangle_infos[index.0]
     }
 
-    pub fn get_point_from_vertex(&self, vertex: usize) -> Vector {
-        self.points[vertex]
+    /// The position of the vertex.
+    pub fn get_point_from_vertex(&self, vertex: VertexId) -> Vector {
+        self.points[vertex.0]
     }
 
-    pub fn get_point_from_index(&self, triangle_index: usize, vertex_index: usize) -> &Vector {
-        &self.points[self.triangle_infos[triangle_index].vertex_indices[vertex_index]]
+    /// The position of the vertex at `vertex_index` (0, 1 or 2) of the triangle.
+    pub fn get_point_from_index(&self, triangle_index: TriangleId, vertex_index: usize) -> &Vector {
+        &self.points[self.triangle_infos[triangle_index.0].vertex_indices[vertex_index].0]
     }
 
+    /// The triangle across the edge that starts at `vertex_index` of the triangle, `None` at the border of the set.
     pub fn get_adjacent_triangle_index(
         &self,
-        triangle_index: usize,
+        triangle_index: TriangleId,
         vertex_index: usize,
-    ) -> Option<usize> {
-        self.triangle_infos[triangle_index].adjacent_triangle_indices[vertex_index]
+    ) -> Option<TriangleId> {
+        self.triangle_infos[triangle_index.0].adjacent_triangle_indices[vertex_index]
     }
 
+    /// The triangle that contains the point, found by walking from `start_triangle` towards it.
+    /// Fails with [`CustomError::PointNotInTriangle`] if no triangle contains the point.
     pub fn find_triangle_that_contains_point(
         &self,
         point: Vector,
-        start_triangle: usize,
-    ) -> Result<usize, CustomError> {
+        start_triangle: TriangleId,
+    ) -> Result<TriangleId, CustomError> {
+        self.walk_to_point(point, start_triangle)
+            .map(|(triangle_index, _)| triangle_index)
+    }
+
+    /// Like [`find_triangle_that_contains_point`](Self::find_triangle_that_contains_point),
+    /// but also returns how many triangles were visited.
+    pub fn walk_to_point(
+        &self,
+        point: Vector,
+        start_triangle: TriangleId,
+    ) -> Result<(TriangleId, usize), CustomError> {
         let mut is_triangle_found = false;
         let mut triangle_index = start_triangle;
         let mut checked_triangles = 0;
@@ -109,47 +257,61 @@ impl TriangleSet {
             }
         }
 
-        if checked_triangles >= self.triangle_count() && self.triangle_count() > 1 {
-            return Err(CustomError::PointNotInTriangle);
+        // finding the triangle with the very last check is still a success
+        if !is_triangle_found && self.triangle_count() > 1 {
+            // in a mesh with a concave outline, like a region of the parallel tesselation,
+            // the walk can get stuck at the outline, although the point is in a triangle on its other side
+            return (0..self.triangle_count())
+                .map(TriangleId)
+                .find(|&triangle_index| self.contains_point(triangle_index, point))
+                .map(|triangle_index| (triangle_index, checked_triangles + triangle_index.0 + 1))
+                .ok_or(CustomError::PointNotInTriangle);
         }
 
-        Ok(triangle_index)
+        Ok((triangle_index, checked_triangles))
+    }
+
+    /// Whether the point is inside of the triangle or on one of its edges.
+    fn contains_point(&self, triangle_index: TriangleId, point: Vector) -> bool {
+        is_point_inside_triangle(&self.get_triangle(triangle_index), &point)
     }
 
+    /// Replaces the neighbor `old_adjacent_triangle` of the triangle with `new_adjacent_triangle`.
+    ///
+    /// Low level: the neighbors themselves are not changed.
     pub fn replace_adjacent(
         &mut self,
-        triangle_index: usize,
-        old_adjacent_triangle: Option<usize>,
-        new_adjacent_triangle: Option<usize>,
+        triangle_index: TriangleId,
+        old_adjacent_triangle: Option<TriangleId>,
+        new_adjacent_triangle: Option<TriangleId>,
     ) {
         for vertex_index in 0..3 {
             if self.get_adjacent_triangle_index(triangle_index, vertex_index)
                 == old_adjacent_triangle
             {
-                self.triangle_infos[triangle_index].adjacent_triangle_indices[vertex_index] =
+                self.triangle_infos[triangle_index.0].adjacent_triangle_indices[vertex_index] =
                     new_adjacent_triangle;
             }
         }
     }
 
-    pub fn replace_triangle(&mut self, triangle_index: usize, new_triangle: &TriangleInfo) {
-        for i in 0..3 {
-            self.triangle_infos[triangle_index].vertex_indices[i] = new_triangle.vertex_indices[i];
-            self.triangle_infos[triangle_index].adjacent_triangle_indices[i] =
-                new_triangle.adjacent_triangle_indices[i];
-        }
+    /// Overwrites the vertices and neighbors of the triangle.
+    ///
+    /// Low level: the neighbors are not told about the change.
+    pub fn replace_triangle(&mut self, triangle_index: TriangleId, new_triangle: &TriangleInfo) {
+        self.triangle_infos[triangle_index.0] = *new_triangle;
     }
 
     /// This method gets all the triangle indices for the triangles in a polygon outline and returns those indices.
-    pub fn get_triangles_in_polygon(
+    pub(crate) fn get_triangles_in_polygon(
         &self,
-        polygon_outline: &Vec<usize>,
-        triangles_to_remove: &mut Vec<usize>,
+        polygon_outline: &[VertexId],
+        triangles_to_remove: &mut Vec<TriangleId>,
     ) -> Result<(), CustomError> {
         // TODO This function takes triangles in a specific order.
         // This method assumes that the edges of the triangles to find were created using the same vertex order
-        // It also assumes all triangles are inside a supertriangle, so no adjacent triangles are -1
-        let mut adjacent_triangle_indices: Vec<usize> = Vec::new();
+        // Edges without an adjacent triangle are the border of the mesh, where the search stops as well
+        let mut adjacent_triangle_indices: Vec<TriangleId> = Vec::new();
 
         // First it gets all the triangles of the outline
         for outline_index in 0..polygon_outline.len() {
@@ -159,11 +321,10 @@ impl TriangleSet {
                 polygon_outline[(outline_index + 1) % polygon_outline.len()],
             ) {
                 // A triangle may form a corner, with 2 consecutive outline edges. This avoids adding it twice
-                let current_triangle = edge_in_triangle.triangle_index;
-                let current_edge = edge_in_triangle.edge_index;
-                if triangles_to_remove.len() > 0 {
-                    let last_added_triangle = triangles_to_remove[triangles_to_remove.len() - 1];
-                    let first_added_triangle = triangles_to_remove[0];
+                let current_triangle = edge_in_triangle.triangle_index();
+                if let (Some(&first_added_triangle), Some(&last_added_triangle)) =
+                    (triangles_to_remove.first(), triangles_to_remove.last())
+                {
                     if (last_added_triangle == current_triangle)
                         || (first_added_triangle == current_triangle)
                     {
@@ -171,7 +332,7 @@ impl TriangleSet {
                     }
                 }
 
-                triangles_to_remove.push(edge_in_triangle.triangle_index);
+                triangles_to_remove.push(current_triangle);
 
                 let previous_outline_edge_vertex_a = polygon_outline
                     [(outline_index + polygon_outline.len() - 1) % polygon_outline.len()];
@@ -181,34 +342,34 @@ impl TriangleSet {
                 let next_outline_edge_vertex_b =
                     polygon_outline[(outline_index + 2) % polygon_outline.len()];
 
-                for adjacent_index in 1..3 {
+                let next_edge = edge_in_triangle.next_edge();
+                for other_edge in [next_edge, next_edge.next_edge()] {
                     // For the 2 adjacent triangles of the other 2 edges in the current triangle
                     let mut is_adjacent_triangle_in_outline = false;
-                    if let Some(adjacent_triangle) = self.triangle_infos[current_triangle]
-                        .adjacent_triangle_indices[(current_edge + adjacent_index) % 3]
-                    {
+                    if let Some(adjacent_triangle) = self.get_adjacent_triangle_index(
+                        other_edge.triangle_index(),
+                        other_edge.edge_index(),
+                    ) {
                         // Compares the contiguous edges of the outline, to the right and to the left of the current one, flipped and not flipped, with the adjacent triangle's edges
                         for k in 0..3 {
                             let adjacent_triangle_edge_vertex_a =
-                                self.triangle_infos[adjacent_triangle].vertex_indices[k];
-                            let adjacent_triangle_edge_vertex_b =
-                                self.triangle_infos[adjacent_triangle].vertex_indices[(k + 1) % 3];
-
-                            // TODO it seems like the comparism after the first and third || is unnecessary
-                            if (adjacent_triangle_edge_vertex_a == previous_outline_edge_vertex_a
-                                && adjacent_triangle_edge_vertex_b
-                                    == previous_outline_edge_vertex_b)
-                                || (adjacent_triangle_edge_vertex_a
-                                    == previous_outline_edge_vertex_b
-                                    && adjacent_triangle_edge_vertex_b
-                                        == previous_outline_edge_vertex_a)
-                                || (adjacent_triangle_edge_vertex_a == next_outline_edge_vertex_a
-                                    && adjacent_triangle_edge_vertex_b
-                                        == next_outline_edge_vertex_b)
-                                || (adjacent_triangle_edge_vertex_a == next_outline_edge_vertex_b
-                                    && adjacent_triangle_edge_vertex_b
-                                        == next_outline_edge_vertex_a)
-                            {
+                                self.triangle_infos[adjacent_triangle.0].vertex_indices[k];
+                            let adjacent_triangle_edge_vertex_b = self.triangle_infos
+                                [adjacent_triangle.0]
+                                .vertex_indices[(k + 1) % 3];
+
+                            // TODO it seems like the comparism of the flipped edges is unnecessary
+                            let adjacent_triangle_edge = Edge::new(
+                                adjacent_triangle_edge_vertex_a,
+                                adjacent_triangle_edge_vertex_b,
+                            );
+                            if adjacent_triangle_edge.eq_undirected(&Edge::new(
+                                previous_outline_edge_vertex_a,
+                                previous_outline_edge_vertex_b,
+                            )) || adjacent_triangle_edge.eq_undirected(&Edge::new(
+                                next_outline_edge_vertex_a,
+                                next_outline_edge_vertex_b,
+                            )) {
                                 is_adjacent_triangle_in_outline = true;
                             }
                         }
@@ -218,8 +379,6 @@ impl TriangleSet {
                         {
                             adjacent_triangle_indices.push(adjacent_triangle);
                         }
-                    } else {
-                        return Err(CustomError::PolygonIsOpen);
                     }
                 }
             } else {
@@ -236,13 +395,13 @@ impl TriangleSet {
             if triangles_to_remove.contains(&adjacent_triangle_index) {
                 continue;
             }
-            for i in 0..3 {
-                if let Some(adjacent_to_adjacent_triangle) =
-                    self.triangle_infos[adjacent_triangle_index].adjacent_triangle_indices[i]
-                {
-                    if !triangles_to_remove.contains(&adjacent_to_adjacent_triangle) {
-                        adjacent_triangle_indices.push(adjacent_to_adjacent_triangle);
-                    }
+            for adjacent_to_adjacent_triangle in self.triangle_infos[adjacent_triangle_index.0]
+                .adjacent_triangle_indices
+                .into_iter()
+                .flatten()
+            {
+                if !triangles_to_remove.contains(&adjacent_to_adjacent_triangle) {
+                    adjacent_triangle_indices.push(adjacent_to_adjacent_triangle);
                 }
             }
 
@@ -251,73 +410,255 @@ impl TriangleSet {
         Ok(())
     }
 
+    /// Splits the triangles into the connected regions between the constrained edges and returns the region of every
+    /// triangle together with the number of triangles in every region.
+    ///
+    /// The convex hull separates regions as well, so the triangles of the supertriangle form regions of their own.
+    /// Regions are numbered in the order of their lowest triangle.
+    pub fn label_regions(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut labels = vec![usize::MAX; self.triangle_count()];
+        let mut sizes = Vec::new();
+        for first_triangle in (0..self.triangle_count()).map(TriangleId) {
+            if labels[first_triangle.0] != usize::MAX {
+                continue;
+            }
+            let region = sizes.len();
+            let is_outside = self.is_supertriangle_triangle(first_triangle);
+            let mut size = 0;
+            labels[first_triangle.0] = region;
+            let mut to_check = vec![first_triangle];
+            while let Some(triangle) = to_check.pop() {
+                size += 1;
+                let triangle_info = self.triangle_infos[triangle.0];
+                for edge_index in 0..3 {
+                    let Some(adjacent) = triangle_info.adjacent_triangle_indices[edge_index] else {
+                        continue;
+                    };
+                    if labels[adjacent.0] != usize::MAX
+                        || self.is_supertriangle_triangle(adjacent) != is_outside
+                        || self.is_constrained_edge(
+                            triangle_info.vertex_indices[edge_index],
+                            triangle_info.vertex_indices[(edge_index + 1) % 3],
+                        )
+                    {
+                        continue;
+                    }
+                    labels[adjacent.0] = region;
+                    to_check.push(adjacent);
+                }
+            }
+            sizes.push(size);
+        }
+        (labels, sizes)
+    }
+
+    /// The triangle with the edge from `edge_vertex_a` to `edge_vertex_b`, `None` if there is no such edge.
     // This will find only one edge_info, because edges are directional
     pub fn find_edge_info_for_vertices(
         &self,
-        edge_vertex_a: usize,
-        edge_vertex_b: usize,
+        edge_vertex_a: VertexId,
+        edge_vertex_b: VertexId,
     ) -> Option<EdgeInfo> {
-        for i in 0..self.triangle_count() {
+        for (i, triangle_info) in self.triangle_infos.iter().enumerate() {
             for j in 0..3 {
-                if self.triangle_infos[i].vertex_indices[j] == edge_vertex_a
-                    && self.triangle_infos[i].vertex_indices[(j + 1) % 3] == edge_vertex_b
+                if triangle_info.vertex_indices[j] == edge_vertex_a
+                    && triangle_info.vertex_indices[(j + 1) % 3] == edge_vertex_b
                 {
-                    return Some(EdgeInfo::new(i, j, edge_vertex_a, edge_vertex_b));
+                    return Some(EdgeInfo::new(
+                        TriangleId(i),
+                        j,
+                        triangle_info.vertex_indices,
+                    ));
                 }
             }
         }
         None
     }
 
+    /// Every triangle that uses the vertex, in the order of the set.
     // TODO because of this function this triangle set might need a vec and adj field
     // instead of what it has right now.
     // but not sure, since everything is on the heap as vec
-    pub fn get_triangle_indices_with_vertex(&self, vertex_index: usize) -> Vec<usize> {
+    pub fn get_triangle_indices_with_vertex(&self, vertex_index: VertexId) -> Vec<TriangleId> {
         let mut output_triangles = Vec::new();
-        for i in 0..self.triangle_count() {
-            for j in 0..3 {
-                if self.triangle_infos[i].vertex_indices[j] == vertex_index {
-                    output_triangles.push(i);
-                    break;
-                }
+        for (i, triangle_info) in self.triangle_infos.iter().enumerate() {
+            if triangle_info.vertex_indices.contains(&vertex_index) {
+                output_triangles.push(TriangleId(i));
             }
         }
         output_triangles
     }
 
-    /// This will find the triangle that contains endpoint a of the polygon and intersects with the a-b edge.
-    pub fn find_triangle_that_contains_edge_start_and_intersects(
+    /// The triangles around the vertex in counter-clockwise order.
+    /// `None` if the vertex is not part of any triangle or the triangles do not close around it.
+    pub fn get_triangle_ring(&self, vertex: VertexId) -> Option<Vec<TriangleId>> {
+        let start = self
+            .triangle_infos
+            .iter()
+            .position(|triangle_info| triangle_info.vertex_indices.contains(&vertex))?;
+        self.get_triangle_ring_from(vertex, TriangleId(start))
+    }
+
+    /// The triangles around the vertex in counter-clockwise order, starting with `start`, which has to contain the vertex.
+    /// `None` if the triangles do not close around the vertex.
+    pub fn get_triangle_ring_from(
         &self,
-        endpoint_a_index: usize,
-        endpoint_b_index: usize,
-    ) -> usize {
-        let triangles_with_endpoint: Vec<usize> =
-            self.get_triangle_indices_with_vertex(endpoint_a_index);
+        vertex: VertexId,
+        start: TriangleId,
+    ) -> Option<Vec<TriangleId>> {
+        let mut ring = vec![start];
+        let mut current = start;
+        loop {
+            let info = self.triangle_infos[current.0];
+            let position = info.vertex_indices.iter().position(|v| *v == vertex)?;
+            // The next triangle counter-clockwise shares the edge that ends at the vertex
+            current = info.adjacent_triangle_indices[(position + 2) % 3]?;
+            if current == start {
+                return Some(ring);
+            }
+            if ring.len() >= self.triangle_count() {
+                return None;
+            }
+            ring.push(current);
+        }
+    }
 
-        let mut found_triangle = None;
-        let endpoint_a = self.points[endpoint_a_index];
-        let endpoint_b = self.points[endpoint_b_index];
+    /// The neighbors of the vertex in counter-clockwise order, `None` if no triangle uses the vertex.
+    ///
+    /// Every triangle of the set counts, also the ones of the supertriangle and the ones a triangulation removed
+    /// for its holes, so the vertices on the hull of the points have closed rings through the supertriangle.
+    /// The rings in the output of a triangulation are given by [`vertex_ring`](crate::vertex_ring).
+    pub fn vertex_ring(&self, vertex: VertexId) -> Option<VertexRing<VertexId>> {
+        let start = self
+            .triangle_infos
+            .iter()
+            .position(|triangle_info| triangle_info.vertex_indices.contains(&vertex))?;
+        Some(walk_vertex_ring(
+            vertex,
+            start,
+            |triangle| self.triangle_infos[triangle].vertex_indices,
+            |triangle, edge| {
+                self.triangle_infos[triangle].adjacent_triangle_indices[edge]
+                    .map(|adjacent| adjacent.0)
+            },
+            self.triangle_count(),
+        ))
+    }
 
-        for i in 0..triangles_with_endpoint.len() {
-            let mut vertex_position_in_triangle = None;
-            for j in 0..3 {
-                if self.triangle_infos[triangles_with_endpoint[i]].vertex_indices[j]
-                    == endpoint_a_index
-                {
-                    vertex_position_in_triangle = Some(j);
-                    break;
+    /// The number of edges at the vertex, see [`TriangleSet::vertex_ring`].
+    pub fn vertex_degree(&self, vertex: VertexId) -> usize {
+        self.vertex_ring(vertex).map_or(0, |ring| ring.degree())
+    }
+
+    /// Removes a triangle that no other triangle is adjacent to anymore, by moving the last triangle into its place.
+    /// Returns the previous index of the moved triangle, if one was moved.
+    ///
+    /// Low level: triangles that still use the removed one as their neighbor keep pointing at its index.
+    pub fn remove_triangle(&mut self, triangle_index: TriangleId) -> Option<TriangleId> {
+        let last_triangle = TriangleId(self.triangle_count() - 1);
+        self.triangle_infos.swap_remove(triangle_index.0);
+        if triangle_index == last_triangle {
+            return None;
+        }
+        for adjacent in self.triangle_infos[triangle_index.0]
+            .adjacent_triangle_indices
+            .into_iter()
+            .flatten()
+        {
+            self.replace_adjacent(adjacent, Some(last_triangle), Some(triangle_index));
+        }
+        Some(last_triangle)
+    }
+
+    /// Removes a point that no triangle uses anymore, by moving the last point into its place.
+    /// Returns the previous index of the moved point, if one was moved.
+    ///
+    /// Low level: a triangle that still uses the removed point ends up with the moved one instead.
+    pub fn remove_point(&mut self, vertex: VertexId) -> Option<VertexId> {
+        let last_vertex = VertexId(self.points.len() - 1);
+        self.points.swap_remove(vertex.0);
+        if vertex == last_vertex {
+            return None;
+        }
+        for triangle_info in &mut self.triangle_infos {
+            for vertex_index in &mut triangle_info.vertex_indices {
+                if *vertex_index == last_vertex {
+                    *vertex_index = vertex;
+                }
+            }
+        }
+        self.constrained_edges = self
+            .constrained_edges
+            .iter()
+            .map(|edge| {
+                let rename = |v: VertexId| if v == last_vertex { vertex } else { v };
+                Edge::undirected(rename(edge.vertex_a()), rename(edge.vertex_b()))
+            })
+            .collect();
+        if let Some(supertriangle_vertices) = &mut self.supertriangle_vertices {
+            for supertriangle_vertex in supertriangle_vertices {
+                if *supertriangle_vertex == last_vertex {
+                    *supertriangle_vertex = vertex;
                 }
             }
-            let triangle_edge_point1 = self.points[self.triangle_infos[triangles_with_endpoint[i]]
-                .vertex_indices[(vertex_position_in_triangle.unwrap() + 1) % 3]];
-            let triangle_edge_point2 = self.points[self.triangle_infos[triangles_with_endpoint[i]]
-                .vertex_indices[(vertex_position_in_triangle.unwrap() + 2) % 3]];
+        }
+        Some(last_vertex)
+    }
+
+    /// Moves the points into a new order, where `order[i]` is the vertex that becomes vertex `i`.
+    /// `order` must list every vertex exactly once, or the triangles end up with the wrong vertices.
+    pub fn reorder_vertices(&mut self, order: &[VertexId]) {
+        let mut new_vertex = vec![VertexId(0); order.len()];
+        for (new, old) in order.iter().enumerate() {
+            new_vertex[old.0] = VertexId(new);
+        }
+        self.points = order.iter().map(|old| self.points[old.0]).collect();
+        for triangle_info in &mut self.triangle_infos {
+            for vertex_index in &mut triangle_info.vertex_indices {
+                *vertex_index = new_vertex[vertex_index.0];
+            }
+        }
+        self.constrained_edges = self
+            .constrained_edges
+            .iter()
+            .map(|edge| {
+                Edge::undirected(new_vertex[edge.vertex_a().0], new_vertex[edge.vertex_b().0])
+            })
+            .collect();
+        self.supertriangle_vertices = self
+            .supertriangle_vertices
+            .map(|vertices| vertices.map(|vertex| new_vertex[vertex.0]));
+    }
+
+    /// This will find the triangle that contains endpoint a of the polygon and intersects with the a-b edge.
+    pub(crate) fn find_triangle_that_contains_edge_start_and_intersects(
+        &self,
+        endpoint_a_index: VertexId,
+        endpoint_b_index: VertexId,
+    ) -> TriangleId {
+        let triangles_with_endpoint: Vec<TriangleId> =
+            self.get_triangle_indices_with_vertex(endpoint_a_index);
+
+        let mut found_triangle = None;
+        let endpoint_a = self.get_point_from_vertex(endpoint_a_index);
+        let endpoint_b = self.get_point_from_vertex(endpoint_b_index);
+
+        for triangle_with_endpoint in triangles_with_endpoint {
+            let vertex_indices = self.triangle_infos[triangle_with_endpoint.0].vertex_indices;
+            let vertex_position_in_triangle = vertex_indices
+                .iter()
+                .position(|vertex| *vertex == endpoint_a_index)
+                .expect("The triangle was found by this vertex");
+            let triangle_edge_point1 =
+                self.get_point_from_vertex(vertex_indices[(vertex_position_in_triangle + 1) % 3]);
+            let triangle_edge_point2 =
+                self.get_point_from_vertex(vertex_indices[(vertex_position_in_triangle + 2) % 3]);
 
             // Is the line in the angle between the 2 contiguous edges of the triangle?
             if is_point_to_the_left_of_edge(&endpoint_a, &triangle_edge_point1, &endpoint_b)
                 && is_point_to_the_left_of_edge(&triangle_edge_point2, &endpoint_a, &endpoint_b)
             {
-                found_triangle = Some(triangles_with_endpoint[i]);
+                found_triangle = Some(triangle_with_endpoint);
                 break;
             }
         }
@@ -325,11 +666,13 @@ impl TriangleSet {
         found_triangle.expect("The beginning should at least be in the super triangle.")
     }
 
-    pub fn get_intersecting_edges(
+    /// The edges the segment from `line_endpoint_a` to `line_endpoint_b` crosses, in order,
+    /// walking from `start_triangle`, which has to contain the start of the segment.
+    pub(crate) fn get_intersecting_edges(
         &self,
         line_endpoint_a: Vector,
         line_endpoint_b: Vector,
-        start_triangle: usize,
+        start_triangle: TriangleId,
     ) -> VecDeque<Edge> {
         let mut intersected_triangle_edges = VecDeque::<Edge>::new();
         let mut is_triangle_containing_b_found = false;
@@ -340,10 +683,11 @@ impl TriangleSet {
             let mut tentative_adjacent_triangle = None;
 
             for i in 0..3 {
-                let edge_vertex_a = self.triangle_infos[triangle_index].vertex_indices[i];
-                let edge_vertex_b = self.triangle_infos[triangle_index].vertex_indices[(i + 1) % 3];
-                let current_a = self.points[edge_vertex_a];
-                let current_b = self.points[edge_vertex_b];
+                let edge_vertex_a = self.triangle_infos[triangle_index.0].vertex_indices[i];
+                let edge_vertex_b =
+                    self.triangle_infos[triangle_index.0].vertex_indices[(i + 1) % 3];
+                let current_a = self.get_point_from_vertex(edge_vertex_a);
+                let current_b = self.get_point_from_vertex(edge_vertex_b);
 
                 // if one point it the endpoint, then this is the end triangle
                 if current_a == line_endpoint_b || current_b == line_endpoint_b {
@@ -353,33 +697,12 @@ impl TriangleSet {
 
                 if is_point_to_the_right_of_edge(&current_a, &current_b, &line_endpoint_b) {
                     tentative_adjacent_triangle = Some(i);
-                    if intersection_between_lines(
-                        &current_a,
-                        &current_b,
-                        &line_endpoint_a,
-                        &line_endpoint_b,
-                    )
-                    .is_some()
+                    if do_segments_cross(&current_a, &current_b, &line_endpoint_a, &line_endpoint_b)
                     {
                         let new_edge = Edge::new(edge_vertex_a, edge_vertex_b);
-
-                        // TODO THIS IS SHIT
-                        if let Some(temp_edge) = intersected_triangle_edges.pop_back() {
-                            if temp_edge == new_edge {
-                                intersected_triangle_edges.push_back(new_edge);
-                            } else {
-                                has_crossed_edge = true;
-                                intersected_triangle_edges.push_back(temp_edge);
-                                intersected_triangle_edges.push_back(new_edge);
-                                triangle_index = self.triangle_infos[triangle_index]
-                                    .adjacent_triangle_indices[i]
-                                    .unwrap();
-                                break;
-                            }
-                        } else {
+                        if push_crossed_edge(&mut intersected_triangle_edges, new_edge) {
                             has_crossed_edge = true;
-                            intersected_triangle_edges.push_back(new_edge);
-                            triangle_index = self.triangle_infos[triangle_index]
+                            triangle_index = self.triangle_infos[triangle_index.0]
                                 .adjacent_triangle_indices[i]
                                 .unwrap();
                             break;
@@ -391,8 +714,8 @@ impl TriangleSet {
             // Continue searching at a different adjacent triangle
             if !has_crossed_edge {
                 if let Some(tentative_adjacent_triangle) = tentative_adjacent_triangle {
-                    triangle_index = self.triangle_infos[triangle_index].adjacent_triangle_indices
-                        [tentative_adjacent_triangle]
+                    triangle_index = self.triangle_infos[triangle_index.0]
+                        .adjacent_triangle_indices[tentative_adjacent_triangle]
                         .expect("This would result in an endless loop");
                 }
             }
@@ -400,3 +723,123 @@ impl TriangleSet {
         intersected_triangle_edges
     }
 }
+
+/// Adds the edge a walk crossed, unless it is the edge the walk crossed last, in either direction.
+/// Going back over that edge would step back into the previous triangle, so the walk has to look for another edge.
+fn push_crossed_edge(crossed_edges: &mut VecDeque<Edge>, edge: Edge) -> bool {
+    if crossed_edges
+        .back()
+        .is_some_and(|last_edge| last_edge.eq_undirected(&edge))
+    {
+        return false;
+    }
+    crossed_edges.push_back(edge);
+    true
+}
+
+/// Whether the vertices turn clockwise by more than rounding them to f32 can explain.
+/// Inserting a point on an edge creates triangles whose vertices were collinear before the rounding.
+fn is_clearly_clockwise([a, b, c]: [Vector; 3]) -> bool {
+    let difference = |p: Vector, q: Vector| (q.x as f64 - p.x as f64, q.y as f64 - p.y as f64);
+    let (ab, ac, bc) = (difference(a, b), difference(a, c), difference(b, c));
+    let determinant = ab.0 * ac.1 - ab.1 * ac.0;
+    let magnitude = [a, b, c]
+        .iter()
+        .map(|point| point.x.abs().max(point.y.abs()) as f64)
+        .fold(0., f64::max);
+    let perimeter: f64 = [ab, ac, bc].iter().map(|side| side.0.hypot(side.1)).sum();
+    determinant < -4. * f32::EPSILON as f64 * magnitude * perimeter
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::{push_crossed_edge, TriangleSet};
+    use crate::{
+        data_structures::{edge::Edge, triangle_info::TriangleInfo},
+        math_utils::calculate_triangle_area,
+        triangulate_with_options, CustomError, Triangle, TriangulateOptions, Vector, VertexId,
+    };
+
+    #[test]
+    fn crossing_the_last_edge_back_is_not_recorded() {
+        let mut crossed_edges = VecDeque::new();
+        let entered = Edge::new(VertexId(4), VertexId(9));
+        assert!(push_crossed_edge(&mut crossed_edges, entered));
+        // the triangle behind the edge sees it in the other direction
+        assert!(!push_crossed_edge(
+            &mut crossed_edges,
+            Edge::new(VertexId(9), VertexId(4))
+        ));
+        assert!(!push_crossed_edge(&mut crossed_edges, entered));
+        assert!(push_crossed_edge(
+            &mut crossed_edges,
+            Edge::new(VertexId(9), VertexId(5))
+        ));
+        assert_eq!(
+            crossed_edges,
+            [entered, Edge::new(VertexId(9), VertexId(5))]
+        );
+    }
+
+    #[test]
+    fn clockwise_triangles_are_turned_around_when_added() {
+        let mut triangle_set = TriangleSet::new(1);
+        let clockwise = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(0., 1.),
+            Vector::new(1., 0.),
+        );
+        let triangle = triangle_set.add_triangle(&clockwise);
+        assert!(calculate_triangle_area(&triangle_set.get_triangle(triangle)) > 0.);
+        assert_eq!(triangle_set.get_triangle(triangle).p(0), clockwise.p(0));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is clockwise")]
+    fn adding_a_clockwise_triangle_info_panics_in_debug_builds() {
+        let mut triangle_set = TriangleSet::new(1);
+        for point in [(0., 0.), (0., 1.), (1., 0.)] {
+            triangle_set.add_point(Vector::from(&point));
+        }
+        triangle_set.add_triangle_info(TriangleInfo::new([VertexId(0), VertexId(1), VertexId(2)]));
+    }
+
+    #[test]
+    fn rings_in_the_triangle_set_close_through_the_supertriangle() -> Result<(), CustomError> {
+        let points: Vec<Vector> = [(0., 0.), (2., 0.), (2., 2.), (0., 2.), (1., 1.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        let triangle_set = result.triangle_set();
+        let vertex_id = |point: (f32, f32)| {
+            let vertex = result
+                .vertices
+                .iter()
+                .position(|vertex| *vertex == Vector::from(&point));
+            result.vertex_ids[vertex.unwrap()]
+        };
+
+        let center = triangle_set.vertex_ring(vertex_id((1., 1.))).unwrap();
+        assert!(center.is_closed);
+        assert_eq!(triangle_set.vertex_degree(vertex_id((1., 1.))), 4);
+        // a corner of the square is connected to the supertriangle as well
+        let corner = triangle_set.vertex_ring(vertex_id((0., 0.))).unwrap();
+        assert!(corner.is_closed);
+        assert!(corner
+            .vertices
+            .iter()
+            .any(|vertex| triangle_set.is_supertriangle_vertex(*vertex)));
+        let supertriangle_vertex = triangle_set.supertriangle_vertices().unwrap()[0];
+        assert!(
+            !triangle_set
+                .vertex_ring(supertriangle_vertex)
+                .unwrap()
+                .is_closed
+        );
+        Ok(())
+    }
+}
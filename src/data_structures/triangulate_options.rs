@@ -0,0 +1,406 @@
+use super::{
+    constraint_intersection_rule::ConstraintIntersectionRule, error::CustomError,
+    hole_rule::HoleRule, insertion_algorithm::InsertionAlgorithm, insertion_order::InsertionOrder,
+    point_on_constraint_rule::PointOnConstraintRule,
+    triangulation_algorithm::TriangulationAlgorithm, vector::Vector,
+};
+
+/// The default for [`TriangulateOptions::steiner_point_limit`].
+pub const DEFAULT_STEINER_POINT_LIMIT: usize = 1_000_000;
+
+/// Everything that can be configured for a triangulation, apart from the points and holes themselves.
+///
+/// ```
+/// use constrained_denaulay_triangulation::TriangulateOptions;
+///
+/// let options = TriangulateOptions::new()
+///     .with_maximum_triangle_area(0.5)
+///     .with_steiner_point_limit(1000);
+/// assert_eq!(options.maximum_triangle_area, Some(0.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriangulateOptions {
+    /// The triangles inside of the point cloud will be tesselated until none of them is bigger than this area.
+    /// The area is given in the same units as the input points.
+    pub maximum_triangle_area: Option<f32>,
+    /// The maximum number of points the tesselation may add.
+    /// If the tesselation needs more points, [`strict_steiner_point_limit`](Self::strict_steiner_point_limit)
+    /// decides whether the triangulation fails or the mesh stays partially tesselated.
+    pub steiner_point_limit: usize,
+    /// Fail with [`CustomError::SteinerPointLimitExceeded`] when the tesselation needs more points than
+    /// the `steiner_point_limit`. Otherwise the tesselation stops there, leaving the mesh partially tesselated,
+    /// and the result contains a [`Warning::RefinementBudgetExhausted`](super::warning::Warning::RefinementBudgetExhausted).
+    /// The splits of a [`conforming`](Self::conforming) triangulation always fail at the limit.
+    pub strict_steiner_point_limit: bool,
+    /// The most vertices the result may have, for a fixed time budget.
+    ///
+    /// The input points are taken in their order, so the most important ones have to come first.
+    /// The end points of the [`segments`](Self::segments) are taken ahead of the others.
+    /// The vertices of the holes are always inserted and count against the limit as well,
+    /// only the input points after the limit are skipped and the tesselation stops once the limit is reached.
+    /// The result is then not a triangulation of all input points anymore,
+    /// it contains a [`Warning::VertexLimitReached`](super::warning::Warning::VertexLimitReached).
+    /// Fails with [`CustomError::InvalidParameter`] if the holes do not leave room for three input points
+    /// or for the end points of the segments, and if it would cut the outline of
+    /// [`assume_closed_boundary`](Self::assume_closed_boundary).
+    pub max_vertices: Option<usize>,
+    /// Removes exact duplicates from the input points before triangulating and reports their number
+    /// in [`TriangulationResult::removed_duplicates`](crate::TriangulationResult::removed_duplicates).
+    pub dedup_input: bool,
+    /// Lists the input points first in the output vertices, in the order of the input, followed by the points of
+    /// the holes and of the tesselation. Otherwise their order depends on the order of insertion.
+    ///
+    /// Input points at the same position share the vertex of the first of them, so the vertex `k` is the input point
+    /// [`DedupSummary::first_occurrence`](crate::DedupSummary::first_occurrence)`[k]`, which is `k`
+    /// for input without duplicates.
+    pub preserve_input_order: bool,
+    /// The order in which the input points are inserted, which only changes how long the triangulation takes.
+    pub insertion_order: InsertionOrder,
+    /// Splits the cells of the grid that sorts the input points, when they hold a lot more points than the average,
+    /// so that the points of a dense cluster are sorted as well instead of landing in one cell.
+    /// Like the insertion order, this only changes how long the triangulation takes.
+    pub adaptive_binning: bool,
+    /// How the input points are inserted, which only changes how long the triangulation takes.
+    /// The points added by the tesselation and the holes are always inserted with [`InsertionAlgorithm::Flips`].
+    pub insertion_algorithm: InsertionAlgorithm,
+    /// How the delaunay triangulation of the input points is built.
+    pub algorithm: TriangulationAlgorithm,
+    /// Which triangles the holes remove, when hole polygons are nested inside of each other.
+    pub hole_rule: HoleRule,
+    /// What happens to points that lie on an edge of a hole.
+    pub point_on_constraint: PointOnConstraintRule,
+    /// What happens to a constrained edge that crosses an earlier one, e.g. within a figure-eight hole.
+    pub constraint_intersection: ConstraintIntersectionRule,
+    /// Splits the constrained edges until the whole mesh is delaunay, also across the constrained edges,
+    /// instead of only a constrained delaunay triangulation.
+    ///
+    /// After all constrained edges are inserted, every constrained edge with a vertex across it inside of the
+    /// circumcircle of the triangle on its other side is split at its midpoint, until there is none left.
+    /// The new vertices become part of the holes and of the [`ConstraintTrace`](crate::ConstraintTrace)s,
+    /// and count against the [`steiner_point_limit`](Self::steiner_point_limit).
+    /// Constrained edges that meet at small angles need many points.
+    pub conforming: bool,
+    /// Treats the input points as the outline of a polygon, in order and closed from the last point back to the first,
+    /// instead of as a point cloud. The triangles between the outline and the convex hull are left out.
+    /// The outline must not intersect itself.
+    pub assume_closed_boundary: bool,
+    /// Edges between two input points, given as their indices, that have to become edges of the mesh,
+    /// like the segments of [`triangulate_pslg`](crate::triangulate_pslg).
+    /// They must not cross each other, but a segment through a point is split at it.
+    pub segments: Vec<(usize, usize)>,
+    /// Points in regions that are enclosed by constrained edges, like the hole points of Triangle.
+    ///
+    /// Every triangle that can be reached from a seed without crossing a constrained edge is removed,
+    /// after the segments, the closed boundary and the hole polygons are inserted.
+    /// Every seed gets a [`HoleReport`](crate::HoleReport) without polygon vertices, after the ones of the hole polygons.
+    /// A seed outside of the mesh or in a region that is already removed removes nothing and adds a
+    /// [`Warning::HoleSeedOutsideMesh`](super::warning::Warning::HoleSeedOutsideMesh) or a
+    /// [`Warning::HoleSeedInRemovedRegion`](super::warning::Warning::HoleSeedInRemovedRegion).
+    pub hole_seeds: Vec<Vector>,
+    /// The smallest and the biggest x and y coordinate of the input points, instead of measuring them.
+    ///
+    /// Without bounds the points have to be looked at once before any of them can be normalized, so
+    /// [`triangulate_from_iter`](crate::triangulate_from_iter) has to collect them first. With bounds it normalizes
+    /// them while reading them, and input points outside of the bounds fail with [`CustomError::PointOutsideBounds`].
+    /// Bounds much bigger than the points waste precision, because the points are normalized into a small part
+    /// of the unit square.
+    pub bounds: Option<(Vector, Vector)>,
+    /// A 2x2 matrix `[a, b, c, d]` that maps every input point `(x, y)` to `(a x + b y, c x + d y)` before it is
+    /// normalized, and that is undone for the output.
+    ///
+    /// The normalization and the grids that sort the points are aligned with the axes, so points in a rotated
+    /// or sheared frame fill them badly. The mesh is Delaunay in the transformed frame, which for a shear
+    /// or a scaling is a different mesh than in the input frame. [`TriangulateOptions::bounds`]
+    /// and [`TriangulateOptions::maximum_triangle_area`] stay in the units of the input.
+    /// The determinant has to be positive, so that counter-clockwise polygons stay counter-clockwise.
+    pub pre_transform: Option<[f32; 4]>,
+    /// Tesselates on a single thread, so that the mesh does not depend on the number of threads.
+    ///
+    /// Otherwise the tesselation splits the mesh into more regions the more threads the rayon pool has,
+    /// and the triangles differ between machines. Tesselations without a strict steiner point limit always run on one thread,
+    /// because which points the budget allows depends on the order of all insertions.
+    #[cfg(feature = "rayon")]
+    pub deterministic: bool,
+}
+
+impl Default for TriangulateOptions {
+    fn default() -> Self {
+        TriangulateOptions {
+            maximum_triangle_area: None,
+            steiner_point_limit: DEFAULT_STEINER_POINT_LIMIT,
+            strict_steiner_point_limit: true,
+            max_vertices: None,
+            dedup_input: false,
+            preserve_input_order: false,
+            insertion_order: InsertionOrder::Spatial,
+            adaptive_binning: false,
+            insertion_algorithm: InsertionAlgorithm::Flips,
+            algorithm: TriangulationAlgorithm::Incremental,
+            hole_rule: HoleRule::Union,
+            point_on_constraint: PointOnConstraintRule::Split,
+            constraint_intersection: ConstraintIntersectionRule::Split,
+            conforming: false,
+            assume_closed_boundary: false,
+            segments: Vec::new(),
+            hole_seeds: Vec::new(),
+            bounds: None,
+            pre_transform: None,
+            #[cfg(feature = "rayon")]
+            deterministic: false,
+        }
+    }
+}
+
+impl TriangulateOptions {
+    /// Creates the default options: no tesselation and the default steiner point limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum area of the tesselated triangles.
+    pub fn with_maximum_triangle_area(mut self, maximum_triangle_area: f32) -> Self {
+        self.maximum_triangle_area = Some(maximum_triangle_area);
+        self
+    }
+
+    /// Sets the maximum number of points the tesselation may add.
+    pub fn with_steiner_point_limit(mut self, steiner_point_limit: usize) -> Self {
+        self.steiner_point_limit = steiner_point_limit;
+        self
+    }
+
+    /// Sets whether the triangulation fails or stops tesselating, when the steiner point limit is not enough.
+    pub fn with_strict_steiner_point_limit(mut self, strict_steiner_point_limit: bool) -> Self {
+        self.strict_steiner_point_limit = strict_steiner_point_limit;
+        self
+    }
+
+    /// Sets the most vertices the result may have.
+    pub fn with_max_vertices(mut self, max_vertices: usize) -> Self {
+        self.max_vertices = Some(max_vertices);
+        self
+    }
+
+    /// Sets whether exact duplicates are removed from the input points up front.
+    pub fn with_dedup_input(mut self, dedup_input: bool) -> Self {
+        self.dedup_input = dedup_input;
+        self
+    }
+
+    /// Sets whether the input points are the first output vertices, in the order of the input.
+    pub fn with_preserve_input_order(mut self, preserve_input_order: bool) -> Self {
+        self.preserve_input_order = preserve_input_order;
+        self
+    }
+
+    /// Sets the order in which the input points are inserted.
+    pub fn with_insertion_order(mut self, insertion_order: InsertionOrder) -> Self {
+        self.insertion_order = insertion_order;
+        self
+    }
+
+    /// Sets whether overfull cells of the grid that sorts the input points are split.
+    pub fn with_adaptive_binning(mut self, adaptive_binning: bool) -> Self {
+        self.adaptive_binning = adaptive_binning;
+        self
+    }
+
+    /// Sets how the input points are inserted.
+    pub fn with_insertion_algorithm(mut self, insertion_algorithm: InsertionAlgorithm) -> Self {
+        self.insertion_algorithm = insertion_algorithm;
+        self
+    }
+
+    /// Sets how the delaunay triangulation of the input points is built.
+    pub fn with_algorithm(mut self, algorithm: TriangulationAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets which triangles the holes remove, when hole polygons are nested inside of each other.
+    pub fn with_hole_rule(mut self, hole_rule: HoleRule) -> Self {
+        self.hole_rule = hole_rule;
+        self
+    }
+
+    /// Sets what happens to points that lie on an edge of a hole.
+    pub fn with_point_on_constraint(mut self, point_on_constraint: PointOnConstraintRule) -> Self {
+        self.point_on_constraint = point_on_constraint;
+        self
+    }
+
+    /// Sets what happens to a constrained edge that crosses an earlier one.
+    pub fn with_constraint_intersection(
+        mut self,
+        constraint_intersection: ConstraintIntersectionRule,
+    ) -> Self {
+        self.constraint_intersection = constraint_intersection;
+        self
+    }
+
+    /// Sets whether the constrained edges are split until the whole mesh is delaunay.
+    pub fn with_conforming(mut self, conforming: bool) -> Self {
+        self.conforming = conforming;
+        self
+    }
+
+    /// Sets whether the input points are the outline of a polygon instead of a point cloud.
+    pub fn with_assume_closed_boundary(mut self, assume_closed_boundary: bool) -> Self {
+        self.assume_closed_boundary = assume_closed_boundary;
+        self
+    }
+
+    /// Sets the edges between input points that have to become edges of the mesh.
+    pub fn with_segments(mut self, segments: Vec<(usize, usize)>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the points whose regions are removed from the mesh.
+    pub fn with_hole_seeds(mut self, hole_seeds: Vec<Vector>) -> Self {
+        self.hole_seeds = hole_seeds;
+        self
+    }
+
+    /// Sets the smallest and the biggest coordinates of the input points.
+    pub fn with_bounds(mut self, min: Vector, max: Vector) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Sets the matrix the input points are transformed with before they are normalized.
+    pub fn with_pre_transform(mut self, pre_transform: [f32; 4]) -> Self {
+        self.pre_transform = Some(pre_transform);
+        self
+    }
+
+    /// Sets whether the tesselation runs on a single thread, so that the mesh is the same on every machine.
+    #[cfg(feature = "rayon")]
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Whether the tesselation splits the mesh into regions that are refined on the rayon thread pool.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn tesselates_in_parallel(&self) -> bool {
+        !self.deterministic && self.strict_steiner_point_limit
+    }
+
+    /// Checks that all numeric options are usable, before any work is done.
+    pub(crate) fn validate(&self) -> Result<(), CustomError> {
+        if let Some(maximum_triangle_area) = self.maximum_triangle_area {
+            validate_positive("maximum_triangle_area", maximum_triangle_area)?;
+        }
+        if let Some(max_vertices) = self.max_vertices {
+            // fewer points than a triangle can not be triangulated
+            if max_vertices < 3 {
+                return Err(CustomError::InvalidParameter(
+                    "max_vertices",
+                    max_vertices as f32,
+                ));
+            }
+        }
+        if let Some((min, max)) = self.bounds {
+            for coordinate in [min.x, min.y, max.x, max.y] {
+                if !coordinate.is_finite() {
+                    return Err(CustomError::InvalidParameter("bounds", coordinate));
+                }
+            }
+            let extent = max - min;
+            for extent in [extent.x, extent.y] {
+                if extent < 0. {
+                    return Err(CustomError::InvalidParameter("bounds", extent));
+                }
+            }
+        }
+        if let Some([a, b, c, d]) = self.pre_transform {
+            // a mirroring matrix would turn the triangles and the holes clockwise
+            let determinant = a * d - b * c;
+            if !determinant.is_finite() || determinant <= 0. {
+                return Err(CustomError::InvalidParameter("pre_transform", determinant));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Non-finite or non-positive values would make the refinement run forever.
+pub(crate) fn validate_positive(name: &'static str, value: f32) -> Result<(), CustomError> {
+    if value.is_finite() && value > 0. {
+        Ok(())
+    } else {
+        Err(CustomError::InvalidParameter(name, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TriangulateOptions;
+    use crate::{CustomError, Vector};
+
+    #[test]
+    fn default_options_are_valid() {
+        assert!(TriangulateOptions::new().validate().is_ok());
+    }
+
+    #[test]
+    fn positive_area_is_valid() {
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.1);
+        assert!(options.validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_areas_are_rejected() {
+        for area in [0., -1., f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let options = TriangulateOptions::new().with_maximum_triangle_area(area);
+            match options.validate() {
+                Err(CustomError::InvalidParameter(name, value)) => {
+                    assert_eq!(name, "maximum_triangle_area");
+                    assert!(value.to_bits() == area.to_bits());
+                }
+                other => panic!("{area} should have been rejected, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn inverted_or_infinite_bounds_are_rejected() {
+        let bounds = |min: (f32, f32), max: (f32, f32)| {
+            TriangulateOptions::new()
+                .with_bounds(Vector::from(&min), Vector::from(&max))
+                .validate()
+        };
+        assert!(bounds((0., 0.), (1., 0.)).is_ok());
+        assert!(matches!(
+            bounds((0., 2.), (1., 1.)),
+            Err(CustomError::InvalidParameter("bounds", extent)) if extent == -1.
+        ));
+        assert!(matches!(
+            bounds((0., 0.), (f32::INFINITY, 1.)),
+            Err(CustomError::InvalidParameter("bounds", _))
+        ));
+    }
+
+    #[test]
+    fn singular_or_mirroring_transforms_are_rejected() {
+        let transform = |matrix: [f32; 4]| {
+            TriangulateOptions::new()
+                .with_pre_transform(matrix)
+                .validate()
+        };
+        assert!(transform([1., 0.5, 0., 1.]).is_ok());
+        assert!(matches!(
+            transform([1., 2., 0.5, 1.]),
+            Err(CustomError::InvalidParameter("pre_transform", determinant)) if determinant == 0.
+        ));
+        assert!(matches!(
+            transform([0., 1., 1., 0.]),
+            Err(CustomError::InvalidParameter("pre_transform", determinant)) if determinant == -1.
+        ));
+        assert!(matches!(
+            transform([f32::NAN, 0., 0., 1.]),
+            Err(CustomError::InvalidParameter("pre_transform", _))
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+/// How the delaunay triangulation of the input points is built, before the holes are cut and the mesh is tesselated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TriangulationAlgorithm {
+    /// The points are inserted one after another into a supertriangle,
+    /// configured by the [`InsertionOrder`](crate::InsertionOrder) and the [`InsertionAlgorithm`](crate::InsertionAlgorithm).
+    #[default]
+    Incremental,
+    /// The points are sorted, split in halves and the triangulations of the halves are merged recursively.
+    ///
+    /// This scales better for huge point clouds. There is no supertriangle, the edges of the convex hull have no neighbor.
+    /// The triangles are the same as with [`TriangulationAlgorithm::Incremental`], as long as no four points are on a common circle,
+    /// except on the convex hull: the supertriangle can take the place of very flat triangles there, this always covers the whole hull.
+    /// The tesselation does not split the edges of the hull.
+    DivideAndConquer,
+}
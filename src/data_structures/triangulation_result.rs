@@ -0,0 +1,378 @@
+use crate::normalize::{denormalize_points, Bounds};
+
+use super::{
+    constraint_trace::ConstraintTrace,
+    dedup_summary::DedupSummary,
+    hole_report::HoleReport,
+    ids::{TriangleId, VertexId},
+    input_diagnostics::InputDiagnostics,
+    insertion_statistics::InsertionStatistics,
+    triangle::Triangle,
+    triangle_ref::TriangleRefs,
+    triangle_set::TriangleSet,
+    triangulation_snapshot::TriangulationSnapshot,
+    triangulation_view::TriangulationView,
+    vector::Vector,
+    warning::Warning,
+};
+
+/// The outcome of a triangulation as an indexed mesh.
+///
+/// The supertriangle and the triangles inside of holes are already removed and all indices refer to the output arrays,
+/// not to the internal [`VertexId`]s and [`TriangleId`]s.
+#[derive(Clone, Debug)]
+pub struct TriangulationResult {
+    /// The points of the mesh in the coordinates of the input.
+    pub vertices: Vec<Vector>,
+    /// The triangles as counter-clockwise indices into `vertices`.
+    pub triangles: Vec<[usize; 3]>,
+    /// For every triangle the adjacent triangle across the edge from vertex `i` to vertex `i + 1`.
+    /// `None` if there is no kept triangle on the other side.
+    pub adjacency: Vec<[Option<usize>; 3]>,
+    /// Everything that did not go as asked for, but did not make the triangulation fail.
+    pub warnings: Vec<Warning>,
+    /// Statistics about the scale of the input points.
+    pub diagnostics: InputDiagnostics,
+    /// How many exact duplicates were removed from the input points, see [`TriangulateOptions::dedup_input`](crate::TriangulateOptions::dedup_input).
+    pub removed_duplicates: usize,
+    /// Which input points became which output vertices.
+    pub dedup_summary: DedupSummary,
+    /// How much work inserting the input points took, see [`TriangulateOptions::insertion_order`](crate::TriangulateOptions::insertion_order).
+    pub statistics: InsertionStatistics,
+    /// What happened to every input hole, in the order of the input.
+    pub hole_reports: Vec<HoleReport>,
+    /// The output vertices along every constrained input edge, in the order of the input:
+    /// the edges of the closed boundary first, then the edges of the holes, or the segments of a planar straight-line graph.
+    pub constrained_edges: Vec<ConstraintTrace>,
+    /// The triangulation the output was created from, in normalized space.
+    pub(crate) triangle_set: TriangleSet,
+    pub(crate) bounds: Bounds,
+    /// The sorted triangles of the triangle set that are not part of the output,
+    /// the supertriangle triangles together with the triangles outside of a closed boundary and of all holes.
+    pub(crate) removed_triangles: Vec<TriangleId>,
+    /// The sorted triangles of the triangle set that use a vertex of the supertriangle.
+    pub(crate) supertriangle_triangles: Vec<TriangleId>,
+    /// The triangle set vertex for every output vertex.
+    pub(crate) vertex_ids: Vec<VertexId>,
+    /// The triangle set triangle for every output triangle.
+    pub(crate) triangle_ids: Vec<TriangleId>,
+}
+
+impl TriangulationResult {
+    /// Builds the output from a finished triangle set,
+    /// skipping the triangles of the supertriangle, the ones outside of a closed boundary and the ones removed for the holes.
+    pub(crate) fn new(
+        triangle_set: TriangleSet,
+        bounds: Bounds,
+        mut supertriangle_triangles: Vec<TriangleId>,
+        outside_triangles: Vec<TriangleId>,
+        diagnostics: InputDiagnostics,
+        hole_reports: Vec<HoleReport>,
+        constrained_edges: Vec<ConstraintTrace>,
+    ) -> Self {
+        supertriangle_triangles.sort();
+        // holes may share triangles with each other and with the supertriangle
+        let mut removed_triangles = supertriangle_triangles.clone();
+        removed_triangles.extend(outside_triangles);
+        for hole_report in &hole_reports {
+            removed_triangles.extend_from_slice(&hole_report.triangle_ids);
+        }
+        removed_triangles.sort();
+        removed_triangles.dedup();
+        let mut result = TriangulationResult {
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+            adjacency: Vec::new(),
+            warnings: diagnostics.warning().into_iter().collect(),
+            diagnostics,
+            removed_duplicates: 0,
+            dedup_summary: DedupSummary::default(),
+            statistics: InsertionStatistics::default(),
+            hole_reports,
+            constrained_edges,
+            triangle_set,
+            bounds,
+            removed_triangles,
+            supertriangle_triangles,
+            vertex_ids: Vec::new(),
+            triangle_ids: Vec::new(),
+        };
+        result.update_output();
+        result
+    }
+
+    /// Recreates the output arrays from the internal triangle set.
+    pub(crate) fn update_output(&mut self) {
+        let triangle_set = &self.triangle_set;
+        self.vertex_ids = triangle_set.mesh_vertices().collect();
+        let mut output_vertex = vec![None; triangle_set.points.len()];
+        for (output_index, vertex) in self.vertex_ids.iter().enumerate() {
+            output_vertex[vertex.0] = Some(output_index);
+        }
+        for hole_report in &mut self.hole_reports {
+            hole_report.vertices = hole_report
+                .vertex_ids
+                .iter()
+                .map(|vertex| output_vertex[vertex.0].expect("holes do not use the supertriangle"))
+                .collect();
+        }
+        for constraint_trace in &mut self.constrained_edges {
+            constraint_trace.vertices = constraint_trace
+                .vertex_ids
+                .iter()
+                .map(|vertex| {
+                    output_vertex[vertex.0].expect("constraints do not use the supertriangle")
+                })
+                .collect();
+        }
+
+        let mut output_triangle = vec![None; triangle_set.triangle_count()];
+        self.triangle_ids.clear();
+        let mut removed = self.removed_triangles.iter().peekable();
+        for triangle_index in (0..triangle_set.triangle_count()).map(TriangleId) {
+            if removed.peek() == Some(&&triangle_index) {
+                removed.next();
+                continue;
+            }
+            output_triangle[triangle_index.0] = Some(self.triangle_ids.len());
+            self.triangle_ids.push(triangle_index);
+        }
+
+        self.triangles = self
+            .triangle_ids
+            .iter()
+            .map(|triangle| {
+                triangle_set
+                    .get_triangle_info(*triangle)
+                    .vertex_indices
+                    .map(|vertex| {
+                        output_vertex[vertex.0]
+                            .expect("kept triangles do not touch the supertriangle")
+                    })
+            })
+            .collect();
+        self.adjacency = self
+            .triangle_ids
+            .iter()
+            .map(|triangle| {
+                triangle_set
+                    .get_triangle_info(*triangle)
+                    .adjacent_triangle_indices
+                    .map(|adjacent| adjacent.and_then(|adjacent| output_triangle[adjacent.0]))
+            })
+            .collect();
+        let points: Vec<Vector> = self
+            .vertex_ids
+            .iter()
+            .map(|vertex| triangle_set.get_point_from_vertex(*vertex))
+            .collect();
+        self.vertices = denormalize_points(&points, &self.bounds);
+    }
+
+    /// Removes a triangle from the triangle set and keeps the removed triangles and hole reports in sync.
+    /// Returns the previous and the new index of the triangle that was moved into its place, if one was moved.
+    /// The output arrays are not updated.
+    pub(crate) fn remove_triangle(
+        &mut self,
+        triangle_index: TriangleId,
+    ) -> Option<(TriangleId, TriangleId)> {
+        let moved_triangle = self.triangle_set.remove_triangle(triangle_index);
+        let remap = |triangles: &mut Vec<TriangleId>| {
+            triangles.retain(|triangle| *triangle != triangle_index);
+            if let Some(moved_triangle) = moved_triangle {
+                for triangle in triangles.iter_mut() {
+                    if *triangle == moved_triangle {
+                        *triangle = triangle_index;
+                    }
+                }
+                triangles.sort();
+            }
+        };
+        remap(&mut self.removed_triangles);
+        remap(&mut self.supertriangle_triangles);
+        for hole_report in &mut self.hole_reports {
+            remap(&mut hole_report.triangle_ids);
+        }
+        moved_triangle.map(|moved_triangle| (moved_triangle, triangle_index))
+    }
+
+    /// A view of the read-only queries, which shares nothing mutable and can be used from many threads at once.
+    /// ```
+    /// use constrained_denaulay_triangulation::{triangulate_with_options, TriangulateOptions, Vector};
+    ///
+    /// let square = vec![
+    ///     Vector::new(0., 0.),
+    ///     Vector::new(1., 0.),
+    ///     Vector::new(1., 1.),
+    ///     Vector::new(0., 1.),
+    /// ];
+    /// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+    /// let view = result.view();
+    /// let found = std::thread::scope(|scope| {
+    ///     let worker = scope.spawn(|| view.locate(Vector::new(0.2, 0.3), None));
+    ///     worker.join().unwrap()
+    /// });
+    /// assert_eq!(found, view.locate(Vector::new(0.2, 0.3), None));
+    /// ```
+    pub fn view(&self) -> TriangulationView<'_> {
+        TriangulationView::new(self)
+    }
+
+    /// Saves the current state, so that later edits like [`refine`](crate::refine) or [`move_vertex`](crate::move_vertex)
+    /// can be undone with [`TriangulationResult::restore`].
+    ///
+    /// This clones all internal points and triangles, which costs as much time and memory as cloning the result,
+    /// but far less than triangulating again.
+    /// ```
+    /// use constrained_denaulay_triangulation::{refine, triangulate_with_options, TriangulateOptions, Vector};
+    ///
+    /// let square = vec![
+    ///     Vector::new(0., 0.),
+    ///     Vector::new(1., 0.),
+    ///     Vector::new(1., 1.),
+    ///     Vector::new(0., 1.),
+    /// ];
+    /// let mut result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+    /// let checkpoint = result.checkpoint();
+    /// refine(&mut result, 0.1, 1000).unwrap();
+    /// assert!(result.len() > 2);
+    /// result.restore(checkpoint);
+    /// assert_eq!(result.len(), 2);
+    /// ```
+    pub fn checkpoint(&self) -> TriangulationSnapshot {
+        TriangulationSnapshot {
+            triangle_set: self.triangle_set.clone(),
+            removed_triangles: self.removed_triangles.clone(),
+            supertriangle_triangles: self.supertriangle_triangles.clone(),
+            hole_reports: self.hole_reports.clone(),
+            constrained_edges: self.constrained_edges.clone(),
+            warnings: self.warnings.clone(),
+        }
+    }
+
+    /// Returns to the state saved by [`TriangulationResult::checkpoint`] and rebuilds the output arrays from it.
+    ///
+    /// The snapshot is moved into the result, so restoring copies nothing apart from the output arrays.
+    /// It should come from this result, a snapshot of another triangulation replaces the whole mesh,
+    /// but keeps the bounds of this one.
+    pub fn restore(&mut self, snapshot: TriangulationSnapshot) {
+        self.triangle_set = snapshot.triangle_set;
+        self.removed_triangles = snapshot.removed_triangles;
+        self.supertriangle_triangles = snapshot.supertriangle_triangles;
+        self.hole_reports = snapshot.hole_reports;
+        self.constrained_edges = snapshot.constrained_edges;
+        self.warnings = snapshot.warnings;
+        self.update_output();
+    }
+
+    /// The triangulation the output was created from, with the supertriangle and the removed triangles,
+    /// in the normalized space of the triangulation, see [`TriangleSet`].
+    pub fn triangle_set(&self) -> &TriangleSet {
+        &self.triangle_set
+    }
+
+    /// The number of triangles in the output.
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Whether the output contains no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// Every output triangle with its vertices, positions, neighbors and [`TriangleKind`](super::triangle_ref::TriangleKind),
+    /// in the order of [`TriangulationResult::triangles`]. `for triangle in &result` does the same.
+    /// ```
+    /// use constrained_denaulay_triangulation::{triangulate_with_options, TriangleKind, TriangulateOptions, Vector};
+    ///
+    /// let square: Vec<Vector> = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].iter().map(Vector::from).collect();
+    /// let hole: Vec<Vector> = [(1., 1.), (3., 1.), (2., 3.)].iter().map(Vector::from).collect();
+    /// let result = triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new()).unwrap();
+    /// assert_eq!(result.iter().len(), result.len());
+    /// let around_hole = result
+    ///     .iter()
+    ///     .filter(|triangle| triangle.kind == TriangleKind::HoleBoundary(0))
+    ///     .count();
+    /// assert_eq!(around_hole, 3);
+    /// ```
+    pub fn iter(&self) -> TriangleRefs<'_> {
+        TriangleRefs::new(self)
+    }
+
+    /// The triangle at the given output index with its vertex positions.
+    pub fn triangle(&self, index: usize) -> Triangle {
+        let [a, b, c] = self.triangles[index];
+        Triangle::new(self.vertices[a], self.vertices[b], self.vertices[c])
+    }
+
+    /// The adjacent triangles of the triangle at the given output index, see [`TriangulationResult::adjacency`].
+    ///
+    /// An edge has no neighbor if it is on the outline of the mesh or of a hole,
+    /// the triangles removed for holes and the supertriangle never show up.
+    pub fn neighbors(&self, index: usize) -> [Option<usize>; 3] {
+        self.adjacency[index]
+    }
+
+    /// Whether the edge from vertex `edge` to vertex `edge + 1` of the triangle at the given output index
+    /// has no triangle on its other side.
+    pub fn is_boundary_edge(&self, index: usize, edge: usize) -> bool {
+        self.adjacency[index][edge].is_none()
+    }
+
+    /// The triangles that were removed for every hole, in the order of [`TriangulationResult::hole_reports`],
+    /// with their vertex positions.
+    ///
+    /// A triangle inside of several holes belongs to only one of them, see [`HoleReport::removed_triangles`].
+    /// Triangles of the supertriangle that a clockwise hole claimed are left out, as are the triangles of filled holes.
+    pub fn removed_triangles_by_hole(&self) -> Vec<Vec<Triangle>> {
+        self.hole_reports
+            .iter()
+            .map(|hole_report| {
+                hole_report
+                    .triangle_ids
+                    .iter()
+                    .filter(|triangle| {
+                        self.supertriangle_triangles
+                            .binary_search(triangle)
+                            .is_err()
+                    })
+                    .map(|triangle| {
+                        let triangle = self.triangle_set.get_triangle(*triangle);
+                        Triangle::new(
+                            self.bounds.denormalize(triangle.p(0)),
+                            self.bounds.denormalize(triangle.p(1)),
+                            self.bounds.denormalize(triangle.p(2)),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// All triangles of the output with their vertex positions.
+    pub fn to_triangles(&self) -> Vec<Triangle> {
+        self.iter_triangles().collect()
+    }
+
+    /// The triangles of [`TriangulationResult::to_triangles`], created one at a time instead of all at once.
+    /// ```
+    /// use constrained_denaulay_triangulation::{triangulate_with_options, TriangulateOptions, Vector};
+    ///
+    /// let square = vec![
+    ///     Vector::new(0., 0.),
+    ///     Vector::new(2., 0.),
+    ///     Vector::new(2., 2.),
+    ///     Vector::new(0., 2.),
+    /// ];
+    /// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+    /// let largest_angle = result
+    ///     .iter_triangles()
+    ///     .flat_map(|triangle| triangle.angles())
+    ///     .fold(0., f32::max);
+    /// assert!((largest_angle - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    /// ```
+    pub fn iter_triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
+        (0..self.len()).map(|index| self.triangle(index))
+    }
+}
@@ -0,0 +1,53 @@
+use super::{
+    constraint_trace::ConstraintTrace, hole_report::HoleReport, ids::TriangleId,
+    triangle_set::TriangleSet, warning::Warning,
+};
+
+/// The editable state of a [`TriangulationResult`](crate::TriangulationResult),
+/// see [`TriangulationResult::checkpoint`](crate::TriangulationResult::checkpoint).
+///
+/// It holds a full copy of the internal points and triangles, so it takes about as much memory as the result itself.
+#[derive(Clone, Debug)]
+pub struct TriangulationSnapshot {
+    pub(crate) triangle_set: TriangleSet,
+    pub(crate) removed_triangles: Vec<TriangleId>,
+    pub(crate) supertriangle_triangles: Vec<TriangleId>,
+    pub(crate) hole_reports: Vec<HoleReport>,
+    pub(crate) constrained_edges: Vec<ConstraintTrace>,
+    pub(crate) warnings: Vec<Warning>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{refine, remove_point, test_util::square_with_hole, CustomError};
+
+    #[test]
+    fn restoring_a_checkpoint_undoes_the_edits() -> Result<(), CustomError> {
+        let mut result = square_with_hole()?;
+        refine(&mut result, 5., 1000)?;
+        let checkpoint = result.checkpoint();
+        let expected = result.clone();
+
+        refine(&mut result, 0.5, 1000)?;
+        let last_vertex = result.vertices.len() - 1;
+        remove_point(&mut result, last_vertex)?;
+        refine(&mut result, 0.1, 5)?;
+        assert_ne!(result.vertices.len(), expected.vertices.len());
+        assert!(!result.warnings.is_empty());
+
+        result.restore(checkpoint);
+        assert_eq!(result.vertices, expected.vertices);
+        assert_eq!(result.triangles, expected.triangles);
+        assert_eq!(result.adjacency, expected.adjacency);
+        assert_eq!(result.warnings, expected.warnings);
+        assert_eq!(result.hole_reports, expected.hole_reports);
+        assert_eq!(result.constrained_edges, expected.constrained_edges);
+        assert_eq!(result.triangle_set.points, expected.triangle_set.points);
+        assert_eq!(
+            result.triangle_set.triangle_infos,
+            expected.triangle_set.triangle_infos
+        );
+        assert_eq!(result.removed_triangles, expected.removed_triangles);
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::queries::{
+    boundary_length, boundary_loops, circumcircles, classify_point, edge_faces, edges_iter,
+    euclidean_mst, gabriel_graph, hole_boundaries, is_conforming_delaunay, is_delaunay,
+    is_manifold, largest_empty_circle, locate, locate_from, nearest_vertex, pair_quads,
+    segment_path, tagged_edges, triangles_bordering_hole, triangles_in_polygon_query,
+    vertex_markers, vertex_ring, winding_number, PointClass, QuadOrTri, SegmentPathStep,
+};
+
+use super::{
+    triangulation_result::TriangulationResult, vector::Vector, vertex_marker::VertexMarker,
+    vertex_ring::VertexRing,
+};
+
+/// The read-only queries of a finished [`TriangulationResult`], see [`TriangulationResult::view`].
+///
+/// The view only borrows the result and keeps no state between calls, so it can be copied into many threads
+/// that query the same mesh at the same time. Walks that can start close to their target take the hint per call.
+#[derive(Clone, Copy, Debug)]
+pub struct TriangulationView<'a> {
+    result: &'a TriangulationResult,
+}
+
+impl<'a> TriangulationView<'a> {
+    pub(crate) fn new(result: &'a TriangulationResult) -> Self {
+        TriangulationView { result }
+    }
+
+    /// The result the view borrows.
+    pub fn result(&self) -> &'a TriangulationResult {
+        self.result
+    }
+
+    /// The output index of a triangle that contains the point, see [`locate`].
+    /// With a hint, the search walks from that triangle like [`locate_from`].
+    pub fn locate(&self, point: Vector, hint: Option<usize>) -> Option<usize> {
+        match hint {
+            Some(hint) => locate_from(self.result, point, hint),
+            None => locate(self.result, point),
+        }
+    }
+
+    /// Whether the point is inside of the mesh, in a hole, on the boundary or outside, see [`classify_point`].
+    pub fn classify_point(&self, point: Vector) -> PointClass {
+        classify_point(self.result, point)
+    }
+
+    /// How many times the boundary winds around the point, see [`winding_number`].
+    pub fn winding_number(&self, point: Vector) -> i32 {
+        winding_number(self.result, point)
+    }
+
+    /// The output index of the vertex of the mesh closest to the point, see [`nearest_vertex`].
+    pub fn nearest_vertex(&self, point: Vector) -> Option<usize> {
+        nearest_vertex(self.result, point)
+    }
+
+    /// Every triangle the segment from `a` to `b` passes through, see [`segment_path`].
+    pub fn walk_segment(&self, a: Vector, b: Vector) -> Vec<SegmentPathStep> {
+        segment_path(self.result, a, b)
+    }
+
+    /// Every edge once with whether it is constrained, see [`tagged_edges`].
+    pub fn tagged_edges(&self) -> Vec<(Vector, Vector, bool)> {
+        tagged_edges(self.result)
+    }
+
+    /// Every edge with the triangles on both sides, see [`edge_faces`].
+    pub fn edge_faces(&self) -> HashMap<(usize, usize), (usize, Option<usize>)> {
+        edge_faces(self.result)
+    }
+
+    /// Every edge once as its two output vertices, see [`edges_iter`].
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        edges_iter(self.result)
+    }
+
+    /// The edges of the Gabriel graph, see [`gabriel_graph`].
+    pub fn gabriel_graph(&self) -> Vec<(usize, usize)> {
+        gabriel_graph(self.result)
+    }
+
+    /// The edges of the minimum spanning tree, see [`euclidean_mst`].
+    pub fn euclidean_mst(&self) -> Vec<(usize, usize)> {
+        euclidean_mst(self.result)
+    }
+
+    /// The outline and the holes as closed loops, see [`boundary_loops`].
+    pub fn boundary_loops(&self) -> Vec<Vec<Vector>> {
+        boundary_loops(self.result)
+    }
+
+    /// The outline of every hole as a loop of vertices, see [`hole_boundaries`].
+    pub fn hole_boundaries(&self) -> Vec<Vec<usize>> {
+        hole_boundaries(self.result)
+    }
+
+    /// The triangles with an edge on the outline of a hole, see [`triangles_bordering_hole`].
+    pub fn triangles_bordering_hole(&self, hole_index: usize) -> Vec<usize> {
+        triangles_bordering_hole(self.result, hole_index)
+    }
+
+    /// The triangles that have a point in common with the polygon, see [`triangles_in_polygon_query`].
+    pub fn triangles_in_polygon(&self, polygon: &[Vector]) -> Vec<usize> {
+        triangles_in_polygon_query(self.result, polygon)
+    }
+
+    /// Where every vertex is, see [`vertex_markers`].
+    pub fn vertex_markers(&self) -> Vec<VertexMarker> {
+        vertex_markers(self.result)
+    }
+
+    /// The neighbors of a vertex in counter-clockwise order, see [`vertex_ring`].
+    pub fn vertex_ring(&self, vertex: usize) -> Option<VertexRing<usize>> {
+        vertex_ring(self.result, vertex)
+    }
+
+    /// The length of the outline and of the hole rims, see [`boundary_length`].
+    pub fn boundary_length(&self) -> f32 {
+        boundary_length(self.result)
+    }
+
+    /// The circumcircle of every triangle, see [`circumcircles`].
+    pub fn circumcircles(&self) -> Vec<(Vector, f32)> {
+        circumcircles(self.result)
+    }
+
+    /// The largest circle inside of the mesh without a vertex in it, see [`largest_empty_circle`].
+    pub fn largest_empty_circle(&self) -> (Vector, f32) {
+        largest_empty_circle(self.result)
+    }
+
+    /// The triangles merged into quadrilaterals where possible, see [`pair_quads`].
+    pub fn pair_quads(&self, max_angle_deviation: f32) -> Vec<QuadOrTri> {
+        pair_quads(self.result, max_angle_deviation)
+    }
+
+    /// Whether the unconstrained edges are Delaunay, see [`is_delaunay`].
+    pub fn is_delaunay(&self) -> bool {
+        is_delaunay(self.result)
+    }
+
+    /// Whether all edges are Delaunay, also the constrained ones, see [`is_conforming_delaunay`].
+    pub fn is_conforming_delaunay(&self) -> bool {
+        is_conforming_delaunay(self.result)
+    }
+
+    /// Whether the triangles form a consistently oriented mesh, see [`is_manifold`].
+    pub fn is_manifold(&self) -> bool {
+        is_manifold(self.result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::TriangulationView;
+    use crate::{
+        test_util::random_points, triangulate_with_options, CustomError, TriangulateOptions, Vector,
+    };
+
+    fn assert_send_and_sync<T: Send + Sync>() {}
+
+    /// Locates the points one after another, with the previous triangle as the hint.
+    fn locate_all(view: TriangulationView, points: &[Vector]) -> Vec<Option<usize>> {
+        let mut hint = None;
+        points
+            .iter()
+            .map(|point| {
+                let triangle = view.locate(*point, hint);
+                hint = triangle.or(hint);
+                triangle
+            })
+            .collect()
+    }
+
+    #[test]
+    fn threads_locate_the_same_triangles_as_a_single_thread() -> Result<(), CustomError> {
+        assert_send_and_sync::<TriangulationView>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.002);
+        let result = triangulate_with_options(&random_points(200, 1), None, &options)?;
+        let view = result.view();
+        // some of the queries are outside of the mesh
+        let queries: Vec<Vector> = random_points(8 * 2000, 2)
+            .into_iter()
+            .map(|point| point * 1.2 - Vector::new(0.1, 0.1))
+            .collect();
+        let expected: Vec<Option<usize>> = queries
+            .chunks(2000)
+            .flat_map(|chunk| locate_all(view, chunk))
+            .collect();
+        assert!(expected.iter().any(Option::is_none));
+        assert!(expected.iter().flatten().count() > queries.len() / 2);
+
+        let located: Vec<Option<usize>> = thread::scope(|scope| {
+            let workers: Vec<_> = queries
+                .chunks(2000)
+                .map(|chunk| scope.spawn(move || locate_all(view, chunk)))
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        assert_eq!(located, expected);
+        Ok(())
+    }
+}
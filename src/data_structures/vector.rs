@@ -1,20 +1,131 @@
+/// Coordinates at most this many representable values apart are the same for [`Vector::is_same_point`].
+pub(crate) const SAME_POINT_ULPS: u32 = 4;
+/// Coordinates at most this far apart are the same for [`Vector::is_same_point`],
+/// which matters around zero, where the representable values are very dense.
+pub(crate) const SAME_POINT_EPSILON: f32 = 1e-7;
+
+/// A two dimensional vector, used for points as well as directions.
 #[derive(PartialEq, Debug, Default, Clone, Copy)]
 pub struct Vector {
+    /// The x coordinate.
     pub x: f32,
+    /// The y coordinate.
     pub y: f32,
 }
 
 impl Vector {
+    /// Creates a new vector.
     #[inline]
     pub fn new(x: f32, y: f32) -> Self {
         Vector { x, y }
     }
 
+    /// The z component of the cross product of both vectors, interpreted as 3d vectors with z = 0.
     #[inline]
     pub fn cross_product(self, rhs: Self) -> f32 {
         (self.x * rhs.y) - (self.y * rhs.x)
     }
+
+    /// The euclidean length of the vector.
+    #[inline]
+    pub fn length(self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// The direction of the vector in radians, counter-clockwise from the positive x axis, in `(-π, π]`.
+    #[inline]
+    pub fn angle(self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
+    /// The signed angle in radians to rotate this vector counter-clockwise onto `other`, in `(-π, π]`.
+    #[inline]
+    pub fn angle_between(self, other: Vector) -> f32 {
+        let dot = self.x * other.x + self.y * other.y;
+        self.cross_product(other).atan2(dot)
+    }
+
+    /// Whether both normalized points are the same up to rounding, because each coordinate differs by at most
+    /// [`SAME_POINT_ULPS`] ulps or by at most [`SAME_POINT_EPSILON`].
+    ///
+    /// The same point computed in two different ways, e.g. as an outline and as a hole vertex, often differs
+    /// in the last bits after the normalization. The triangle set uses this wherever it checks the identity of points.
+    pub(crate) fn is_same_point(self, other: Vector) -> bool {
+        let is_same_coordinate = |a: f32, b: f32| {
+            // the bits of floats with the same sign are ordered like the floats, neighbors differ by one
+            (a - b).abs() <= SAME_POINT_EPSILON
+                || (a.is_sign_negative() == b.is_sign_negative()
+                    && a.to_bits().abs_diff(b.to_bits()) <= SAME_POINT_ULPS)
+        };
+        is_same_coordinate(self.x, other.x) && is_same_coordinate(self.y, other.y)
+    }
+
+    /// Creates a vector with the length `r` pointing in the direction `theta` (in radians).
+    #[inline]
+    pub fn from_polar(r: f32, theta: f32) -> Vector {
+        let (sin, cos) = theta.sin_cos();
+        Vector::new(r * cos, r * sin)
+    }
+
+    /// The vector rotated counter-clockwise by `radians` around the origin.
+    #[inline]
+    pub fn rotated(self, radians: f32) -> Vector {
+        let (sin, cos) = radians.sin_cos();
+        Vector::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// The point rotated counter-clockwise by `radians` around `center`.
+    #[inline]
+    pub fn rotated_around(self, center: Vector, radians: f32) -> Vector {
+        (self - center).rotated(radians) + center
+    }
+
+    /// The vector mirrored at the x axis, so with the sign of `y` flipped.
+    ///
+    /// Mirroring turns counter-clockwise polygons into clockwise ones.
+    #[inline]
+    pub fn reflected_x(self) -> Vector {
+        Vector::new(self.x, -self.y)
+    }
+
+    /// The vector mirrored at the y axis, so with the sign of `x` flipped.
+    #[inline]
+    pub fn reflected_y(self) -> Vector {
+        Vector::new(-self.x, self.y)
+    }
+
+    /// The smaller of both coordinates, for each axis on its own.
+    /// Like [`f32::min`], a NaN coordinate is ignored in favor of the other one.
+    #[inline]
+    pub fn min(self, other: Vector) -> Vector {
+        Vector::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// The bigger of both coordinates, for each axis on its own.
+    /// Like [`f32::max`], a NaN coordinate is ignored in favor of the other one.
+    #[inline]
+    pub fn max(self, other: Vector) -> Vector {
+        Vector::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// The point moved into the rectangle from `lo` to `hi`, for each axis on its own.
+    ///
+    /// Unlike [`f32::clamp`], it does not panic if `lo` is bigger than `hi` on an axis, that axis becomes `hi`.
+    #[inline]
+    pub fn clamp(self, lo: Vector, hi: Vector) -> Vector {
+        self.max(lo).min(hi)
+    }
 }
+impl std::fmt::Display for Vector {
+    /// Formats the vector as `(x, y)`, a precision like `{:.2}` is used for both coordinates.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "({:.*}, {:.*})", precision, self.x, precision, self.y),
+            None => write!(f, "({}, {})", self.x, self.y),
+        }
+    }
+}
+
 impl From<&mut (f32, f32)> for Vector {
     fn from(value: &mut (f32, f32)) -> Self {
         Vector::new(value.0, value.1)
@@ -100,3 +211,108 @@ impl std::ops::Sub<Vector> for Vector {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Vector;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn points_a_few_ulps_apart_are_the_same() {
+        let next_up = |value: f32, steps: u32| f32::from_bits(value.to_bits() + steps);
+        let point = Vector::new(0.54385514, 0.7452999);
+        assert!(point.is_same_point(Vector::new(next_up(point.x, 1), point.y)));
+        assert!(point.is_same_point(Vector::new(next_up(point.x, 4), next_up(point.y, 4))));
+        assert!(!point.is_same_point(Vector::new(next_up(point.x, 5), point.y)));
+        // around zero the absolute epsilon applies, also across the sign
+        assert!(Vector::new(0., 0.).is_same_point(Vector::new(-5e-8, 1e-7)));
+        assert!(!Vector::new(0., 0.).is_same_point(Vector::new(0., 1e-6)));
+    }
+
+    #[test]
+    fn angles_of_axis_aligned_vectors() {
+        assert_eq!(Vector::new(1., 0.).angle(), 0.);
+        assert_eq!(Vector::new(0., 1.).angle(), FRAC_PI_2);
+        assert_eq!(Vector::new(-1., 0.).angle(), PI);
+        assert_eq!(Vector::new(0., -1.).angle(), -FRAC_PI_2);
+    }
+
+    #[test]
+    fn angle_between_is_signed() {
+        let x = Vector::new(2., 0.);
+        let y = Vector::new(0., 3.);
+        assert_eq!(x.angle_between(x), 0.);
+        assert_eq!(x.angle_between(y), FRAC_PI_2);
+        assert_eq!(y.angle_between(x), -FRAC_PI_2);
+    }
+
+    #[test]
+    fn display_as_tuple() {
+        assert_eq!(Vector::new(1.5, -2.).to_string(), "(1.5, -2)");
+        assert_eq!(format!("{:.2}", Vector::new(1., 1. / 3.)), "(1.00, 0.33)");
+    }
+
+    #[test]
+    fn from_polar_round_trips() {
+        for theta in [0., 0.5, FRAC_PI_2, 2., -1., -3.] {
+            let vector = Vector::from_polar(2.5, theta);
+            assert!((vector.angle() - theta).abs() < 1e-6);
+            assert!((vector.x.hypot(vector.y) - 2.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn length_is_euclidean() {
+        assert_eq!(Vector::new(3., -4.).length(), 5.);
+        assert_eq!(Vector::default().length(), 0.);
+    }
+
+    #[test]
+    fn rotating_by_a_quarter_turn() {
+        let rotated = Vector::new(1., 0.).rotated(FRAC_PI_2);
+        assert!(rotated.x.abs() < 1e-6 && (rotated.y - 1.).abs() < 1e-6);
+        let rotated = Vector::new(0., 1.).rotated(-FRAC_PI_2);
+        assert!((rotated.x - 1.).abs() < 1e-6 && rotated.y.abs() < 1e-6);
+
+        let around = Vector::new(3., 2.).rotated_around(Vector::new(2., 2.), FRAC_PI_2);
+        assert!((around.x - 2.).abs() < 1e-6 && (around.y - 3.).abs() < 1e-6);
+        // the center itself stays in place
+        let center = Vector::new(-4., 7.5);
+        assert_eq!(center.rotated_around(center, 1.), center);
+    }
+
+    #[test]
+    fn reflecting_across_the_axes() {
+        let vector = Vector::new(1.5, -2.);
+        assert_eq!(vector.reflected_x(), Vector::new(1.5, 2.));
+        assert_eq!(vector.reflected_y(), Vector::new(-1.5, -2.));
+        assert_eq!(vector.reflected_x().reflected_x(), vector);
+        // mirroring flips the orientation of a corner
+        let (a, b) = (Vector::new(1., 0.), Vector::new(0., 1.));
+        assert!(a.cross_product(b) > 0.);
+        assert!(a.reflected_x().cross_product(b.reflected_x()) < 0.);
+        assert!(a.reflected_y().cross_product(b.reflected_y()) < 0.);
+    }
+
+    #[test]
+    fn min_and_max_are_per_axis() {
+        let a = Vector::new(1., 5.);
+        let b = Vector::new(3., -2.);
+        assert_eq!(a.min(b), Vector::new(1., -2.));
+        assert_eq!(a.max(b), Vector::new(3., 5.));
+        assert_eq!(a.min(Vector::new(f32::NAN, 7.)), a);
+    }
+
+    #[test]
+    fn clamp_moves_the_point_into_the_rectangle() {
+        let (lo, hi) = (Vector::new(0., 0.), Vector::new(1., 1.));
+        assert_eq!(Vector::new(0.5, 0.25).clamp(lo, hi), Vector::new(0.5, 0.25));
+        assert_eq!(Vector::new(-3., 0.5).clamp(lo, hi), Vector::new(0., 0.5));
+        assert_eq!(Vector::new(2., -1.).clamp(lo, hi), Vector::new(1., 0.));
+        // an empty range on an axis does not panic
+        assert_eq!(
+            Vector::new(0.5, 0.5).clamp(Vector::new(0.8, 0.), Vector::new(0.2, 1.)),
+            Vector::new(0.2, 0.5)
+        );
+    }
+}
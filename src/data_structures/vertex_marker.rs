@@ -0,0 +1,12 @@
+/// Where an output vertex lies in the mesh, like the boundary markers of the `.node` files of Triangle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VertexMarker {
+    /// The vertex is surrounded by triangles.
+    Interior,
+    /// The vertex is on the outline of the mesh, but not on a hole.
+    Hull,
+    /// The vertex belongs to the polygon of the hole with this index in
+    /// [`TriangulationResult::hole_reports`](crate::TriangulationResult::hole_reports).
+    /// This takes precedence over [`VertexMarker::Hull`], and the hole with the lowest index over the others.
+    HoleBoundary(usize),
+}
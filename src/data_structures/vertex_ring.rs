@@ -0,0 +1,72 @@
+/// The neighbors of a vertex in counter-clockwise order,
+/// see [`TriangleSet::vertex_ring`](super::triangle_set::TriangleSet::vertex_ring) and [`vertex_ring`](crate::vertex_ring).
+///
+/// `V` is a [`VertexId`](super::ids::VertexId) for a triangle set and an output index for a result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VertexRing<V> {
+    /// The vertices connected to the vertex by an edge, counter-clockwise around it.
+    /// If the ring is open, the chain starts and ends at the two border edges of the vertex.
+    pub vertices: Vec<V>,
+    /// Whether the triangles close around the vertex. Vertices on the border have an open ring.
+    pub is_closed: bool,
+}
+
+impl<V> VertexRing<V> {
+    /// The number of edges at the vertex.
+    pub fn degree(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+/// Walks around `vertex` through the adjacency, starting at the triangle `start`, which has to contain the vertex.
+///
+/// `vertices_of` gives the counter-clockwise vertices of a triangle, `adjacent_of(triangle, edge)` the triangle across
+/// the edge from vertex `edge` to the next one.
+pub(crate) fn walk_vertex_ring<V: Copy + PartialEq>(
+    vertex: V,
+    start: usize,
+    vertices_of: impl Fn(usize) -> [V; 3],
+    adjacent_of: impl Fn(usize, usize) -> Option<usize>,
+    triangle_count: usize,
+) -> VertexRing<V> {
+    let position = |triangle: usize| {
+        vertices_of(triangle)
+            .iter()
+            .position(|v| *v == vertex)
+            .expect("the triangles around a vertex contain it")
+    };
+    // the neighbors clockwise share the edge that starts at the vertex
+    let mut first = start;
+    let mut steps = 0;
+    while let Some(previous) = adjacent_of(first, position(first)) {
+        steps += 1;
+        if previous == start || steps > triangle_count {
+            break;
+        }
+        first = previous;
+    }
+    let mut vertices = Vec::new();
+    let mut current = first;
+    loop {
+        let triangle_vertices = vertices_of(current);
+        let at = position(current);
+        vertices.push(triangle_vertices[(at + 1) % 3]);
+        // the next triangle counter-clockwise shares the edge that ends at the vertex
+        match adjacent_of(current, (at + 2) % 3) {
+            Some(next) if next == first => {
+                return VertexRing {
+                    vertices,
+                    is_closed: true,
+                }
+            }
+            Some(next) if vertices.len() <= triangle_count => current = next,
+            _ => {
+                vertices.push(triangle_vertices[(at + 2) % 3]);
+                return VertexRing {
+                    vertices,
+                    is_closed: false,
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::constraint_trace::ConstraintSource;
+
+/// Something that did not stop the triangulation, but left the result different from what was asked for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Warning {
+    /// The refinement ran out of steiner points before every triangle was small enough.
+    /// The mesh is only partially refined.
+    RefinementBudgetExhausted {
+        /// How many points the refinement inserted.
+        inserted: usize,
+        /// How many triangles of the result still violate the refinement criterion.
+        remaining_bad_triangles: usize,
+    },
+    /// The input spans more orders of magnitude than survive the normalization,
+    /// e.g. because of a single point far away from all others. See [`InputDiagnostics`](crate::InputDiagnostics).
+    LargeDynamicRange {
+        /// The ratio between the largest and the smallest distance of the input.
+        ratio: f32,
+    },
+    /// A hole seed is outside of the mesh, so it removed nothing.
+    /// See [`TriangulateOptions::hole_seeds`](crate::TriangulateOptions::hole_seeds).
+    HoleSeedOutsideMesh {
+        /// The index of the seed.
+        seed: usize,
+    },
+    /// A hole seed is in a region that a hole or an earlier seed already removed, so it removed nothing.
+    HoleSeedInRemovedRegion {
+        /// The index of the seed.
+        seed: usize,
+    },
+    /// [`TriangulateOptions::max_vertices`](crate::TriangulateOptions::max_vertices) was reached,
+    /// so the last input points are not part of the mesh.
+    VertexLimitReached {
+        /// How many input points were left out, counted from the end of the input.
+        skipped_points: usize,
+    },
+    /// The outlines of two holes come so close to each other without touching,
+    /// that their points may be merged once they are normalized.
+    HolesNearlyTouching {
+        /// The index of the first hole.
+        hole_a: usize,
+        /// The index of the second hole, which is larger than `hole_a`.
+        hole_b: usize,
+        /// The smallest distance between the outlines, in the units of the input.
+        distance: f32,
+    },
+    /// A constrained input edge starts and ends at the same point, e.g. because a hole repeats a vertex,
+    /// so it was left out. Its [`ConstraintTrace`](crate::ConstraintTrace) only has that one vertex.
+    ZeroLengthConstraint {
+        /// The input edge.
+        source: ConstraintSource,
+    },
+}
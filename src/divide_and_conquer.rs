@@ -0,0 +1,325 @@
+//! Delaunay triangulation by recursive merging, after Guibas and Stolfi.
+//!
+//! The halves are triangulated as quad-edge meshes and zipped together from the bottom to the top.
+//! The finished mesh is converted into a [`TriangleSet`] without a supertriangle.
+use crate::data_structures::{
+    ids::{TriangleId, VertexId},
+    triangle_info::TriangleInfo,
+    triangle_set::TriangleSet,
+    vector::Vector,
+};
+
+/// A directed edge of the quad-edge mesh: four times the index of its quad edge plus its rotation.
+type EdgeRef = usize;
+
+/// Relative error bound of the in-circle determinant in f64, as a factor of the magnitude of its products.
+/// Points closer to the circle than that count as on it, so that rounding never lets co-circular points
+/// delete the edges between them.
+const IN_CIRCLE_ERROR_BOUND: f64 = 16. * f64::EPSILON;
+
+/// Every quad edge is an edge, its reverse and their two duals, stored as four consecutive directed edges.
+///
+/// Only the origins of the primal edges, rotation 0 and 2, are set.
+struct QuadEdgeMesh<'a> {
+    points: &'a [Vector],
+    next: Vec<EdgeRef>,
+    origin: Vec<usize>,
+    is_deleted: Vec<bool>,
+}
+
+impl<'a> QuadEdgeMesh<'a> {
+    fn new(points: &'a [Vector]) -> Self {
+        // a triangulation of n points has at most 3n edges
+        QuadEdgeMesh {
+            points,
+            next: Vec::with_capacity(points.len() * 12),
+            origin: Vec::with_capacity(points.len() * 12),
+            is_deleted: Vec::with_capacity(points.len() * 3),
+        }
+    }
+
+    fn rot(edge: EdgeRef) -> EdgeRef {
+        (edge & !3) | ((edge + 1) & 3)
+    }
+
+    fn sym(edge: EdgeRef) -> EdgeRef {
+        (edge & !3) | ((edge + 2) & 3)
+    }
+
+    fn rot_inv(edge: EdgeRef) -> EdgeRef {
+        (edge & !3) | ((edge + 3) & 3)
+    }
+
+    /// The next edge counter-clockwise around the origin.
+    fn onext(&self, edge: EdgeRef) -> EdgeRef {
+        self.next[edge]
+    }
+
+    /// The next edge clockwise around the origin.
+    fn oprev(&self, edge: EdgeRef) -> EdgeRef {
+        Self::rot(self.onext(Self::rot(edge)))
+    }
+
+    /// The next edge counter-clockwise around the face to the left.
+    fn lnext(&self, edge: EdgeRef) -> EdgeRef {
+        Self::rot(self.onext(Self::rot_inv(edge)))
+    }
+
+    /// The previous edge around the face to the right.
+    fn rprev(&self, edge: EdgeRef) -> EdgeRef {
+        self.onext(Self::sym(edge))
+    }
+
+    fn org(&self, edge: EdgeRef) -> usize {
+        self.origin[edge]
+    }
+
+    fn dest(&self, edge: EdgeRef) -> usize {
+        self.origin[Self::sym(edge)]
+    }
+
+    fn make_edge(&mut self, origin: usize, destination: usize) -> EdgeRef {
+        let edge = self.next.len();
+        self.next.extend([edge, edge + 3, edge + 2, edge + 1]);
+        self.origin
+            .extend([origin, usize::MAX, destination, usize::MAX]);
+        self.is_deleted.push(false);
+        edge
+    }
+
+    /// Joins the rings around the origins of both edges if they are separate and splits them otherwise.
+    fn splice(&mut self, a: EdgeRef, b: EdgeRef) {
+        let alpha = Self::rot(self.onext(a));
+        let beta = Self::rot(self.onext(b));
+        self.next.swap(a, b);
+        self.next.swap(alpha, beta);
+    }
+
+    /// Adds an edge from the destination of `a` to the origin of `b`, with the same face to the left.
+    fn connect(&mut self, a: EdgeRef, b: EdgeRef) -> EdgeRef {
+        let edge = self.make_edge(self.dest(a), self.org(b));
+        self.splice(edge, self.lnext(a));
+        self.splice(Self::sym(edge), b);
+        edge
+    }
+
+    fn delete_edge(&mut self, edge: EdgeRef) {
+        self.splice(edge, self.oprev(edge));
+        self.splice(Self::sym(edge), self.oprev(Self::sym(edge)));
+        self.is_deleted[edge / 4] = true;
+    }
+
+    fn point(&self, vertex: usize) -> (f64, f64) {
+        let point = self.points[vertex];
+        (point.x as f64, point.y as f64)
+    }
+
+    fn is_ccw(&self, a: usize, b: usize, c: usize) -> bool {
+        let (a, b, c) = (self.point(a), self.point(b), self.point(c));
+        (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0) > 0.
+    }
+
+    fn is_right_of(&self, vertex: usize, edge: EdgeRef) -> bool {
+        self.is_ccw(vertex, self.dest(edge), self.org(edge))
+    }
+
+    fn is_left_of(&self, vertex: usize, edge: EdgeRef) -> bool {
+        self.is_ccw(vertex, self.org(edge), self.dest(edge))
+    }
+
+    /// Whether `d` is strictly inside of the circumcircle of the counter-clockwise triangle `a`, `b`, `c`,
+    /// by more than the rounding error of the determinant.
+    fn is_in_circle(&self, a: usize, b: usize, c: usize, d: usize) -> bool {
+        let d_point = self.point(d);
+        let [a, b, c] = [a, b, c].map(|vertex| {
+            let point = self.point(vertex);
+            let (x, y) = (point.0 - d_point.0, point.1 - d_point.1);
+            (x, y, x * x + y * y)
+        });
+        let determinant = a.0 * (b.1 * c.2 - b.2 * c.1) - a.1 * (b.0 * c.2 - b.2 * c.0)
+            + a.2 * (b.0 * c.1 - b.1 * c.0);
+        let magnitude = a.0.abs() * ((b.1 * c.2).abs() + (b.2 * c.1).abs())
+            + a.1.abs() * ((b.0 * c.2).abs() + (b.2 * c.0).abs())
+            + a.2 * ((b.0 * c.1).abs() + (b.1 * c.0).abs());
+        determinant > IN_CIRCLE_ERROR_BOUND * magnitude
+    }
+
+    /// Triangulates the sorted points `start..end`, of which there are at least two.
+    /// Returns the counter-clockwise hull edge out of the leftmost point and the clockwise one out of the rightmost point.
+    fn triangulate(&mut self, start: usize, end: usize) -> (EdgeRef, EdgeRef) {
+        match end - start {
+            2 => {
+                let a = self.make_edge(start, start + 1);
+                (a, Self::sym(a))
+            }
+            3 => {
+                let (p0, p1, p2) = (start, start + 1, start + 2);
+                let a = self.make_edge(p0, p1);
+                let b = self.make_edge(p1, p2);
+                self.splice(Self::sym(a), b);
+                if self.is_ccw(p0, p1, p2) {
+                    self.connect(b, a);
+                    (a, Self::sym(b))
+                } else if self.is_ccw(p0, p2, p1) {
+                    let c = self.connect(b, a);
+                    (Self::sym(c), c)
+                } else {
+                    // the three points are on a line
+                    (a, Self::sym(b))
+                }
+            }
+            count => {
+                let middle = start + count / 2;
+                let (mut left_outer, mut left_inner) = self.triangulate(start, middle);
+                let (mut right_inner, mut right_outer) = self.triangulate(middle, end);
+                self.merge(
+                    &mut left_outer,
+                    &mut left_inner,
+                    &mut right_inner,
+                    &mut right_outer,
+                );
+                (left_outer, right_outer)
+            }
+        }
+    }
+
+    /// Zips the triangulations of two halves together, starting with their lower common tangent.
+    fn merge(
+        &mut self,
+        left_outer: &mut EdgeRef,
+        left_inner: &mut EdgeRef,
+        right_inner: &mut EdgeRef,
+        right_outer: &mut EdgeRef,
+    ) {
+        loop {
+            if self.is_left_of(self.org(*right_inner), *left_inner) {
+                *left_inner = self.lnext(*left_inner);
+            } else if self.is_right_of(self.org(*left_inner), *right_inner) {
+                *right_inner = self.rprev(*right_inner);
+            } else {
+                break;
+            }
+        }
+
+        let mut base = self.connect(Self::sym(*right_inner), *left_inner);
+        if self.org(*left_inner) == self.org(*left_outer) {
+            *left_outer = Self::sym(base);
+        }
+        if self.org(*right_inner) == self.org(*right_outer) {
+            *right_outer = base;
+        }
+
+        loop {
+            let is_valid =
+                |mesh: &Self, edge: EdgeRef, base: EdgeRef| mesh.is_right_of(mesh.dest(edge), base);
+            // removes the edges of the left half whose triangles are not delaunay with the base
+            let mut left_candidate = self.onext(Self::sym(base));
+            if is_valid(self, left_candidate, base) {
+                while self.is_in_circle(
+                    self.dest(base),
+                    self.org(base),
+                    self.dest(left_candidate),
+                    self.dest(self.onext(left_candidate)),
+                ) {
+                    let next = self.onext(left_candidate);
+                    self.delete_edge(left_candidate);
+                    left_candidate = next;
+                }
+            }
+            // and the same for the right half
+            let mut right_candidate = self.oprev(base);
+            if is_valid(self, right_candidate, base) {
+                while self.is_in_circle(
+                    self.dest(base),
+                    self.org(base),
+                    self.dest(right_candidate),
+                    self.dest(self.oprev(right_candidate)),
+                ) {
+                    let next = self.oprev(right_candidate);
+                    self.delete_edge(right_candidate);
+                    right_candidate = next;
+                }
+            }
+
+            let is_left_valid = is_valid(self, left_candidate, base);
+            let is_right_valid = is_valid(self, right_candidate, base);
+            if !is_left_valid && !is_right_valid {
+                // the upper common tangent is reached
+                break;
+            }
+            base = if !is_left_valid
+                || (is_right_valid
+                    && self.is_in_circle(
+                        self.dest(left_candidate),
+                        self.org(left_candidate),
+                        self.org(right_candidate),
+                        self.dest(right_candidate),
+                    ))
+            {
+                self.connect(right_candidate, Self::sym(base))
+            } else {
+                self.connect(Self::sym(base), Self::sym(left_candidate))
+            };
+        }
+    }
+
+    /// Converts the faces of the mesh into triangles. The face outside of the hull is skipped,
+    /// so the edges of the hull have no adjacent triangle.
+    fn to_triangle_set(&self, supertriangle: [Vector; 3]) -> TriangleSet {
+        let mut triangle_set = TriangleSet::new(self.points.len() * 2);
+        triangle_set.points.extend(supertriangle);
+        triangle_set.supertriangle_vertices = Some([0, 1, 2].map(VertexId));
+        triangle_set.points.extend_from_slice(self.points);
+        let vertex = |index: usize| VertexId(index + supertriangle.len());
+
+        let mut face_of_edge: Vec<Option<TriangleId>> = vec![None; self.next.len()];
+        let mut triangle_edges = Vec::new();
+        let primal_edges =
+            (0..self.next.len()).filter(|edge| edge % 2 == 0 && !self.is_deleted[edge / 4]);
+        for edge in primal_edges {
+            if face_of_edge[edge].is_some() {
+                continue;
+            }
+            let second = self.lnext(edge);
+            let third = self.lnext(second);
+            if self.lnext(third) != edge
+                || !self.is_ccw(self.org(edge), self.org(second), self.org(third))
+            {
+                continue;
+            }
+            let triangle_index = TriangleId(triangle_edges.len());
+            for edge in [edge, second, third] {
+                face_of_edge[edge] = Some(triangle_index);
+            }
+            triangle_edges.push([edge, second, third]);
+        }
+
+        for edges in triangle_edges {
+            let vertices = edges.map(|edge| vertex(self.org(edge)));
+            let [first, second, third] = edges.map(|edge| face_of_edge[Self::sym(edge)]);
+            triangle_set
+                .add_triangle_info(TriangleInfo::new(vertices).with_adjacent(first, second, third));
+        }
+        triangle_set
+    }
+}
+
+/// Triangulates the points without a supertriangle. The vertices of the supertriangle are still added first,
+/// so that the input points start at the same [`VertexId`] as with the incremental triangulation,
+/// but no triangle uses them.
+///
+/// Duplicates are removed like in [`TriangleSet::add_point`] and the points are stored sorted by x and then by y.
+pub fn triangulate_divide_and_conquer(
+    points: &[Vector],
+    supertriangle: [Vector; 3],
+) -> TriangleSet {
+    let mut sorted_points = points.to_vec();
+    sorted_points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted_points.dedup_by(|point, previous| point.is_same_point(*previous));
+
+    let mut mesh = QuadEdgeMesh::new(&sorted_points);
+    if sorted_points.len() >= 2 {
+        mesh.triangulate(0, sorted_points.len());
+    }
+    mesh.to_triangle_set(supertriangle)
+}
@@ -0,0 +1,712 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{
+    data_structures::{
+        error::CustomError,
+        ids::{TriangleId, VertexId},
+        triangle::Triangle,
+        triangle_info::TriangleInfo,
+        triangle_set::TriangleSet,
+        triangulation_result::TriangulationResult,
+        vector::Vector,
+    },
+    normalize::normalize_points,
+    triangulation::{insert_vertex, legalize_triangles, swap_edges, TriangleIndexPair},
+};
+
+/// Moves a vertex of the result to a new position and restores the delaunay constraint around it.
+///
+/// If the vertex stays inside of the triangles around it, only the edges around it are swapped.
+/// Otherwise it is taken out of the triangulation and inserted again at the new position, which is only possible
+/// for vertices that are neither on a constrained edge nor on the border of the mesh.
+/// Constrained edges are never swapped and the new position has to be inside of the mesh.
+/// ```
+/// use constrained_denaulay_triangulation::{move_vertex, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let points = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(10., 0.),
+///     Vector::new(10., 10.),
+///     Vector::new(0., 10.),
+///     Vector::new(4., 5.),
+/// ];
+/// let mut result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+/// let center = result.vertices.iter().position(|vertex| *vertex == Vector::new(4., 5.)).unwrap();
+/// move_vertex(&mut result, center, Vector::new(6., 5.)).unwrap();
+/// assert_eq!(result.vertices[center], Vector::new(6., 5.));
+/// assert_eq!(result.len(), 4);
+/// ```
+pub fn move_vertex(
+    result: &mut TriangulationResult,
+    vertex: usize,
+    new_position: Vector,
+) -> Result<(), CustomError> {
+    let vertex_id = *result
+        .vertex_ids
+        .get(vertex)
+        .ok_or(CustomError::VertexNotFound(vertex))?;
+    let (normalized_position, _) = normalize_points(&[new_position], Some(result.bounds));
+    let position = normalized_position[0];
+
+    let incident_triangles = result
+        .triangle_set
+        .get_triangle_indices_with_vertex(vertex_id);
+    let keeps_orientation = incident_triangles.iter().all(|triangle| {
+        let vertex_indices = result
+            .triangle_set
+            .get_triangle_info(*triangle)
+            .vertex_indices;
+        let position_in_triangle = vertex_indices
+            .iter()
+            .position(|v| *v == vertex_id)
+            .expect("The triangle was found by this vertex");
+        let next = result
+            .triangle_set
+            .get_point_from_vertex(vertex_indices[(position_in_triangle + 1) % 3]);
+        let previous = result
+            .triangle_set
+            .get_point_from_vertex(vertex_indices[(position_in_triangle + 2) % 3]);
+        (next - position).cross_product(previous - position) > 0.
+    });
+
+    let changed_triangles = if keeps_orientation {
+        result.triangle_set.points[vertex_id.0] = position;
+        incident_triangles
+    } else {
+        let containing_triangle = result
+            .triangle_set
+            .find_triangle_that_contains_point(position, incident_triangles[0])?;
+        if result
+            .removed_triangles
+            .binary_search(&containing_triangle)
+            .is_ok()
+        {
+            return Err(CustomError::PointNotInTriangle);
+        }
+
+        let mut changed_triangles = detach_vertex(result, vertex_id)?;
+        result.triangle_set.points[vertex_id.0] = position;
+        let removed_triangles = &result.removed_triangles;
+        let is_kept = |triangle: TriangleId| removed_triangles.binary_search(&triangle).is_err();
+        insert_vertex(
+            &mut result.triangle_set,
+            vertex_id,
+            changed_triangles[0],
+            &|current, adjacent| is_kept(current) && is_kept(adjacent),
+            &mut changed_triangles,
+        )?;
+        changed_triangles
+    };
+
+    let removed_triangles = &result.removed_triangles;
+    let is_kept = |triangle: TriangleId| removed_triangles.binary_search(&triangle).is_err();
+    legalize_triangles(
+        &mut result.triangle_set,
+        changed_triangles,
+        &|_, current, adjacent| is_kept(current) && is_kept(adjacent),
+    )?;
+    result.update_output();
+    Ok(())
+}
+
+/// Removes a vertex from the result and triangulates the area around it again, keeping the delaunay constraint.
+///
+/// Like [`Vec::swap_remove`], the last vertex of the result takes the index of the removed one.
+/// Only vertices inside of the mesh can be removed, not the ones on its border, on holes or on constrained edges.
+/// ```
+/// use constrained_denaulay_triangulation::{remove_point, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let points = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(10., 0.),
+///     Vector::new(10., 10.),
+///     Vector::new(0., 10.),
+///     Vector::new(4., 5.),
+/// ];
+/// let mut result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+/// let center = result.vertices.iter().position(|vertex| *vertex == Vector::new(4., 5.)).unwrap();
+/// remove_point(&mut result, center).unwrap();
+/// assert_eq!(result.vertices.len(), 4);
+/// assert_eq!(result.len(), 2);
+/// ```
+pub fn remove_point(result: &mut TriangulationResult, vertex: usize) -> Result<(), CustomError> {
+    let vertex_id = *result
+        .vertex_ids
+        .get(vertex)
+        .ok_or(CustomError::VertexNotFound(vertex))?;
+    retriangulate_without(result, vertex_id)?;
+    remove_orphan_point(result, vertex_id);
+    result.update_output();
+    Ok(())
+}
+
+/// Creates a coarser version of the result with at most `target_triangles` triangles, if possible.
+///
+/// The shortest edges are collapsed first: the vertex is taken out and the triangles around it are legalized again.
+/// Only vertices inside of the mesh are collapsed, the outline, the holes and constrained edges stay as they are.
+/// If there are not enough of those vertices, the result has more triangles than the target.
+/// ```
+/// use constrained_denaulay_triangulation::{decimate, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(10., 0.),
+///     Vector::new(10., 10.),
+///     Vector::new(0., 10.),
+/// ];
+/// let options = TriangulateOptions::new().with_maximum_triangle_area(1.);
+/// let result = triangulate_with_options(&square, None, &options).unwrap();
+/// let coarse = decimate(&result, 20).unwrap();
+/// assert!(coarse.len() <= 20);
+/// assert_eq!(coarse.vertices.len(), result.vertices.len() - (result.len() - coarse.len()) / 2);
+/// ```
+pub fn decimate(
+    result: &TriangulationResult,
+    target_triangles: usize,
+) -> Result<TriangulationResult, CustomError> {
+    let mut decimated = result.clone();
+    let point_count = decimated.triangle_set.points.len();
+    // Outdated entries of the queue are skipped by comparing their version
+    let mut versions = vec![0; point_count];
+    let mut queue = BinaryHeap::new();
+    for vertex in decimated.triangle_set.mesh_vertices() {
+        if let Some(length) = shortest_edge_length(&decimated, vertex) {
+            // the bits of positive floats are ordered like the floats
+            queue.push(Reverse((length.to_bits(), vertex, 0)));
+        }
+    }
+
+    let mut removed_vertices = Vec::new();
+    let triangle_count = |result: &TriangulationResult| {
+        result.triangle_set.triangle_count() - result.removed_triangles.len()
+    };
+    while triangle_count(&decimated) > target_triangles {
+        let Some(Reverse((_, vertex, version))) = queue.pop() else {
+            break;
+        };
+        if version != versions[vertex.0] {
+            continue;
+        }
+        let Some(ring) = free_vertex_ring(&decimated, vertex) else {
+            continue;
+        };
+        let neighbors = ring_neighbors(&decimated.triangle_set, vertex, &ring);
+        retriangulate_without(&mut decimated, vertex)?;
+        versions[vertex.0] += 1;
+        removed_vertices.push(vertex);
+
+        for neighbor in neighbors {
+            versions[neighbor.0] += 1;
+            if let Some(length) = shortest_edge_length(&decimated, neighbor) {
+                queue.push(Reverse((length.to_bits(), neighbor, versions[neighbor.0])));
+            }
+        }
+    }
+
+    // Removing the highest vertex first never moves another removed vertex
+    removed_vertices.sort_unstable_by(|a, b| b.cmp(a));
+    for vertex in removed_vertices {
+        remove_orphan_point(&mut decimated, vertex);
+    }
+    decimated.update_output();
+    Ok(decimated)
+}
+
+/// Takes the vertex out of the triangles and restores the delaunay constraint around it.
+/// The point of the vertex stays in the triangle set.
+fn retriangulate_without(
+    result: &mut TriangulationResult,
+    vertex: VertexId,
+) -> Result<(), CustomError> {
+    let changed_triangles = detach_vertex(result, vertex)?;
+
+    let removed_triangles = &result.removed_triangles;
+    let is_kept = |triangle: TriangleId| removed_triangles.binary_search(&triangle).is_err();
+    legalize_triangles(
+        &mut result.triangle_set,
+        changed_triangles,
+        &|_, current, adjacent| is_kept(current) && is_kept(adjacent),
+    )
+}
+
+/// Removes the point of a vertex that is not used by any triangle anymore, the last vertex takes its index.
+fn remove_orphan_point(result: &mut TriangulationResult, vertex: VertexId) {
+    if let Some(moved_vertex) = result.triangle_set.remove_point(vertex) {
+        let constrained_vertices = result
+            .hole_reports
+            .iter_mut()
+            .flat_map(|hole_report| &mut hole_report.vertex_ids)
+            .chain(
+                result
+                    .constrained_edges
+                    .iter_mut()
+                    .flat_map(|constraint_trace| &mut constraint_trace.vertex_ids),
+            );
+        for constrained_vertex in constrained_vertices {
+            if *constrained_vertex == moved_vertex {
+                *constrained_vertex = vertex;
+            }
+        }
+    }
+}
+
+/// The length of the shortest edge of a vertex that can be taken out of the triangulation.
+fn shortest_edge_length(result: &TriangulationResult, vertex: VertexId) -> Option<f32> {
+    let ring = free_vertex_ring(result, vertex)?;
+    let triangle_set = &result.triangle_set;
+    let center = triangle_set.get_point_from_vertex(vertex);
+    ring_neighbors(triangle_set, vertex, &ring)
+        .into_iter()
+        .map(|neighbor| {
+            let edge = triangle_set.get_point_from_vertex(neighbor) - center;
+            edge.x.hypot(edge.y)
+        })
+        .min_by(f32::total_cmp)
+}
+
+/// Takes the vertex out of the triangles, so that its point is not used by any triangle anymore.
+///
+/// The edges around the vertex are swapped away until only three triangles are left, which are merged into one.
+/// Returns the triangles that were changed, the merged one first.
+pub(crate) fn detach_vertex(
+    result: &mut TriangulationResult,
+    vertex: VertexId,
+) -> Result<Vec<TriangleId>, CustomError> {
+    let mut ring = free_vertex_ring(result, vertex).ok_or(CustomError::VertexIsFixed(vertex))?;
+    let mut changed_triangles = Vec::new();
+    while ring.len() > 3 {
+        let triangle_set = &mut result.triangle_set;
+        let neighbors = ring_neighbors(triangle_set, vertex, &ring);
+        let point = |v: VertexId| triangle_set.get_point_from_vertex(v);
+        let center = point(vertex);
+        let count = ring.len();
+        // The ear between the neighbors before and after neighbor i can be cut off by swapping the edge to neighbor i
+        let is_convex = |i: usize| {
+            let (previous, current, next) = (
+                point(neighbors[(i + count - 1) % count]),
+                point(neighbors[i]),
+                point(neighbors[(i + 1) % count]),
+            );
+            (current - previous).cross_product(next - previous) > 0.
+        };
+        // How far the vertex is on the inner side of the diagonal that cuts off the ear
+        let inner_side = |i: usize| {
+            let previous = point(neighbors[(i + count - 1) % count]);
+            let next = point(neighbors[(i + 1) % count]);
+            (previous - center).cross_product(next - center)
+        };
+        let is_ear = |i: usize| is_convex(i) && inner_side(i) > 0.;
+        // Prefer the ear whose circumcircle contains no other neighbor, it is part of the delaunay triangulation
+        let is_delaunay_ear = |i: usize| {
+            let ear = Triangle::new(
+                point(neighbors[(i + count - 1) % count]),
+                point(neighbors[i]),
+                point(neighbors[(i + 1) % count]),
+            );
+            let (Some(circumcenter), Some(radius)) = (ear.circumcenter(), ear.circumradius())
+            else {
+                return false;
+            };
+            let ear_neighbors = [(i + count - 1) % count, i, (i + 1) % count];
+            (0..count).filter(|j| !ear_neighbors.contains(j)).all(|j| {
+                let offset = point(neighbors[j]) - circumcenter;
+                offset.x.hypot(offset.y) >= radius
+            })
+        };
+        let ear = (0..count)
+            .find(|i| is_ear(*i) && is_delaunay_ear(*i))
+            .or_else(|| (0..count).find(|i| is_ear(*i)))
+            // If the vertex is on a diagonal up to rounding errors, the triangle between it and the diagonal is
+            // degenerate, but it is merged away with the vertex in the end
+            .or_else(|| {
+                (0..count)
+                    .filter(|i| is_convex(*i))
+                    .max_by(|a, b| inner_side(*a).total_cmp(&inner_side(*b)))
+            })
+            .ok_or(CustomError::SwappingFailed)?;
+
+        // ring[ear - 1] and ring[ear] share the edge from the vertex to neighbor `ear`
+        let current = ring[(ear + count - 1) % count];
+        let adjacent = ring[ear];
+        let shared_vertex_index = triangle_set
+            .get_triangle_info(current)
+            .vertex_indices
+            .iter()
+            .position(|v| *v == neighbors[ear])
+            .expect("Neighboring triangles of the ring share the neighbor");
+        swap_edges(
+            &TriangleIndexPair::new(adjacent, current),
+            triangle_set,
+            shared_vertex_index,
+        )?;
+        changed_triangles.extend([current, adjacent]);
+        // one of the swapped triangles still contains the vertex
+        let start = [current, adjacent]
+            .into_iter()
+            .find(|triangle| {
+                triangle_set
+                    .get_triangle_info(*triangle)
+                    .vertex_indices
+                    .contains(&vertex)
+            })
+            .ok_or(CustomError::SwappingFailed)?;
+        ring = triangle_set
+            .get_triangle_ring_from(vertex, start)
+            .ok_or(CustomError::SwappingFailed)?;
+    }
+
+    // Merge the last three triangles into the first one
+    let triangle_set = &mut result.triangle_set;
+    let neighbors = ring_neighbors(triangle_set, vertex, &ring);
+    let outer_adjacent: Vec<Option<TriangleId>> = ring
+        .iter()
+        .zip(&neighbors)
+        .map(|(triangle, neighbor)| {
+            let info = triangle_set.get_triangle_info(*triangle);
+            let position = info
+                .vertex_indices
+                .iter()
+                .position(|v| v == neighbor)
+                .expect("Every triangle of the ring contains its neighbor");
+            info.adjacent_triangle_indices[position]
+        })
+        .collect();
+    let merged = TriangleInfo::new([neighbors[0], neighbors[1], neighbors[2]]).with_adjacent(
+        outer_adjacent[0],
+        outer_adjacent[1],
+        outer_adjacent[2],
+    );
+    triangle_set.replace_triangle(ring[0], &merged);
+    for i in 1..3 {
+        if let Some(adjacent) = outer_adjacent[i] {
+            triangle_set.replace_adjacent(adjacent, Some(ring[i]), Some(ring[0]));
+        }
+    }
+
+    let mut merged_triangle = ring[0];
+    let mut obsolete_triangles = [ring[1], ring[2]];
+    // Removing the higher index first keeps the lower index in place
+    obsolete_triangles.sort_by(|a, b| b.cmp(a));
+    for obsolete_triangle in obsolete_triangles {
+        changed_triangles.retain(|triangle| *triangle != obsolete_triangle);
+        if let Some((moved_from, moved_to)) = result.remove_triangle(obsolete_triangle) {
+            for triangle in changed_triangles.iter_mut() {
+                if *triangle == moved_from {
+                    *triangle = moved_to;
+                }
+            }
+            if merged_triangle == moved_from {
+                merged_triangle = moved_to;
+            }
+        }
+    }
+    changed_triangles.retain(|triangle| *triangle != merged_triangle);
+    changed_triangles.insert(0, merged_triangle);
+    Ok(changed_triangles)
+}
+
+/// Moves every free vertex a `factor` of the way towards the average of its neighbors, `iterations` times.
+///
+/// Vertices on the border of the mesh, on holes and on constrained edges stay exactly where they are,
+/// so the outline and the holes keep their shape. A step that would flip a triangle over is skipped.
+/// The triangles are not swapped afterwards, so the result does not have to be delaunay anymore.
+/// ```
+/// use constrained_denaulay_triangulation::{refine, smooth, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let mut result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// refine(&mut result, 0.05, 1000).unwrap();
+/// smooth(&mut result, 3, 0.5);
+/// ```
+pub fn smooth(result: &mut TriangulationResult, iterations: usize, factor: f32) {
+    let free_vertices: Vec<(VertexId, Vec<TriangleId>)> = result
+        .vertex_ids
+        .iter()
+        .filter_map(|vertex| Some((*vertex, free_vertex_ring(result, *vertex)?)))
+        .collect();
+
+    let triangle_set = &mut result.triangle_set;
+    for _ in 0..iterations {
+        for (vertex, ring) in &free_vertices {
+            let neighbors = ring_neighbors(triangle_set, *vertex, ring);
+            let sum = neighbors.iter().fold(Vector::default(), |sum, neighbor| {
+                sum + triangle_set.get_point_from_vertex(*neighbor)
+            });
+            let average = sum / neighbors.len() as f32;
+            let position = triangle_set.get_point_from_vertex(*vertex);
+            let new_position = position + (average - position) * factor;
+
+            // The triangles of the ring are (vertex, neighbor i, neighbor i + 1)
+            let keeps_orientation = (0..neighbors.len()).all(|i| {
+                let first = triangle_set.get_point_from_vertex(neighbors[i]);
+                let second =
+                    triangle_set.get_point_from_vertex(neighbors[(i + 1) % neighbors.len()]);
+                (first - new_position).cross_product(second - new_position) > 0.
+            });
+            if keeps_orientation {
+                triangle_set.points[vertex.0] = new_position;
+            }
+        }
+    }
+    result.update_output();
+}
+
+/// The triangles around the vertex, if it can be moved freely:
+/// it is surrounded by kept triangles and is not part of a constrained edge.
+fn free_vertex_ring(result: &TriangulationResult, vertex: VertexId) -> Option<Vec<TriangleId>> {
+    let triangle_set = &result.triangle_set;
+    let ring = triangle_set.get_triangle_ring(vertex)?;
+    let is_fixed = ring.iter().any(|triangle| {
+        let vertex_indices = triangle_set.get_triangle_info(*triangle).vertex_indices;
+        result.removed_triangles.binary_search(triangle).is_ok()
+            || vertex_indices
+                .iter()
+                .any(|other| triangle_set.is_constrained_edge(vertex, *other))
+    });
+    (!is_fixed).then_some(ring)
+}
+
+/// For every triangle of the ring around the vertex, the neighbor that follows the vertex counter-clockwise.
+fn ring_neighbors(
+    triangle_set: &TriangleSet,
+    vertex: VertexId,
+    ring: &[TriangleId],
+) -> Vec<VertexId> {
+    ring.iter()
+        .map(|triangle| {
+            let vertex_indices = triangle_set.get_triangle_info(*triangle).vertex_indices;
+            let position = vertex_indices
+                .iter()
+                .position(|v| *v == vertex)
+                .expect("Every triangle of the ring contains the vertex");
+            vertex_indices[(position + 1) % 3]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimate, move_vertex, remove_point, smooth};
+    use crate::{
+        is_delaunay,
+        math_utils::calculate_triangle_area,
+        refine,
+        test_util::{assert_same_mesh, refined_square},
+        triangulate_with_options, CustomError, TriangulateOptions, TriangulationResult, Vector,
+        DEFAULT_STEINER_POINT_LIMIT,
+    };
+
+    fn closest_vertex(result: &TriangulationResult, point: Vector) -> usize {
+        let distance = |vertex: &Vector| (*vertex - point).x.hypot((*vertex - point).y);
+        (0..result.vertices.len())
+            .min_by(|a, b| {
+                distance(&result.vertices[*a]).total_cmp(&distance(&result.vertices[*b]))
+            })
+            .unwrap()
+    }
+
+    /// The edges without a neighbor as sorted pairs of vertex positions.
+    fn boundary_edges(result: &TriangulationResult) -> Vec<[(u32, u32); 2]> {
+        let position = |vertex: usize| {
+            let point = result.vertices[vertex];
+            (point.x.to_bits(), point.y.to_bits())
+        };
+        let mut edges: Vec<[(u32, u32); 2]> = (0..result.len())
+            .flat_map(|index| (0..3).map(move |edge| (index, edge)))
+            .filter(|(index, edge)| result.adjacency[*index][*edge].is_none())
+            .map(|(index, edge)| {
+                let triangle = result.triangles[index];
+                let mut edge = [position(triangle[edge]), position(triangle[(edge + 1) % 3])];
+                edge.sort();
+                edge
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    fn hole_outline(result: &TriangulationResult) -> Vec<Vector> {
+        result.hole_reports[0]
+            .vertices
+            .iter()
+            .map(|vertex| result.vertices[*vertex])
+            .collect()
+    }
+
+    /// Every triangle is counter-clockwise and every neighbor is a neighbor back across the same edge.
+    fn assert_topology(result: &TriangulationResult) {
+        for (index, triangle) in result.triangles.iter().enumerate() {
+            assert!(calculate_triangle_area(&result.triangle(index)) > 0.);
+            for edge in 0..3 {
+                let Some(adjacent) = result.adjacency[index][edge] else {
+                    continue;
+                };
+                let back_edge = result.triangles[adjacent]
+                    .iter()
+                    .position(|vertex| *vertex == triangle[(edge + 1) % 3])
+                    .unwrap();
+                assert_eq!(
+                    result.triangles[adjacent][(back_edge + 1) % 3],
+                    triangle[edge]
+                );
+                assert_eq!(result.adjacency[adjacent][back_edge], Some(index));
+            }
+        }
+    }
+
+    fn assert_valid(result: &TriangulationResult) {
+        assert!(is_delaunay(result));
+        let mut total_area = 0.;
+        for triangle in result.to_triangles() {
+            let area = calculate_triangle_area(&triangle);
+            assert!(area > 0.);
+            total_area += area;
+        }
+        assert!((total_area - 100.).abs() < 0.01);
+    }
+
+    #[test]
+    fn small_move_stays_delaunay() -> Result<(), CustomError> {
+        let mut result = refined_square(4.)?;
+        let triangle_count = result.len();
+        let vertex = closest_vertex(&result, Vector::new(5., 5.));
+        let new_position = result.vertices[vertex] + Vector::new(0.1, 0.05);
+
+        move_vertex(&mut result, vertex, new_position)?;
+
+        assert_eq!(result.vertices[vertex], new_position);
+        assert_eq!(result.len(), triangle_count);
+        assert_valid(&result);
+        Ok(())
+    }
+
+    #[test]
+    fn move_across_edges_reinserts_the_vertex() -> Result<(), CustomError> {
+        let mut result = refined_square(4.)?;
+        let triangle_count = result.len();
+        let vertex_count = result.vertices.len();
+        let vertex = closest_vertex(&result, Vector::new(5., 5.));
+        let new_position = Vector::new(8.2, 7.9);
+
+        move_vertex(&mut result, vertex, new_position)?;
+
+        assert!((result.vertices[vertex] - new_position).x.abs() < 1e-5);
+        assert!((result.vertices[vertex] - new_position).y.abs() < 1e-5);
+        assert_eq!(result.vertices.len(), vertex_count);
+        assert_eq!(result.len(), triangle_count);
+        assert_valid(&result);
+        Ok(())
+    }
+
+    #[test]
+    fn smoothing_improves_angles_and_keeps_the_border() -> Result<(), CustomError> {
+        let mut result = refined_square(4.)?;
+        let mean_min_angle = |result: &TriangulationResult| {
+            let triangles = result.to_triangles();
+            triangles
+                .iter()
+                .map(|triangle| triangle.min_angle())
+                .sum::<f32>()
+                / triangles.len() as f32
+        };
+        let on_border = |vertex: &Vector| {
+            vertex.x == 0. || vertex.x == 10. || vertex.y == 0. || vertex.y == 10.
+        };
+        let border_before: Vec<(usize, Vector)> = result
+            .vertices
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, vertex)| on_border(vertex))
+            .collect();
+        let angle_before = mean_min_angle(&result);
+
+        smooth(&mut result, 5, 0.5);
+
+        assert!(mean_min_angle(&result) > angle_before);
+        for (index, vertex) in border_before {
+            assert_eq!(result.vertices[index].x.to_bits(), vertex.x.to_bits());
+            assert_eq!(result.vertices[index].y.to_bits(), vertex.y.to_bits());
+        }
+        for triangle in result.to_triangles() {
+            assert!(calculate_triangle_area(&triangle) > 0.);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn removing_a_point_equals_triangulating_without_it() -> Result<(), CustomError> {
+        let mut state: u32 = 7;
+        let mut next = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            1. + 8. * (state >> 8) as f32 / (1 << 24) as f32
+        };
+        let mut points: Vec<Vector> = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        points.extend((0..30).map(|_| Vector::new(next(), next())));
+        let mut result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+
+        let removed_point = points.remove(17);
+        let vertex = result
+            .vertices
+            .iter()
+            .position(|vertex| *vertex == removed_point)
+            .unwrap();
+        remove_point(&mut result, vertex)?;
+        let expected = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+
+        assert!(is_delaunay(&result));
+        assert_eq!(result.vertices.len(), points.len());
+        assert!(!result.vertices.contains(&removed_point));
+        assert_same_mesh(&result, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn decimation_keeps_the_boundaries() -> Result<(), CustomError> {
+        let square = [(0., 0.), (100., 0.), (100., 100.), (0., 100.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let hole = [(40., 40.), (60., 40.), (60., 60.), (40., 60.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let mut result =
+            triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new())?;
+        refine(&mut result, 2., DEFAULT_STEINER_POINT_LIMIT)?;
+        assert!(result.len() > 10_000);
+
+        let decimated = decimate(&result, 1000)?;
+
+        assert!(decimated.len() <= 1000);
+        assert_eq!(boundary_edges(&decimated), boundary_edges(&result));
+        assert_eq!(hole_outline(&decimated), hole_outline(&result));
+        assert_topology(&decimated);
+        assert!(is_delaunay(&decimated));
+        Ok(())
+    }
+
+    #[test]
+    fn border_vertices_can_not_be_reinserted() -> Result<(), CustomError> {
+        let mut result = refined_square(4.)?;
+        let corner = closest_vertex(&result, Vector::new(0., 0.));
+        assert!(matches!(
+            move_vertex(&mut result, corner, Vector::new(5., 5.)),
+            Err(CustomError::VertexIsFixed(_))
+        ));
+        let missing_vertex = result.vertices.len();
+        assert!(matches!(
+            move_vertex(&mut result, missing_vertex, Vector::new(5., 5.)),
+            Err(CustomError::VertexNotFound(_))
+        ));
+        Ok(())
+    }
+}
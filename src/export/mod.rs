@@ -0,0 +1,8 @@
+//! Triangulations for looking at outside of this crate, as images without a GPU and as files for visualization tools.
+#[cfg(feature = "image")]
+mod raster;
+mod vtk;
+
+#[cfg(feature = "image")]
+pub use raster::{rasterize, BACKGROUND_COLOR, EDGE_COLOR, FILL_COLOR, HOLE_COLOR};
+pub use vtk::to_vtk;
@@ -0,0 +1,191 @@
+//! Images of triangulations without a GPU, for tests and bug reports.
+use image::{Rgba, RgbaImage};
+
+use crate::data_structures::{triangle::Triangle, vector::Vector};
+
+/// The color of the image where there are no triangles.
+pub const BACKGROUND_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+/// The color the triangles are filled with.
+pub const FILL_COLOR: Rgba<u8> = Rgba([200, 220, 240, 255]);
+/// The color of the edges of the triangles.
+pub const EDGE_COLOR: Rgba<u8> = Rgba([40, 40, 60, 255]);
+/// The color of the outlines of the holes.
+pub const HOLE_COLOR: Rgba<u8> = Rgba([220, 30, 30, 255]);
+
+/// The empty pixels around the drawing, so that lines on the border stay visible.
+const MARGIN: f32 = 2.;
+
+/// Draws the triangles filled and with their edges, and the outlines of the holes on top.
+///
+/// The drawing is scaled to the bounds of all points to fit into `width` pixels, the height follows from the bounds.
+/// The y axis points up, like in the input. The image can be saved as a PNG with [`RgbaImage::save`].
+/// ```
+/// use constrained_denaulay_triangulation::{export::{rasterize, FILL_COLOR}, Triangle, Vector};
+///
+/// let triangle = Triangle::new(Vector::new(0., 0.), Vector::new(10., 0.), Vector::new(0., 10.));
+/// let image = rasterize(&[triangle], None, 64);
+/// assert_eq!(image.width(), 64);
+/// assert_eq!(image.height(), 64);
+/// assert_eq!(*image.get_pixel(16, 40), FILL_COLOR);
+/// ```
+pub fn rasterize(triangles: &[Triangle], holes: Option<&[Vec<Vector>]>, width: u32) -> RgbaImage {
+    let holes = holes.unwrap_or_default();
+    let points = triangles
+        .iter()
+        .flat_map(|triangle| [0, 1, 2].map(|i| triangle.p(i)))
+        .chain(holes.iter().flatten().copied());
+    let (min, max) = points.fold(
+        (
+            Vector::new(f32::INFINITY, f32::INFINITY),
+            Vector::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+        ),
+        |(min, max), point| (min.min(point), max.max(point)),
+    );
+    let width = width.max(1);
+    if !(min.x <= max.x && min.y <= max.y) {
+        return RgbaImage::from_pixel(width, 1, BACKGROUND_COLOR);
+    }
+
+    let size = max - min;
+    let drawing_width = (width as f32 - 1. - 2. * MARGIN).max(1.);
+    // points on a vertical line are scaled by their height instead
+    let extent = [size.x, size.y, 1.]
+        .into_iter()
+        .find(|extent| *extent > 0.)
+        .unwrap_or(1.);
+    let scale = drawing_width / extent;
+    let height = (size.y * scale + 1. + 2. * MARGIN).ceil().max(1.) as u32;
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND_COLOR);
+    let to_pixel = |point: Vector| {
+        Vector::new(
+            MARGIN + (point.x - min.x) * scale,
+            height as f32 - 1. - MARGIN - (point.y - min.y) * scale,
+        )
+    };
+
+    for triangle in triangles {
+        let corners = Triangle::new(
+            to_pixel(triangle.p(0)),
+            to_pixel(triangle.p(1)),
+            to_pixel(triangle.p(2)),
+        );
+        fill_triangle(&mut image, &corners);
+    }
+    for triangle in triangles {
+        for i in 0..3 {
+            let (start, end) = (triangle.p(i), triangle.p((i + 1) % 3));
+            draw_line(&mut image, to_pixel(start), to_pixel(end), EDGE_COLOR);
+        }
+    }
+    for hole in holes {
+        for i in 0..hole.len() {
+            let (start, end) = (hole[i], hole[(i + 1) % hole.len()]);
+            draw_line(&mut image, to_pixel(start), to_pixel(end), HOLE_COLOR);
+        }
+    }
+    image
+}
+
+/// Fills every pixel whose center is inside of the triangle, which is given in pixel coordinates.
+fn fill_triangle(image: &mut RgbaImage, triangle: &Triangle) {
+    let xs = [0, 1, 2].map(|i| triangle.p(i).x);
+    let ys = [0, 1, 2].map(|i| triangle.p(i).y);
+    let pixel_range = |values: [f32; 3], size: u32| {
+        let low = values
+            .iter()
+            .copied()
+            .fold(f32::INFINITY, f32::min)
+            .floor()
+            .max(0.) as u32;
+        let high = values
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max)
+            .ceil() as u32;
+        low..high.min(size)
+    };
+    for y in pixel_range(ys, image.height()) {
+        for x in pixel_range(xs, image.width()) {
+            if triangle.contains(Vector::new(x as f32 + 0.5, y as f32 + 0.5)) {
+                image.put_pixel(x, y, FILL_COLOR);
+            }
+        }
+    }
+}
+
+/// Draws a line between two points in pixel coordinates, with one pixel for every step along its longer axis.
+fn draw_line(image: &mut RgbaImage, start: Vector, end: Vector, color: Rgba<u8>) {
+    let delta = end - start;
+    let steps = delta.x.abs().max(delta.y.abs()).ceil().max(1.) as u32;
+    for step in 0..=steps {
+        let point = start + delta * (step as f32 / steps as f32);
+        let (x, y) = (point.x.round(), point.y.round());
+        if x >= 0. && y >= 0. && (x as u32) < image.width() && (y as u32) < image.height() {
+            image.put_pixel(x as u32, y as u32, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rasterize, EDGE_COLOR, FILL_COLOR, HOLE_COLOR};
+    use crate::{triangulate, Vector};
+
+    /// The mean brightness of each cell of an 8 x 8 grid over the image, which barely changes with small differences.
+    fn fingerprint(image: &image::RgbaImage) -> [u8; 64] {
+        let mut sums = [(0u32, 0u32); 64];
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let cell = (y * 8 / image.height() * 8 + x * 8 / image.width()) as usize;
+            let brightness = (pixel[0] as u32 + pixel[1] as u32 + pixel[2] as u32) / 3;
+            sums[cell].0 += brightness;
+            sums[cell].1 += 1;
+        }
+        sums.map(|(sum, count)| (sum / count.max(1)) as u8)
+    }
+
+    #[test]
+    fn readme_example_matches_golden_image() {
+        let input_points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let minihole = [(-1.5, 3.5), (-0.5, 3.5), (-1., 2.5)];
+        let bighole = [(-4., 4.), (0., -2.), (4., 4.)];
+        let holes: Vec<Vec<Vector>> = [minihole.as_slice(), bighole.as_slice()]
+            .iter()
+            .map(|hole| hole.iter().map(Vector::from).collect())
+            .collect();
+        let triangles = triangulate(&input_points, Some(&holes), None).unwrap();
+
+        let image = rasterize(&triangles, Some(&holes), 128);
+        assert_eq!(image.width(), 128);
+        let colors = [FILL_COLOR, EDGE_COLOR, HOLE_COLOR];
+        for color in colors {
+            assert!(image.pixels().any(|pixel| *pixel == color));
+        }
+
+        let golden: [u8; 64] = [
+            237, 242, 231, 230, 242, 242, 242, 237, 245, 254, 234, 226, 255, 255, 254, 245, 255,
+            244, 255, 252, 255, 255, 244, 255, 255, 246, 253, 255, 255, 253, 246, 255, 255, 255,
+            244, 255, 255, 244, 255, 255, 255, 255, 247, 252, 252, 247, 255, 255, 255, 255, 255,
+            244, 244, 255, 255, 255, 255, 255, 255, 247, 246, 255, 255, 255,
+        ];
+        let fingerprint = fingerprint(&image);
+        for (cell, (actual, expected)) in fingerprint.iter().zip(golden).enumerate() {
+            assert!(
+                actual.abs_diff(expected) <= 8,
+                "cell {cell} has brightness {actual} instead of {expected}"
+            );
+        }
+    }
+}
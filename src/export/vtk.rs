@@ -0,0 +1,133 @@
+//! Legacy VTK files, which ParaView and VisIt open directly.
+use std::io::{Result, Write};
+
+use crate::{
+    data_structures::{mesh_statistics::MeshStatistics, triangulation_result::TriangulationResult},
+    io::triangle_fmt::boundary_marker,
+    queries::vertex_markers,
+};
+
+/// The VTK cell type of a triangle.
+const VTK_TRIANGLE: usize = 5;
+
+/// Writes the result as an ASCII unstructured grid of the legacy VTK format.
+///
+/// The cells have the area and the smallest angle in degrees of their triangle as data,
+/// the points their boundary marker like in a `.node` file, see [`boundary_marker`].
+/// ```
+/// use constrained_denaulay_triangulation::{export::to_vtk, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let mut file = Vec::new();
+/// to_vtk(&result, &mut file).unwrap();
+/// let file = String::from_utf8(file).unwrap();
+/// assert!(file.starts_with("# vtk DataFile Version 3.0"));
+/// assert!(file.contains("CELLS 2 8"));
+/// ```
+pub fn to_vtk<W: Write>(result: &TriangulationResult, mut writer: W) -> Result<()> {
+    writeln!(writer, "# vtk DataFile Version 3.0")?;
+    writeln!(writer, "constrained delaunay triangulation")?;
+    writeln!(writer, "ASCII")?;
+    writeln!(writer, "DATASET UNSTRUCTURED_GRID")?;
+
+    writeln!(writer, "POINTS {} float", result.vertices.len())?;
+    for vertex in &result.vertices {
+        writeln!(writer, "{} {} 0", vertex.x, vertex.y)?;
+    }
+    let triangle_count = result.triangles.len();
+    writeln!(writer, "CELLS {} {}", triangle_count, triangle_count * 4)?;
+    for [a, b, c] in &result.triangles {
+        writeln!(writer, "3 {a} {b} {c}")?;
+    }
+    writeln!(writer, "CELL_TYPES {triangle_count}")?;
+    for _ in 0..triangle_count {
+        writeln!(writer, "{VTK_TRIANGLE}")?;
+    }
+
+    let statistics = MeshStatistics::from_result(result);
+    writeln!(writer, "CELL_DATA {triangle_count}")?;
+    writeln!(writer, "SCALARS area float 1")?;
+    writeln!(writer, "LOOKUP_TABLE default")?;
+    for area in statistics.areas() {
+        writeln!(writer, "{area}")?;
+    }
+    writeln!(writer, "SCALARS min_angle float 1")?;
+    writeln!(writer, "LOOKUP_TABLE default")?;
+    for min_angle in statistics.min_angles() {
+        writeln!(writer, "{}", min_angle.to_degrees())?;
+    }
+
+    writeln!(writer, "POINT_DATA {}", result.vertices.len())?;
+    writeln!(writer, "SCALARS boundary_marker int 1")?;
+    writeln!(writer, "LOOKUP_TABLE default")?;
+    for marker in vertex_markers(result) {
+        writeln!(writer, "{}", boundary_marker(marker))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_vtk;
+    use crate::{triangulate_with_options, TriangulateOptions, Vector};
+
+    #[test]
+    fn sections_have_the_counts_of_the_mesh() {
+        let points = [(0., 0.), (10., 0.), (10., 10.), (0., 10.), (5., 5.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+        let mut file = Vec::new();
+        to_vtk(&result, &mut file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        let lines: Vec<&str> = file.lines().collect();
+
+        assert_eq!(lines[0], "# vtk DataFile Version 3.0");
+        assert_eq!(lines[2], "ASCII");
+        assert_eq!(lines[3], "DATASET UNSTRUCTURED_GRID");
+        // every section header is followed by as many lines as it announces
+        let section = |header: &str| {
+            let start = lines
+                .iter()
+                .position(|line| line.starts_with(header))
+                .unwrap();
+            let count: usize = lines[start]
+                .split_whitespace()
+                .nth(1)
+                .unwrap()
+                .parse()
+                .unwrap();
+            (start, count)
+        };
+        let (points_start, point_count) = section("POINTS");
+        assert_eq!(point_count, 5);
+        assert!(lines[points_start + 1..=points_start + point_count]
+            .iter()
+            .all(|line| line.split_whitespace().count() == 3));
+        let (cells_start, cell_count) = section("CELLS");
+        assert_eq!(cell_count, 4);
+        assert!(lines[cells_start + 1..=cells_start + cell_count]
+            .iter()
+            .all(|line| line.starts_with("3 ")));
+        let (types_start, type_count) = section("CELL_TYPES");
+        assert_eq!(type_count, 4);
+        assert_eq!(lines[types_start + 1], "5");
+        assert_eq!(section("CELL_DATA").1, 4);
+        assert_eq!(section("POINT_DATA").1, 5);
+        // the area of every triangle is a quarter of the square
+        let area_start = lines
+            .iter()
+            .position(|line| *line == "SCALARS area float 1")
+            .unwrap();
+        assert!(lines[area_start + 2..area_start + 6]
+            .iter()
+            .all(|line| *line == "25"));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::data_structures::{triangulation_result::TriangulationResult, vector::Vector};
+
+/// One directed side of an edge, belonging to exactly one triangle (face).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HalfEdge {
+    /// The vertex this half-edge starts at.
+    pub vertex: usize,
+    /// The half-edge of the neighboring face that runs the opposite way. `None` on the boundary of the mesh.
+    pub twin: Option<usize>,
+    /// The next half-edge counter-clockwise around the same face.
+    pub next: usize,
+    /// The face (triangle) this half-edge belongs to.
+    pub face: usize,
+}
+
+/// A half-edge representation of a triangulation.
+///
+/// The half-edges of face `f` are stored at `3 * f`, `3 * f + 1` and `3 * f + 2`,
+/// and half-edge `3 * f + i` starts at vertex `i` of triangle `f` of the [`TriangulationResult`].
+#[derive(Clone, Debug, Default)]
+pub struct HalfEdgeMesh {
+    /// The positions of the vertices.
+    pub vertices: Vec<Vector>,
+    /// All half-edges, three per face.
+    pub half_edges: Vec<HalfEdge>,
+    /// One outgoing half-edge for every vertex, `None` for vertices that are not part of any face.
+    /// For vertices on the boundary this is the half-edge that has no twin, if there is one.
+    pub vertex_half_edges: Vec<Option<usize>>,
+}
+
+impl HalfEdgeMesh {
+    /// The number of faces (triangles).
+    pub fn face_count(&self) -> usize {
+        self.half_edges.len() / 3
+    }
+
+    /// The three half-edges of a face, in counter-clockwise order.
+    pub fn face_half_edges(&self, face: usize) -> [usize; 3] {
+        [3 * face, 3 * face + 1, 3 * face + 2]
+    }
+
+    /// The vertex a half-edge ends at.
+    pub fn destination(&self, half_edge: usize) -> usize {
+        self.half_edges[self.half_edges[half_edge].next].vertex
+    }
+
+    /// The previous half-edge around the same face.
+    pub fn previous(&self, half_edge: usize) -> usize {
+        self.half_edges[self.half_edges[half_edge].next].next
+    }
+}
+
+/// Builds the half-edge structure of a triangulation from its adjacency.
+/// ```
+/// use constrained_denaulay_triangulation::{to_half_edge, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let mesh = to_half_edge(&result);
+/// assert_eq!(mesh.face_count(), 2);
+/// // the diagonal is the only edge with two sides
+/// assert_eq!(mesh.half_edges.iter().filter(|half_edge| half_edge.twin.is_some()).count(), 2);
+/// ```
+pub fn to_half_edge(result: &TriangulationResult) -> HalfEdgeMesh {
+    let mut half_edges = Vec::with_capacity(result.triangles.len() * 3);
+    let mut vertex_half_edges = vec![None; result.vertices.len()];
+
+    for (face, (vertices, adjacent)) in result.triangles.iter().zip(&result.adjacency).enumerate() {
+        for i in 0..3 {
+            // The twin starts where this half-edge ends
+            let twin = adjacent[i].map(|adjacent_face| {
+                let position = result.triangles[adjacent_face]
+                    .iter()
+                    .position(|vertex| *vertex == vertices[(i + 1) % 3])
+                    .expect("adjacent triangles share their edge");
+                3 * adjacent_face + position
+            });
+            let half_edge = HalfEdge {
+                vertex: vertices[i],
+                twin,
+                next: 3 * face + (i + 1) % 3,
+                face,
+            };
+
+            let outgoing = &mut vertex_half_edges[vertices[i]];
+            if outgoing.is_none() || twin.is_none() {
+                *outgoing = Some(half_edges.len());
+            }
+            half_edges.push(half_edge);
+        }
+    }
+
+    HalfEdgeMesh {
+        vertices: result.vertices.clone(),
+        half_edges,
+        vertex_half_edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_half_edge;
+    use crate::{triangulate_with_options, CustomError, TriangulateOptions, Vector};
+
+    #[test]
+    fn half_edges_are_consistent() -> Result<(), CustomError> {
+        let input_points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let hole = [(-1.5, 3.5), (-0.5, 3.5), (-1., 2.5)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(4.);
+        let result = triangulate_with_options(&input_points, Some(&[hole]), &options)?;
+        let mesh = to_half_edge(&result);
+
+        assert_eq!(mesh.face_count(), result.len());
+        for (index, half_edge) in mesh.half_edges.iter().enumerate() {
+            // twins are reciprocal and run the opposite way
+            if let Some(twin) = half_edge.twin {
+                assert_eq!(mesh.half_edges[twin].twin, Some(index));
+                assert_eq!(mesh.half_edges[twin].vertex, mesh.destination(index));
+                assert_eq!(mesh.destination(twin), half_edge.vertex);
+            }
+            // every face is a cycle of exactly three half-edges
+            let next = half_edge.next;
+            assert_ne!(next, index);
+            assert_eq!(mesh.half_edges[mesh.half_edges[next].next].next, index);
+            assert_eq!(mesh.half_edges[next].face, half_edge.face);
+        }
+        for (vertex, outgoing) in mesh.vertex_half_edges.iter().enumerate() {
+            if let Some(outgoing) = outgoing {
+                assert_eq!(mesh.half_edges[*outgoing].vertex, vertex);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,73 +1,521 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::{
     data_structures::{
-        edge::Edge, error::CustomError, triangle_set::TriangleSet,
-        vector::Vector,
+        constraint_intersection_rule::ConstraintIntersectionRule,
+        constraint_trace::{ConstraintSource, ConstraintTrace},
+        edge::Edge,
+        error::CustomError,
+        found_or_added::FoundOrAdded,
+        hole_problem::HoleProblem,
+        hole_report::HoleReport,
+        hole_rule::HoleRule,
+        ids::{TriangleId, VertexId},
+        point_on_constraint_rule::PointOnConstraintRule,
+        triangle_set::TriangleSet,
+        triangulate_options::TriangulateOptions,
+        triangulation_result::TriangulationResult,
+        vector::{Vector, SAME_POINT_ULPS},
+        warning::Warning,
     },
     math_utils::{
-        intersection_between_lines, is_point_inside_circumcircle, is_quadrilateral_convex,
+        distance_segment_segment, do_segments_cross, do_segments_intersect,
+        is_point_strictly_inside_circumcircle, is_quadrilateral_convex, segment_intersection,
     },
     normalize::{normalize_points, Bounds},
-    triangulation::{swap_edges, triangulate_point, TriangleIndexPair},
+    triangulation::{legalize_triangles, swap_edges, triangulate_point, TriangleIndexPair},
 };
 
-/// returns triangles to remove
-pub fn create_holes(
-    mut triangle_set: &mut TriangleSet,
-    holes: &mut Vec<Vec<Vector>>,
+/// How far from an edge of a hole, in normalized coordinates, a vertex still counts as lying on it.
+const ON_EDGE_TOLERANCE: f64 = 1e-6;
+
+/// Forces the outlines of the holes into the triangulation as constrained edges and returns a report for every hole,
+/// with the vertices of its outline. Also returns the vertices along every edge of the holes.
+/// The triangles inside of the holes are removed afterwards by [`remove_holes`].
+///
+/// The vertices from `first_steiner_vertex` up to the first hole vertex were added by the tesselation.
+/// An edge is always split at them, the other points on an edge are handled by `point_on_constraint`.
+/// Edges that cross earlier constrained edges are handled by `constraint_intersection`, the splits are added to `splits`.
+pub fn insert_holes(
+    triangle_set: &mut TriangleSet,
+    holes: &[Vec<Vector>],
     bounds: Bounds,
-) -> Result<Vec<usize>, CustomError> {
+    options: &TriangulateOptions,
+    first_steiner_vertex: VertexId,
+    splits: &mut ConstraintSplits,
+) -> Result<(Vec<HoleReport>, Vec<ConstraintTrace>), CustomError> {
     // 8: Holes creation (constrained edges)
     // Adds the points of all the polygons to the triangulation
     let mut hole_indices = Vec::new();
+    let steiner_vertices = first_steiner_vertex.0..triangle_set.points.len();
 
-    for mut hole in holes {
+    for hole in holes {
         // 5.1: Normalize
-        let (normalized_hole, _) = normalize_points(&mut hole, Some(bounds));
+        let (normalized_hole, _) = normalize_points(hole, Some(bounds));
         let mut polygon_vertices = Vec::new();
 
         for point_to_insert in normalized_hole {
             // 5.2: Add the points to the Triangle set
-            polygon_vertices.push(triangulate_point(&mut triangle_set, point_to_insert)?.value());
+            polygon_vertices.push(triangulate_point(triangle_set, point_to_insert)?.vertex());
         }
 
         hole_indices.push(polygon_vertices);
     }
 
-    for constraint_edge_indices in &hole_indices {
+    let mut hole_reports = Vec::with_capacity(hole_indices.len());
+    let mut constraint_traces = Vec::new();
+    for (hole, polygon_vertices) in hole_indices.into_iter().enumerate() {
+        // the points on the edges become vertices of the outline
+        let mut constraint_edge_indices = Vec::with_capacity(polygon_vertices.len());
+        for j in 0..polygon_vertices.len() {
+            let endpoint_a = polygon_vertices[j];
+            let endpoint_b = polygon_vertices[(j + 1) % polygon_vertices.len()];
+            let vertices_on_edge = find_vertices_on_edge(triangle_set, endpoint_a, endpoint_b);
+            if options.point_on_constraint == PointOnConstraintRule::Reject {
+                if let Some(vertex) = vertices_on_edge
+                    .iter()
+                    .find(|vertex| !steiner_vertices.contains(&vertex.0))
+                {
+                    let point = triangle_set.get_point_from_vertex(*vertex);
+                    return Err(CustomError::PointOnConstraint(bounds.denormalize(point)));
+                }
+            }
+            let mut chain = vec![endpoint_a];
+            chain.extend(vertices_on_edge);
+            constraint_edge_indices.extend_from_slice(&chain);
+            if endpoint_b != endpoint_a {
+                chain.push(endpoint_b);
+            }
+            constraint_traces.push(ConstraintTrace::new(
+                ConstraintSource::HoleEdge { hole, edge: j },
+                chain,
+            ));
+        }
+
+        let mut hole_report = HoleReport::default();
         // 5.3: create the constrained edges
         for j in 0..constraint_edge_indices.len() {
-            add_constrained_edge_to_triangulation(
-                &mut triangle_set,
-                constraint_edge_indices[j],
-                constraint_edge_indices[(j + 1) % constraint_edge_indices.len()],
-            )?;
+            let endpoint_a = constraint_edge_indices[j];
+            let endpoint_b = constraint_edge_indices[(j + 1) % constraint_edge_indices.len()];
+            let is_inserted = add_constraint(
+                triangle_set,
+                endpoint_a,
+                endpoint_b,
+                options.constraint_intersection,
+                splits,
+            )
+            .map_err(|error| denormalize_error(error, bounds))?;
+            if is_inserted {
+                hole_report.inserted_edges += 1;
+            } else {
+                hole_report.existing_edges += 1;
+            }
         }
+        hole_report.vertex_ids = constraint_edge_indices;
+        hole_reports.push(hole_report);
+    }
+    Ok((hole_reports, constraint_traces))
+}
+
+/// Removes the triangles inside of the holes that [`insert_holes`] inserted and records them in the reports.
+/// The triangles of the supertriangle are not part of any hole.
+pub fn remove_holes(
+    triangle_set: &TriangleSet,
+    hole_reports: &mut [HoleReport],
+    hole_rule: HoleRule,
+    splits: &ConstraintSplits,
+) -> Result<(), CustomError> {
+    // later edges may have split the earlier ones
+    for hole_report in hole_reports.iter_mut() {
+        hole_report.vertex_ids = insert_split_vertices(&hole_report.vertex_ids, true, splits);
     }
+    match hole_rule {
+        HoleRule::Union => remove_union_of_holes(triangle_set, hole_reports),
+        HoleRule::EvenOdd => remove_even_odd_regions(triangle_set, hole_reports),
+    }
+}
 
-    let mut triangles_to_remove = Vec::<usize>::new();
+/// Removes the triangles inside of every hole, the triangles inside of several holes belong to the first one.
+fn remove_union_of_holes(
+    triangle_set: &TriangleSet,
+    hole_reports: &mut [HoleReport],
+) -> Result<(), CustomError> {
+    let mut triangles_to_remove = Vec::<TriangleId>::new();
     // 5.4: Identify all the triangles in the polygon
-    for constraint_edge_indices in &mut hole_indices {
-        triangle_set
-            .get_triangles_in_polygon(&constraint_edge_indices, &mut triangles_to_remove)?;
+    for hole_report in hole_reports {
+        // The triangles of earlier holes stop the propagation, so only the new ones belong to this hole
+        let first_new_triangle = triangles_to_remove.len();
+        for polygon_loop in polygon_loops(triangle_set, &hole_report.vertex_ids) {
+            triangle_set.get_triangles_in_polygon(&polygon_loop, &mut triangles_to_remove)?;
+        }
+        let mut triangles_in_hole = triangles_to_remove[first_new_triangle..].to_vec();
+        triangles_in_hole.sort();
+        triangles_in_hole.dedup();
+        hole_report.removed_triangles = triangles_in_hole.len();
+        hole_report.triangle_ids = triangles_in_hole;
+    }
+    Ok(())
+}
+
+/// The closed loops of a hole polygon, split at the vertices it visits more than once,
+/// like the crossing of a figure-eight. If there are several loops, they are all turned counter-clockwise,
+/// because the lobes of a figure-eight wind in opposite directions.
+fn polygon_loops(triangle_set: &TriangleSet, polygon: &[VertexId]) -> Vec<Vec<VertexId>> {
+    let mut loops = Vec::new();
+    let mut path: Vec<VertexId> = Vec::with_capacity(polygon.len());
+    let mut position_in_path = HashMap::with_capacity(polygon.len());
+    for vertex in polygon {
+        if let Some(start) = position_in_path.get(vertex).copied() {
+            let polygon_loop = path.split_off(start);
+            for removed in &polygon_loop {
+                position_in_path.remove(removed);
+            }
+            loops.push(polygon_loop);
+        }
+        position_in_path.insert(*vertex, path.len());
+        path.push(*vertex);
+    }
+    if loops.is_empty() {
+        return vec![path];
+    }
+    loops.push(path);
+    // a loop of two vertices is an edge that the polygon runs along twice
+    loops.retain(|polygon_loop| polygon_loop.len() >= 3);
+    for polygon_loop in &mut loops {
+        let point = |vertex: VertexId| {
+            let point = triangle_set.get_point_from_vertex(vertex);
+            (point.x as f64, point.y as f64)
+        };
+        let twice_area: f64 = (0..polygon_loop.len())
+            .map(|i| {
+                let (a, b) = (
+                    point(polygon_loop[i]),
+                    point(polygon_loop[(i + 1) % polygon_loop.len()]),
+                );
+                a.0 * b.1 - a.1 * b.0
+            })
+            .sum();
+        if twice_area < 0. {
+            polygon_loop.reverse();
+        }
+    }
+    loops
+}
+
+/// Removes the triangles inside of an odd number of hole polygons.
+///
+/// Every removed triangle belongs to the innermost polygon around it, which is the one with the fewest triangles inside.
+fn remove_even_odd_regions(
+    triangle_set: &TriangleSet,
+    hole_reports: &mut [HoleReport],
+) -> Result<(), CustomError> {
+    let mut triangles_in_polygons = Vec::with_capacity(hole_reports.len());
+    let mut polygons_around_triangle = HashMap::<TriangleId, Vec<usize>>::new();
+    for (hole_id, hole_report) in hole_reports.iter().enumerate() {
+        let mut triangles_in_polygon = Vec::new();
+        for polygon_loop in polygon_loops(triangle_set, &hole_report.vertex_ids) {
+            triangle_set.get_triangles_in_polygon(&polygon_loop, &mut triangles_in_polygon)?;
+        }
+        triangles_in_polygon.sort();
+        triangles_in_polygon.dedup();
+        for triangle in &triangles_in_polygon {
+            polygons_around_triangle
+                .entry(*triangle)
+                .or_default()
+                .push(hole_id);
+        }
+        triangles_in_polygons.push(triangles_in_polygon);
+    }
+
+    for (triangle, polygons) in polygons_around_triangle {
+        if polygons.len() % 2 == 0 {
+            continue;
+        }
+        let innermost = polygons
+            .into_iter()
+            .min_by_key(|hole_id| triangles_in_polygons[*hole_id].len())
+            .expect("a triangle inside of an odd number of polygons is inside of one");
+        hole_reports[innermost].triangle_ids.push(triangle);
+    }
+    for hole_report in hole_reports {
+        hole_report.triangle_ids.sort();
+        hole_report.removed_triangles = hole_report.triangle_ids.len();
+    }
+    Ok(())
+}
+
+/// Puts the triangles of a hole back into the mesh, as if the hole had never been cut.
+///
+/// The constrained edges of the hole are released, unless another hole, a segment or the closed boundary
+/// still uses them, and the [`ConstraintTrace`]s of the hole are dropped from
+/// [`TriangulationResult::constrained_edges`]. The edges in and around the former hole are swapped until they are delaunay again.
+/// `hole_id` is the index of the hole in the input and in [`TriangulationResult::hole_reports`].
+/// Filling a hole twice does nothing.
+/// ```
+/// use constrained_denaulay_triangulation::{fill_hole, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let outline = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(10., 0.),
+///     Vector::new(10., 10.),
+///     Vector::new(0., 10.),
+/// ];
+/// let hole = vec![Vector::new(4., 4.), Vector::new(6., 4.), Vector::new(5., 6.)];
+/// let mut result = triangulate_with_options(&outline, Some(&[hole]), &TriangulateOptions::new()).unwrap();
+/// let triangles_with_hole = result.len();
+/// fill_hole(&mut result, 0).unwrap();
+/// assert!(result.len() > triangles_with_hole);
+/// assert!(result.hole_reports[0].filled);
+/// ```
+pub fn fill_hole(result: &mut TriangulationResult, hole_id: usize) -> Result<(), CustomError> {
+    let hole_report = result
+        .hole_reports
+        .get(hole_id)
+        .ok_or(CustomError::HoleNotFound(hole_id))?;
+    if hole_report.filled {
+        return Ok(());
+    }
+    let triangle_set = &mut result.triangle_set;
+
+    // Triangles of the supertriangle stay removed, even if a clockwise hole claimed them
+    let restored_triangles: Vec<TriangleId> = hole_report
+        .triangle_ids
+        .iter()
+        .copied()
+        .filter(|triangle| !triangle_set.is_supertriangle_triangle(*triangle))
+        .collect();
+    result
+        .removed_triangles
+        .retain(|triangle| restored_triangles.binary_search(triangle).is_err());
+
+    let is_of_hole = |trace: &ConstraintTrace| matches!(trace.source, ConstraintSource::HoleEdge { hole, .. } if hole == hole_id);
+    let trace_edges = |trace: &ConstraintTrace| -> Vec<Edge> {
+        trace
+            .vertex_ids
+            .windows(2)
+            .map(|edge| Edge::undirected(edge[0], edge[1]))
+            .collect()
+    };
+    // the traces of filled holes are gone, the others still need their edges
+    let used_edges: HashSet<Edge> = result
+        .constrained_edges
+        .iter()
+        .filter(|trace| !is_of_hole(trace))
+        .flat_map(trace_edges)
+        .collect();
+    for trace in result
+        .constrained_edges
+        .iter()
+        .filter(|trace| is_of_hole(trace))
+    {
+        for edge in trace_edges(trace) {
+            if !used_edges.contains(&edge) {
+                triangle_set.remove_constrained_edge(edge.vertex_a(), edge.vertex_b());
+            }
+        }
+    }
+    result.constrained_edges.retain(|trace| !is_of_hole(trace));
+
+    // The neighbors of the hole have to be checked as well, their shared edges were forced into the triangulation
+    let mut queue = restored_triangles.clone();
+    for triangle in &restored_triangles {
+        queue.extend(
+            triangle_set
+                .get_triangle_info(*triangle)
+                .adjacent_triangle_indices
+                .into_iter()
+                .flatten(),
+        );
+    }
+    let removed_triangles = &result.removed_triangles;
+    let is_kept = |triangle: TriangleId| removed_triangles.binary_search(&triangle).is_err();
+    legalize_triangles(triangle_set, queue, &|_, current, adjacent| {
+        is_kept(current) && is_kept(adjacent)
+    })?;
+
+    let hole_report = &mut result.hole_reports[hole_id];
+    hole_report.filled = true;
+    hole_report.triangle_ids.clear();
+    result.update_output();
+    Ok(())
+}
+
+/// Normalized hole outlines closer than this may snap onto each other,
+/// as coordinates inside of the unit square that are [`SAME_POINT_ULPS`] ulps apart are the same point.
+const HOLE_MERGE_DISTANCE: f32 = SAME_POINT_ULPS as f32 * f32::EPSILON;
+
+/// Warns about every pair of holes whose outlines come within [`HOLE_MERGE_DISTANCE`] of each other
+/// once they are normalized, without touching.
+pub fn find_nearly_touching_holes(holes: &[Vec<Vector>], bounds: Bounds) -> Vec<Warning> {
+    let normalized_holes: Vec<Vec<Vector>> = holes
+        .iter()
+        .map(|hole| normalize_points(hole, Some(bounds)).0)
+        .collect();
+    let extents: Vec<(Vector, Vector)> = normalized_holes
+        .iter()
+        .map(|hole| {
+            hole.iter().fold(
+                (
+                    Vector::new(f32::MAX, f32::MAX),
+                    Vector::new(f32::MIN, f32::MIN),
+                ),
+                |(min, max), point| (min.min(*point), max.max(*point)),
+            )
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    for hole_a in 0..normalized_holes.len() {
+        for hole_b in hole_a + 1..normalized_holes.len() {
+            let ((min_a, max_a), (min_b, max_b)) = (extents[hole_a], extents[hole_b]);
+            if min_b.x - max_a.x > HOLE_MERGE_DISTANCE
+                || min_a.x - max_b.x > HOLE_MERGE_DISTANCE
+                || min_b.y - max_a.y > HOLE_MERGE_DISTANCE
+                || min_a.y - max_b.y > HOLE_MERGE_DISTANCE
+            {
+                continue;
+            }
+            let mut closest = (f32::INFINITY, Vector::default(), Vector::default());
+            for (a1, b1) in polygon_edges(&normalized_holes[hole_a]) {
+                for (a2, b2) in polygon_edges(&normalized_holes[hole_b]) {
+                    let candidate = distance_segment_segment(a1, b1, a2, b2);
+                    if candidate.0 < closest.0 {
+                        closest = candidate;
+                    }
+                }
+            }
+            if closest.0 > 0. && closest.0 <= HOLE_MERGE_DISTANCE {
+                let (on_a, on_b) = (bounds.denormalize(closest.1), bounds.denormalize(closest.2));
+                warnings.push(Warning::HolesNearlyTouching {
+                    hole_a,
+                    hole_b,
+                    distance: (on_a - on_b).length(),
+                });
+            }
+        }
+    }
+    warnings
+}
+
+/// The edges of the closed polygon, from every vertex to the next one.
+fn polygon_edges(polygon: &[Vector]) -> impl Iterator<Item = (Vector, Vector)> + '_ {
+    (0..polygon.len()).map(|i| (polygon[i], polygon[(i + 1) % polygon.len()]))
+}
+
+/// Checks the holes before triangulating, without changing them.
+///
+/// Lists every [`HoleProblem`] of every hole: less than three vertices, consecutive vertices at the same position,
+/// clockwise winding and edges that cross or touch each other. Holes with less than three vertices are not checked further.
+/// ```
+/// use constrained_denaulay_triangulation::{validate_holes, HoleProblem, Vector};
+///
+/// let counter_clockwise = vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(0., 1.)];
+/// let clockwise: Vec<Vector> = counter_clockwise.iter().rev().copied().collect();
+/// assert!(validate_holes(&[counter_clockwise.clone()]).is_ok());
+/// assert_eq!(
+///     validate_holes(&[counter_clockwise, clockwise]),
+///     Err(vec![HoleProblem::NotCounterClockwise { hole: 1 }])
+/// );
+/// ```
+pub fn validate_holes(holes: &[Vec<Vector>]) -> Result<(), Vec<HoleProblem>> {
+    let mut problems = Vec::new();
+    for (hole_index, hole) in holes.iter().enumerate() {
+        if hole.len() < 3 {
+            problems.push(HoleProblem::TooFewVertices {
+                hole: hole_index,
+                vertices: hole.len(),
+            });
+            continue;
+        }
+        let edge = |i: usize| (hole[i], hole[(i + 1) % hole.len()]);
+
+        for i in 0..hole.len() {
+            let (start, end) = edge(i);
+            if start == end {
+                problems.push(HoleProblem::DuplicateConsecutiveVertices {
+                    hole: hole_index,
+                    vertex: i,
+                });
+            }
+        }
+
+        let twice_area: f32 = (0..hole.len())
+            .map(|i| {
+                let (start, end) = edge(i);
+                start.cross_product(end)
+            })
+            .sum();
+        if twice_area <= 0. {
+            problems.push(HoleProblem::NotCounterClockwise { hole: hole_index });
+        }
+
+        // edges of length zero are already reported as duplicates, without them the edges around them are consecutive
+        let edges: Vec<usize> = (0..hole.len())
+            .filter(|i| {
+                let (start, end) = edge(*i);
+                start != end
+            })
+            .collect();
+        for i in 0..edges.len() {
+            for j in i + 1..edges.len() {
+                let ((a1, b1), (a2, b2)) = (edge(edges[i]), edge(edges[j]));
+                // consecutive edges share a vertex, they only intersect if the second one folds back onto the first
+                let folds_back = |p: Vector, q: Vector, r: Vector| {
+                    let (first, second) = (q - p, r - q);
+                    first.cross_product(second) == 0.
+                        && first.x * second.x + first.y * second.y < 0.
+                };
+                let is_intersecting = if j == i + 1 {
+                    folds_back(a1, b1, b2)
+                } else if i == 0 && j == edges.len() - 1 {
+                    // the closing edge comes right before the first one
+                    folds_back(a2, a1, b1)
+                } else {
+                    do_segments_intersect(a1, b1, a2, b2)
+                };
+                if is_intersecting {
+                    problems.push(HoleProblem::SelfIntersection {
+                        hole: hole_index,
+                        edges: [edges[i], edges[j]],
+                    });
+                }
+            }
+        }
     }
 
-    get_supertriangle_triangles(&mut triangle_set, &mut triangles_to_remove);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
 
-    triangles_to_remove.sort();
+/// The vertices at which constrained edges were split, because other constrained edges crossed them,
+/// by the [`Edge::undirected`] that was split.
+pub type ConstraintSplits = HashMap<Edge, VertexId>;
 
-    return Ok(triangles_to_remove);
+/// What [`add_constrained_edge_to_triangulation`] did.
+enum EdgeInsertion {
+    /// The edge was already part of the triangulation.
+    Existing,
+    /// The edge was forced into the triangulation.
+    Inserted,
+    /// The edge crosses this constrained edge at the point, so nothing was changed.
+    Crossing((VertexId, VertexId), Vector),
 }
 
+/// Forces the edge into the triangulation, unless it crosses a constrained edge.
 fn add_constrained_edge_to_triangulation(
     triangle_set: &mut TriangleSet,
-    endpoint_a_index: usize,
-    endpoint_b_index: usize,
-) -> Result<(), CustomError> {
+    endpoint_a_index: VertexId,
+    endpoint_b_index: VertexId,
+) -> Result<EdgeInsertion, CustomError> {
     // Detects if the edge already exists
-    if let Some(_) = triangle_set.find_edge_info_for_vertices(endpoint_a_index, endpoint_b_index) {
-        return Ok(());
+    if triangle_set
+        .find_edge_info_for_vertices(endpoint_a_index, endpoint_b_index)
+        .is_some()
+    {
+        return Ok(EdgeInsertion::Existing);
     }
     // 5.3.1: Search for the triangle that contains the beginning of the new edge
     let triangle_containing_a = triangle_set
@@ -81,6 +529,22 @@ fn add_constrained_edge_to_triangulation(
         edge_endpoint_b,
         triangle_containing_a,
     );
+    // swapping would remove the crossed constrained edge from the triangulation
+    if let Some(crossed_edge) = intersected_triangle_edges
+        .iter()
+        .find(|edge| triangle_set.is_constrained_edge(edge.vertex_a(), edge.vertex_b()))
+    {
+        let intersection = segment_intersection(
+            edge_endpoint_a,
+            edge_endpoint_b,
+            triangle_set.get_point_from_vertex(crossed_edge.vertex_a()),
+            triangle_set.get_point_from_vertex(crossed_edge.vertex_b()),
+        );
+        return Ok(EdgeInsertion::Crossing(
+            (crossed_edge.vertex_a(), crossed_edge.vertex_b()),
+            intersection,
+        ));
+    }
 
     let mut new_edges = Vec::<Edge>::new();
 
@@ -91,54 +555,59 @@ fn add_constrained_edge_to_triangulation(
                 intersected_triangle_edge.vertex_b(),
             )
             .unwrap();
-        let opposite_triangle_index = triangle_set.triangle_infos[current_edge_info.triangle_index]
-            .adjacent_triangle_indices[current_edge_info.edge_index]
+        let opposite_triangle_index = triangle_set
+            .get_adjacent_triangle_index(
+                current_edge_info.triangle_index(),
+                current_edge_info.edge_index(),
+            )
             .unwrap();
-        // for loop to get index
-        let mut opposite_vertex_index = None;
-        for i in 0..3 {
-            if triangle_set.triangle_infos[opposite_triangle_index].vertex_indices[i]
-                == current_edge_info.vertex_a()
-            {
-                opposite_vertex_index = Some((i + 1) % 3);
-                break;
-            }
-        }
+        // the vertex after the shared vertex a is the one opposite to the edge
+        let opposite_vertex_index = triangle_set
+            .get_triangle_info(opposite_triangle_index)
+            .vertex_indices
+            .iter()
+            .position(|vertex| *vertex == current_edge_info.vertex_a())
+            .map(|i| (i + 1) % 3);
         let opposite_point = triangle_set
             .get_point_from_index(opposite_triangle_index, opposite_vertex_index.unwrap());
+        // only the first intersected edge has the start of the constrained edge as the third vertex of its triangle
+        let current_point = triangle_set.get_point_from_index(
+            current_edge_info.triangle_index(),
+            current_edge_info.opposite_vertex_index(),
+        );
 
         if is_quadrilateral_convex(
-            &triangle_set.points[current_edge_info.vertex_b()],
-            &edge_endpoint_a,
-            &triangle_set.points[current_edge_info.vertex_a()],
+            &triangle_set.get_point_from_vertex(current_edge_info.vertex_b()),
+            current_point,
+            &triangle_set.get_point_from_vertex(current_edge_info.vertex_a()),
             opposite_point,
         ) {
             let index_pair = TriangleIndexPair {
-                current: current_edge_info.triangle_index,
+                current: current_edge_info.triangle_index(),
                 adjacent: opposite_triangle_index,
             };
-            swap_edges(&index_pair, triangle_set, current_edge_info.edge_index)?;
+            swap_edges(&index_pair, triangle_set, current_edge_info.edge_index())?;
             let new_triangle_shared_point_a =
-                triangle_set.get_point_from_index(current_edge_info.triangle_index, 2);
+                triangle_set.get_point_from_index(current_edge_info.triangle_index(), 2);
             let new_triangle_shared_point_b =
-                triangle_set.get_point_from_index(current_edge_info.triangle_index, 0);
+                triangle_set.get_point_from_index(current_edge_info.triangle_index(), 0);
 
-            let new_edge = Edge::new(
-                triangle_set.triangle_infos[current_edge_info.triangle_index].vertex_indices[2],
-                triangle_set.triangle_infos[current_edge_info.triangle_index].vertex_indices[0],
-            );
+            let new_triangle_vertices = triangle_set
+                .get_triangle_info(current_edge_info.triangle_index())
+                .vertex_indices;
+            let new_edge = Edge::new(new_triangle_vertices[2], new_triangle_vertices[0]);
 
-            if let Some(_) = intersection_between_lines(
+            if do_segments_cross(
                 &edge_endpoint_a,
                 &edge_endpoint_b,
                 new_triangle_shared_point_a,
                 new_triangle_shared_point_b,
             ) {
                 // if it still intersects after swapping, it needs to be put into the vec again
-                if *new_triangle_shared_point_a != edge_endpoint_b
-                    && *new_triangle_shared_point_b != edge_endpoint_b
-                    && *new_triangle_shared_point_a != edge_endpoint_a
-                    && *new_triangle_shared_point_b != edge_endpoint_a
+                if !new_triangle_shared_point_a.is_same_point(edge_endpoint_b)
+                    && !new_triangle_shared_point_b.is_same_point(edge_endpoint_b)
+                    && !new_triangle_shared_point_a.is_same_point(edge_endpoint_a)
+                    && !new_triangle_shared_point_b.is_same_point(edge_endpoint_a)
                 {
                     intersected_triangle_edges.push_front(new_edge);
                 } else {
@@ -155,74 +624,1046 @@ fn add_constrained_edge_to_triangulation(
         }
     }
 
-    // 5.3.4. Check Delaunay constraint and swap edges
-    for i in 0..new_edges.len() {
-        {
-            // Checks if the constrained edge coincides with the new edge
-            let triangle_edge_point_a = triangle_set.get_point_from_vertex(new_edges[i].vertex_a());
-            let triangle_edge_point_b = triangle_set.get_point_from_vertex(new_edges[i].vertex_b());
+    // 5.3.4. Check Delaunay constraint and swap edges, until no more swaps take place
+    let mut queue = Vec::with_capacity(new_edges.len() * 2);
+    for new_edge in &new_edges {
+        let edge_info = triangle_set
+            .find_edge_info_for_vertices(new_edge.vertex_a(), new_edge.vertex_b())
+            .expect("Those edges were just created and the triangulation should contain them");
+        queue.push(edge_info.triangle_index());
+        queue.extend(
+            triangle_set
+                .get_adjacent_triangle_index(edge_info.triangle_index(), edge_info.edge_index()),
+        );
+    }
+    // neither the new edge nor the hull between the mesh and the supertriangle is swapped away
+    let can_swap = |triangle_set: &TriangleSet, current: TriangleId, adjacent: TriangleId| {
+        let [current, adjacent] = [current, adjacent]
+            .map(|triangle| triangle_set.get_triangle_info(triangle).vertex_indices);
+        let has_edge = |vertices: [VertexId; 3]| {
+            vertices.contains(&endpoint_a_index) && vertices.contains(&endpoint_b_index)
+        };
+        let is_supertriangle_triangle = |vertices: [VertexId; 3]| {
+            vertices
+                .iter()
+                .any(|vertex| triangle_set.is_supertriangle_vertex(*vertex))
+        };
+        !(has_edge(current) && has_edge(adjacent))
+            && is_supertriangle_triangle(current) == is_supertriangle_triangle(adjacent)
+    };
+    legalize_triangles(triangle_set, queue, &can_swap)?;
+    Ok(EdgeInsertion::Inserted)
+}
 
-            if (triangle_edge_point_a == edge_endpoint_a)
-                && (triangle_edge_point_b == edge_endpoint_b)
-            {
+/// Moves the position of a [`CustomError::ConstraintIntersection`] from the normalized space back into the input.
+pub fn denormalize_error(error: CustomError, bounds: Bounds) -> CustomError {
+    match error {
+        CustomError::ConstraintIntersection(point) => {
+            CustomError::ConstraintIntersection(bounds.denormalize(point))
+        }
+        error => error,
+    }
+}
+
+/// Forces the edge into the triangulation and marks it as constrained. Returns false, if it was already part of it.
+///
+/// An edge that crosses a constrained edge is either rejected, or both are split at their intersection point,
+/// which is recorded in `splits`. The edge then becomes the constrained edges between its endpoints and the new vertex.
+fn add_constraint(
+    triangle_set: &mut TriangleSet,
+    endpoint_a: VertexId,
+    endpoint_b: VertexId,
+    rule: ConstraintIntersectionRule,
+    splits: &mut ConstraintSplits,
+) -> Result<bool, CustomError> {
+    match add_constrained_edge_to_triangulation(triangle_set, endpoint_a, endpoint_b)? {
+        EdgeInsertion::Existing => {
+            triangle_set.add_constrained_edge(endpoint_a, endpoint_b);
+            Ok(false)
+        }
+        EdgeInsertion::Inserted => {
+            triangle_set.add_constrained_edge(endpoint_a, endpoint_b);
+            Ok(true)
+        }
+        EdgeInsertion::Crossing(crossed_edge, intersection) => {
+            if rule == ConstraintIntersectionRule::Reject {
+                return Err(CustomError::ConstraintIntersection(intersection));
+            }
+            // the crossed edge is released, so that the new vertex can be connected across it
+            triangle_set.remove_constrained_edge(crossed_edge.0, crossed_edge.1);
+            let vertex = triangulate_point(triangle_set, intersection)?.vertex();
+            // rounding may put the intersection onto one of the endpoints
+            for (start, end) in [crossed_edge, (endpoint_a, endpoint_b)] {
+                if vertex != start && vertex != end {
+                    splits.insert(Edge::undirected(start, end), vertex);
+                }
+                for (piece_start, piece_end) in [(start, vertex), (vertex, end)] {
+                    if piece_start != piece_end {
+                        add_constraint(triangle_set, piece_start, piece_end, rule, splits)?;
+                    }
+                }
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// Splits the constrained edges at their midpoints, until the vertex across every constrained edge is outside of
+/// the circumcircle of the triangle on its other side, so that the whole triangulation is delaunay.
+/// Returns the number of added points.
+///
+/// The triangles of the supertriangle are left out. The splits are added to `splits`.
+/// Edges so short that their midpoint is one of their endpoints are not split any further.
+pub fn conform_constraints(
+    triangle_set: &mut TriangleSet,
+    rule: ConstraintIntersectionRule,
+    splits: &mut ConstraintSplits,
+    steiner_point_limit: usize,
+) -> Result<usize, CustomError> {
+    let mut added_points = 0;
+    let mut unsplittable_edges = HashSet::new();
+    loop {
+        let encroached_edges = find_encroached_constraints(triangle_set, &unsplittable_edges);
+        if encroached_edges.is_empty() {
+            return Ok(added_points);
+        }
+        for edge in encroached_edges {
+            let (start, end) = (edge.vertex_a(), edge.vertex_b());
+            // an earlier split may have flipped the edge away
+            if !triangle_set.is_constrained_edge(start, end) {
                 continue;
             }
+            let midpoint = (triangle_set.get_point_from_vertex(start)
+                + triangle_set.get_point_from_vertex(end))
+                / 2.;
+            triangle_set.remove_constrained_edge(start, end);
+            let vertex = match triangulate_point(triangle_set, midpoint)? {
+                FoundOrAdded::Added(vertex) => vertex,
+                FoundOrAdded::Found(vertex) if vertex != start && vertex != end => vertex,
+                FoundOrAdded::Found(_) => {
+                    triangle_set.add_constrained_edge(start, end);
+                    unsplittable_edges.insert(edge);
+                    continue;
+                }
+            };
+            added_points += 1;
+            if added_points > steiner_point_limit {
+                return Err(CustomError::SteinerPointLimitExceeded(steiner_point_limit));
+            }
+            splits.insert(edge, vertex);
+            add_constraint(triangle_set, start, vertex, rule, splits)?;
+            add_constraint(triangle_set, vertex, end, rule, splits)?;
+        }
+    }
+}
 
-            //this should not happen, since the swap is always in the same order
-            if (triangle_edge_point_b == edge_endpoint_a)
-                && (triangle_edge_point_a == edge_endpoint_b)
+/// The constrained edges with a vertex across them inside of the circumcircle of the triangle on their other side,
+/// in the order of their triangles.
+fn find_encroached_constraints(
+    triangle_set: &TriangleSet,
+    unsplittable_edges: &HashSet<Edge>,
+) -> Vec<Edge> {
+    let mut encroached_edges = Vec::new();
+    for triangle in (0..triangle_set.triangle_count()).map(TriangleId) {
+        if triangle_set.is_supertriangle_triangle(triangle) {
+            continue;
+        }
+        let triangle_info = triangle_set.get_triangle_info(triangle);
+        for i in 0..3 {
+            let edge = Edge::undirected(
+                triangle_info.vertex_indices[i],
+                triangle_info.vertex_indices[(i + 1) % 3],
+            );
+            let Some(adjacent) = triangle_info.adjacent_triangle_indices[i] else {
+                continue;
+            };
+            // every edge between two triangles is looked at from both sides
+            if adjacent < triangle
+                || triangle_set.is_supertriangle_triangle(adjacent)
+                || !triangle_set.is_constrained_edge(edge.vertex_a(), edge.vertex_b())
+                || unsplittable_edges.contains(&edge)
             {
                 continue;
             }
+            let vertex_across = triangle_info.vertex_indices[(i + 2) % 3];
+            if is_point_strictly_inside_circumcircle(
+                triangle_set.get_triangle(adjacent),
+                triangle_set.get_point_from_vertex(vertex_across),
+            ) {
+                encroached_edges.push(edge);
+            }
+        }
+    }
+    encroached_edges
+}
 
-            // Deduces the data for both triangles
-            let current_edge = triangle_set
-                .find_edge_info_for_vertices(new_edges[i].vertex_a(), new_edges[i].vertex_b())
-                .expect("Those edges were just created and the triangulation should contain them");
+/// The vertices of the chain, with the vertices its constrained edges were split at in between.
+/// A closed chain also gets the vertices between its last and its first vertex, at its end.
+pub fn insert_split_vertices(
+    chain: &[VertexId],
+    is_closed: bool,
+    splits: &ConstraintSplits,
+) -> Vec<VertexId> {
+    fn push_split_vertices(
+        start: VertexId,
+        end: VertexId,
+        splits: &ConstraintSplits,
+        vertices: &mut Vec<VertexId>,
+    ) {
+        if let Some(vertex) = splits.get(&Edge::undirected(start, end)) {
+            push_split_vertices(start, *vertex, splits, vertices);
+            vertices.push(*vertex);
+            push_split_vertices(*vertex, end, splits, vertices);
+        }
+    }
+    if splits.is_empty() || chain.is_empty() {
+        return chain.to_vec();
+    }
+    let mut vertices = Vec::with_capacity(chain.len());
+    for pair in chain.windows(2) {
+        vertices.push(pair[0]);
+        push_split_vertices(pair[0], pair[1], splits, &mut vertices);
+    }
+    vertices.push(chain[chain.len() - 1]);
+    if is_closed {
+        push_split_vertices(chain[chain.len() - 1], chain[0], splits, &mut vertices);
+    }
+    vertices
+}
+
+/// Forces the segments of a planar straight-line graph into the triangulation and marks them as constrained.
+/// Returns the vertices along every segment, from its start to its end.
+///
+/// Segments from a vertex to itself, e.g. between two duplicate input points, are skipped and only have that vertex.
+/// Segments through other vertices are split at them, crossing segments are handled by `rule`.
+/// The chains do not contain the vertices of the crossings yet, see [`insert_split_vertices`].
+/// The positions in the errors are normalized.
+pub fn add_segments(
+    triangle_set: &mut TriangleSet,
+    segments: &[(VertexId, VertexId)],
+    rule: ConstraintIntersectionRule,
+    splits: &mut ConstraintSplits,
+) -> Result<Vec<Vec<VertexId>>, CustomError> {
+    let mut chains = Vec::with_capacity(segments.len());
+    for (endpoint_a, endpoint_b) in segments.iter().copied() {
+        if endpoint_a == endpoint_b {
+            chains.push(vec![endpoint_a]);
+            continue;
+        }
+        let mut chain = vec![endpoint_a];
+        chain.extend(find_vertices_on_edge(triangle_set, endpoint_a, endpoint_b));
+        chain.push(endpoint_b);
+        for pair in chain.windows(2) {
+            add_constraint(triangle_set, pair[0], pair[1], rule, splits)?;
+        }
+        chains.push(chain);
+    }
+    Ok(chains)
+}
 
-            let current_edge_triangle = triangle_set.get_triangle_info(current_edge.triangle_index);
+/// The vertices that lie on the edge between both vertices, without the endpoints, in the order from `endpoint_a`.
+///
+/// Rounding puts computed points like midpoints slightly next to the line,
+/// so every vertex closer to it than [`ON_EDGE_TOLERANCE`] counts.
+fn find_vertices_on_edge(
+    triangle_set: &TriangleSet,
+    endpoint_a: VertexId,
+    endpoint_b: VertexId,
+) -> Vec<VertexId> {
+    let to_f64 = |point: Vector| (point.x as f64, point.y as f64);
+    let a = to_f64(triangle_set.get_point_from_vertex(endpoint_a));
+    let b = to_f64(triangle_set.get_point_from_vertex(endpoint_b));
+    let direction = (b.0 - a.0, b.1 - a.1);
+    let length_squared = direction.0 * direction.0 + direction.1 * direction.1;
+    if length_squared == 0. {
+        return Vec::new();
+    }
+    let length = length_squared.sqrt();
 
-            let triangle_vertex_not_shared = (current_edge.edge_index + 2) % 3;
-            let triangle_point_not_shared = triangle_set.get_point_from_vertex(
-                current_edge_triangle.vertex_indices[triangle_vertex_not_shared],
-            );
+    let mut vertices_on_edge = Vec::new();
+    // the vertices of the supertriangle are never on an edge inside of it
+    for vertex in triangle_set.mesh_vertices() {
+        if vertex == endpoint_a || vertex == endpoint_b {
+            continue;
+        }
+        let point = to_f64(triangle_set.get_point_from_vertex(vertex));
+        let offset = (point.0 - a.0, point.1 - a.1);
+        let distance_to_line = (direction.0 * offset.1 - direction.1 * offset.0).abs() / length;
+        let distance_along = (direction.0 * offset.0 + direction.1 * offset.1) / length;
+        if distance_to_line <= ON_EDGE_TOLERANCE
+            && distance_along > ON_EDGE_TOLERANCE
+            && distance_along < length - ON_EDGE_TOLERANCE
+        {
+            vertices_on_edge.push((distance_along, vertex));
+        }
+    }
+    vertices_on_edge.sort_by(|a, b| a.0.total_cmp(&b.0));
+    vertices_on_edge
+        .into_iter()
+        .map(|(_, vertex)| vertex)
+        .collect()
+}
 
-            let opposite_triangle_index =
-                current_edge_triangle.adjacent_triangle_indices[current_edge.edge_index].unwrap();
+/// Removes every triangle that can be reached from one of the normalized seed points without crossing a constrained edge,
+/// like the hole points of Triangle. Returns a report with the removed triangles for every seed.
+///
+/// A region that is not closed by constrained edges is removed up to the border of the mesh.
+/// Seeds outside of the mesh, which includes the `outside_triangles` of a closed boundary,
+/// and seeds in a triangle that one of the `hole_reports` or an earlier seed already removed remove nothing
+/// and get a warning instead.
+pub fn remove_seeded_regions(
+    triangle_set: &TriangleSet,
+    seeds: &[Vector],
+    outside_triangles: &[TriangleId],
+    hole_reports: &[HoleReport],
+) -> Result<(Vec<HoleReport>, Vec<Warning>), CustomError> {
+    let (labels, _) = triangle_set.label_regions();
+    let outside_triangles: HashSet<TriangleId> = outside_triangles.iter().copied().collect();
+    let mut removed_triangles: HashSet<TriangleId> = hole_reports
+        .iter()
+        .flat_map(|hole_report| hole_report.triangle_ids.iter().copied())
+        .collect();
+    let mut seed_reports = Vec::with_capacity(seeds.len());
+    let mut warnings = Vec::new();
+    for (i, seed) in seeds.iter().enumerate() {
+        let mut hole_report = HoleReport::default();
+        let start_triangle = TriangleId(triangle_set.triangle_count() - 1);
+        let (seed_triangle, _) = triangle_set.walk_to_point(*seed, start_triangle)?;
+        let is_outside = outside_triangles.contains(&seed_triangle)
+            || triangle_set.is_supertriangle_triangle(seed_triangle);
+        if is_outside {
+            warnings.push(Warning::HoleSeedOutsideMesh { seed: i });
+        } else if removed_triangles.contains(&seed_triangle) {
+            warnings.push(Warning::HoleSeedInRemovedRegion { seed: i });
+        } else {
+            let region = labels[seed_triangle.0];
+            hole_report.triangle_ids = (0..labels.len())
+                .filter(|triangle| labels[*triangle] == region)
+                .map(TriangleId)
+                .collect();
+            // a hole polygon may already have removed a part of the region
+            hole_report.removed_triangles = hole_report
+                .triangle_ids
+                .iter()
+                .filter(|triangle| removed_triangles.insert(**triangle))
+                .count();
+        }
+        seed_reports.push(hole_report);
+    }
+    Ok((seed_reports, warnings))
+}
 
-            let opposite_triangle = triangle_set.get_triangle(
-                current_edge_triangle.adjacent_triangle_indices[current_edge.edge_index].unwrap(),
-            );
+/// The triangles between the convex hull and the constrained edges of a closed boundary.
+///
+/// These are the regions that reach the hull without crossing a constrained edge,
+/// apart from the triangles of the supertriangle itself.
+pub fn get_triangles_outside_of_boundary(triangle_set: &TriangleSet) -> Vec<TriangleId> {
+    let (labels, sizes) = triangle_set.label_regions();
+    let is_supertriangle_triangle =
+        |triangle: TriangleId| triangle_set.is_supertriangle_triangle(triangle);
+    let mesh_triangles = (0..triangle_set.triangle_count())
+        .map(TriangleId)
+        .filter(|triangle| !is_supertriangle_triangle(*triangle));
 
-            if is_point_inside_circumcircle(opposite_triangle, triangle_point_not_shared) {
-                // Swap
-                swap_edges(
-                    &TriangleIndexPair {
-                        adjacent: opposite_triangle_index,
-                        current: current_edge.triangle_index,
-                    },
-                    triangle_set,
-                    current_edge.edge_index,
-                )?;
+    let mut is_outside_region = vec![false; sizes.len()];
+    for triangle in mesh_triangles.clone() {
+        let triangle_info = triangle_set.get_triangle_info(triangle);
+        // without a supertriangle, the hull edges have no adjacent triangle
+        let reaches_hull = (0..3).any(|edge_index| {
+            triangle_info.adjacent_triangle_indices[edge_index]
+                .is_none_or(is_supertriangle_triangle)
+                && !triangle_set.is_constrained_edge(
+                    triangle_info.vertex_indices[edge_index],
+                    triangle_info.vertex_indices[(edge_index + 1) % 3],
+                )
+        });
+        if reaches_hull {
+            is_outside_region[labels[triangle.0]] = true;
+        }
+    }
+    mesh_triangles
+        .filter(|triangle| is_outside_region[labels[triangle.0]])
+        .collect()
+}
+
+/// Forces the edges of the convex hull of the vertices into the triangulation.
+///
+/// The supertriangle is only finitely large, so a vertex very close to the hull can be closer to its circumcircle
+/// than the hull edge next to it, and the Delaunay swaps connect it to a vertex of the supertriangle instead.
+/// Vertices on the hull between two corners are kept, and like the edges of the holes,
+/// the hull edges are split at vertices that rounding put just inside of them.
+pub fn restore_convex_hull(triangle_set: &mut TriangleSet) -> Result<(), CustomError> {
+    let mut vertices: Vec<VertexId> = triangle_set.mesh_vertices().collect();
+    let point = |vertex: VertexId| {
+        let point = triangle_set.get_point_from_vertex(vertex);
+        (point.x as f64, point.y as f64)
+    };
+    vertices.sort_by(|a, b| {
+        let (a, b) = (point(*a), point(*b));
+        a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+    });
+    vertices.dedup_by(|vertex, previous| point(*vertex) == point(*previous));
+    let turn = |a: VertexId, b: VertexId, c: VertexId| {
+        let (a, b, c) = (point(a), point(b), point(c));
+        (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+    };
+    let half_hull = |vertices: &mut dyn Iterator<Item = &VertexId>| {
+        let mut half: Vec<VertexId> = Vec::new();
+        for vertex in vertices {
+            // only right turns are removed, so the vertices along a hull edge stay
+            while let [.., a, b] = half[..] {
+                if turn(a, b, *vertex) >= 0. {
+                    break;
+                }
+                half.pop();
             }
+            half.push(*vertex);
+        }
+        half.pop();
+        half
+    };
+    let mut hull = half_hull(&mut vertices.iter());
+    hull.extend(half_hull(&mut vertices.iter().rev()));
+    // points on a single line have no triangles to restore
+    if hull.len() < 3 || (2..hull.len()).all(|i| turn(hull[0], hull[1], hull[i]) == 0.) {
+        return Ok(());
+    }
+    for i in 0..hull.len() {
+        let (endpoint_a, endpoint_b) = (hull[i], hull[(i + 1) % hull.len()]);
+        let mut chain = vec![endpoint_a];
+        chain.extend(find_vertices_on_edge(triangle_set, endpoint_a, endpoint_b));
+        chain.push(endpoint_b);
+        for pair in chain.windows(2) {
+            // there are no constrained edges yet that the hull could cross
+            add_constrained_edge_to_triangulation(triangle_set, pair[0], pair[1])?;
         }
     }
-    return Ok(());
+    Ok(())
 }
 
 pub fn get_supertriangle_triangles(
     triangle_set: &mut TriangleSet,
-    output_triangles: &mut Vec<usize>,
+    output_triangles: &mut Vec<TriangleId>,
 ) {
-    for i in 0..3 {
-        // Vertices of the supertriangle
-        let triangles_that_share_vertex = triangle_set.get_triangle_indices_with_vertex(i);
+    for vertex in triangle_set.supertriangle_vertices().into_iter().flatten() {
+        let triangles_that_share_vertex = triangle_set.get_triangle_indices_with_vertex(vertex);
 
-        for j in 0..triangles_that_share_vertex.len() {
+        for triangle_that_shares_vertex in triangles_that_share_vertex {
             // if the triangles that share the vertex of the super triangles are not in there, put them in there
-            if !output_triangles.contains(&triangles_that_share_vertex[j]) {
-                output_triangles.push(triangles_that_share_vertex[j]);
+            if !output_triangles.contains(&triangle_that_shares_vertex) {
+                output_triangles.push(triangle_that_shares_vertex);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_hole, validate_holes};
+    use crate::{
+        is_delaunay, is_manifold, math_utils::calculate_triangle_area, tagged_edges,
+        test_util::assert_same_mesh, triangulate_with_options, ConstraintIntersectionRule,
+        ConstraintSource, CustomError, HoleProblem, HoleRule, PointOnConstraintRule,
+        TriangulateOptions, TriangulationResult, Vector, Warning,
+    };
+
+    fn polygon(points: &[(f32, f32)]) -> Vec<Vector> {
+        points.iter().map(Vector::from).collect()
+    }
+
+    #[test]
+    fn hole_vertex_one_ulp_off_an_input_point_is_the_same_vertex() -> Result<(), CustomError> {
+        let shared = (5.4385514, 7.452999);
+        let points = polygon(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            shared,
+            (2., 2.),
+            (9., 6.),
+        ]);
+        // the same vertex, computed in a way that rounds differently
+        let hole = polygon(&[
+            (3., 3.),
+            (8., 3.5),
+            (f32::from_bits(shared.0.to_bits() + 1), shared.1),
+        ]);
+        let result = triangulate_with_options(&points, Some(&[hole]), &TriangulateOptions::new())?;
+        assert_eq!(result.vertices.len(), points.len() + 2);
+        assert_eq!(result.hole_reports[0].removed_triangles, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn hole_reports_of_the_doc_example() -> Result<(), CustomError> {
+        let input_points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let holes = [
+            vec![(-1.5, 3.5), (-0.5, 3.5), (-1., 2.5)],
+            vec![(-4., 4.), (0., -2.), (4., 4.)],
+        ]
+        .map(|hole| hole.iter().map(Vector::from).collect::<Vec<Vector>>());
+        let result =
+            triangulate_with_options(&input_points, Some(&holes), &TriangulateOptions::new())?;
+
+        assert_eq!(result.hole_reports.len(), 2);
+        for (hole_report, hole) in result.hole_reports.iter().zip(&holes) {
+            for (vertex, point) in hole_report.vertices.iter().zip(hole) {
+                let difference = result.vertices[*vertex] - *point;
+                assert!(difference.x.abs() < 1e-5 && difference.y.abs() < 1e-5);
+            }
+            assert_eq!(hole_report.inserted_edges + hole_report.existing_edges, 3);
+        }
+        // The first hole is clockwise, so it removes everything outside of it instead of its inside
+        assert_eq!(result.hole_reports[0].removed_triangles, 28);
+        assert_eq!(result.hole_reports[1].removed_triangles, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn triangles_next_to_the_big_hole_have_no_neighbor_across_it() -> Result<(), CustomError> {
+        let input_points = polygon(&[
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]);
+        // the small hole of the doc example is counter-clockwise here, so that it does not remove the whole mesh
+        let holes = [
+            polygon(&[(-1., 2.5), (-0.5, 3.5), (-1.5, 3.5)]),
+            polygon(&[(-4., 4.), (0., -2.), (4., 4.)]),
+        ];
+        let result =
+            triangulate_with_options(&input_points, Some(&holes), &TriangulateOptions::new())?;
+
+        let big_hole = &result.hole_reports[1].vertices;
+        let is_big_hole_edge = |a: usize, b: usize| {
+            (0..big_hole.len()).any(|i| {
+                let (start, end) = (big_hole[i], big_hole[(i + 1) % big_hole.len()]);
+                (start, end) == (a, b) || (start, end) == (b, a)
+            })
+        };
+        let is_hull_edge = |a: usize, b: usize| {
+            let (start, end) = (result.vertices[a], result.vertices[b]);
+            result
+                .vertices
+                .iter()
+                .all(|vertex| (end - start).cross_product(*vertex - start) >= 0.)
+        };
+        let mut hole_facing_edges = 0;
+        for (index, triangle) in result.triangles.iter().enumerate() {
+            for edge in 0..3 {
+                let (a, b) = (triangle[edge], triangle[(edge + 1) % 3]);
+                if is_big_hole_edge(a, b) {
+                    hole_facing_edges += 1;
+                }
+                assert_eq!(
+                    result.is_boundary_edge(index, edge),
+                    is_big_hole_edge(a, b) || is_hull_edge(a, b),
+                    "edge {edge} of triangle {index}"
+                );
+                if let Some(neighbor) = result.neighbors(index)[edge] {
+                    assert!(result.neighbors(neighbor).contains(&Some(index)));
+                }
+            }
+        }
+        assert_eq!(hole_facing_edges, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn hole_in_a_tesselated_square_keeps_the_mesh_delaunay() -> Result<(), CustomError> {
+        // swapping the edges crossed by the hole outline used to check the convexity with the wrong quadrilateral,
+        // which inverted triangles on the way
+        let outline = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let hole = polygon(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)]);
+        let options = TriangulateOptions::new().with_maximum_triangle_area(1.);
+        let result = triangulate_with_options(&outline, Some(&[hole]), &options)?;
+        assert!(is_delaunay(&result));
+        for triangle in result.to_triangles() {
+            assert!(
+                (triangle.p(1) - triangle.p(0)).cross_product(triangle.p(2) - triangle.p(0)) > 0.
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn island_inside_of_a_hole_keeps_its_triangles() -> Result<(), CustomError> {
+        let outline = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let hole = polygon(&[(2., 2.), (8., 2.), (8., 8.), (2., 8.)]);
+        let island = polygon(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)]);
+        let holes = [hole, island];
+        let area = |result: &TriangulationResult| -> f32 {
+            result
+                .to_triangles()
+                .iter()
+                .map(|triangle| {
+                    (triangle.p(1) - triangle.p(0)).cross_product(triangle.p(2) - triangle.p(0))
+                        / 2.
+                })
+                .sum()
+        };
+
+        let options = TriangulateOptions::new().with_hole_rule(HoleRule::EvenOdd);
+        let result = triangulate_with_options(&outline, Some(&holes), &options)?;
+        assert!((area(&result) - (100. - 36. + 4.)).abs() < 1e-3);
+        let island_vertices = &result.hole_reports[1].vertices;
+        let island_triangles = result
+            .triangles
+            .iter()
+            .filter(|triangle| {
+                triangle
+                    .iter()
+                    .all(|vertex| island_vertices.contains(vertex))
+            })
+            .count();
+        assert_eq!(island_triangles, 2);
+        // the ring between the hole and the island belongs to the hole, the island removes nothing
+        assert_eq!(result.hole_reports[0].removed_triangles, 8);
+        assert_eq!(result.hole_reports[1].removed_triangles, 0);
+
+        let union = triangulate_with_options(&outline, Some(&holes), &TriangulateOptions::new())?;
+        assert!((area(&union) - (100. - 36.)).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn filled_hole_matches_the_triangulation_without_it() -> Result<(), CustomError> {
+        let outline = [
+            (0., 0.),
+            (10., 0.5),
+            (10.5, 9.),
+            (0.3, 10.),
+            (5., -1.),
+            (-1., 4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let hole = [(4., 4.), (6., 4.2), (5.1, 6.), (3.8, 5.3)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let mut result = triangulate_with_options(
+            &outline,
+            Some(std::slice::from_ref(&hole)),
+            &TriangulateOptions::new(),
+        )?;
+        assert!(result.hole_reports[0].removed_triangles > 0);
+
+        fill_hole(&mut result, 0)?;
+        let mut all_points = outline;
+        all_points.extend(hole);
+        let without_hole = triangulate_with_options(&all_points, None, &TriangulateOptions::new())?;
+
+        assert!(result.hole_reports[0].filled);
+        assert_same_mesh(&result, &without_hole);
+        assert!(matches!(
+            fill_hole(&mut result, 1),
+            Err(CustomError::HoleNotFound(1))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn filling_a_hole_keeps_the_edges_of_segments() -> Result<(), CustomError> {
+        let points = polygon(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            (4., 4.),
+            (6., 4.),
+        ]);
+        let hole = polygon(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)]);
+        // the segment is the first edge of the hole as well
+        let options = TriangulateOptions::new().with_segments(vec![(4, 5)]);
+        let mut result = triangulate_with_options(&points, Some(&[hole]), &options)?;
+        let is_segment_constrained = |result: &TriangulationResult| {
+            tagged_edges(result)
+                .iter()
+                .any(|(start, end, constrained)| {
+                    *constrained
+                        && [*start, *end].contains(&Vector::new(4., 4.))
+                        && [*start, *end].contains(&Vector::new(6., 4.))
+                })
+        };
+        assert!(is_segment_constrained(&result));
+        assert_eq!(result.constrained_edges.len(), 5);
+
+        fill_hole(&mut result, 0)?;
+        assert!(is_segment_constrained(&result));
+        assert_eq!(result.constrained_edges.len(), 1);
+        assert_eq!(
+            result.constrained_edges[0].source,
+            ConstraintSource::Segment(0)
+        );
+        // the other edges of the hole are free again
+        let constrained_edges = tagged_edges(&result)
+            .iter()
+            .filter(|(_, _, constrained)| *constrained)
+            .count();
+        assert_eq!(constrained_edges, 1);
+        assert!(is_manifold(&result));
+        Ok(())
+    }
+
+    #[test]
+    fn valid_holes_have_no_problems() {
+        let square = polygon(&[(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        let concave = polygon(&[(2., 0.), (4., 0.), (4., 2.), (3., 1.), (2., 2.)]);
+        assert_eq!(validate_holes(&[square, concave]), Ok(()));
+        assert_eq!(validate_holes(&[]), Ok(()));
+    }
+
+    #[test]
+    fn holes_with_too_few_vertices() {
+        let holes = [vec![], polygon(&[(0., 0.), (1., 0.)])];
+        assert_eq!(
+            validate_holes(&holes),
+            Err(vec![
+                HoleProblem::TooFewVertices {
+                    hole: 0,
+                    vertices: 0
+                },
+                HoleProblem::TooFewVertices {
+                    hole: 1,
+                    vertices: 2
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn repeated_vertices_are_duplicates() {
+        // closed explicitly by repeating the first vertex
+        let closed = polygon(&[(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+        let doubled = polygon(&[(0., 0.), (1., 0.), (1., 0.), (0., 1.)]);
+        assert_eq!(
+            validate_holes(&[closed, doubled]),
+            Err(vec![
+                HoleProblem::DuplicateConsecutiveVertices { hole: 0, vertex: 4 },
+                HoleProblem::DuplicateConsecutiveVertices { hole: 1, vertex: 1 },
+            ])
+        );
+    }
+
+    #[test]
+    fn clockwise_and_flat_holes_are_not_counter_clockwise() {
+        let clockwise = polygon(&[(0., 0.), (0., 1.), (1., 1.), (1., 0.)]);
+        let flat = polygon(&[(0., 0.), (1., 0.), (2., 0.)]);
+        let problems = validate_holes(&[clockwise, flat]).unwrap_err();
+        assert_eq!(problems[0], HoleProblem::NotCounterClockwise { hole: 0 });
+        assert!(problems.contains(&HoleProblem::NotCounterClockwise { hole: 1 }));
+    }
+
+    #[test]
+    fn crossing_and_touching_edges_are_self_intersections() {
+        // a bow tie, whose halves cancel out to no area
+        let bow_tie = polygon(&[(0., 0.), (2., 2.), (2., 0.), (0., 2.)]);
+        assert_eq!(
+            validate_holes(std::slice::from_ref(&bow_tie)),
+            Err(vec![
+                HoleProblem::NotCounterClockwise { hole: 0 },
+                HoleProblem::SelfIntersection {
+                    hole: 0,
+                    edges: [0, 2]
+                },
+            ])
+        );
+
+        // the vertex at (2, 0) touches the first edge
+        let touching = polygon(&[(0., 0.), (4., 0.), (4., 4.), (2., 0.), (0., 4.)]);
+        assert_eq!(
+            validate_holes(&[touching]),
+            Err(vec![
+                HoleProblem::SelfIntersection {
+                    hole: 0,
+                    edges: [0, 2]
+                },
+                HoleProblem::SelfIntersection {
+                    hole: 0,
+                    edges: [0, 3]
+                },
+            ])
+        );
+
+        // the last edge runs back along the first one
+        let spike = polygon(&[(0., 0.), (4., 0.), (4., 4.), (0., 4.), (2., 0.)]);
+        let problems = validate_holes(&[spike]).unwrap_err();
+        assert!(problems.contains(&HoleProblem::SelfIntersection {
+            hole: 0,
+            edges: [0, 4]
+        }));
+    }
+
+    /// Whether the point is inside of the polygon, by counting the crossings of a ray to the right.
+    fn is_inside_polygon(point: Vector, polygon: &[Vector]) -> bool {
+        let mut is_inside = false;
+        for i in 0..polygon.len() {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+            {
+                is_inside = !is_inside;
+            }
+        }
+        is_inside
+    }
+
+    #[test]
+    fn removed_triangles_are_grouped_by_their_hole() -> Result<(), CustomError> {
+        let points = polygon(&[
+            (0., 0.),
+            (20., 0.),
+            (20., 10.),
+            (0., 10.),
+            (10., 5.),
+            (5., 1.),
+            (15., 9.),
+        ]);
+        let holes = [
+            polygon(&[(2., 2.), (6., 2.), (6., 7.), (3., 8.)]),
+            polygon(&[(13., 3.), (18., 4.), (16., 8.)]),
+        ];
+        let result = triangulate_with_options(&points, Some(&holes), &TriangulateOptions::new())?;
+
+        let groups = result.removed_triangles_by_hole();
+        assert_eq!(groups.len(), 2);
+        for (hole, group) in holes.iter().zip(&groups) {
+            assert!(!group.is_empty());
+            for triangle in group {
+                let centroid = (triangle.p(0) + triangle.p(1) + triangle.p(2)) / 3.;
+                assert!(
+                    is_inside_polygon(centroid, hole),
+                    "{triangle:?} is outside of {hole:?}"
+                );
+            }
+        }
+        assert_eq!(groups[0].len(), result.hole_reports[0].removed_triangles);
+        assert_eq!(groups[1].len(), result.hole_reports[1].removed_triangles);
+        Ok(())
+    }
+
+    #[test]
+    fn square_with_two_holes_has_four_regions() -> Result<(), CustomError> {
+        let square = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.), (5., 5.)]);
+        let holes = [
+            polygon(&[(1., 1.), (4., 1.), (4., 4.), (1., 4.)]),
+            polygon(&[(6., 6.), (9., 6.), (9., 9.), (6., 9.)]),
+        ];
+        let result = triangulate_with_options(&square, Some(&holes), &TriangulateOptions::new())?;
+        let (labels, sizes) = result.triangle_set.label_regions();
+        assert_eq!(sizes.len(), 4);
+        assert_eq!(sizes.iter().sum::<usize>(), labels.len());
+
+        // every hole is a region of its own and the outside of the hull is another one
+        let mut hole_regions = Vec::new();
+        for hole_report in &result.hole_reports {
+            let region = labels[hole_report.triangle_ids[0].0];
+            assert!(hole_report
+                .triangle_ids
+                .iter()
+                .all(|triangle| labels[triangle.0] == region));
+            assert_eq!(sizes[region], hole_report.removed_triangles);
+            hole_regions.push(region);
+        }
+        let outside_region = labels[result.supertriangle_triangles[0].0];
+        let interior_region = labels[result.triangle_ids[0].0];
+        let mut regions = [
+            hole_regions[0],
+            hole_regions[1],
+            outside_region,
+            interior_region,
+        ];
+        regions.sort();
+        assert_eq!(regions, [0, 1, 2, 3]);
+        assert_eq!(sizes[interior_region], result.len());
+        Ok(())
+    }
+
+    #[test]
+    fn point_on_a_hole_edge_splits_it_or_is_rejected() -> Result<(), CustomError> {
+        // (5, 3) is the middle of the lower edge of the hole
+        let points = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.), (5., 3.)]);
+        let holes = [polygon(&[(3., 3.), (7., 3.), (7., 7.), (3., 7.)])];
+
+        let result = triangulate_with_options(&points, Some(&holes), &TriangulateOptions::new())?;
+        let on_edge = result
+            .vertices
+            .iter()
+            .position(|vertex| *vertex == Vector::new(5., 3.))
+            .unwrap();
+        let hole_vertices = &result.hole_reports[0].vertices;
+        assert_eq!(hole_vertices.len(), 5);
+        assert_eq!(hole_vertices[1], on_edge);
+        let area: f32 = result
+            .to_triangles()
+            .iter()
+            .map(calculate_triangle_area)
+            .sum();
+        assert!((area - 84.).abs() < 1e-3);
+        assert!(is_delaunay(&result));
+
+        let options =
+            TriangulateOptions::new().with_point_on_constraint(PointOnConstraintRule::Reject);
+        let rejected = triangulate_with_options(&points, Some(&holes), &options);
+        assert!(
+            matches!(rejected, Err(CustomError::PointOnConstraint(point)) if point == Vector::new(5., 3.))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hole_edges_along_collinear_points_are_inserted() -> Result<(), CustomError> {
+        // the walk along the hole edges and the swaps used to miss or flatten triangles at collinear points and stall
+        let grid: Vec<Vector> = (0..8)
+            .flat_map(|x| (0..8).map(move |y| Vector::new(x as f32, y as f32)))
+            .collect();
+        let hole = polygon(&[(1., 1.), (6., 2.), (2., 6.)]);
+        let result = triangulate_with_options(&grid, Some(&[hole]), &TriangulateOptions::new())?;
+        let area: f32 = result
+            .iter_triangles()
+            .map(|t| calculate_triangle_area(&t))
+            .sum();
+        assert!((area - 37.).abs() < 1e-3);
+
+        let points = polygon(&[
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]);
+        let holes = [
+            polygon(&[(-1., 2.5), (-0.5, 3.5), (-1.5, 3.5)]),
+            polygon(&[(-4., 4.), (0., -2.), (4., 4.)]),
+        ];
+        for maximum_area in [0.25, 0.5, 1., 2.] {
+            let options = TriangulateOptions::new().with_maximum_triangle_area(maximum_area);
+            let result = triangulate_with_options(&points, Some(&holes), &options)?;
+            let area: f32 = result
+                .iter_triangles()
+                .map(|t| calculate_triangle_area(&t))
+                .sum();
+            assert!((area - 80.5).abs() < 1e-2, "{maximum_area}: {area}");
+        }
+        Ok(())
+    }
+
+    fn square_with_figure_eight(
+        rule: ConstraintIntersectionRule,
+    ) -> Result<TriangulationResult, CustomError> {
+        let square = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let figure_eight = polygon(&[(2., 2.), (6., 6.), (6., 2.), (2., 6.)]);
+        let options = TriangulateOptions::new().with_constraint_intersection(rule);
+        triangulate_with_options(&square, Some(&[figure_eight]), &options)
+    }
+
+    #[test]
+    fn crossing_hole_edges_are_split_at_the_intersection() -> Result<(), CustomError> {
+        let result = square_with_figure_eight(ConstraintIntersectionRule::Split)?;
+        // both lobes of the figure-eight are removed
+        let area: f32 = result
+            .iter_triangles()
+            .map(|t| calculate_triangle_area(&t))
+            .sum();
+        assert!((area - 92.).abs() < 1e-3, "{area}");
+        assert!(is_manifold(&result));
+        // the crossing is listed on both of the edges it splits
+        let hole_vertices = &result.hole_reports[0].vertices;
+        assert_eq!(hole_vertices.len(), 6);
+        let crossings = hole_vertices
+            .iter()
+            .filter(|vertex| (result.vertices[**vertex] - Vector::new(4., 4.)).length() < 1e-4)
+            .count();
+        assert_eq!(crossings, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn crossing_hole_edges_are_rejected() {
+        match square_with_figure_eight(ConstraintIntersectionRule::Reject) {
+            Err(CustomError::ConstraintIntersection(point)) => {
+                assert!((point - Vector::new(4., 4.)).length() < 1e-4, "{point:?}");
             }
+            other => panic!("expected a constraint intersection, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn holes_closer_than_the_merge_distance_are_reported() -> Result<(), CustomError> {
+        let square = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let left = polygon(&[(2., 2.), (4.999997, 2.), (4.999997, 6.), (2., 6.)]);
+        let right = polygon(&[(5., 4.), (8., 4.), (8., 8.), (5., 8.)]);
+        let far = polygon(&[(2., 7.), (4., 7.), (3., 9.)]);
+        let result = triangulate_with_options(
+            &square,
+            Some(&[left, far.clone(), right.clone()]),
+            &TriangulateOptions::new(),
+        )?;
+        match result.warnings.as_slice() {
+            [Warning::HolesNearlyTouching {
+                hole_a: 0,
+                hole_b: 2,
+                distance,
+            }] => assert!((distance - 3e-6).abs() < 1e-6, "{distance}"),
+            warnings => panic!("expected the first and the last hole, got {warnings:?}"),
         }
+
+        // the corners of the right hole are on one circle, which used to flip its diagonal forever
+        let apart = polygon(&[(2., 2.), (4.9, 2.), (4.9, 6.), (2., 6.)]);
+        let result = triangulate_with_options(
+            &square,
+            Some(&[apart, far, right]),
+            &TriangulateOptions::new(),
+        )?;
+        assert!(result.warnings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn repeated_hole_vertices_are_reported_as_zero_length_edges() -> Result<(), CustomError> {
+        let square = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let hole = polygon(&[(2., 2.), (4., 2.), (4., 2.), (4., 4.), (2., 4.)]);
+        let result = triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new())?;
+        assert_eq!(
+            result.warnings,
+            [Warning::ZeroLengthConstraint {
+                source: ConstraintSource::HoleEdge { hole: 0, edge: 1 }
+            }]
+        );
+        assert_eq!(result.constrained_edges[1].vertices.len(), 1);
+        // the hole is cut without the repeated vertex
+        let area: f32 = result
+            .to_triangles()
+            .iter()
+            .map(calculate_triangle_area)
+            .sum();
+        assert!((area - 96.).abs() < 1e-3);
+        Ok(())
     }
 }
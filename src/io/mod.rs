@@ -0,0 +1,2 @@
+//! Reading and writing meshes in the file formats of other tools.
+pub mod triangle_fmt;
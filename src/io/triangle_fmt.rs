@@ -0,0 +1,355 @@
+//! The `.poly`, `.node` and `.ele` files of Shewchuk's Triangle.
+//!
+//! Lines are split at whitespace and everything after a `#` is a comment.
+//! The numbering of the vertices starts at the number of the first vertex, which is 0 or 1,
+//! and the returned indices always start at 0.
+//! The files written here number from 1, like Triangle does by default.
+use std::io::{BufRead, Error, ErrorKind, Result, Write};
+
+use crate::{
+    data_structures::{
+        triangulation_result::TriangulationResult, vector::Vector, vertex_marker::VertexMarker,
+    },
+    queries::vertex_markers,
+};
+
+/// The most records of a section that are reserved up front. The counts in the headers are not trusted
+/// for more, so that a malformed count fails once the records run out instead of allocating all of it.
+const MAX_RESERVED_RECORDS: usize = 1 << 16;
+
+/// The points, the segments as pairs of point indices and the hole seed points of a `.poly` file.
+pub type Poly = (Vec<Vector>, Vec<(usize, usize)>, Vec<Vector>);
+
+/// The boundary marker of a vertex in a `.node` file: 0 inside of the mesh, 1 on the hull
+/// and 2 plus the index of the hole on a hole.
+pub fn boundary_marker(marker: VertexMarker) -> usize {
+    match marker {
+        VertexMarker::Interior => 0,
+        VertexMarker::Hull => 1,
+        VertexMarker::HoleBoundary(hole_id) => hole_id + 2,
+    }
+}
+
+/// Reads a `.poly` file as its points, its segments as pairs of point indices and its hole seed points.
+///
+/// Attributes, boundary markers and regional attributes are skipped.
+/// A `.poly` file that refers to a separate `.node` file for its points, by having zero points, is not supported.
+/// ```
+/// use constrained_denaulay_triangulation::io::triangle_fmt::read_poly;
+///
+/// let poly = "
+/// 4 2 0 0 # a square with a hole in the middle
+/// 1 0 0
+/// 2 10 0
+/// 3 10 10
+/// 4 0 10
+/// 4 0
+/// 1 1 2
+/// 2 2 3
+/// 3 3 4
+/// 4 4 1
+/// 1
+/// 1 5 5
+/// ";
+/// let (points, segments, holes) = read_poly(poly.as_bytes()).unwrap();
+/// assert_eq!(points.len(), 4);
+/// assert_eq!(segments[3], (3, 0));
+/// assert_eq!(holes.len(), 1);
+/// ```
+pub fn read_poly(reader: impl BufRead) -> Result<Poly> {
+    let mut lines = Lines::new(reader);
+    let (points, first_index, _) = read_vertices(&mut lines)?;
+    if points.is_empty() {
+        return Err(invalid_data(
+            "poly files without points, that refer to a node file, are not supported",
+        ));
+    }
+
+    let header = lines.next_record()?;
+    let segment_count = parse::<usize>(&header, 0)?;
+    let mut segments = Vec::with_capacity(segment_count.min(MAX_RESERVED_RECORDS));
+    for _ in 0..segment_count {
+        let record = lines.next_record()?;
+        let endpoint = |position| -> Result<usize> {
+            parse::<usize>(&record, position)?
+                .checked_sub(first_index)
+                .filter(|index| *index < points.len())
+                .ok_or_else(|| invalid_data(&format!("segment endpoint out of range: {record:?}")))
+        };
+        segments.push((endpoint(1)?, endpoint(2)?));
+    }
+
+    // the hole section may be missing at the end of the file
+    let hole_count = match lines.next_record_if_any()? {
+        Some(header) => parse::<usize>(&header, 0)?,
+        None => 0,
+    };
+    let mut holes = Vec::with_capacity(hole_count.min(MAX_RESERVED_RECORDS));
+    for _ in 0..hole_count {
+        let record = lines.next_record()?;
+        holes.push(Vector::new(parse(&record, 1)?, parse(&record, 2)?));
+    }
+    Ok((points, segments, holes))
+}
+
+/// Reads a `.node` file as its points and their boundary markers, which are 0 if the file has none.
+pub fn read_node(reader: impl BufRead) -> Result<(Vec<Vector>, Vec<usize>)> {
+    let mut lines = Lines::new(reader);
+    let (points, _, markers) = read_vertices(&mut lines)?;
+    Ok((points, markers))
+}
+
+/// Reads a `.ele` file as its triangles. The vertex numbering starts at `first_index`,
+/// which is the number of the first vertex in the `.node` file.
+pub fn read_ele(reader: impl BufRead, first_index: usize) -> Result<Vec<[usize; 3]>> {
+    let mut lines = Lines::new(reader);
+    let header = lines.next_record()?;
+    let triangle_count = parse::<usize>(&header, 0)?;
+    if parse::<usize>(&header, 1)? != 3 {
+        return Err(invalid_data(
+            "only triangles with three nodes are supported",
+        ));
+    }
+    let mut triangles = Vec::with_capacity(triangle_count.min(MAX_RESERVED_RECORDS));
+    for _ in 0..triangle_count {
+        let record = lines.next_record()?;
+        let mut triangle = [0; 3];
+        for (i, vertex) in triangle.iter_mut().enumerate() {
+            *vertex = parse::<usize>(&record, i + 1)?
+                .checked_sub(first_index)
+                .ok_or_else(|| invalid_data(&format!("vertex out of range: {record:?}")))?;
+        }
+        triangles.push(triangle);
+    }
+    Ok(triangles)
+}
+
+/// Writes the vertices of the result as a `.node` file, with the markers of [`boundary_marker`].
+pub fn write_node(mut writer: impl Write, result: &TriangulationResult) -> Result<()> {
+    writeln!(
+        writer,
+        "# vertices, dimension, attributes, boundary markers"
+    )?;
+    writeln!(writer, "{} 2 0 1", result.vertices.len())?;
+    for (index, (vertex, marker)) in result
+        .vertices
+        .iter()
+        .zip(vertex_markers(result))
+        .enumerate()
+    {
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            index + 1,
+            vertex.x,
+            vertex.y,
+            boundary_marker(marker)
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the triangles of the result as a `.ele` file, counter-clockwise like in the result.
+pub fn write_ele(mut writer: impl Write, result: &TriangulationResult) -> Result<()> {
+    writeln!(writer, "# triangles, nodes per triangle, attributes")?;
+    writeln!(writer, "{} 3 0", result.triangles.len())?;
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            index + 1,
+            triangle[0] + 1,
+            triangle[1] + 1,
+            triangle[2] + 1
+        )?;
+    }
+    Ok(())
+}
+
+/// Reads the vertex section of a `.node` or `.poly` file as the points, the number of the first vertex and the markers.
+fn read_vertices(lines: &mut Lines<impl BufRead>) -> Result<(Vec<Vector>, usize, Vec<usize>)> {
+    let header = lines.next_record()?;
+    let vertex_count = parse::<usize>(&header, 0)?;
+    if header.len() > 1 && parse::<usize>(&header, 1)? != 2 {
+        return Err(invalid_data("only two dimensional points are supported"));
+    }
+    let attribute_count = if header.len() > 2 {
+        parse::<usize>(&header, 2)?
+    } else {
+        0
+    };
+    let has_markers = header.len() > 3 && parse::<usize>(&header, 3)? > 0;
+
+    let mut points = Vec::with_capacity(vertex_count.min(MAX_RESERVED_RECORDS));
+    let mut markers = Vec::with_capacity(vertex_count.min(MAX_RESERVED_RECORDS));
+    let mut first_index = 0;
+    for i in 0..vertex_count {
+        let record = lines.next_record()?;
+        if i == 0 {
+            first_index = parse::<usize>(&record, 0)?;
+            if first_index > 1 {
+                return Err(invalid_data("the vertex numbering has to start at 0 or 1"));
+            }
+        }
+        points.push(Vector::new(parse(&record, 1)?, parse(&record, 2)?));
+        markers.push(if has_markers {
+            parse(&record, 3 + attribute_count)?
+        } else {
+            0
+        });
+    }
+    Ok((points, first_index, markers))
+}
+
+/// The lines of a file without comments and blank lines, split at whitespace.
+struct Lines<R> {
+    lines: std::io::Lines<R>,
+}
+
+impl<R: BufRead> Lines<R> {
+    fn new(reader: R) -> Self {
+        Lines {
+            lines: reader.lines(),
+        }
+    }
+
+    fn next_record_if_any(&mut self) -> Result<Option<Vec<String>>> {
+        for line in self.lines.by_ref() {
+            let line = line?;
+            let content = line.split('#').next().unwrap_or_default();
+            let fields: Vec<String> = content.split_whitespace().map(String::from).collect();
+            if !fields.is_empty() {
+                return Ok(Some(fields));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The next record, which has to exist because a header counted it.
+    fn next_record(&mut self) -> Result<Vec<String>> {
+        self.next_record_if_any()?
+            .ok_or_else(|| invalid_data("the file ended before all records that its headers count"))
+    }
+}
+
+fn parse<T: std::str::FromStr>(record: &[String], position: usize) -> Result<T> {
+    record
+        .get(position)
+        .and_then(|field| field.parse().ok())
+        .ok_or_else(|| {
+            invalid_data(&format!(
+                "field {position} is missing or invalid in {record:?}"
+            ))
+        })
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::{read_ele, read_node, read_poly, write_ele, write_node};
+    use crate::{triangulate_with_options, TriangulateOptions, Vector};
+
+    #[test]
+    fn zero_based_poly_with_comments_and_attributes() {
+        let poly = "
+            # a triangle with an attribute and markers on every vertex
+            3 2 1 1
+            0 0.0 0.0 7.5 1 # first
+            1 4.0 0.0 7.5 1
+
+            2 0.0 3.0 7.5 1
+            3 1
+            0 0 1 1
+            1 1 2 1
+            2 2 0 1
+            0
+        ";
+        let (points, segments, holes) = read_poly(poly.as_bytes()).unwrap();
+        assert_eq!(
+            points,
+            vec![
+                Vector::new(0., 0.),
+                Vector::new(4., 0.),
+                Vector::new(0., 3.)
+            ]
+        );
+        assert_eq!(segments, vec![(0, 1), (1, 2), (2, 0)]);
+        assert!(holes.is_empty());
+    }
+
+    #[test]
+    fn one_based_poly_without_hole_section() {
+        let poly = "3 2 0 0\n1 0 0\n2 4 0\n3 0 3\n1 0\n1 3 1\n";
+        let (points, segments, holes) = read_poly(poly.as_bytes()).unwrap();
+        assert_eq!(points.len(), 3);
+        assert_eq!(segments, vec![(2, 0)]);
+        assert!(holes.is_empty());
+    }
+
+    #[test]
+    fn segments_to_missing_vertices_are_rejected() {
+        let poly = "2 2 0 0\n1 0 0\n2 4 0\n1 0\n1 1 3\n0\n";
+        assert!(read_poly(poly.as_bytes()).is_err());
+        let truncated = "3 2 0 0\n1 0 0\n2 4 0\n";
+        assert!(read_poly(truncated.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn huge_counts_fail_once_the_records_run_out() {
+        let poly = "18446744073709551615 2 0 0\n1 0 0\n";
+        let error = read_poly(poly.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let segments = "3 2 0 0\n1 0 0\n2 4 0\n3 0 3\n18446744073709551615 0\n1 1 2\n";
+        let error = read_poly(segments.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let ele = "1000000000000 3 0\n1 1 2 3\n";
+        let error = read_ele(ele.as_bytes(), 1).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated_files_are_invalid() {
+        let node = "3 2 0 1\n1 0 0 1\n2 4 0 1\n";
+        let error = read_node(node.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let ele = "2 3 0\n1 1 2 3\n";
+        let error = read_ele(ele.as_bytes(), 1).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let poly = "3 2 0 0\n1 0 0\n2 4 0\n3 0 3\n1 0\n1 3 1\n2 0\n1 1 1\n";
+        let error = read_poly(poly.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn node_and_ele_round_trip() {
+        let points = [(0., 0.), (10., 0.), (10., 10.), (0., 10.), (5., 5.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+
+        let mut node = Vec::new();
+        write_node(&mut node, &result).unwrap();
+        let mut ele = Vec::new();
+        write_ele(&mut ele, &result).unwrap();
+
+        let (vertices, markers) = read_node(node.as_slice()).unwrap();
+        assert_eq!(vertices, result.vertices);
+        // the center is the only vertex that is not on the hull
+        let center = result
+            .vertices
+            .iter()
+            .position(|vertex| *vertex == Vector::new(5., 5.))
+            .unwrap();
+        for (index, marker) in markers.iter().enumerate() {
+            assert_eq!(*marker, usize::from(index != center));
+        }
+        let triangles = read_ele(ele.as_slice(), 1).unwrap();
+        assert_eq!(triangles, result.triangles);
+    }
+}
@@ -1,26 +1,98 @@
-#[warn(missing_docs)]
+//! Constrained delaunay triangulation of point clouds with holes.
+#![warn(missing_docs)]
 // do they need pub use?
+#[cfg(feature = "bevy")]
+pub use bevy_plugin::{
+    TriangulationPlugin, TriangulationReady, TriangulationRequest, TriangulationTask,
+};
 pub use data_structures::vector::Vector;
-pub use data_structures::{error::CustomError, triangle::Triangle};
+pub use data_structures::{
+    constraint_intersection_rule::ConstraintIntersectionRule,
+    constraint_trace::{ConstraintSource, ConstraintTrace},
+    dedup_summary::DedupSummary,
+    edge::Edge,
+    edge_info::EdgeInfo,
+    error::CustomError,
+    estimate::{estimate, Estimate},
+    ffi_mesh::FfiMesh,
+    found_or_added::FoundOrAdded,
+    hole_problem::HoleProblem,
+    hole_report::HoleReport,
+    hole_rule::HoleRule,
+    ids::{TriangleId, VertexId},
+    input_diagnostics::{InputDiagnostics, MAX_DYNAMIC_RANGE},
+    insertion_algorithm::InsertionAlgorithm,
+    insertion_order::InsertionOrder,
+    insertion_statistics::InsertionStatistics,
+    mesh_statistics::MeshStatistics,
+    point2::Point2,
+    point_bin_grid::PointBinGrid,
+    point_on_constraint_rule::PointOnConstraintRule,
+    triangle::Triangle,
+    triangle_info::TriangleInfo,
+    triangle_ref::{TriangleKind, TriangleRef, TriangleRefs},
+    triangle_set::TriangleSet,
+    triangulate_options::{TriangulateOptions, DEFAULT_STEINER_POINT_LIMIT},
+    triangulation_algorithm::TriangulationAlgorithm,
+    triangulation_result::TriangulationResult,
+    triangulation_snapshot::TriangulationSnapshot,
+    triangulation_view::TriangulationView,
+    vertex_marker::VertexMarker,
+    vertex_ring::VertexRing,
+    warning::Warning,
+};
+pub use editing::{decimate, move_vertex, remove_point, smooth};
+pub use half_edge::{to_half_edge, HalfEdge, HalfEdgeMesh};
+pub use hole_creation::{fill_hole, validate_holes};
+pub use math_utils::{distance_segment_segment, is_point_inside_triangle, is_quadrilateral_convex};
+#[cfg(feature = "debug")]
+pub use normalize::Bounds;
+pub use preflight::preflight;
+pub use queries::{
+    boundary_length, boundary_loops, circumcircles, classify_point, edge_faces, edges_iter,
+    euclidean_mst, gabriel_graph, hole_boundaries, is_conforming_delaunay, is_delaunay,
+    is_manifold, largest_empty_circle, locate, locate_from, nearest_vertex, pair_quads,
+    region_areas, repair_winding, segment_path, slivers, tagged_edges, triangles_bordering_hole,
+    triangles_in_polygon_query, vertex_markers, vertex_ring, winding_number, PointClass, QuadOrTri,
+    SegmentPathStep,
+};
+pub use refinement::refine;
+pub use strips::{join_strips, stripify, to_triangle_strips};
 
+#[cfg(feature = "bevy")]
+mod bevy_plugin;
+mod bowyer_watson;
 mod data_structures;
+mod divide_and_conquer;
+mod editing;
+pub mod export;
+mod half_edge;
 mod hole_creation;
+pub mod io;
 mod math_utils;
 mod normalize;
+#[cfg(feature = "rayon")]
+mod parallel_refinement;
+mod preflight;
+mod queries;
+mod random;
+mod refinement;
+mod strips;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 mod triangulation;
 
 /// This will triangulate any polygon using the delaunay constraint
 ///
 /// You may provide input points in the given vector type, which will be used to create the triangulated polygon.
-/// Then you can use optionally a vec of holes to create holes in the polygon mentioned above.
+/// Then you can use optionally a slice of holes to create holes in the polygon mentioned above.
 /// At least you can tesselate the area so that it may only contain triangles of the maximum area size given.
 /// # Examples
-/// This example uses an easy convex polygon.
+/// This example cuts two holes out of a point cloud.
 /// ```
-/// use constrained_denaulay_triangulation::{triangulate, Vector};
+/// use constrained_denaulay_triangulation::{triangulate, CustomError, Triangle, Vector};
 ///
-/// fn main() {
-/// let mut input_points = vec![
+/// let input_points: Vec<Vector> = [
 ///     (0., 7.),
 ///     (-5., 5.),
 ///     (5., 5.),
@@ -32,103 +104,266 @@ mod triangulation;
 ///     (5., -4.),
 /// ]
 /// .iter()
-/// .map(|x| Vector::from(x))
-/// .collect::<Vec<Vector>>();
-
-/// let mut holes: Vec<Vec<Vector>> = vec![];
-/// let minihole = vec![(-1.5, 3.5), (-0.5, 3.5), (-1., 2.5)]
+/// .map(Vector::from)
+/// .collect();
+///
+/// // the holes are counter-clockwise, see validate_holes
+/// let minihole: Vec<Vector> = [(-1., 2.5), (-0.5, 3.5), (-1.5, 3.5)]
 ///     .iter()
-///     .map(|x| Vector::from(x))
-///     .collect::<Vec<Vector>>();
-/// holes.push(minihole);
-
-/// let bighole = vec![(-4., 4.), (0., -2.), (4., 4.)]
+///     .map(Vector::from)
+///     .collect();
+/// let bighole: Vec<Vector> = [(-4., 4.), (0., -2.), (4., 4.)]
 ///     .iter()
-///     .map(|x| Vector::from(x))
-///     .collect::<Vec<Vector>>();
-/// holes.push(bighole);
-
-/// let input_hole = Some(&mut holes);
-
-/// let a = match triangulate(&mut input_points, input_hole, None) {
-///     Ok(result) => result,
-///     Err(err) => panic!("triangulation failed!{:?}", err),
-/// };
-/// assert!(a.len() > 0);
-/// }
+///     .map(Vector::from)
+///     .collect();
+/// let holes = [minihole, bighole];
 ///
+/// let triangles: Result<Vec<Triangle>, CustomError> = triangulate(&input_points, Some(&holes), None);
+/// let triangles = triangles?;
+/// let area: f32 = triangles
+///     .iter()
+///     .map(|t| ((t.p(1) - t.p(0)).cross_product(t.p(2) - t.p(0)) / 2.).abs())
+///     .sum();
+/// // the small hole lies inside of the big one
+/// assert!((area - 80.5).abs() < 1e-3);
+/// # Ok::<(), CustomError>(())
 /// ```
 /// Even more complex are no problem either. (such as with collinear lines to the super triangle and each other.)
 /// ```
 /// use constrained_denaulay_triangulation::{triangulate, Vector};
 ///
-/// fn main() {
-/// let mut input_points = Vec::new();
-/// input_points.push(Vector::new(1., 1.));
-/// input_points.push(Vector::new(3., 4.));
-/// input_points.push(Vector::new(-2., 3.));
-/// input_points.push(Vector::new(-2., 3.));
-/// input_points.push(Vector::new(-2.,-2.));
-/// input_points.push(Vector::new(-1., -1.));
-/// input_points.push(Vector::new(-2.,-3.));
-/// input_points.push(Vector::new(4.,-2.));
-/// let triangles = match triangulate(&mut input_points, None, None){
-///     Ok(result) => result,
-///     Err(err) => panic!("triangulation failed!{:?}", err),
-/// };
-/// assert!(triangles.len() > 0);
-/// }
+/// let input_points = vec![
+///     Vector::new(1., 1.),
+///     Vector::new(3., 4.),
+///     Vector::new(-2., 3.),
+///     Vector::new(-2., 3.),
+///     Vector::new(-2., -2.),
+///     Vector::new(-1., -1.),
+///     Vector::new(-2., -3.),
+///     Vector::new(4., -2.),
+/// ];
+/// let triangles = triangulate(&input_points, None, None)?;
+/// assert!(!triangles.is_empty());
+/// # Ok::<(), constrained_denaulay_triangulation::CustomError>(())
 /// ```
+/// # Errors
+/// A `maximum_triangle_area` that is not finite or not positive returns [`CustomError::InvalidParameter`].
 /// # Panics
 /// The triangulation might panic if the holes are 50x the size of the polygon to be triangulated.
 /// # Known limitations
 /// The function will not work with holes that are bigger than the point cloud or outside of the point cloud
 pub fn triangulate(
-    input_points: &mut Vec<Vector>,
-    holes: Option<&mut Vec<Vec<Vector>>>,
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
     maximum_triangle_area: Option<f32>,
 ) -> Result<Vec<Triangle>, CustomError> {
-    Ok(triangulation::triangulate(
-        input_points,
-        holes,
+    let options = TriangulateOptions {
         maximum_triangle_area,
-    )?)
+        ..Default::default()
+    };
+    triangulate_with_options(input_points, holes, &options).map(|result| result.to_triangles())
 }
 
-fn test() {
-/// let mut input_points = vec![
-///     (0., 7.),
-///     (-5., 5.),
-///     (5., 5.),
-///     (-1., 3.),
-///     (3., 1.),
-///     (-4., -1.),
-///     (1., -2.),
-///     (-6., -4.),
-///     (5., -4.),
-/// ]
-/// .iter()
-/// .map(|x| Vector::from(x))
-/// .collect::<Vec<Vector>>();
+/// Does the same as [`triangulate`], but takes all the settings as [`TriangulateOptions`]
+/// and returns the mesh as a [`TriangulationResult`], with shared vertices and adjacency.
+///
+/// Points or holes with a NaN or infinite coordinate fail with [`CustomError::NonFinitePoint`],
+/// and fewer than three points with [`CustomError::TooFewPoints`], see [`preflight`] for the other checks.
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let options = TriangulateOptions::new().with_maximum_triangle_area(0.1);
+/// let result = triangulate_with_options(&square, None, &options).unwrap();
+/// assert!(result.len() > 2);
+/// for triangle in &result {
+///     // the neighbors are the triangles across the edges starting at each vertex
+///     for (edge, neighbor) in triangle.neighbors.iter().enumerate() {
+///         if let Some(neighbor) = neighbor {
+///             assert!(result.triangles[*neighbor].contains(&triangle.vertices[(edge + 1) % 3]));
+///         }
+///     }
+/// }
+/// ```
+pub fn triangulate_with_options(
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<TriangulationResult, CustomError> {
+    options.validate()?;
+    triangulation::triangulate(input_points, holes, options)
+}
 
-/// let mut holes: Vec<Vec<Vector>> = vec![];
-/// let minihole = vec![(-1.5, 3.5), (-0.5, 3.5), (-1., 2.5)]
-///     .iter()
-///     .map(|x| Vector::from(x))
-///     .collect::<Vec<Vector>>();
-/// holes.push(minihole);
+/// Does the same as [`triangulate_with_options`], but writes the triangles into `triangles`,
+/// which is cleared first, so that its capacity is reused across calls.
+///
+/// Only the output buffer is reused, the triangulation itself still allocates its working memory.
+/// If the triangulation fails, `triangles` is left empty.
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_into, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let mut triangles = Vec::new();
+/// for _ in 0..3 {
+///     triangulate_into(&square, None, &TriangulateOptions::new(), &mut triangles).unwrap();
+///     assert_eq!(triangles.len(), 2);
+/// }
+/// ```
+/// # Errors
+/// The same as the ones of [`triangulate_with_options`].
+pub fn triangulate_into(
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+    triangles: &mut Vec<Triangle>,
+) -> Result<(), CustomError> {
+    triangles.clear();
+    let result = triangulate_with_options(input_points, holes, options)?;
+    triangles.extend(result.iter_triangles());
+    Ok(())
+}
+
+/// Does the same as [`triangulate_with_options`], but reads the input points from an iterator.
+///
+/// The points are normalized onto the unit square before they are triangulated, which needs their bounds.
+/// Without [`TriangulateOptions::bounds`] the iterator is collected first to measure them,
+/// so the input is held twice, once as it was read and once normalized.
+/// With bounds every point is normalized as soon as it is read and only the normalized copy is kept.
+/// `dedup_input` then compares the normalized points, which needs a second copy again.
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_from_iter, TriangulateOptions, Vector};
+///
+/// let grid = (0..100).map(|i| Vector::new((i % 10) as f32, (i / 10) as f32));
+/// let options = TriangulateOptions::new().with_bounds(Vector::new(0., 0.), Vector::new(9., 9.));
+/// let result = triangulate_from_iter(grid, None, &options).unwrap();
+/// assert_eq!(result.len(), 2 * 9 * 9);
+/// ```
+/// # Errors
+/// Invalid options return [`CustomError::InvalidParameter`],
+/// and a point outside of the bounds returns [`CustomError::PointOutsideBounds`].
+pub fn triangulate_from_iter(
+    input_points: impl ExactSizeIterator<Item = Vector>,
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<TriangulationResult, CustomError> {
+    options.validate()?;
+    triangulation::triangulate_from_iter(input_points, holes, options)
+}
+
+/// Does the same as [`triangulate_with_options`], but also returns the normalized input points the triangulation ran on
+/// and the [`Bounds`] they were normalized with, to debug numerical problems.
+///
+/// The input points are mapped onto the unit square, separately for x and y.
+/// If [`TriangulateOptions::dedup_input`] is set, the duplicates are already removed from the normalized points.
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_debug, TriangulateOptions, Vector};
+///
+/// let points = vec![Vector::new(-2., 0.), Vector::new(2., 0.), Vector::new(0., 1.)];
+/// let (result, normalized_points, bounds) = triangulate_debug(&points, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(result.len(), 1);
+/// assert_eq!(normalized_points[2], Vector::new(0.5, 1.));
+/// assert_eq!(bounds.denormalize(normalized_points[2]), points[2]);
+/// ```
+#[cfg(feature = "debug")]
+pub fn triangulate_debug(
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<(TriangulationResult, Vec<Vector>, Bounds), CustomError> {
+    options.validate()?;
+    let (result, normalized_points) =
+        triangulation::triangulate_normalized(input_points, holes, options)?;
+    let bounds = result.bounds;
+    Ok((result, normalized_points, bounds))
+}
 
-/// let bighole = vec![(-4., 4.), (0., -2.), (4., 4.)]
+/// Does the same as [`triangulate_with_options`], but returns the mesh as the flat arrays of an [`FfiMesh`].
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_ffi, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let mesh = triangulate_ffi(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(mesh.vertices.len(), 8);
+/// assert_eq!(mesh.indices.len(), 6);
+/// // the diagonal is the only edge with a neighbor
+/// assert_eq!(mesh.adjacency.iter().filter(|adjacent| **adjacent == -1).count(), 4);
+/// ```
+pub fn triangulate_ffi(
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<FfiMesh, CustomError> {
+    triangulate_with_options(input_points, holes, options).map(|result| FfiMesh::from(&result))
+}
+
+/// Triangulates a planar straight-line graph, the input format of Shewchuk's Triangle:
+/// the vertices, segments as pairs of vertex indices that have to become edges of the mesh,
+/// and hole seed points that remove the region around them up to the nearest segments.
+///
+/// The segments do not have to form closed polygons. They must not cross each other, but a segment through a vertex
+/// is split at it.
+/// The mesh covers the convex hull of the vertices, a concavity is removed by putting a hole seed into it.
+/// Every seed gets a [`HoleReport`] without polygon vertices.
+/// ```
+/// use constrained_denaulay_triangulation::{triangulate_pslg, Vector};
+///
+/// let vertices = [(0., 0.), (10., 0.), (10., 10.), (0., 10.), (4., 4.), (6., 4.), (6., 6.), (4., 6.)]
 ///     .iter()
-///     .map(|x| Vector::from(x))
+///     .map(Vector::from)
 ///     .collect::<Vec<Vector>>();
-/// holes.push(bighole);
-
-/// let input_hole = Some(&mut holes);
+/// let segments = [(4, 5), (5, 6), (6, 7), (7, 4)];
+/// let result = triangulate_pslg(&vertices, &segments, Some(&[Vector::new(5., 5.)])).unwrap();
+/// assert_eq!(result.len(), 8);
+/// assert_eq!(result.hole_reports[0].removed_triangles, 2);
+/// ```
+/// # Errors
+/// A segment that connects a vertex to itself or refers to a missing vertex returns [`CustomError::InvalidSegment`].
+pub fn triangulate_pslg(
+    vertices: &[Vector],
+    segments: &[(usize, usize)],
+    holes: Option<&[Vector]>,
+) -> Result<TriangulationResult, CustomError> {
+    triangulation::triangulate_pslg(vertices, segments, holes.unwrap_or_default())
+}
 
-/// let a = match triangulate(&mut input_points, input_hole, None) {
-///     Ok(result) => result,
-///     Err(err) => panic!("triangulation failed!{:?}", err),
-/// };
-/// assert!(a.len() > 0);
+/// Triangulates points of any type that implements [`Point2`], like [`triangulate`] without tesselation.
+///
+/// The triangles are returned as counter-clockwise indices into the points, followed by the vertices of the holes,
+/// in the order of the holes. Points at the same position share the index of the first of them.
+/// ```
+/// use constrained_denaulay_triangulation::triangulate_generic;
+///
+/// let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+/// let triangles = triangulate_generic(&square, None).unwrap();
+/// assert_eq!(triangles.len(), 2);
+/// assert!(triangles.iter().flatten().all(|index| *index < square.len()));
+/// ```
+pub fn triangulate_generic<P: Point2>(
+    input_points: &[P],
+    holes: Option<&[Vec<P>]>,
+) -> Result<Vec<[usize; 3]>, CustomError> {
+    let to_vectors = |points: &[P]| -> Vec<Vector> {
+        points
+            .iter()
+            .map(|point| Vector::new(point.x(), point.y()))
+            .collect()
+    };
+    let holes: Option<Vec<Vec<Vector>>> =
+        holes.map(|holes| holes.iter().map(|hole| to_vectors(hole)).collect());
+    triangulation::triangulate_indexed(&to_vectors(input_points), holes.as_deref())
 }
@@ -1,38 +1,40 @@
+use std::cell::Cell;
+
 use crate::data_structures::{triangle::Triangle, vector::Vector};
-/// Calculates the determinant of a 3 columns x 3 rows matrix.
-///
-/// # Arguments
-///
-/// * `m00` - The element at position (0, 0).
-/// * `m10` - The element at position (1, 0).
-/// * `m20` - The element at position (2, 0).
-/// * `m01` - The element at position (0, 1).
-/// * `m11` - The element at position (1, 1).
-/// * `m21` - The element at position (2, 1).
-/// * `m02` - The element at position (0, 2).
-/// * `m12` - The element at position (1, 2).
-/// * `m22` - The element at position (2, 2).
-///
-/// # Returns
-///
-/// The determinant.
-pub fn calculate_matrix3x3_determinant(
-    m00: f32,
-    m10: f32,
-    m20: f32,
-    m01: f32,
-    m11: f32,
-    m21: f32,
-    m02: f32,
-    m12: f32,
-    m22: f32,
-) -> f32 {
-    m00 * m11 * m22 + m10 * m21 * m02 + m20 * m01 * m12
-        - m20 * m11 * m02
-        - m10 * m01 * m22
-        - m00 * m21 * m12
+
+/// Relative error bound of the orientation determinant in f32, as a factor of the magnitude of its two products.
+/// Below it the sign is not reliable and the determinant is recomputed in f64.
+const ORIENTATION_ERROR_BOUND: f32 = 4. * f32::EPSILON;
+/// Relative error bound of the circumcircle determinant in f32, as a factor of the magnitude of its six products.
+const CIRCUMCIRCLE_ERROR_BOUND: f32 = 16. * f32::EPSILON;
+/// The same bound for the determinant recomputed in f64. Below it the point counts as being on the circle.
+const CIRCUMCIRCLE_ERROR_BOUND_F64: f64 = 16. * f64::EPSILON;
+/// The distance from the line through an edge, below which a point counts as being on the edge
+/// in [`is_point_to_the_right_of_edge`]. Extremely small negative determinants caused wrong results without it.
+const EDGE_DISTANCE_TOLERANCE: f32 = 1e-8;
+/// The sine of the angle below which three points count as being on a line in [`is_quadrilateral_convex`].
+/// Above the rounding of f32 coordinates, so that swapping a diagonal never leaves a flat or inverted triangle.
+const COLLINEAR_TOLERANCE: f64 = 1e-6;
+
+thread_local! {
+    /// How often the orientation and the circumcircle determinant were recomputed in f64 on this thread.
+    static PREDICATE_FALLBACKS: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+/// How often the orientation and the circumcircle determinant were too close to zero for f32 on this thread so far.
+pub fn predicate_fallbacks() -> (usize, usize) {
+    PREDICATE_FALLBACKS.get()
 }
 
+/// Counts fallbacks that happened on other threads, as if they had happened on this one.
+#[cfg(feature = "rayon")]
+pub fn add_predicate_fallbacks(orientation: usize, circumcircle: usize) {
+    let (own_orientation, own_circumcircle) = PREDICATE_FALLBACKS.get();
+    PREDICATE_FALLBACKS.set((
+        own_orientation + orientation,
+        own_circumcircle + circumcircle,
+    ));
+}
 /// Checks whether a point lies on the right side of an edge.
 ///
 /// # Arguments
@@ -53,8 +55,23 @@ pub fn is_point_to_the_right_of_edge(
     let p2 = point.y - edge_endpoint_a.y;
     let p3 = edge_endpoint_b.y - edge_endpoint_a.y;
     let p4 = point.x - edge_endpoint_a.x;
-    let determinante = p1 * p2 - p3 * p4;
-    determinante < -0.00000001 // Note: Due to extremely small negative values causing wrong results, a tolerance is used instead of zero
+    let mut determinante = p1 * p2 - p3 * p4;
+    if determinante.abs() <= ORIENTATION_ERROR_BOUND * ((p1 * p2).abs() + (p3 * p4).abs()) {
+        PREDICATE_FALLBACKS.set({
+            let (orientation, circumcircle) = PREDICATE_FALLBACKS.get();
+            (orientation + 1, circumcircle)
+        });
+        let difference = |a: f32, b: f32| a as f64 - b as f64;
+        determinante = (difference(edge_endpoint_b.x, edge_endpoint_a.x)
+            * difference(point.y, edge_endpoint_a.y)
+            - difference(edge_endpoint_b.y, edge_endpoint_a.y)
+                * difference(point.x, edge_endpoint_a.x)) as f32;
+    }
+    // points closer to the line through the edge than the tolerance count as being on it,
+    // relative to the length of the edge, so that the edges inside of a dense cluster are not all thick lines
+    determinante < 0.
+        && determinante * determinante
+            > EDGE_DISTANCE_TOLERANCE * EDGE_DISTANCE_TOLERANCE * (p1 * p1 + p3 * p3)
 }
 
 /// Checks whether a point lies on the left side of an edge.
@@ -78,153 +95,297 @@ pub fn is_point_to_the_left_of_edge(
 
 // https://gamedev.stackexchange.com/questions/71328/how-can-i-add-and-subtract-convex-polygons
 pub fn is_point_inside_circumcircle(triangle: Triangle, point_to_check: Vector) -> bool {
-			// This first part will simplify how we calculate the determinant
-			let a = triangle.p(0).x - point_to_check.x;
-			let d = triangle.p(1).x - point_to_check.x;
-			let g = triangle.p(2).x - point_to_check.x;
-
-			let b = triangle.p(0).y - point_to_check.y;
-			let e = triangle.p(1).y - point_to_check.y;
-			let h = triangle.p(2).y - point_to_check.y;
-
-			let c = a * a + b * b;
-			let f = d * d + e * e;
-			let i = g * g + h * h;
-
-			let determinant = (a * e * i) + (b * f * g) + (c * d * h) - (g * e * c) - (h * f * a) - (i * d * b);
-
-			return determinant >= 0.; // zero means on the perimeter
-//    // sloan algorithm
-//    let x02 = triangle.p(0).x - triangle.p(2).x;
-//    let x12 = triangle.p(1).x - triangle.p(2).x;
-//    let x0p = triangle.p(0).x - point_to_check.x;
-//    let x1p = triangle.p(1).x - point_to_check.x;
-//    let y02 = triangle.p(0).y - triangle.p(2).y;
-//    let y12 = triangle.p(1).y - triangle.p(2).y;
-//    let y0p = triangle.p(0).y - point_to_check.y;
-//    let y1p = triangle.p(1).y - point_to_check.y;
-//
-//    let cosa = x02 * x12 + y02 * y12;
-//    let cosb = x0p * x1p + y0p * y1p;
-//
-//    if cosa >= 0. && cosb >= 0. {
-//        return false;
-//    }
-//    if cosa < 0. && cosb < 0. {
-//        return true;
-//    }
-//
-//    let sina = x02 * y12 - x12 * y02;
-//    let sinb = x1p * y0p - x0p * y1p;
-//    if sina * cosb + sinb * cosa < 0. {
-//        return true;
-//    }
-//    false
-}
-
-/// Calculates whether 2 line segments intersect and returns the intersection point.
-pub fn intersection_between_lines(
-    endpoint_a1: &Vector,
-    endpoint_b1: &Vector,
-    endpoint_a2: &Vector,
-    endpoint_b2: &Vector,
-) -> Option<Vector> {
-    // https://stackoverflow.com/questions/4543506/algorithm-for-intersection-of-2-lines
-    // TODO It would be great, if there was no intersection, if they started at the same point
-    let mut intersection_point = Vector::new(f32::MAX, f32::MAX);
-
-    let is_line1_vertical = endpoint_b1.x == endpoint_a1.x;
-    let is_line2_vertical = endpoint_b2.x == endpoint_a2.x;
-
-    let mut x = f32::MAX;
-    let mut y = f32::MAX;
-
-    if is_line1_vertical && !is_line2_vertical {
-        // First it calculates the standard form (Ax + By = C)
-        let m2 = (endpoint_b2.y - endpoint_a2.y) / (endpoint_b2.x - endpoint_a2.x);
-
-        let c2 = endpoint_a2.x * m2 - endpoint_a2.y;
-
-        x = endpoint_a1.x;
-        y = m2 * endpoint_a1.x - c2;
-    } else if is_line2_vertical && !is_line1_vertical {
-        // First it calculates the standard form (Ax + By = C)
-        let m1 = (endpoint_b1.y - endpoint_a1.y) / (endpoint_b1.x - endpoint_a1.x);
-
-        let c1 = endpoint_a1.x * m1 - endpoint_a1.y;
-
-        x = endpoint_a2.x;
-        y = m1 * endpoint_a2.x - c1;
-    } else if !is_line1_vertical && !is_line2_vertical {
-        let m1 = (endpoint_b1.y - endpoint_a1.y) / (endpoint_b1.x - endpoint_a1.x);
-
-        let b1 = -1.0;
-        let c1 = endpoint_a1.x * m1 - endpoint_a1.y;
-
-        let m2 = (endpoint_b2.y - endpoint_a2.y) / (endpoint_b2.x - endpoint_a2.x);
-
-        let b2 = -1.0;
-        let c2 = endpoint_a2.x * m2 - endpoint_a2.y;
-
-        let determinant = m1 * b2 - m2 * b1;
-
-        if determinant == 0.0 {
-            // Lines do not intersect
-            return None;
-        }
+    circumcircle_determinant(triangle, point_to_check) >= 0. // zero means on the perimeter
+}
 
-        x = (b2 * c1 - b1 * c2) / determinant;
-        y = (m1 * c2 - m2 * c1) / determinant;
-    }
+/// Like [`is_point_inside_circumcircle`], but points on the perimeter are outside.
+/// Flipping edges with this never loops on co-circular points.
+pub fn is_point_strictly_inside_circumcircle(triangle: Triangle, point_to_check: Vector) -> bool {
+    circumcircle_determinant(triangle, point_to_check) > 0.
+}
 
-    // Checks whether the point is in the segment determined by the endpoints of both lines
-    if x <= endpoint_a1.x.max(endpoint_b1.x)
-        && x >= endpoint_a1.x.min(endpoint_b1.x)
-        && y <= endpoint_a1.y.max(endpoint_b1.y)
-        && y >= endpoint_a1.y.min(endpoint_b1.y)
-        && x <= endpoint_a2.x.max(endpoint_b2.x)
-        && x >= endpoint_a2.x.min(endpoint_b2.x)
-        && y <= endpoint_a2.y.max(endpoint_b2.y)
-        && y >= endpoint_a2.y.min(endpoint_b2.y)
+/// Positive if the point is inside of the circumcircle of the counter-clockwise triangle, zero on it.
+///
+/// If the result is too close to zero to trust its sign in f32, it is recomputed in f64.
+fn circumcircle_determinant(triangle: Triangle, point_to_check: Vector) -> f32 {
+    let products = circumcircle_products(
+        [0, 1, 2].map(|i| (triangle.p(i).x, triangle.p(i).y)),
+        (point_to_check.x, point_to_check.y),
+    );
+    let determinant = products[..3].iter().sum::<f32>() - products[3..].iter().sum::<f32>();
+    let magnitude: f32 = products.iter().map(|product| product.abs()).sum();
+    // products of large coordinates overflow to infinity, and infinities of both signs add up to NaN
+    if determinant.is_finite()
+        && magnitude.is_finite()
+        && determinant.abs() > CIRCUMCIRCLE_ERROR_BOUND * magnitude
     {
-        intersection_point.x = x;
-        intersection_point.y = y;
-        Some(intersection_point)
+        return determinant;
+    }
+
+    PREDICATE_FALLBACKS.set({
+        let (orientation, circumcircle) = PREDICATE_FALLBACKS.get();
+        (orientation, circumcircle + 1)
+    });
+    let products = circumcircle_products(
+        [0, 1, 2].map(|i| (triangle.p(i).x as f64, triangle.p(i).y as f64)),
+        (point_to_check.x as f64, point_to_check.y as f64),
+    );
+    let determinant = products[..3].iter().sum::<f64>() - products[3..].iter().sum::<f64>();
+    let magnitude: f64 = products.iter().map(|product| product.abs()).sum();
+    // f64 does not overflow for finite f32 coordinates, but it still rounds the products,
+    // so the corners of a rectangle would be inside of each other's circumcircles and flip forever.
+    // Infinite or NaN coordinates count as being on the circle as well.
+    if determinant.is_nan() || determinant.abs() <= CIRCUMCIRCLE_ERROR_BOUND_F64 * magnitude {
+        0.
     } else {
-        None
+        determinant as f32
+    }
+}
+
+/// The six products of the circumcircle determinant, the positive ones first.
+fn circumcircle_products<T>(triangle: [(T, T); 3], point_to_check: (T, T)) -> [T; 6]
+where
+    T: Copy + std::ops::Sub<Output = T> + std::ops::Mul<Output = T> + std::ops::Add<Output = T>,
+{
+    // This first part will simplify how we calculate the determinant
+    let a = triangle[0].0 - point_to_check.0;
+    let d = triangle[1].0 - point_to_check.0;
+    let g = triangle[2].0 - point_to_check.0;
+
+    let b = triangle[0].1 - point_to_check.1;
+    let e = triangle[1].1 - point_to_check.1;
+    let h = triangle[2].1 - point_to_check.1;
+
+    let c = a * a + b * b;
+    let f = d * d + e * e;
+    let i = g * g + h * h;
+
+    [
+        a * e * i,
+        b * f * g,
+        c * d * h,
+        g * e * c,
+        h * f * a,
+        i * d * b,
+    ]
+    //    // sloan algorithm
+    //    let x02 = triangle.p(0).x - triangle.p(2).x;
+    //    let x12 = triangle.p(1).x - triangle.p(2).x;
+    //    let x0p = triangle.p(0).x - point_to_check.x;
+    //    let x1p = triangle.p(1).x - point_to_check.x;
+    //    let y02 = triangle.p(0).y - triangle.p(2).y;
+    //    let y12 = triangle.p(1).y - triangle.p(2).y;
+    //    let y0p = triangle.p(0).y - point_to_check.y;
+    //    let y1p = triangle.p(1).y - point_to_check.y;
+    //
+    //    let cosa = x02 * x12 + y02 * y12;
+    //    let cosb = x0p * x1p + y0p * y1p;
+    //
+    //    if cosa >= 0. && cosb >= 0. {
+    //        return false;
+    //    }
+    //    if cosa < 0. && cosb < 0. {
+    //        return true;
+    //    }
+    //
+    //    let sina = x02 * y12 - x12 * y02;
+    //    let sinb = x1p * y0p - x0p * y1p;
+    //    if sina * cosb + sinb * cosa < 0. {
+    //        return true;
+    //    }
+    //    false
+}
+
+/// Whether the closed segments from `a1` to `b1` and from `a2` to `b2` have at least one point in common,
+/// including touching endpoints and overlapping collinear segments.
+pub fn do_segments_intersect(a1: Vector, b1: Vector, a2: Vector, b2: Vector) -> bool {
+    let orientation = |a: Vector, b: Vector, c: Vector| (b - a).cross_product(c - a);
+    // whether c, which is on the line through a and b, is between them
+    let is_on_segment = |a: Vector, b: Vector, c: Vector| {
+        c.x >= a.x.min(b.x) && c.x <= a.x.max(b.x) && c.y >= a.y.min(b.y) && c.y <= a.y.max(b.y)
+    };
+    let o1 = orientation(a1, b1, a2);
+    let o2 = orientation(a1, b1, b2);
+    let o3 = orientation(a2, b2, a1);
+    let o4 = orientation(a2, b2, b1);
+    if o1 * o2 < 0. && o3 * o4 < 0. {
+        return true;
     }
+    (o1 == 0. && is_on_segment(a1, b1, a2))
+        || (o2 == 0. && is_on_segment(a1, b1, b2))
+        || (o3 == 0. && is_on_segment(a2, b2, a1))
+        || (o4 == 0. && is_on_segment(a2, b2, b1))
 }
 
-// TODO this is never used
-pub fn is_triangle_vertices_cw(point0: &Vector, point1: &Vector, point2: &Vector) -> bool {
-    calculate_matrix3x3_determinant(
-        point0.x, point0.y, 1.0, point1.x, point1.y, 1.0, point2.x, point2.y, 1.0,
-    ) < 0.0
+/// Whether the segments from `a1` to `b1` and from `a2` to `b2` cross each other at a single point inside of both,
+/// decided by the signs of their signed areas in f64. Segments that only touch or are collinear do not cross.
+pub fn do_segments_cross(a1: &Vector, b1: &Vector, a2: &Vector, b2: &Vector) -> bool {
+    let orientation = |a: &Vector, b: &Vector, c: &Vector| {
+        let (ab, ac) = (
+            (b.x as f64 - a.x as f64, b.y as f64 - a.y as f64),
+            (c.x as f64 - a.x as f64, c.y as f64 - a.y as f64),
+        );
+        let determinant = ab.0 * ac.1 - ab.1 * ac.0;
+        (determinant > 0.) as i32 - (determinant < 0.) as i32
+    };
+    orientation(a1, b1, a2) * orientation(a1, b1, b2) < 0
+        && orientation(a2, b2, a1) * orientation(a2, b2, b1) < 0
 }
 
-pub fn is_quadrilateral_convex(a: &Vector, b: &Vector, c: &Vector, d: &Vector) -> bool {
-    let abc = is_triangle_vertices_cw(a, b, c);
-    let abd = is_triangle_vertices_cw(a, b, d);
-    let bcd = is_triangle_vertices_cw(b, c, d);
-    let cad = is_triangle_vertices_cw(c, a, d);
+/// The point where the segment from `a1` to `b1` crosses the segment from `a2` to `b2`, computed in f64.
+pub fn segment_intersection(a1: Vector, b1: Vector, a2: Vector, b2: Vector) -> Vector {
+    let to_f64 = |point: Vector| (point.x as f64, point.y as f64);
+    let (a1, b1, a2, b2) = (to_f64(a1), to_f64(b1), to_f64(a2), to_f64(b2));
+    let cross = |u: (f64, f64), v: (f64, f64)| u.0 * v.1 - u.1 * v.0;
+    let direction_1 = (b1.0 - a1.0, b1.1 - a1.1);
+    let direction_2 = (b2.0 - a2.0, b2.1 - a2.1);
+    let t = cross((a2.0 - a1.0, a2.1 - a1.1), direction_2) / cross(direction_1, direction_2);
+    Vector::new(
+        (a1.0 + t * direction_1.0) as f32,
+        (a1.1 + t * direction_1.1) as f32,
+    )
+}
 
-    let mut is_convex = false;
+/// The closest point to `point` on the segment from `start` to `end`, computed in f64.
+/// A segment of length zero is its start point.
+fn closest_point_on_segment(start: Vector, end: Vector, point: Vector) -> Vector {
+    let (direction, offset) = (
+        (end.x as f64 - start.x as f64, end.y as f64 - start.y as f64),
+        (
+            point.x as f64 - start.x as f64,
+            point.y as f64 - start.y as f64,
+        ),
+    );
+    let length_squared = direction.0 * direction.0 + direction.1 * direction.1;
+    if length_squared == 0. {
+        return start;
+    }
+    let along = ((offset.0 * direction.0 + offset.1 * direction.1) / length_squared).clamp(0., 1.);
+    Vector::new(
+        (start.x as f64 + along * direction.0) as f32,
+        (start.y as f64 + along * direction.1) as f32,
+    )
+}
 
-    if abc && abd && bcd && !cad {
-        is_convex = true;
-    } else if abc && abd && !bcd && cad {
-        is_convex = true;
-    } else if abc && !abd && bcd && cad {
-        is_convex = true;
-    } else if !abc && !abd && !bcd && cad {
-        is_convex = true;
-    } else if !abc && !abd && bcd && !cad {
-        is_convex = true;
-    } else if !abc && abd && !bcd && !cad {
-        is_convex = true;
+/// The smallest distance between the segment from `a1` to `b1` and the segment from `a2` to `b2`,
+/// with the closest point on the first and on the second segment.
+///
+/// Crossing segments have the distance zero at their intersection point.
+/// Otherwise the closest pair always includes an endpoint, which also covers parallel segments,
+/// where the pair is not unique, and segments of length zero, which are points.
+/// ```
+/// use constrained_denaulay_triangulation::{distance_segment_segment, Vector};
+///
+/// let (distance, on_first, on_second) = distance_segment_segment(
+///     Vector::new(0., 0.),
+///     Vector::new(4., 0.),
+///     Vector::new(1., 3.),
+///     Vector::new(2., 1.),
+/// );
+/// assert_eq!(distance, 1.);
+/// assert_eq!(on_first, Vector::new(2., 0.));
+/// assert_eq!(on_second, Vector::new(2., 1.));
+/// ```
+pub fn distance_segment_segment(
+    a1: Vector,
+    b1: Vector,
+    a2: Vector,
+    b2: Vector,
+) -> (f32, Vector, Vector) {
+    if do_segments_cross(&a1, &b1, &a2, &b2) {
+        let intersection = segment_intersection(a1, b1, a2, b2);
+        return (0., intersection, intersection);
     }
+    let distance = |p: Vector, q: Vector| (p.x as f64 - q.x as f64).hypot(p.y as f64 - q.y as f64);
+    let candidates = [
+        (a1, closest_point_on_segment(a2, b2, a1)),
+        (b1, closest_point_on_segment(a2, b2, b1)),
+        (closest_point_on_segment(a1, b1, a2), a2),
+        (closest_point_on_segment(a1, b1, b2), b2),
+    ];
+    let mut closest = (f64::INFINITY, a1, a2);
+    for (on_first, on_second) in candidates {
+        let candidate_distance = distance(on_first, on_second);
+        if candidate_distance < closest.0 {
+            closest = (candidate_distance, on_first, on_second);
+        }
+    }
+    (closest.0 as f32, closest.1, closest.2)
+}
 
-    is_convex
+/// Whether the point is strictly inside of the circle that has the segment from `a` to `b` as its diameter,
+/// which is where the segment is seen at an obtuse angle. Points on the circle are outside.
+pub fn is_point_inside_diametral_circle(a: &Vector, b: &Vector, point: &Vector) -> bool {
+    let (to_a, to_b) = (
+        (a.x as f64 - point.x as f64, a.y as f64 - point.y as f64),
+        (b.x as f64 - point.x as f64, b.y as f64 - point.y as f64),
+    );
+    to_a.0 * to_b.0 + to_a.1 * to_b.1 < 0.
+}
+
+/// Checks whether the quadrilateral with the corners `a`, `b`, `c` and `d`, in this order around it in either direction,
+/// is convex, so that its diagonal from `a` to `c` can be swapped for the one from `b` to `d`.
+///
+/// The sides are compared by the signs of their signed areas in f64, with a tolerance relative to their lengths.
+/// A straight angle at `b` or `d` still counts as convex, as the swapped diagonal splits the quadrilateral
+/// into two proper triangles. A straight angle at `a` or `c`, coinciding corners or all corners on a line do not.
+/// ```
+/// use constrained_denaulay_triangulation::{is_quadrilateral_convex, Vector};
+///
+/// let [a, b, c, d] = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)].map(|p| Vector::new(p.0, p.1));
+/// assert!(is_quadrilateral_convex(&a, &b, &c, &d));
+/// // the corners out of order cross each other
+/// assert!(!is_quadrilateral_convex(&a, &c, &b, &d));
+/// // b on the diagonal from a to c
+/// assert!(is_quadrilateral_convex(&a, &Vector::new(0.5, 0.5), &c, &d));
+/// ```
+pub fn is_quadrilateral_convex(a: &Vector, b: &Vector, c: &Vector, d: &Vector) -> bool {
+    // the new diagonal has to separate a and c, while b and d may not be on the same side of the old one
+    let a_side = side_of_line(b, d, a);
+    a_side != 0
+        && side_of_line(b, d, c) == -a_side
+        && side_of_line(a, c, b) * side_of_line(a, c, d) <= 0
+}
+
+/// On which side of the line from `start` through `end` the point is, `1` to the left, `-1` to the right
+/// and `0` if the sine of its angle at `start` is within [`COLLINEAR_TOLERANCE`].
+fn side_of_line(start: &Vector, end: &Vector, point: &Vector) -> i32 {
+    let line = (end.x as f64 - start.x as f64, end.y as f64 - start.y as f64);
+    let to_point = (
+        point.x as f64 - start.x as f64,
+        point.y as f64 - start.y as f64,
+    );
+    let doubled_area = line.0 * to_point.1 - line.1 * to_point.0;
+    let lengths = line.0.hypot(line.1) * to_point.0.hypot(to_point.1);
+    if doubled_area.abs() <= COLLINEAR_TOLERANCE * lengths {
+        0
+    } else {
+        doubled_area.signum() as i32
+    }
+}
+
+/// Whether the point is inside of the counter-clockwise triangle or on one of its edges,
+/// decided with the same orientation test as the walks through the triangulation.
+///
+/// Unlike [`Triangle::contains`], the winding matters: a clockwise triangle only contains points on its edges.
+/// A point with a NaN or infinite coordinate is in no triangle.
+/// ```
+/// use constrained_denaulay_triangulation::{is_point_inside_triangle, Triangle, Vector};
+///
+/// let [a, b, c] = [(0., 0.), (2., 0.), (0., 2.)].map(|p| Vector::new(p.0, p.1));
+/// assert!(is_point_inside_triangle(&Triangle::new(a, b, c), &Vector::new(0.5, 0.5)));
+/// // a point on an edge is inside of both triangles next to the edge
+/// assert!(is_point_inside_triangle(&Triangle::new(a, b, c), &Vector::new(1., 1.)));
+/// assert!(!is_point_inside_triangle(&Triangle::new(a, c, b), &Vector::new(0.5, 0.5)));
+/// assert!(!is_point_inside_triangle(&Triangle::new(a, b, c), &Vector::new(f32::NAN, 0.5)));
+/// ```
+pub fn is_point_inside_triangle(triangle: &Triangle, point: &Vector) -> bool {
+    // every comparison with NaN is false, so a NaN point would be on the inner side of every edge
+    point.x.is_finite()
+        && point.y.is_finite()
+        && (0..3).all(|edge| {
+            !is_point_to_the_right_of_edge(&triangle.p(edge), &triangle.p((edge + 1) % 3), point)
+        })
 }
 
 /// Calculates the area of a triangle, according to its 3 vertices.
@@ -243,3 +404,249 @@ pub fn is_quadrilateral_convex(a: &Vector, b: &Vector, c: &Vector, d: &Vector) -
 pub fn calculate_triangle_area(triangle: &Triangle) -> f32 {
     (triangle.p(1) - triangle.p(0)).cross_product(triangle.p(2) - triangle.p(0)) * 0.5
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        distance_segment_segment, do_segments_cross, is_point_inside_circumcircle,
+        is_point_inside_diametral_circle, is_point_strictly_inside_circumcircle,
+        is_point_to_the_right_of_edge, is_quadrilateral_convex, predicate_fallbacks,
+    };
+    use crate::{Triangle, Vector};
+
+    fn points<const N: usize>(points: [(f32, f32); N]) -> [Vector; N] {
+        points.map(|p| Vector::new(p.0, p.1))
+    }
+
+    #[test]
+    fn convex_quadrilaterals_in_both_directions() {
+        let [a, b, c, d] = points([(0., 0.), (2., -1.), (3., 2.), (-1., 1.)]);
+        assert!(is_quadrilateral_convex(&a, &b, &c, &d));
+        assert!(is_quadrilateral_convex(&d, &c, &b, &a));
+        assert!(is_quadrilateral_convex(&b, &c, &d, &a));
+        // crossing sides
+        assert!(!is_quadrilateral_convex(&a, &c, &b, &d));
+    }
+
+    #[test]
+    fn every_reflex_corner_makes_a_quadrilateral_concave() {
+        // the dart has its reflex corner first, every rotation moves it to the next corner
+        let dart = points([(1., 1.), (0., 3.), (4., 0.), (0., -3.)]);
+        for reflex_corner in 0..4 {
+            let [a, b, c, d] = [0, 1, 2, 3].map(|i| dart[(i + 4 - reflex_corner) % 4]);
+            assert!(!is_quadrilateral_convex(&a, &b, &c, &d), "{reflex_corner}");
+            assert!(!is_quadrilateral_convex(&d, &c, &b, &a), "{reflex_corner}");
+        }
+    }
+
+    #[test]
+    fn straight_angles_are_only_convex_beside_the_diagonal() {
+        // b is on the diagonal from a to c, but a and c are still on both sides of the one from b to d
+        let [a, b, c, d] = points([(0., 0.), (1., 1.), (2., 2.), (0., 2.)]);
+        assert!(is_quadrilateral_convex(&a, &b, &c, &d));
+        assert!(is_quadrilateral_convex(&c, &d, &a, &b));
+        // swapping would put the diagonal through b
+        assert!(!is_quadrilateral_convex(&b, &c, &d, &a));
+        assert!(!is_quadrilateral_convex(&d, &a, &b, &c));
+        // rounding a straight angle to either side does not matter
+        let almost_straight = Vector::new(1.0000001, 0.9999999);
+        assert!(!is_quadrilateral_convex(&almost_straight, &c, &d, &a));
+    }
+
+    #[test]
+    fn degenerate_quadrilaterals_are_not_convex() {
+        let line = points([(0., 0.), (1., 2.), (2., 4.), (3., 6.)]);
+        for start in 0..4 {
+            let [a, b, c, d] = [0, 1, 2, 3].map(|i| line[(start + i) % 4]);
+            assert!(!is_quadrilateral_convex(&a, &b, &c, &d));
+        }
+        let [a, b, c] = points([(0., 0.), (1., 0.), (1., 1.)]);
+        assert!(!is_quadrilateral_convex(&a, &b, &c, &c));
+        assert!(!is_quadrilateral_convex(&a, &a, &c, &c));
+    }
+
+    #[test]
+    fn touching_segments_do_not_cross() {
+        let [a, b, c, d] = points([(0., 0.), (2., 2.), (0., 2.), (2., 0.)]);
+        assert!(do_segments_cross(&a, &b, &c, &d));
+        assert!(!do_segments_cross(&a, &b, &a, &c));
+        assert!(!do_segments_cross(&a, &b, &Vector::new(1., 1.), &d));
+        assert!(!do_segments_cross(&a, &c, &b, &d));
+        // collinear overlapping segments
+        assert!(!do_segments_cross(
+            &a,
+            &b,
+            &Vector::new(1., 1.),
+            &Vector::new(3., 3.)
+        ));
+    }
+
+    #[test]
+    fn ambiguous_orientations_are_decided_in_f64() {
+        let (orientation_fallbacks, _) = predicate_fallbacks();
+        // f32 rounds both of these to the wrong side of the tolerance
+        let a = Vector::new(0.5767532, 0.0067846775);
+        let b = Vector::new(0.097773194, 0.9946749);
+        assert!(!is_point_to_the_right_of_edge(
+            &a,
+            &b,
+            &Vector::new(0.41320068, 0.3441097)
+        ));
+        let a = Vector::new(0.07103425, 0.086769044);
+        let b = Vector::new(0.60758543, 0.6040089);
+        assert!(is_point_to_the_right_of_edge(
+            &a,
+            &b,
+            &Vector::new(0.4464612, 0.44868377)
+        ));
+        assert_eq!(predicate_fallbacks().0, orientation_fallbacks + 2);
+
+        // clear cases stay on the fast path
+        assert!(is_point_to_the_right_of_edge(&a, &b, &Vector::new(1., 0.)));
+        assert_eq!(predicate_fallbacks().0, orientation_fallbacks + 2);
+    }
+
+    #[test]
+    fn points_next_to_a_short_edge_are_not_on_it() {
+        // an edge inside of a dense cluster, and a point a tenth of its length to the right of it
+        let a = Vector::new(0.5, 0.5);
+        let b = Vector::new(0.5001, 0.5);
+        let point = Vector::new(0.50005, 0.49999);
+        assert!(is_point_to_the_right_of_edge(&a, &b, &point));
+        assert!(!is_point_to_the_right_of_edge(&b, &a, &point));
+        // closer than the tolerance to a long edge is still on it
+        let [c, d] = points([(0., 0.), (1., 0.)]);
+        assert!(!is_point_to_the_right_of_edge(
+            &c,
+            &d,
+            &Vector::new(0.5, -1e-9)
+        ));
+    }
+
+    #[test]
+    fn co_circular_points_are_on_the_circumcircle() {
+        let (_, circumcircle_fallbacks) = predicate_fallbacks();
+        let triangle = Triangle::new(
+            Vector::new(1., 0.),
+            Vector::new(0., 1.),
+            Vector::new(-1., 0.),
+        );
+        let on_circle = Vector::new(0., -1.);
+        assert!(is_point_inside_circumcircle(triangle, on_circle));
+        assert!(!is_point_strictly_inside_circumcircle(triangle, on_circle));
+        assert_eq!(predicate_fallbacks().1, circumcircle_fallbacks + 2);
+    }
+
+    #[test]
+    fn large_coordinates_fall_back_to_f64_instead_of_overflowing() {
+        // near f32::MAX.sqrt(), the squared distances fit into f32, but the products of the determinant do not
+        let scale = 2f32.powi(63);
+        let scaled = |x: f32, y: f32| Vector::new(x * scale, y * scale);
+        let triangle = Triangle::new(scaled(0., 0.), scaled(1., 0.), scaled(0., 1.));
+        let (_, circumcircle_fallbacks) = predicate_fallbacks();
+
+        assert!(is_point_strictly_inside_circumcircle(
+            triangle,
+            scaled(0.9, 0.9)
+        ));
+        assert!(!is_point_inside_circumcircle(triangle, scaled(-0.5, -0.5)));
+        assert!(!is_point_inside_circumcircle(triangle, scaled(2., 0.5)));
+        let on_circle = scaled(1., 1.);
+        assert!(is_point_inside_circumcircle(triangle, on_circle));
+        assert!(!is_point_strictly_inside_circumcircle(triangle, on_circle));
+        assert_eq!(predicate_fallbacks().1, circumcircle_fallbacks + 5);
+
+        // infinite coordinates are on every circle
+        let infinite = Vector::new(f32::INFINITY, 0.);
+        assert!(is_point_inside_circumcircle(triangle, infinite));
+        assert!(!is_point_strictly_inside_circumcircle(triangle, infinite));
+    }
+
+    #[test]
+    fn only_obtuse_angles_are_inside_the_diametral_circle() {
+        let [a, b] = points([(0., 0.), (2., 0.)]);
+        assert!(is_point_inside_diametral_circle(
+            &a,
+            &b,
+            &Vector::new(1., 0.5)
+        ));
+        assert!(is_point_inside_diametral_circle(
+            &a,
+            &b,
+            &Vector::new(1., 0.)
+        ));
+        // a right angle is on the circle
+        assert!(!is_point_inside_diametral_circle(
+            &a,
+            &b,
+            &Vector::new(1., 1.)
+        ));
+        assert!(!is_point_inside_diametral_circle(
+            &a,
+            &b,
+            &Vector::new(1., -1.5)
+        ));
+        assert!(!is_point_inside_diametral_circle(&a, &b, &a));
+    }
+
+    #[test]
+    fn distance_between_crossing_and_separate_segments() {
+        let [a1, b1, a2, b2] = points([(0., 0.), (4., 4.), (0., 4.), (4., 0.)]);
+        assert_eq!(
+            distance_segment_segment(a1, b1, a2, b2),
+            (0., Vector::new(2., 2.), Vector::new(2., 2.))
+        );
+        // the end of the second segment touches the inside of the first one
+        let [a2, b2] = points([(3., -2.), (1., 1.)]);
+        assert_eq!(
+            distance_segment_segment(a1, b1, a2, b2),
+            (0., Vector::new(1., 1.), Vector::new(1., 1.))
+        );
+        // the closest points are both endpoints, the triangle of 3, 4 and 5
+        let [a2, b2] = points([(7., 8.), (9., 8.)]);
+        assert_eq!(
+            distance_segment_segment(a1, b1, a2, b2),
+            (5., Vector::new(4., 4.), Vector::new(7., 8.))
+        );
+        // the end of the first segment is closest to the inside of the second one
+        let [a2, b2] = points([(6., 0.), (6., 9.)]);
+        assert_eq!(
+            distance_segment_segment(a1, b1, a2, b2),
+            (2., Vector::new(4., 4.), Vector::new(6., 4.))
+        );
+    }
+
+    #[test]
+    fn distance_between_parallel_and_degenerate_segments() {
+        let [a1, b1] = points([(0., 0.), (4., 0.)]);
+        let (distance, on_first, on_second) =
+            distance_segment_segment(a1, b1, Vector::new(1., 3.), Vector::new(6., 3.));
+        assert_eq!(distance, 3.);
+        assert_eq!(on_second - on_first, Vector::new(0., 3.));
+        // collinear, with a gap between them
+        assert_eq!(
+            distance_segment_segment(a1, b1, Vector::new(6., 0.), Vector::new(9., 0.)).0,
+            2.
+        );
+        // collinear and overlapping
+        assert_eq!(
+            distance_segment_segment(a1, b1, Vector::new(3., 0.), Vector::new(9., 0.)).0,
+            0.
+        );
+        // a point above the inside of the segment, on it and next to another point
+        let point = Vector::new(1., 2.);
+        assert_eq!(
+            distance_segment_segment(a1, b1, point, point),
+            (2., Vector::new(1., 0.), point)
+        );
+        let point = Vector::new(3., 0.);
+        assert_eq!(
+            distance_segment_segment(point, point, a1, b1),
+            (0., point, point)
+        );
+        assert_eq!(
+            distance_segment_segment(a1, a1, Vector::new(-3., 4.), Vector::new(-3., 4.)).0,
+            5.
+        );
+    }
+}
@@ -1,97 +1,244 @@
 use crate::data_structures::vector::Vector;
 
+/// The rectangle around the input points, which is mapped onto the unit square for the triangulation.
+///
+/// With a [`TriangulateOptions::pre_transform`](crate::TriangulateOptions::pre_transform) the points are transformed
+/// first, and the rectangle is around the transformed points.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Bounds {
     min: Vector,
     max: Vector,
+    transform: Option<[f32; 4]>,
 }
 
-/// Takes vectors and normalizes them, either using their own bounds or the given bounds. Also outputs their original minimal x and y vector as a value and their maximum x and y vector. 
-/// ```
-/// 
-pub fn normalize_points(points: &mut Vec<Vector>, bounds: Option<Bounds>) -> (Vec<Vector>, Bounds) {
-    let bounds = if let Some(bounds) = bounds {
+impl Bounds {
+    /// The bounds from the smallest to the biggest coordinates.
+    pub(crate) fn new(min: Vector, max: Vector) -> Self {
+        Bounds {
+            min,
+            max,
+            transform: None,
+        }
+    }
+
+    /// The bounds around the points after the transform.
+    pub(crate) fn around(points: &[Vector], transform: Option<[f32; 4]>) -> Self {
+        let mut bounds = Bounds {
+            min: Vector::new(f32::MAX, f32::MAX),
+            max: Vector::new(f32::MIN, f32::MIN),
+            transform,
+        };
+        for point in points {
+            let point = bounds.transform(*point);
+            bounds.min = bounds.min.min(point);
+            bounds.max = bounds.max.max(point);
+        }
         bounds
-    } else {
-        let mut min = Vector::new(f32::MAX, f32::MAX);
-        let mut max = Vector::new(f32::MIN, f32::MIN);
+    }
 
-        for i in 0..points.len() {
-            if points[i].x > max.x {
-                max.x = points[i].x;
-            }
+    /// The bounds around the rectangle from `min` to `max` after the transform,
+    /// which contain every transformed point of the rectangle.
+    pub(crate) fn around_rectangle(min: Vector, max: Vector, transform: Option<[f32; 4]>) -> Self {
+        let corners = [
+            min,
+            Vector::new(max.x, min.y),
+            max,
+            Vector::new(min.x, max.y),
+        ];
+        Self::around(&corners, transform)
+    }
 
-            if points[i].y > max.y {
-                max.y = points[i].y;
-            }
+    /// The smallest x and y coordinate, after the transform.
+    pub fn min(&self) -> Vector {
+        self.min
+    }
 
-            if points[i].x < min.x {
-                min.x = points[i].x;
-            }
+    /// The biggest x and y coordinate, after the transform.
+    pub fn max(&self) -> Vector {
+        self.max
+    }
+
+    /// Applies the transform the points are normalized in, see
+    /// [`TriangulateOptions::pre_transform`](crate::TriangulateOptions::pre_transform).
+    pub(crate) fn transform(&self, point: Vector) -> Vector {
+        match self.transform {
+            Some([a, b, c, d]) => Vector::new(a * point.x + b * point.y, c * point.x + d * point.y),
+            None => point,
+        }
+    }
 
-            if points[i].y < min.y {
-                min.y = points[i].y;
+    /// Undoes [`Bounds::transform`], with the inverse matrix computed in f64 so that the round trip stays close.
+    fn untransform(&self, point: Vector) -> Vector {
+        match self.transform {
+            Some(matrix) => {
+                let [a, b, c, d] = matrix.map(f64::from);
+                let determinant = a * d - b * c;
+                let (x, y) = (point.x as f64, point.y as f64);
+                Vector::new(
+                    ((d * x - b * y) / determinant) as f32,
+                    ((a * y - c * x) / determinant) as f32,
+                )
             }
+            None => point,
         }
-        Bounds { min, max }
-    };
+    }
+
+    /// Whether the point is inside of the bounds or on their border.
+    pub(crate) fn contains(&self, point: Vector) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The size of the bounds that is mapped onto 1, per axis.
+    /// An axis without extent, where all points have the same coordinate, keeps its scale instead of dividing by zero.
+    fn scale(&self) -> Vector {
+        let size = self.max - self.min;
+        let safe = |extent: f32| if extent > 0. { extent } else { 1. };
+        Vector::new(safe(size.x), safe(size.y))
+    }
+
+    /// Converts a point in the coordinates of the input into the normalized space.
+    pub fn normalize(&self, point: Vector) -> Vector {
+        (self.transform(point) - self.min) / self.scale()
+    }
+
+    /// Converts a normalized point back into the coordinates of the input.
+    pub fn denormalize(&self, point: Vector) -> Vector {
+        self.untransform(point * self.scale() + self.min)
+    }
+
+    /// Converts an area given in the units of the input points into the normalized space.
+    pub fn normalize_area(&self, area: f32) -> f32 {
+        let scale = self.scale();
+        let determinant = self.transform.map_or(1., |[a, b, c, d]| a * d - b * c);
+        area * determinant.abs() / (scale.x * scale.y)
+    }
+}
+
+/// Takes vectors and normalizes them, either using their own bounds or the given bounds. Also outputs their original minimal x and y vector as a value and their maximum x and y vector.
+pub fn normalize_points(points: &[Vector], bounds: Option<Bounds>) -> (Vec<Vector>, Bounds) {
+    let bounds = bounds.unwrap_or_else(|| Bounds::around(points, None));
 
     let points = points
         .iter()
-        .map(|point| (*point - bounds.min) / (bounds.max - bounds.min))
+        .map(|point| bounds.normalize(*point))
         .collect::<Vec<_>>();
     (points, bounds)
 }
 
-pub fn denormalize_points(input_points: &mut Vec<Vector>, bounds: &Bounds)->Vec<Vector>{
-    input_points.iter().map(|point| (*point * (bounds.max - bounds.min) + bounds.min)).collect()
+pub fn denormalize_points(input_points: &[Vector], bounds: &Bounds) -> Vec<Vector> {
+    input_points
+        .iter()
+        .map(|point| bounds.denormalize(*point))
+        .collect()
 }
 
 #[test]
-fn normalize_points_without_bounds(){
-    let mut input_points = Vec::new();
-    input_points.push(Vector::new(-0., 5.0)); 
-    input_points.push(Vector::new(-5., 0.)); 
-    input_points.push(Vector::new(5., -5.)); 
-    let output = normalize_points(&mut input_points, None);
-
-    let expected_bounds = Bounds{min: Vector::new(-5., -5.), max:Vector::new(5.,5.)};
-    let mut expected_points= Vec::new();
-    expected_points.push(Vector::new(0.5, 1.)); 
-    expected_points.push(Vector::new(0., 0.5)); 
-    expected_points.push(Vector::new(1., 0.)); 
+fn normalize_points_without_bounds() {
+    let input_points = vec![
+        Vector::new(-0., 5.0),
+        Vector::new(-5., 0.),
+        Vector::new(5., -5.),
+    ];
+    let output = normalize_points(&input_points, None);
+
+    let expected_bounds = Bounds {
+        min: Vector::new(-5., -5.),
+        max: Vector::new(5., 5.),
+        transform: None,
+    };
+    let expected_points = vec![
+        Vector::new(0.5, 1.),
+        Vector::new(0., 0.5),
+        Vector::new(1., 0.),
+    ];
     assert_eq!(output, (expected_points, expected_bounds));
 }
 
 #[test]
-fn normalize_points_with_given_bounds(){
-    let bounds = Bounds{min: Vector::new(-10., -10.), max:Vector::new(10.,10.)};
-    let mut input_points = Vec::new();
-    input_points.push(Vector::new(-0., 5.0)); 
-    input_points.push(Vector::new(-5., 0.)); 
-    input_points.push(Vector::new(5., -5.)); 
-    let output = normalize_points(&mut input_points, Some(bounds));
-
-    let expected_bounds = Bounds{min: Vector::new(-10., -10.), max:Vector::new(10.,10.)};
-    let mut expected_points= Vec::new();
-    expected_points.push(Vector::new(0.5, 0.75)); 
-    expected_points.push(Vector::new(0.25, 0.5)); 
-    expected_points.push(Vector::new(0.75, 0.25)); 
+fn normalize_points_with_given_bounds() {
+    let bounds = Bounds {
+        min: Vector::new(-10., -10.),
+        max: Vector::new(10., 10.),
+        transform: None,
+    };
+    let input_points = vec![
+        Vector::new(-0., 5.0),
+        Vector::new(-5., 0.),
+        Vector::new(5., -5.),
+    ];
+    let output = normalize_points(&input_points, Some(bounds));
+
+    let expected_bounds = Bounds {
+        min: Vector::new(-10., -10.),
+        max: Vector::new(10., 10.),
+        transform: None,
+    };
+    let expected_points = vec![
+        Vector::new(0.5, 0.75),
+        Vector::new(0.25, 0.5),
+        Vector::new(0.75, 0.25),
+    ];
     assert_eq!(output, (expected_points, expected_bounds));
 }
 
 #[test]
-fn denormalize_points_with_given_bounds(){
-    let mut input_points= Vec::new();
-    input_points.push(Vector::new(0.5, 0.75)); 
-    input_points.push(Vector::new(0.25, 0.5)); 
-    input_points.push(Vector::new(0.75, 0.25)); 
-    let input_bounds = Bounds{min: Vector::new(-10., -10.), max:Vector::new(10.,10.)};
-
-    let mut expected_points = Vec::new();
-    expected_points.push(Vector::new(-0., 5.0)); 
-    expected_points.push(Vector::new(-5., 0.)); 
-    expected_points.push(Vector::new(5., -5.)); 
-    let output = denormalize_points(&mut input_points, &input_bounds);
+fn denormalize_points_with_given_bounds() {
+    let input_points = vec![
+        Vector::new(0.5, 0.75),
+        Vector::new(0.25, 0.5),
+        Vector::new(0.75, 0.25),
+    ];
+    let input_bounds = Bounds {
+        min: Vector::new(-10., -10.),
+        max: Vector::new(10., 10.),
+        transform: None,
+    };
+
+    let expected_points = vec![
+        Vector::new(-0., 5.0),
+        Vector::new(-5., 0.),
+        Vector::new(5., -5.),
+    ];
+    let output = denormalize_points(&input_points, &input_bounds);
     assert_eq!(output, expected_points);
-}
\ No newline at end of file
+}
+
+/// Normalizes the points with their own bounds and checks that every coordinate is finite and inside of `[0, 1]`.
+#[cfg(test)]
+fn assert_normalized_into_unit_square(input_points: &[Vector]) {
+    let (points, bounds) = normalize_points(input_points, None);
+    for (point, input_point) in points.iter().zip(input_points) {
+        for coordinate in [point.x, point.y] {
+            assert!((0. ..=1.).contains(&coordinate), "{point:?} is outside");
+        }
+        let error = bounds.denormalize(*point) - *input_point;
+        assert!(error.x.abs() < 1e-4 && error.y.abs() < 1e-4);
+    }
+}
+
+#[test]
+fn normalize_square_wide_and_tall_bounds() {
+    let square = [(0., 0.), (4., 4.), (1., 3.)];
+    let wide = [(-100., 0.), (100., 1.), (20., 0.5)];
+    let tall = [(0., -100.), (1., 100.), (0.5, 20.)];
+    for points in [square, wide, tall] {
+        assert_normalized_into_unit_square(&points.iter().map(Vector::from).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn normalize_bounds_without_extent_on_one_axis() {
+    let horizontal = [(-3., 2.), (5., 2.), (1., 2.)];
+    let vertical = [(2., -3.), (2., 5.), (2., 1.)];
+    for points in [horizontal, vertical] {
+        assert_normalized_into_unit_square(&points.iter().map(Vector::from).collect::<Vec<_>>());
+    }
+
+    let (_, bounds) = normalize_points(&[Vector::new(-3., 2.), Vector::new(5., 2.)], None);
+    assert_eq!(bounds.normalize_area(8.), 1.);
+    let off_the_line = Vector::new(1., 4.);
+    assert_eq!(
+        bounds.denormalize(bounds.normalize(off_the_line)),
+        off_the_line
+    );
+}
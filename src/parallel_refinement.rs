@@ -0,0 +1,322 @@
+//! Tesselation of independent regions of the mesh on the rayon thread pool.
+//!
+//! The refinable triangles are grouped into regions by the bin grid cell of their centroid.
+//! Every region is copied into a triangle set of its own and refined there, on its own thread.
+//! The edges between regions have no neighbor in the copies, so they are neither split nor swapped.
+//! Afterwards the regions are copied back one after another, the edges between them are swapped until they are
+//! delaunay again, and a last serial refinement splits the triangles those swaps made too big.
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    data_structures::{
+        error::CustomError,
+        ids::{TriangleId, VertexId},
+        point_bin_grid::PointBinGrid,
+        triangle_info::TriangleInfo,
+        triangle_set::TriangleSet,
+    },
+    math_utils::{add_predicate_fallbacks, predicate_fallbacks},
+    refinement::{refine_triangles, PointBudget, Refinement},
+    triangulation::legalize_triangles,
+};
+
+/// How many regions there are for every thread of the pool, so that threads with quick regions can take over others.
+const REGIONS_PER_THREAD: usize = 4;
+
+/// A region of the mesh after it was refined in its own triangle set.
+struct Region {
+    /// The triangle set of the region, its first triangles are copies of `triangles`.
+    local: TriangleSet,
+    /// The triangles of the mesh the region was copied from.
+    triangles: Vec<TriangleId>,
+    /// The vertices of the mesh the first points of `local` were copied from.
+    vertices: Vec<VertexId>,
+    /// The edges of the region without a neighbor in the region, from the first to the second vertex of a triangle
+    /// of the region, with the triangle on their other side.
+    outside_neighbors: Vec<((VertexId, VertexId), Option<TriangleId>)>,
+    /// The number of points the refinement of the region added.
+    inserted: usize,
+    /// The predicate fallbacks of the refinement, on the thread that refined the region.
+    fallbacks: (usize, usize),
+}
+
+/// Splits every refinable triangle that is bigger than the maximum area, like the serial tesselation,
+/// but refines the regions of the mesh on the rayon thread pool.
+///
+/// The budget has to be strict, because which points a lenient budget allows depends on the order of all insertions.
+pub fn tesselate_in_parallel(
+    triangle_set: &mut TriangleSet,
+    maximum_triangle_area: f32,
+    budget: PointBudget,
+    is_refinable: &dyn Fn(&TriangleSet, TriangleId) -> bool,
+) -> Result<Refinement, CustomError> {
+    let cells_per_side = ((REGIONS_PER_THREAD * rayon::current_num_threads()) as f32)
+        .sqrt()
+        .ceil() as usize;
+    let grid = PointBinGrid::new(cells_per_side);
+    let mut regions = vec![Vec::new(); cells_per_side * cells_per_side];
+    for triangle_index in (0..triangle_set.triangle_count()).map(TriangleId) {
+        if is_refinable(triangle_set, triangle_index) {
+            let triangle = triangle_set.get_triangle(triangle_index);
+            let centroid = (triangle.p(0) + triangle.p(1) + triangle.p(2)) / 3.;
+            regions[grid.cell_index_of(centroid)].push(triangle_index);
+        }
+    }
+
+    let fallbacks_before = predicate_fallbacks();
+    let shared: &TriangleSet = triangle_set;
+    let regions = regions
+        .into_par_iter()
+        .filter(|triangles| !triangles.is_empty())
+        .map(|triangles| refine_region(shared, triangles, maximum_triangle_area, budget))
+        .collect::<Result<Vec<Region>, CustomError>>()?;
+    // regions refined on this thread are already counted
+    let fallbacks_here = predicate_fallbacks();
+    let (orientation, circumcircle) = regions.iter().fold((0, 0), |sum, region| {
+        (sum.0 + region.fallbacks.0, sum.1 + region.fallbacks.1)
+    });
+    add_predicate_fallbacks(
+        orientation - (fallbacks_here.0 - fallbacks_before.0),
+        circumcircle - (fallbacks_here.1 - fallbacks_before.1),
+    );
+
+    let inserted: usize = regions.iter().map(|region| region.inserted).sum();
+    if inserted > budget.max_points {
+        return Err(CustomError::SteinerPointLimitExceeded(budget.max_points));
+    }
+    let seams = merge_regions(triangle_set, regions);
+    legalize_triangles(triangle_set, seams, &|_, _, _| true)?;
+
+    let candidates = (0..triangle_set.triangle_count()).map(TriangleId).collect();
+    let remaining_budget = PointBudget {
+        max_points: budget.max_points - inserted,
+        ..budget
+    };
+    let last_pass = refine_triangles(
+        triangle_set,
+        candidates,
+        maximum_triangle_area,
+        remaining_budget,
+        is_refinable,
+        &|_, _| true,
+    )?;
+    Ok(Refinement {
+        inserted: inserted + last_pass.inserted,
+        budget_exhausted: false,
+    })
+}
+
+/// Copies the triangles into a triangle set of their own and refines it.
+fn refine_region(
+    triangle_set: &TriangleSet,
+    triangles: Vec<TriangleId>,
+    maximum_triangle_area: f32,
+    budget: PointBudget,
+) -> Result<Region, CustomError> {
+    let fallbacks_before = predicate_fallbacks();
+    let local_triangles: HashMap<TriangleId, TriangleId> = triangles
+        .iter()
+        .enumerate()
+        .map(|(i, triangle_index)| (*triangle_index, TriangleId(i)))
+        .collect();
+    let mut local = TriangleSet::new(triangles.len());
+    let mut local_vertices: HashMap<VertexId, VertexId> = HashMap::new();
+    let mut vertices = Vec::new();
+    let mut outside_neighbors = Vec::new();
+    for triangle_index in &triangles {
+        let triangle_info = triangle_set.get_triangle_info(*triangle_index);
+        let mut local_info = TriangleInfo::new(triangle_info.vertex_indices.map(|vertex| {
+            *local_vertices.entry(vertex).or_insert_with(|| {
+                vertices.push(vertex);
+                local
+                    .points
+                    .push(triangle_set.get_point_from_vertex(vertex));
+                VertexId(local.points.len() - 1)
+            })
+        }));
+        for i in 0..3 {
+            let edge = (
+                triangle_info.vertex_indices[i],
+                triangle_info.vertex_indices[(i + 1) % 3],
+            );
+            let adjacent = triangle_info.adjacent_triangle_indices[i];
+            match adjacent.and_then(|adjacent| local_triangles.get(&adjacent)) {
+                Some(local_adjacent) => {
+                    local_info.adjacent_triangle_indices[i] = Some(*local_adjacent)
+                }
+                None => outside_neighbors.push((edge, adjacent)),
+            }
+            if triangle_set.is_constrained_edge(edge.0, edge.1) {
+                local.add_constrained_edge(
+                    local_info.vertex_indices[i],
+                    local_info.vertex_indices[(i + 1) % 3],
+                );
+            }
+        }
+        local.add_triangle_info(local_info);
+    }
+
+    let refinement = refine_triangles(
+        &mut local,
+        (0..triangles.len()).map(TriangleId).collect(),
+        maximum_triangle_area,
+        budget,
+        &|_, _| true,
+        &|_, _| true,
+    )?;
+    let fallbacks_after = predicate_fallbacks();
+    Ok(Region {
+        local,
+        triangles,
+        vertices,
+        outside_neighbors,
+        inserted: refinement.inserted,
+        fallbacks: (
+            fallbacks_after.0 - fallbacks_before.0,
+            fallbacks_after.1 - fallbacks_before.1,
+        ),
+    })
+}
+
+/// Copies the refined regions back into the mesh and connects them to each other and to the triangles around them.
+/// Returns the triangles along the borders of the regions.
+fn merge_regions(triangle_set: &mut TriangleSet, regions: Vec<Region>) -> Vec<TriangleId> {
+    // the triangle of the mesh and the edge index of every edge on the border of a region,
+    // in a vec as well, so that the order does not depend on the hashes
+    let mut border_edges: HashMap<(VertexId, VertexId), (TriangleId, usize)> = HashMap::new();
+    let mut border_edge_order = Vec::new();
+    let mut outside_neighbors = HashMap::new();
+    for region in regions {
+        let mut vertex_map = region.vertices;
+        for point in &region.local.points[vertex_map.len()..] {
+            triangle_set.points.push(*point);
+            vertex_map.push(VertexId(triangle_set.points.len() - 1));
+        }
+        let first_new_triangle = triangle_set.triangle_count();
+        let triangle_map = |local: TriangleId| match region.triangles.get(local.0) {
+            Some(triangle_index) => *triangle_index,
+            None => TriangleId(first_new_triangle + local.0 - region.triangles.len()),
+        };
+        for (i, local_info) in region.local.triangle_infos.iter().enumerate() {
+            let triangle_index = triangle_map(TriangleId(i));
+            let triangle_info = TriangleInfo {
+                vertex_indices: local_info.vertex_indices.map(|vertex| vertex_map[vertex.0]),
+                adjacent_triangle_indices: local_info
+                    .adjacent_triangle_indices
+                    .map(|adjacent| adjacent.map(triangle_map)),
+            };
+            for edge_index in 0..3 {
+                if triangle_info.adjacent_triangle_indices[edge_index].is_none() {
+                    let edge = (
+                        triangle_info.vertex_indices[edge_index],
+                        triangle_info.vertex_indices[(edge_index + 1) % 3],
+                    );
+                    border_edges.insert(edge, (triangle_index, edge_index));
+                    border_edge_order.push(edge);
+                }
+            }
+            if triangle_index.0 < first_new_triangle {
+                triangle_set.replace_triangle(triangle_index, &triangle_info);
+            } else {
+                triangle_set.add_triangle_info(triangle_info);
+            }
+        }
+        outside_neighbors.extend(region.outside_neighbors);
+    }
+
+    // the borders are neither split nor swapped, so every edge still has the same neighbor on its other side,
+    // which is another region or a triangle that was not refined
+    let mut seams = Vec::with_capacity(border_edge_order.len());
+    for (start, end) in border_edge_order {
+        let (triangle_index, edge_index) = border_edges[&(start, end)];
+        let neighbor = match border_edges.get(&(end, start)) {
+            Some((neighbor, _)) => Some(*neighbor),
+            None => {
+                let outside = outside_neighbors[&(start, end)];
+                if let Some(outside) = outside {
+                    let outside_info = triangle_set.get_triangle_info(outside);
+                    let back_edge = (0..3)
+                        .find(|&i| outside_info.vertex_indices[i] == end)
+                        .expect("the neighbor shares the edge");
+                    triangle_set.triangle_infos[outside.0].adjacent_triangle_indices[back_edge] =
+                        Some(triangle_index);
+                }
+                outside
+            }
+        };
+        triangle_set.triangle_infos[triangle_index.0].adjacent_triangle_indices[edge_index] =
+            neighbor;
+        seams.push(triangle_index);
+    }
+    seams
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        is_delaunay, is_manifold, math_utils::calculate_triangle_area, region_areas,
+        test_util::random_points, triangulate_with_options, CustomError, TriangulateOptions,
+        TriangulationResult, Vector,
+    };
+
+    /// Random points in the unit square, with its corners so that the normalization is a uniform scale.
+    fn points_in_unit_square(n: usize, seed: u64) -> Vec<Vector> {
+        let mut points = random_points(n, seed);
+        points.extend([Vector::new(0., 0.), Vector::new(1., 1.)]);
+        points
+    }
+
+    fn triangulate_on_threads(
+        threads: usize,
+        points: &[Vector],
+        options: &TriangulateOptions,
+    ) -> Result<TriangulationResult, CustomError> {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+            .install(|| triangulate_with_options(points, None, options))
+    }
+
+    #[test]
+    fn large_parallel_tesselation_keeps_the_invariants() -> Result<(), CustomError> {
+        let points = points_in_unit_square(500, 3);
+        let maximum_area = 0.00002;
+        let options = TriangulateOptions::new().with_maximum_triangle_area(maximum_area);
+        let result = triangulate_on_threads(8, &points, &options)?;
+
+        assert!(result.len() > 100_000);
+        assert!(is_manifold(&result));
+        assert!(is_delaunay(&result));
+        let triangles = result.to_triangles();
+        assert!(triangles
+            .iter()
+            .all(|triangle| calculate_triangle_area(triangle) <= maximum_area * 1.001));
+        let area: f32 = triangles.iter().map(calculate_triangle_area).sum();
+        let (hull_area, _) = region_areas(&points, &[]);
+        assert!((area - hull_area).abs() < hull_area * 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_tesselation_does_not_depend_on_the_threads() -> Result<(), CustomError> {
+        let points = points_in_unit_square(100, 4);
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.001);
+
+        let parallel = triangulate_on_threads(8, &points, &options)?;
+        assert_eq!(
+            triangulate_on_threads(8, &points, &options)?.triangles,
+            parallel.triangles
+        );
+        let deterministic = options.with_deterministic(true);
+        let single_thread = triangulate_on_threads(1, &points, &deterministic)?;
+        assert_eq!(
+            triangulate_on_threads(8, &points, &deterministic)?.triangles,
+            single_thread.triangles
+        );
+        assert!(is_delaunay(&parallel) && is_delaunay(&single_thread));
+        Ok(())
+    }
+}
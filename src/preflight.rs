@@ -0,0 +1,151 @@
+use crate::{
+    data_structures::{error::CustomError, vector::Vector},
+    hole_creation::validate_holes,
+    queries::convex_hull,
+};
+
+/// Runs the cheap checks of the input before a triangulation and returns the first problem it finds.
+///
+/// The checks run in this order: every coordinate of the points and the holes is finite, there are at least
+/// three points, they are not all at the same position, they are not all on one line,
+/// the holes pass [`validate_holes`], and every vertex of a hole is inside of the convex hull of the points.
+/// They take `O(n log n)` for the hull and `O(m²)` per hole for its self-intersections.
+/// Passing them does not guarantee that the triangulation succeeds, e.g. holes may still overlap each other.
+/// Every triangulation runs the first two checks itself, as they only take `O(n)`.
+/// ```
+/// use constrained_denaulay_triangulation::{preflight, CustomError, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// assert!(preflight(&square, None).is_ok());
+/// let line = vec![Vector::new(0., 0.), Vector::new(1., 1.), Vector::new(2., 2.)];
+/// assert!(matches!(preflight(&line, None), Err(CustomError::CollinearPoints)));
+/// ```
+pub fn preflight(points: &[Vector], holes: Option<&[Vec<Vector>]>) -> Result<(), CustomError> {
+    let holes = holes.unwrap_or_default();
+    check_finite(points.iter().chain(holes.iter().flatten()))?;
+    check_point_count(points.len())?;
+    let first = points[0];
+    let Some(second) = points.iter().find(|point| **point != first) else {
+        return Err(CustomError::CoincidentPoints);
+    };
+    // products of f32 differences are exact in f64, so the sign is exact as well
+    let orientation = |point: &Vector| {
+        let (ab, ap) = (*second - first, *point - first);
+        ab.x as f64 * ap.y as f64 - ab.y as f64 * ap.x as f64
+    };
+    if points.iter().all(|point| orientation(point) == 0.) {
+        return Err(CustomError::CollinearPoints);
+    }
+
+    if let Err(problems) = validate_holes(holes) {
+        return Err(CustomError::InvalidHole(problems[0].clone()));
+    }
+    let hull = convex_hull(points);
+    let is_inside_of_hull = |point: &Vector| {
+        (0..hull.len()).all(|i| {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            (b - a).cross_product(*point - a) >= 0.
+        })
+    };
+    match holes
+        .iter()
+        .position(|hole| !hole.iter().all(is_inside_of_hull))
+    {
+        Some(hole) => Err(CustomError::HoleOutsideOfPoints(hole)),
+        None => Ok(()),
+    }
+}
+
+/// The first point with a coordinate that is NaN or infinite, which would break the normalization.
+/// Every triangulation runs this check, see [`preflight`].
+pub(crate) fn check_finite<'a>(
+    points: impl IntoIterator<Item = &'a Vector>,
+) -> Result<(), CustomError> {
+    match points
+        .into_iter()
+        .find(|point| !point.x.is_finite() || !point.y.is_finite())
+    {
+        Some(point) => Err(CustomError::NonFinitePoint(*point)),
+        None => Ok(()),
+    }
+}
+
+/// Fewer than three points have no triangle. Every triangulation runs this check, see [`preflight`].
+pub(crate) fn check_point_count(points_len: usize) -> Result<(), CustomError> {
+    if points_len < 3 {
+        Err(CustomError::TooFewPoints(points_len))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::preflight;
+    use crate::{CustomError, HoleProblem, Vector};
+
+    fn vectors(points: &[(f32, f32)]) -> Vec<Vector> {
+        points.iter().map(Vector::from).collect()
+    }
+
+    fn square() -> Vec<Vector> {
+        vectors(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)])
+    }
+
+    #[test]
+    fn valid_points_and_holes_pass() {
+        let hole = vectors(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)]);
+        // a hole vertex on the hull is still inside
+        let touching_hole = vectors(&[(8., 0.), (9., 2.), (8., 2.)]);
+        assert!(preflight(&square(), None).is_ok());
+        assert!(preflight(&square(), Some(&[hole, touching_hole])).is_ok());
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut with_nan = square();
+        with_nan[2].y = f32::NAN;
+        assert!(matches!(
+            preflight(&with_nan, None),
+            Err(CustomError::NonFinitePoint(point)) if point.x == 10.
+        ));
+        let infinite_hole = vectors(&[(4., 4.), (f32::INFINITY, 4.), (6., 6.)]);
+        assert!(matches!(
+            preflight(&square(), Some(&[infinite_hole])),
+            Err(CustomError::NonFinitePoint(_))
+        ));
+
+        assert!(matches!(
+            preflight(&square()[..2], None),
+            Err(CustomError::TooFewPoints(2))
+        ));
+        assert!(matches!(
+            preflight(&vectors(&[(1., 2.); 5]), None),
+            Err(CustomError::CoincidentPoints)
+        ));
+        assert!(matches!(
+            preflight(&vectors(&[(0., 1.), (0., 1.), (3., 7.), (1.5, 4.)]), None),
+            Err(CustomError::CollinearPoints)
+        ));
+
+        let clockwise_hole = vectors(&[(4., 4.), (4., 6.), (6., 6.), (6., 4.)]);
+        assert!(matches!(
+            preflight(&square(), Some(&[clockwise_hole])),
+            Err(CustomError::InvalidHole(HoleProblem::NotCounterClockwise {
+                hole: 0
+            }))
+        ));
+
+        let hole = vectors(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)]);
+        let outside_hole = vectors(&[(8., 8.), (12., 8.), (8., 9.)]);
+        assert!(matches!(
+            preflight(&square(), Some(&[hole, outside_hole])),
+            Err(CustomError::HoleOutsideOfPoints(1))
+        ));
+    }
+}
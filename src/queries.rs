@@ -0,0 +1,2178 @@
+use std::{
+    collections::{HashMap, HashSet},
+    f32::consts::FRAC_PI_2,
+};
+
+use crate::{
+    data_structures::{
+        constraint_trace::ConstraintSource,
+        ids::TriangleId,
+        triangle::Triangle,
+        triangulation_result::TriangulationResult,
+        vector::Vector,
+        vertex_marker::VertexMarker,
+        vertex_ring::{walk_vertex_ring, VertexRing},
+    },
+    math_utils::{
+        calculate_triangle_area, do_segments_intersect, is_point_inside_diametral_circle,
+        is_point_inside_triangle, is_point_to_the_right_of_edge, is_quadrilateral_convex,
+    },
+    normalize::normalize_points,
+};
+
+/// A quadrilateral made of two adjacent triangles, or a triangle that could not be paired.
+///
+/// The vertices are output indices into [`TriangulationResult::vertices`], sorted counter-clockwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuadOrTri {
+    /// Two triangles merged across their shared edge.
+    Quad([usize; 4]),
+    /// A triangle without a partner.
+    Tri([usize; 3]),
+}
+
+/// Where a point is relative to the mesh, see [`classify_point`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointClass {
+    /// Inside of the triangle with this output index.
+    Inside(usize),
+    /// Inside of the hole with this index in [`TriangulationResult::hole_reports`].
+    InHole(usize),
+    /// On the outline of the mesh or of a hole, on the edge between these two output vertices, the smaller one first.
+    OnBoundary((usize, usize)),
+    /// Neither in the mesh nor in one of its holes.
+    Outside,
+}
+
+/// One triangle that a segment passes through.
+///
+/// Edge `i` of a triangle is the edge from its vertex `i` to its vertex `i + 1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SegmentPathStep {
+    /// The output index of the triangle.
+    pub triangle: usize,
+    /// The edge through which the segment enters the triangle, `None` for the triangle containing the start.
+    pub entry_edge: Option<usize>,
+    /// The edge through which the segment leaves the triangle, `None` for the triangle containing the end.
+    pub exit_edge: Option<usize>,
+}
+
+/// Walks along the straight segment from `a` to `b` and returns every triangle it passes through, in order.
+///
+/// The walk begins in the triangle containing `a` and ends in the triangle containing `b`.
+/// If the segment leaves the mesh, through its outline or a hole, the walk ends there and the last step has an `exit_edge`
+/// without a neighbor. If `a` is not inside of the mesh, the path is empty.
+/// ```
+/// use constrained_denaulay_triangulation::{segment_path, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let path = segment_path(&result, Vector::new(0.1, 0.5), Vector::new(0.9, 0.5));
+/// // the segment crosses the diagonal of the square
+/// assert_eq!(path.len(), 2);
+/// assert_eq!(path[0].exit_edge.is_some(), path[1].entry_edge.is_some());
+/// ```
+pub fn segment_path(result: &TriangulationResult, a: Vector, b: Vector) -> Vec<SegmentPathStep> {
+    let mut path = Vec::new();
+    let Some(mut triangle) = locate(result, a) else {
+        return path;
+    };
+    let mut entry_edge = None;
+
+    // every triangle can only be passed once by a straight segment
+    while path.len() < result.len() {
+        let points = result.triangles[triangle].map(|vertex| result.vertices[vertex]);
+        let exit_edge = (0..3)
+            .filter(|edge| Some(*edge) != entry_edge)
+            .find(|&edge| {
+                let (start, end) = (points[edge], points[(edge + 1) % 3]);
+                // b is behind the edge and the edge lies across the line from a to b
+                is_point_to_the_right_of_edge(&start, &end, &b)
+                    && !is_point_to_the_right_of_edge(&b, &a, &start)
+                    && !is_point_to_the_right_of_edge(&a, &b, &end)
+            });
+        path.push(SegmentPathStep {
+            triangle,
+            entry_edge,
+            exit_edge,
+        });
+
+        let Some(exit_edge) = exit_edge else {
+            break;
+        };
+        let Some(next_triangle) = result.adjacency[triangle][exit_edge] else {
+            break;
+        };
+        // the shared edge runs the other way around in the next triangle
+        let exit_end = result.triangles[triangle][(exit_edge + 1) % 3];
+        entry_edge = result.triangles[next_triangle]
+            .iter()
+            .position(|vertex| *vertex == exit_end);
+        triangle = next_triangle;
+    }
+    path
+}
+
+/// Every edge of the result once, as its two end points and whether it is a constrained edge, like the outline of a hole.
+///
+/// The edges are in the order of the triangles, which runs counter-clockwise around each triangle.
+/// ```
+/// use constrained_denaulay_triangulation::{tagged_edges, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let edges = tagged_edges(&result);
+/// // the outline and the diagonal, without constraints
+/// assert_eq!(edges.len(), 5);
+/// assert!(edges.iter().all(|(_, _, is_constrained)| !is_constrained));
+/// ```
+pub fn tagged_edges(result: &TriangulationResult) -> Vec<(Vector, Vector, bool)> {
+    let mut edges = Vec::new();
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        for edge in 0..3 {
+            // shared edges are taken from the triangle with the lower index
+            if result.adjacency[index][edge].is_some_and(|adjacent| adjacent < index) {
+                continue;
+            }
+            let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+            let is_constrained = result
+                .triangle_set
+                .is_constrained_edge(result.vertex_ids[start], result.vertex_ids[end]);
+            edges.push((result.vertices[start], result.vertices[end], is_constrained));
+        }
+    }
+    edges
+}
+
+/// Every edge of the result as its two output vertices, the smaller one first, mapped to the triangles on both sides.
+///
+/// The first triangle is the one with the lower index. Edges on the outline of the mesh or of a hole have no second triangle.
+/// ```
+/// use constrained_denaulay_triangulation::{edge_faces, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let faces = edge_faces(&result);
+/// assert_eq!(faces.len(), 5);
+/// assert_eq!(faces.values().filter(|(_, second)| second.is_some()).count(), 1);
+/// ```
+pub fn edge_faces(result: &TriangulationResult) -> HashMap<(usize, usize), (usize, Option<usize>)> {
+    let mut faces = HashMap::with_capacity(result.len() * 2);
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        for edge in 0..3 {
+            let adjacent = result.adjacency[index][edge];
+            // shared edges are taken from the triangle with the lower index
+            if adjacent.is_some_and(|adjacent| adjacent < index) {
+                continue;
+            }
+            let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+            faces.insert((start.min(end), start.max(end)), (index, adjacent));
+        }
+    }
+    faces
+}
+
+/// Every edge of the result once, as its two output vertices with the smaller one first.
+///
+/// Without holes, constraints or tesselation these are the edges of the Delaunay triangulation of the input points.
+/// ```
+/// use constrained_denaulay_triangulation::{edges_iter, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(edges_iter(&result).count(), 5);
+/// ```
+pub fn edges_iter(result: &TriangulationResult) -> impl Iterator<Item = (usize, usize)> + '_ {
+    result
+        .triangles
+        .iter()
+        .enumerate()
+        .flat_map(move |(index, triangle)| {
+            (0..3).filter_map(move |edge| {
+                // shared edges are taken from the triangle with the lower index
+                if result.adjacency[index][edge].is_some_and(|adjacent| adjacent < index) {
+                    return None;
+                }
+                let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+                Some((start.min(end), start.max(end)))
+            })
+        })
+}
+
+/// The edges of the Gabriel graph, in the order of [`edges_iter`]: the edges whose diametral circle,
+/// the circle with the edge as its diameter, has no vertex of the triangles beside the edge strictly inside of it.
+///
+/// For a Delaunay triangulation those two vertices are the only ones that can be inside of the circle.
+/// ```
+/// use constrained_denaulay_triangulation::{gabriel_graph, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+///     Vector::new(0.5, 0.5),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// // the sides of the square are seen from the center at a right angle, which keeps them
+/// assert_eq!(gabriel_graph(&result).len(), 8);
+/// ```
+pub fn gabriel_graph(result: &TriangulationResult) -> Vec<(usize, usize)> {
+    let opposite = |triangle: usize, start: usize, end: usize| {
+        result.triangles[triangle]
+            .into_iter()
+            .find(|vertex| *vertex != start && *vertex != end)
+            .expect("a triangle has a third vertex")
+    };
+    let faces = edge_faces(result);
+    edges_iter(result)
+        .filter(|(start, end)| {
+            let (first, second) = faces[&(*start, *end)];
+            let (a, b) = (result.vertices[*start], result.vertices[*end]);
+            std::iter::once(first).chain(second).all(|triangle| {
+                let point = result.vertices[opposite(triangle, *start, *end)];
+                !is_point_inside_diametral_circle(&a, &b, &point)
+            })
+        })
+        .collect()
+}
+
+/// The edges of the minimum spanning tree of the mesh, the shortest edges that connect all of its vertices,
+/// as output vertices with the smaller one first, from the shortest to the longest edge.
+///
+/// The Euclidean minimum spanning tree of a point set is part of its Delaunay triangulation, so without holes,
+/// constraints or tesselation this is the Euclidean minimum spanning tree of the input points.
+/// A mesh made of several separate parts gets a tree for every part.
+/// ```
+/// use constrained_denaulay_triangulation::{euclidean_mst, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let points: Vec<Vector> = [(0., 0.), (1., 0.), (3., 0.5), (1., 2.)].iter().map(Vector::from).collect();
+/// let result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+/// let tree = euclidean_mst(&result);
+/// assert_eq!(tree.len(), result.vertices.len() - 1);
+/// ```
+pub fn euclidean_mst(result: &TriangulationResult) -> Vec<(usize, usize)> {
+    let length = |(start, end): (usize, usize)| {
+        let offset = result.vertices[end] - result.vertices[start];
+        offset.x.hypot(offset.y)
+    };
+    let mut edges: Vec<(usize, usize)> = edges_iter(result).collect();
+    edges.sort_by(|a, b| length(*a).total_cmp(&length(*b)));
+
+    // Kruskal's algorithm: an edge is part of the tree if it connects two trees of the shorter edges
+    let mut parents: Vec<usize> = (0..result.vertices.len()).collect();
+    let root = |mut vertex: usize, parents: &mut [usize]| {
+        while parents[vertex] != vertex {
+            parents[vertex] = parents[parents[vertex]];
+            vertex = parents[vertex];
+        }
+        vertex
+    };
+    edges
+        .into_iter()
+        .filter(|(start, end)| {
+            let (start_root, end_root) = (root(*start, &mut parents), root(*end, &mut parents));
+            parents[start_root] = end_root;
+            start_root != end_root
+        })
+        .collect()
+}
+
+/// Like [`locate`], but walks from the triangle `hint` towards the point, which is fast if the point is close to it.
+///
+/// Passing the previous result as the next hint makes locating a sequence of nearby points cheap.
+/// An invalid hint starts the walk anywhere. Whatever the hint, the answer is the same as the one of [`locate`],
+/// also for points on edges and vertices.
+/// ```
+/// use constrained_denaulay_triangulation::{locate, locate_from, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(10., 0.),
+///     Vector::new(10., 10.),
+///     Vector::new(0., 10.),
+/// ];
+/// let options = TriangulateOptions::new().with_maximum_triangle_area(1.);
+/// let result = triangulate_with_options(&square, None, &options).unwrap();
+/// let mut hint = 0;
+/// for x in 0..100 {
+///     let point = Vector::new(x as f32 * 0.1 + 0.05, 5.03);
+///     hint = locate_from(&result, point, hint).unwrap();
+/// }
+/// assert_eq!(locate(&result, Vector::new(9.95, 5.03)), Some(hint));
+/// assert_eq!(locate_from(&result, Vector::new(11., 5.), hint), None);
+/// ```
+pub fn locate_from(result: &TriangulationResult, point: Vector, hint: usize) -> Option<usize> {
+    let start = result
+        .triangle_ids
+        .get(hint)
+        .copied()
+        .unwrap_or(TriangleId(0));
+    let (normalized_point, _) = normalize_points(&[point], Some(result.bounds));
+    // the walk may pass removed triangles, they still cover the holes and the space around the mesh
+    match result
+        .triangle_set
+        .find_triangle_that_contains_point(normalized_point[0], start)
+    {
+        Ok(triangle) => match result.triangle_ids.binary_search(&triangle) {
+            Ok(index) => lowest_triangle_containing(result, index, point),
+            // a point on the rim of a hole may end the walk in the removed triangle behind the rim
+            Err(_)
+                if is_point_strictly_inside(
+                    &result.triangle_set.get_triangle(triangle),
+                    &normalized_point[0],
+                ) =>
+            {
+                None
+            }
+            Err(_) => locate(result, point),
+        },
+        Err(_) => locate(result, point),
+    }
+}
+
+/// The boundary of the result as closed loops of vertices, first the outer loops counter-clockwise, then the loops
+/// around the holes clockwise.
+///
+/// The mesh is always on the left of a loop. The first vertex is not repeated at the end.
+/// A vertex where the boundary touches itself shows up in every loop passing through it.
+/// ```
+/// use constrained_denaulay_triangulation::{boundary_loops, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let loops = boundary_loops(&result);
+/// assert_eq!(loops.len(), 1);
+/// assert_eq!(loops[0].len(), 4);
+/// ```
+pub fn boundary_loops(result: &TriangulationResult) -> Vec<Vec<Vector>> {
+    // the triangles are counter-clockwise, so their edges without a neighbor already run around the mesh the right way
+    let mut next_vertices: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut starts = Vec::new();
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        for edge in 0..3 {
+            if result.adjacency[index][edge].is_none() {
+                let start = triangle[edge];
+                next_vertices
+                    .entry(start)
+                    .or_default()
+                    .push(triangle[(edge + 1) % 3]);
+                starts.push(start);
+            }
+        }
+    }
+
+    let mut loops = Vec::new();
+    for first in starts {
+        let mut boundary_loop = Vec::new();
+        let mut vertex = first;
+        while let Some(next) = next_vertices.get_mut(&vertex).and_then(Vec::pop) {
+            boundary_loop.push(result.vertices[vertex]);
+            vertex = next;
+        }
+        if !boundary_loop.is_empty() {
+            loops.push(boundary_loop);
+        }
+    }
+    // the sort is stable, so the loops keep the order of their first triangle otherwise
+    loops.sort_by_key(|boundary_loop| polygon_area(boundary_loop) < 0.);
+    loops
+}
+
+/// How many times the [`boundary_loops`] of the result wind around the point, counter-clockwise positive.
+///
+/// The outer loops run counter-clockwise and the loops around the holes clockwise, so the number is `1` inside
+/// of the mesh and `0` in a hole or outside of it, also where the outline is concave. Unlike counting the crossings
+/// of a ray, only the side of an edge the point is on matters, which is decided with the same orientation test as
+/// the walks through the triangulation. For points on the boundary the number is not defined,
+/// [`classify_point`] tells them apart.
+/// ```
+/// use constrained_denaulay_triangulation::{winding_number, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(winding_number(&result, Vector::new(0.5, 0.5)), 1);
+/// assert_eq!(winding_number(&result, Vector::new(2., 0.5)), 0);
+/// ```
+pub fn winding_number(result: &TriangulationResult, point: Vector) -> i32 {
+    boundary_loops(result)
+        .iter()
+        .map(|boundary_loop| loop_winding_number(boundary_loop, point))
+        .sum()
+}
+
+/// The winding number of a single closed loop around the point, from the edges that cross its horizontal line
+/// upwards with the point on the left and downwards with the point on the right.
+fn loop_winding_number(boundary_loop: &[Vector], point: Vector) -> i32 {
+    let mut winding = 0;
+    for (index, a) in boundary_loop.iter().enumerate() {
+        let b = &boundary_loop[(index + 1) % boundary_loop.len()];
+        if a.y <= point.y {
+            if b.y > point.y && is_point_to_the_right_of_edge(b, a, &point) {
+                winding += 1;
+            }
+        } else if b.y <= point.y && is_point_to_the_right_of_edge(a, b, &point) {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// The output indices of the triangles with an edge on the outline of the hole with this index in the input,
+/// in the order of [`TriangulationResult::triangles`].
+///
+/// The outline is made up of the [`TriangulationResult::constrained_edges`] that came from the edges of the hole.
+/// A triangle that borders several holes is part of each of their lists.
+/// If the hole was filled again, the triangles inside of it border its outline as well.
+/// ```
+/// use constrained_denaulay_triangulation::{triangles_bordering_hole, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square: Vec<Vector> = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].iter().map(Vector::from).collect();
+/// let hole: Vec<Vector> = [(1., 1.), (3., 1.), (2., 3.)].iter().map(Vector::from).collect();
+/// let result = triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new()).unwrap();
+/// // one triangle on every side of the hole
+/// assert_eq!(triangles_bordering_hole(&result, 0).len(), 3);
+/// assert!(triangles_bordering_hole(&result, 1).is_empty());
+/// ```
+pub fn triangles_bordering_hole(result: &TriangulationResult, hole_index: usize) -> Vec<usize> {
+    let hole_edges: HashSet<(usize, usize)> = result
+        .constrained_edges
+        .iter()
+        .filter(|trace| {
+            matches!(trace.source, ConstraintSource::HoleEdge { hole, .. } if hole == hole_index)
+        })
+        .flat_map(|trace| {
+            trace
+                .vertices
+                .windows(2)
+                .map(|edge| (edge[0].min(edge[1]), edge[0].max(edge[1])))
+        })
+        .collect();
+    result
+        .triangles
+        .iter()
+        .enumerate()
+        .filter(|(_, triangle)| {
+            (0..3).any(|edge| {
+                let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+                hole_edges.contains(&(start.min(end), start.max(end)))
+            })
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The outline of every input hole as a closed loop of output vertices, in the order of the input holes.
+///
+/// The loops are put together from the [`TriangulationResult::constrained_edges`] of the edges of each hole,
+/// so they run counter-clockwise like the hole polygons and contain the vertices that split their edges.
+/// The first vertex is not repeated at the end. Edges that collapsed to a single vertex are left out,
+/// and a hole that was rejected or filled gets an empty loop.
+/// ```
+/// use constrained_denaulay_triangulation::{hole_boundaries, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// // the last point lies on the first edge of the hole
+/// let points: Vec<Vector> = [(0., 0.), (4., 0.), (4., 4.), (0., 4.), (2., 1.)].iter().map(Vector::from).collect();
+/// let hole: Vec<Vector> = [(1., 1.), (3., 1.), (2., 3.)].iter().map(Vector::from).collect();
+/// let result = triangulate_with_options(&points, Some(&[hole]), &TriangulateOptions::new()).unwrap();
+/// let boundaries = hole_boundaries(&result);
+/// assert_eq!(boundaries.len(), 1);
+/// let outline: Vec<Vector> = boundaries[0].iter().map(|vertex| result.vertices[*vertex]).collect();
+/// assert_eq!(outline[..2], [Vector::new(1., 1.), Vector::new(2., 1.)]);
+/// assert_eq!(outline.len(), 4);
+/// ```
+pub fn hole_boundaries(result: &TriangulationResult) -> Vec<Vec<usize>> {
+    let mut hole_edges: Vec<(usize, usize, &[usize])> = result
+        .constrained_edges
+        .iter()
+        .filter_map(|trace| match trace.source {
+            ConstraintSource::HoleEdge { hole, edge } => Some((hole, edge, &trace.vertices[..])),
+            _ => None,
+        })
+        .collect();
+    hole_edges.sort_by_key(|(hole, edge, _)| (*hole, *edge));
+    let mut boundaries = vec![Vec::new(); result.hole_reports.len()];
+    for (hole, _, vertices) in hole_edges {
+        // the end of every edge is the start of the next one
+        if let (Some(boundary), Some((_, start_vertices))) =
+            (boundaries.get_mut(hole), vertices.split_last())
+        {
+            boundary.extend_from_slice(start_vertices);
+        }
+    }
+    boundaries
+}
+
+/// The output indices of the triangles that have at least one point in common with the polygon, sorted ascending.
+///
+/// The polygon is closed from its last vertex back to the first one and may have any winding.
+/// Triangles that only touch its outline count as well.
+/// The search starts at the triangles that contain a vertex of the polygon or that have a boundary edge touching it,
+/// and spreads to the neighbors of every triangle it keeps, so only the triangles around the polygon are tested.
+/// ```
+/// use constrained_denaulay_triangulation::{triangles_in_polygon_query, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square: Vec<Vector> = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].iter().map(Vector::from).collect();
+/// let options = TriangulateOptions::new().with_maximum_triangle_area(0.5);
+/// let result = triangulate_with_options(&square, None, &options).unwrap();
+/// let window: Vec<Vector> = [(1., 1.), (2., 1.), (2., 2.), (1., 2.)].iter().map(Vector::from).collect();
+/// let triangles = triangles_in_polygon_query(&result, &window);
+/// assert!(!triangles.is_empty() && triangles.len() < result.len());
+/// // a polygon around the whole mesh contains every triangle
+/// let around: Vec<Vector> = [(-1., -1.), (5., -1.), (5., 5.), (-1., 5.)].iter().map(Vector::from).collect();
+/// assert_eq!(triangles_in_polygon_query(&result, &around).len(), result.len());
+/// ```
+pub fn triangles_in_polygon_query(result: &TriangulationResult, polygon: &[Vector]) -> Vec<usize> {
+    if polygon.is_empty() {
+        return Vec::new();
+    }
+    let intersects =
+        |index: usize| does_triangle_intersect_polygon(&result.triangle(index), polygon);
+    let mut seeds = Vec::new();
+    let mut hint = None;
+    for vertex in polygon {
+        let triangle = match hint {
+            Some(hint) => locate_from(result, *vertex, hint),
+            None => locate(result, *vertex),
+        };
+        hint = triangle.or(hint);
+        seeds.extend(triangle);
+    }
+    // parts of the polygon without one of its vertices reach into the mesh over the outline or a hole rim
+    seeds.extend(
+        (0..result.len())
+            .filter(|index| result.adjacency[*index].contains(&None) && intersects(*index)),
+    );
+
+    let mut is_visited = vec![false; result.len()];
+    let mut is_found = vec![false; result.len()];
+    while let Some(index) = seeds.pop() {
+        if is_visited[index] {
+            continue;
+        }
+        is_visited[index] = true;
+        if intersects(index) {
+            is_found[index] = true;
+            seeds.extend(result.adjacency[index].iter().flatten());
+        }
+    }
+    (0..result.len()).filter(|index| is_found[*index]).collect()
+}
+
+/// Whether the triangle and the closed polygon have at least one point in common:
+/// their outlines touch, or one of them has a vertex inside of the other.
+fn does_triangle_intersect_polygon(triangle: &Triangle, polygon: &[Vector]) -> bool {
+    let polygon_edges = (0..polygon.len()).map(|i| (polygon[i], polygon[(i + 1) % polygon.len()]));
+    polygon_edges.clone().any(|(a, b)| {
+        (0..3).any(|edge| do_segments_intersect(triangle.p(edge), triangle.p((edge + 1) % 3), a, b))
+    }) || triangle.contains(polygon[0])
+        || is_point_inside_polygon(triangle.p(0), polygon_edges)
+}
+
+/// Whether the point is inside of the polygon with these edges, by counting the crossings of a ray to the right.
+fn is_point_inside_polygon(point: Vector, edges: impl Iterator<Item = (Vector, Vector)>) -> bool {
+    edges
+        .filter(|(a, b)| {
+            (a.y > point.y) != (b.y > point.y)
+                && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+/// A marker for every output vertex, in the order of [`TriangulationResult::vertices`].
+///
+/// The vertices of hole polygons that were not filled again are [`VertexMarker::HoleBoundary`],
+/// even if a surrounding hole removed all triangles around them.
+/// The other vertices on an edge without a neighbor are [`VertexMarker::Hull`].
+/// ```
+/// use constrained_denaulay_triangulation::{vertex_markers, triangulate_with_options, TriangulateOptions, Vector, VertexMarker};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(4., 0.),
+///     Vector::new(4., 4.),
+///     Vector::new(0., 4.),
+///     Vector::new(2., 2.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let markers = vertex_markers(&result);
+/// let center = result.vertices.iter().position(|vertex| *vertex == Vector::new(2., 2.)).unwrap();
+/// assert_eq!(markers[center], VertexMarker::Interior);
+/// assert_eq!(markers.iter().filter(|marker| **marker == VertexMarker::Hull).count(), 4);
+/// ```
+pub fn vertex_markers(result: &TriangulationResult) -> Vec<VertexMarker> {
+    let mut markers = vec![VertexMarker::Interior; result.vertices.len()];
+    for (triangle, adjacent) in result.triangles.iter().zip(&result.adjacency) {
+        for edge in 0..3 {
+            if adjacent[edge].is_none() {
+                markers[triangle[edge]] = VertexMarker::Hull;
+                markers[triangle[(edge + 1) % 3]] = VertexMarker::Hull;
+            }
+        }
+    }
+    // the holes are marked backwards, so the first hole through a vertex is the one that stays
+    for (hole_id, hole_report) in result.hole_reports.iter().enumerate().rev() {
+        if hole_report.filled {
+            continue;
+        }
+        for vertex in &hole_report.vertices {
+            markers[*vertex] = VertexMarker::HoleBoundary(hole_id);
+        }
+    }
+    markers
+}
+
+/// The neighbors of the output vertex in counter-clockwise order, `None` if no triangle of the result uses it.
+///
+/// Only the triangles of the result count, so the vertices on the outline and on the rims of the holes
+/// have open rings, which start and end at their two boundary edges.
+/// ```
+/// use constrained_denaulay_triangulation::{vertex_ring, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let points: Vec<Vector> = [(0., 0.), (2., 0.), (2., 2.), (0., 2.), (1., 1.)].iter().map(Vector::from).collect();
+/// let result = triangulate_with_options(&points, None, &TriangulateOptions::new()).unwrap();
+/// let center = result.vertices.iter().position(|vertex| *vertex == Vector::new(1., 1.)).unwrap();
+/// let ring = vertex_ring(&result, center).unwrap();
+/// assert!(ring.is_closed);
+/// assert_eq!(ring.degree(), 4);
+/// let corner = result.vertices.iter().position(|vertex| *vertex == Vector::new(0., 0.)).unwrap();
+/// assert!(!vertex_ring(&result, corner).unwrap().is_closed);
+/// ```
+pub fn vertex_ring(result: &TriangulationResult, vertex: usize) -> Option<VertexRing<usize>> {
+    let start = result
+        .triangles
+        .iter()
+        .position(|triangle| triangle.contains(&vertex))?;
+    Some(walk_vertex_ring(
+        vertex,
+        start,
+        |triangle| result.triangles[triangle],
+        |triangle, edge| result.adjacency[triangle][edge],
+        result.len(),
+    ))
+}
+
+/// The circumcircle of every triangle of the result as center and radius, in the order of the triangles.
+/// Degenerate triangles, that have no circumcircle, are skipped.
+/// ```
+/// use constrained_denaulay_triangulation::{circumcircles, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(2., 0.),
+///     Vector::new(2., 2.),
+///     Vector::new(0., 2.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// for (center, radius) in circumcircles(&result) {
+///     assert!((center.x - 1.).abs() < 1e-5 && (center.y - 1.).abs() < 1e-5);
+///     assert!((radius - 2f32.sqrt()).abs() < 1e-5);
+/// }
+/// ```
+pub fn circumcircles(result: &TriangulationResult) -> Vec<(Vector, f32)> {
+    result
+        .to_triangles()
+        .iter()
+        .filter_map(|triangle| Some((triangle.circumcenter()?, triangle.circumradius()?)))
+        .collect()
+}
+
+/// The biggest circle with its center inside of the mesh that contains no vertex and does not cross the boundary,
+/// as center and radius, like the pole of inaccessibility for placing a label.
+///
+/// The center of such a circle is a vertex of the voronoi diagram, which is a circumcenter, or where a voronoi edge
+/// crosses the boundary. Both kinds of candidates are searched: the circumcenters inside of the mesh and the midpoints
+/// of the boundary edges. The radius of a candidate is limited by the nearest vertex and the nearest boundary edge.
+/// An empty result gives a circle of radius zero at the origin.
+/// ```
+/// use constrained_denaulay_triangulation::{largest_empty_circle, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(4., 0.),
+///     Vector::new(4., 4.),
+///     Vector::new(0., 4.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let (center, radius) = largest_empty_circle(&result);
+/// assert!((center.x - 2.).abs() < 1e-5 && (center.y - 2.).abs() < 1e-5);
+/// assert!((radius - 2.).abs() < 1e-5);
+/// ```
+pub fn largest_empty_circle(result: &TriangulationResult) -> (Vector, f32) {
+    let boundary_edges: Vec<(Vector, Vector)> = boundary_loops(result)
+        .iter()
+        .flat_map(|boundary_loop| {
+            (0..boundary_loop.len()).map(|i| {
+                (
+                    boundary_loop[i],
+                    boundary_loop[(i + 1) % boundary_loop.len()],
+                )
+            })
+        })
+        .collect();
+    let distance_to_boundary = |point: Vector| {
+        boundary_edges
+            .iter()
+            .map(|(start, end)| distance_to_segment(point, *start, *end))
+            .fold(f32::INFINITY, f32::min)
+    };
+
+    // the circumcircle of a delaunay triangle contains no vertex, so its radius is the distance to the nearest one
+    let circumcenters =
+        result
+            .to_triangles()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, triangle)| {
+                let center = triangle.circumcenter()?;
+                locate_from(result, center, index)?;
+                Some((center, triangle.circumradius()?))
+            });
+    // a midpoint touches the boundary already, it only matters if there is no circumcenter inside of the mesh
+    let midpoints = boundary_edges
+        .iter()
+        .map(|(start, end)| ((*start + *end) / 2., 0.));
+    circumcenters
+        .map(|(center, radius)| (center, radius.min(distance_to_boundary(center))))
+        .chain(midpoints)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or_default()
+}
+
+/// The distance from the point to the closest point of the segment from `start` to `end`.
+fn distance_to_segment(point: Vector, start: Vector, end: Vector) -> f32 {
+    let direction = end - start;
+    let length_squared = direction.x * direction.x + direction.y * direction.y;
+    let offset = point - start;
+    let along = if length_squared > 0. {
+        ((offset.x * direction.x + offset.y * direction.y) / length_squared).clamp(0., 1.)
+    } else {
+        0.
+    };
+    let closest = start + direction * along;
+    let difference = point - closest;
+    (difference.x * difference.x + difference.y * difference.y).sqrt()
+}
+
+/// The indices of all triangles whose smallest angle is below `min_angle_degrees`.
+///
+/// Such slivers are bad for interpolation and simulation, refining the mesh may get rid of them.
+/// ```
+/// use constrained_denaulay_triangulation::{slivers, Triangle, Vector};
+///
+/// let triangles = [
+///     Triangle::new(Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(0.5, 0.9)),
+///     Triangle::new(Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(0.5, 0.01)),
+/// ];
+/// assert_eq!(slivers(&triangles, 10.), vec![1]);
+/// ```
+pub fn slivers(result: &[Triangle], min_angle_degrees: f32) -> Vec<usize> {
+    let min_angle = min_angle_degrees.to_radians();
+    result
+        .iter()
+        .enumerate()
+        .filter(|(_, triangle)| triangle.min_angle() < min_angle)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Reverses every clockwise triangle, so that all of them face the same way, and returns the indices of those triangles.
+///
+/// The triangulation only creates counter-clockwise triangles, a non-empty result means that something went wrong
+/// and should be reported. Triangles without area are left as they are.
+/// ```
+/// use constrained_denaulay_triangulation::{repair_winding, Triangle, Vector};
+///
+/// let mut triangles = vec![
+///     Triangle::new(Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(0., 1.)),
+///     Triangle::new(Vector::new(0., 0.), Vector::new(0., 1.), Vector::new(1., 0.)),
+/// ];
+/// assert_eq!(repair_winding(&mut triangles), vec![1]);
+/// assert_eq!(triangles[1].p(1), Vector::new(1., 0.));
+/// ```
+pub fn repair_winding(triangles: &mut [Triangle]) -> Vec<usize> {
+    let mut repaired = Vec::new();
+    for (index, triangle) in triangles.iter_mut().enumerate() {
+        if calculate_triangle_area(triangle) < 0. {
+            *triangle = Triangle::new(triangle.p(0), triangle.p(2), triangle.p(1));
+            repaired.push(index);
+        }
+    }
+    repaired
+}
+
+/// Merges pairs of adjacent triangles into quadrilaterals, where their union is convex and roughly rectangular.
+///
+/// Every pair of triangles sharing an edge that is not constrained is a candidate, if all four angles of its union
+/// are at most `max_angle_deviation` degrees away from 90°. The candidates are matched greedily, the ones whose
+/// largest deviation is smallest first. The returned quads and leftover triangles cover the same area as the result,
+/// in the order of their first triangle.
+/// ```
+/// use constrained_denaulay_triangulation::{pair_quads, triangulate_with_options, QuadOrTri, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let pieces = pair_quads(&result, 10.);
+/// assert!(matches!(pieces[..], [QuadOrTri::Quad(_)]));
+/// ```
+pub fn pair_quads(result: &TriangulationResult, max_angle_deviation: f32) -> Vec<QuadOrTri> {
+    let max_angle_deviation = max_angle_deviation.to_radians();
+    let mut candidates = Vec::new();
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        for edge in 0..3 {
+            let Some(adjacent) = result.adjacency[index][edge] else {
+                continue;
+            };
+            let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+            if adjacent < index
+                || result
+                    .triangle_set
+                    .is_constrained_edge(result.vertex_ids[start], result.vertex_ids[end])
+            {
+                continue;
+            }
+            let Some(opposite) = result.triangles[adjacent]
+                .iter()
+                .copied()
+                .find(|vertex| *vertex != start && *vertex != end)
+            else {
+                continue;
+            };
+            // the adjacent triangle is to the right of the shared edge
+            let quad = [start, opposite, end, triangle[(edge + 2) % 3]];
+            let corners = quad.map(|vertex| result.vertices[vertex]);
+            if !is_quadrilateral_convex(&corners[0], &corners[1], &corners[2], &corners[3]) {
+                continue;
+            }
+            let deviation = (0..4)
+                .map(|corner| {
+                    let previous = corners[(corner + 3) % 4] - corners[corner];
+                    let next = corners[(corner + 1) % 4] - corners[corner];
+                    (next.angle_between(previous).abs() - FRAC_PI_2).abs()
+                })
+                .fold(0., f32::max);
+            if deviation <= max_angle_deviation {
+                candidates.push((deviation, index, adjacent, quad));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut quads = vec![None; result.len()];
+    let mut is_paired = vec![false; result.len()];
+    for (_, index, adjacent, quad) in candidates {
+        if !is_paired[index] && !is_paired[adjacent] {
+            is_paired[index] = true;
+            is_paired[adjacent] = true;
+            quads[index.min(adjacent)] = Some(quad);
+        }
+    }
+    (0..result.len())
+        .filter_map(|index| match quads[index] {
+            Some(quad) => Some(QuadOrTri::Quad(quad)),
+            None if !is_paired[index] => Some(QuadOrTri::Tri(result.triangles[index])),
+            None => None,
+        })
+        .collect()
+}
+
+/// The area covered by the outer region of the input and the area of each hole, from the shoelace formula.
+///
+/// The triangulation covers the convex hull of the points, so the outer area is the area of that hull.
+/// The holes are measured as given, no matter whether they are clockwise or not.
+/// This does not triangulate anything, so it can be used to check how much of the region the triangles cover.
+/// ```
+/// use constrained_denaulay_triangulation::{region_areas, Vector};
+///
+/// let square = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].map(|p| Vector::new(p.0, p.1));
+/// let hole = [(1., 1.), (1., 3.), (3., 1.)].map(|p| Vector::new(p.0, p.1));
+/// let (outer_area, hole_areas) = region_areas(&square, &[hole.to_vec()]);
+/// assert_eq!(outer_area, 16.);
+/// assert_eq!(hole_areas, vec![2.]);
+/// ```
+pub fn region_areas(points: &[Vector], holes: &[Vec<Vector>]) -> (f32, Vec<f32>) {
+    let hole_areas = holes.iter().map(|hole| polygon_area(hole).abs()).collect();
+    (polygon_area(&convex_hull(points)), hole_areas)
+}
+
+/// The signed area of a polygon, which is positive if it is counter-clockwise.
+fn polygon_area(polygon: &[Vector]) -> f32 {
+    let twice_area: f32 = (0..polygon.len())
+        .map(|i| polygon[i].cross_product(polygon[(i + 1) % polygon.len()]))
+        .sum();
+    twice_area / 2.
+}
+
+/// The convex hull of the points in counter-clockwise order, built with the monotone chain algorithm.
+pub(crate) fn convex_hull(points: &[Vector]) -> Vec<Vector> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let half_hull = |points: &mut dyn Iterator<Item = &Vector>| {
+        let mut half: Vec<Vector> = Vec::new();
+        for point in points {
+            // only keep left turns
+            while let [.., a, b] = half[..] {
+                if (b - a).cross_product(*point - a) > 0. {
+                    break;
+                }
+                half.pop();
+            }
+            half.push(*point);
+        }
+        // the last point is the first one of the other half
+        half.pop();
+        half
+    };
+    let mut hull = half_hull(&mut sorted.iter());
+    hull.extend(half_hull(&mut sorted.iter().rev()));
+    hull
+}
+
+/// Whether every edge between two triangles of the result, that is not constrained, fulfills the delaunay constraint:
+/// the vertex across the edge is not inside of the circumcircle of the triangle.
+/// A small tolerance accepts points that are on the circumcircle up to rounding errors.
+/// See [`is_conforming_delaunay`] to check the constrained edges as well.
+/// ```
+/// use constrained_denaulay_triangulation::{is_delaunay, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert!(is_delaunay(&result));
+/// ```
+pub fn is_delaunay(result: &TriangulationResult) -> bool {
+    are_edges_delaunay(result, false)
+}
+
+/// Whether every edge between two triangles of the result fulfills the delaunay constraint like in [`is_delaunay`],
+/// including the constrained edges, as with [`TriangulateOptions::conforming`](crate::TriangulateOptions::conforming).
+/// ```
+/// use constrained_denaulay_triangulation::{
+///     is_conforming_delaunay, is_delaunay, triangulate_with_options, TriangulateOptions, Vector,
+/// };
+///
+/// // the segment between the two flat corners is not a delaunay edge
+/// let points = [(0., 0.), (2., -0.2), (4., 0.), (2., 0.2), (2., -2.), (2., 2.)]
+///     .map(|p| Vector::new(p.0, p.1));
+/// let options = TriangulateOptions::new().with_segments(vec![(0, 2)]);
+/// let result = triangulate_with_options(&points, None, &options).unwrap();
+/// assert!(is_delaunay(&result));
+/// assert!(!is_conforming_delaunay(&result));
+///
+/// let result = triangulate_with_options(&points, None, &options.with_conforming(true)).unwrap();
+/// assert!(is_conforming_delaunay(&result));
+/// ```
+pub fn is_conforming_delaunay(result: &TriangulationResult) -> bool {
+    are_edges_delaunay(result, true)
+}
+
+/// Checks the delaunay constraint of every edge between two triangles, see [`is_delaunay`].
+fn are_edges_delaunay(result: &TriangulationResult, include_constrained: bool) -> bool {
+    result
+        .triangles
+        .iter()
+        .enumerate()
+        .all(|(index, triangle)| {
+            let (Some(circumcenter), Some(radius)) = (
+                result.triangle(index).circumcenter(),
+                result.triangle(index).circumradius(),
+            ) else {
+                return false;
+            };
+            (0..3).all(|edge| {
+                let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+                let Some(adjacent) = result.adjacency[index][edge] else {
+                    return true;
+                };
+                if !include_constrained
+                    && result
+                        .triangle_set
+                        .is_constrained_edge(result.vertex_ids[start], result.vertex_ids[end])
+                {
+                    return true;
+                }
+                result.triangles[adjacent]
+                    .iter()
+                    .filter(|vertex| **vertex != start && **vertex != end)
+                    .all(|vertex| {
+                        let offset = result.vertices[*vertex] - circumcenter;
+                        offset.x.hypot(offset.y) >= radius * (1. - 1e-4)
+                    })
+            })
+        })
+}
+
+/// Whether the triangles of the result form a consistently oriented mesh:
+/// no triangle repeats a vertex, every edge belongs to at most two triangles that run along it in opposite directions,
+/// and [`TriangulationResult::adjacency`] names exactly those neighbours.
+///
+/// Vertices where holes or parts of the outline touch each other are allowed.
+/// ```
+/// use constrained_denaulay_triangulation::{is_manifold, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert!(is_manifold(&result));
+/// ```
+pub fn is_manifold(result: &TriangulationResult) -> bool {
+    let mut edge_owners = HashMap::with_capacity(result.len() * 3);
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        if triangle[0] == triangle[1] || triangle[1] == triangle[2] || triangle[2] == triangle[0] {
+            return false;
+        }
+        for edge in 0..3 {
+            // a directed edge in two triangles means they overlap or one of them is flipped
+            let directed = (triangle[edge], triangle[(edge + 1) % 3]);
+            if edge_owners.insert(directed, index).is_some() {
+                return false;
+            }
+        }
+    }
+    result
+        .triangles
+        .iter()
+        .enumerate()
+        .all(|(index, triangle)| {
+            (0..3).all(|edge| {
+                let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+                result.adjacency[index][edge] == edge_owners.get(&(end, start)).copied()
+            })
+        })
+}
+
+/// The output index of a triangle that contains the point, including its edges, see [`is_point_inside_triangle`].
+/// `None` if the point is outside of the mesh or inside of a hole.
+///
+/// A point on an edge or on a vertex is inside of all triangles around it. Of those, the one with the lowest index
+/// is returned, so that the answer does not flicker between neighbors with the rounding of the query.
+/// Every triangle is checked, for many points close to each other [`locate_from`] is faster.
+/// ```
+/// use constrained_denaulay_triangulation::{locate, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square: Vec<Vector> = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)].iter().map(Vector::from).collect();
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// // the center is on the diagonal, which both triangles share
+/// assert_eq!(locate(&result, Vector::new(0.5, 0.5)), Some(0));
+/// ```
+pub fn locate(result: &TriangulationResult, point: Vector) -> Option<usize> {
+    (0..result.len()).find(|index| is_point_inside_triangle(&result.triangle(*index), &point))
+}
+
+/// The lowest output index of the triangles that contain the point, like [`locate`],
+/// found by spreading from `start` to the neighbors that contain the point as well.
+fn lowest_triangle_containing(
+    result: &TriangulationResult,
+    start: usize,
+    point: Vector,
+) -> Option<usize> {
+    let contains = |index: usize| is_point_inside_triangle(&result.triangle(index), &point);
+    if !contains(start) {
+        // the walk decided in normalized space, which rounds differently
+        return locate(result, point);
+    }
+    let mut visited = vec![start];
+    let mut to_check = vec![start];
+    while let Some(index) = to_check.pop() {
+        for adjacent in result.adjacency[index].into_iter().flatten() {
+            if !visited.contains(&adjacent) && contains(adjacent) {
+                visited.push(adjacent);
+                to_check.push(adjacent);
+            }
+        }
+    }
+    visited.into_iter().min()
+}
+
+/// Whether the point is inside of the mesh, inside of a hole, on an edge of either, or outside of all of them.
+///
+/// The boundary is checked first, with the same orientation test as the triangulation,
+/// so a point exactly on the outline or on a hole edge is always [`PointClass::OnBoundary`], never inside of a triangle.
+/// Points in filled holes are inside of the mesh again, see [`fill_hole`](crate::fill_hole).
+/// ```
+/// use constrained_denaulay_triangulation::{classify_point, triangulate_with_options, PointClass, TriangulateOptions, Vector};
+///
+/// let square = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].map(|p| Vector::new(p.0, p.1));
+/// let hole = [(1., 1.), (3., 1.), (2., 3.)].map(|p| Vector::new(p.0, p.1)).to_vec();
+/// let result = triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new()).unwrap();
+/// assert_eq!(classify_point(&result, Vector::new(2., 2.)), PointClass::InHole(0));
+/// assert!(matches!(classify_point(&result, Vector::new(0.5, 3.)), PointClass::Inside(_)));
+/// assert!(matches!(classify_point(&result, Vector::new(2., 1.)), PointClass::OnBoundary(_)));
+/// assert_eq!(classify_point(&result, Vector::new(5., 2.)), PointClass::Outside);
+/// ```
+pub fn classify_point(result: &TriangulationResult, point: Vector) -> PointClass {
+    for (index, triangle) in result.triangles.iter().enumerate() {
+        for edge in 0..3 {
+            if result.adjacency[index][edge].is_some() {
+                continue;
+            }
+            let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+            if is_point_on_segment(&result.vertices[start], &result.vertices[end], &point) {
+                return PointClass::OnBoundary((start.min(end), start.max(end)));
+            }
+        }
+    }
+    if let Some(triangle) = locate(result, point) {
+        return PointClass::Inside(triangle);
+    }
+    result
+        .removed_triangles_by_hole()
+        .iter()
+        .position(|triangles| {
+            triangles
+                .iter()
+                .any(|triangle| is_point_inside_triangle(triangle, &point))
+        })
+        .map_or(PointClass::Outside, PointClass::InHole)
+}
+
+/// Whether the point is inside of the counter-clockwise triangle and not on one of its edges.
+fn is_point_strictly_inside(triangle: &Triangle, point: &Vector) -> bool {
+    // left of an edge is right of the reversed edge
+    (0..3).all(|edge| {
+        is_point_to_the_right_of_edge(&triangle.p((edge + 1) % 3), &triangle.p(edge), point)
+    })
+}
+
+/// Whether the point is on the segment from `a` to `b`, on neither side of it for [`is_point_to_the_right_of_edge`].
+fn is_point_on_segment(a: &Vector, b: &Vector, point: &Vector) -> bool {
+    let direction = *b - *a;
+    let along = |offset: Vector| offset.x * direction.x + offset.y * direction.y;
+    !is_point_to_the_right_of_edge(a, b, point)
+        && !is_point_to_the_right_of_edge(b, a, point)
+        && along(*point - *a) >= 0.
+        && along(*point - *b) <= 0.
+}
+
+/// The summed length of all edges without a triangle on their other side: the outline of the mesh and the rims of the holes.
+/// ```
+/// use constrained_denaulay_triangulation::{boundary_length, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].map(|p| Vector::new(p.0, p.1));
+/// let hole = [(1., 1.), (2., 1.), (2., 2.), (1., 2.)].map(|p| Vector::new(p.0, p.1)).to_vec();
+/// let result = triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new()).unwrap();
+/// assert_eq!(boundary_length(&result), 16. + 4.);
+/// ```
+pub fn boundary_length(result: &TriangulationResult) -> f32 {
+    result
+        .triangles
+        .iter()
+        .zip(&result.adjacency)
+        .flat_map(|(triangle, adjacency)| {
+            (0..3)
+                .filter(|edge| adjacency[*edge].is_none())
+                .map(|edge| {
+                    (result.vertices[triangle[edge]] - result.vertices[triangle[(edge + 1) % 3]])
+                        .length()
+                })
+        })
+        .sum()
+}
+
+/// The output index of the vertex of the mesh that is closest to the point, `None` if the mesh has no triangles.
+///
+/// Vertices without a triangle, like the ones inside of holes, are skipped.
+/// ```
+/// use constrained_denaulay_triangulation::{nearest_vertex, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let nearest = nearest_vertex(&result, Vector::new(0.9, 1.2)).unwrap();
+/// assert_eq!(result.vertices[nearest], Vector::new(1., 1.));
+/// ```
+pub fn nearest_vertex(result: &TriangulationResult, point: Vector) -> Option<usize> {
+    result.triangles.iter().flatten().copied().min_by(|a, b| {
+        let distance = |vertex: usize| {
+            let offset = result.vertices[vertex] - point;
+            offset.x * offset.x + offset.y * offset.y
+        };
+        distance(*a).total_cmp(&distance(*b))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        boundary_length, boundary_loops, circumcircles, classify_point,
+        does_triangle_intersect_polygon, edge_faces, edges_iter, euclidean_mst, gabriel_graph,
+        hole_boundaries, is_manifold, largest_empty_circle, locate, locate_from, nearest_vertex,
+        pair_quads, polygon_area, region_areas, segment_path, slivers, tagged_edges,
+        triangles_bordering_hole, triangles_in_polygon_query, vertex_markers, vertex_ring,
+        winding_number, PointClass, QuadOrTri,
+    };
+    use crate::{
+        math_utils::is_point_inside_triangle,
+        test_util::{random_points, refined_square},
+        triangulate_with_options, CustomError, Triangle, TriangulateOptions, TriangulationResult,
+        Vector, VertexMarker,
+    };
+
+    #[test]
+    fn largest_empty_circle_of_a_regular_octagon_touches_its_sides() -> Result<(), CustomError> {
+        let octagon: Vec<Vector> = (0..8)
+            .map(|i| Vector::from_polar(3., i as f32 * std::f32::consts::FRAC_PI_4))
+            .collect();
+        let result = triangulate_with_options(&octagon, None, &TriangulateOptions::new())?;
+        let (center, radius) = largest_empty_circle(&result);
+        assert!(center.x.abs() < 1e-4 && center.y.abs() < 1e-4);
+        // the apothem is closer than the vertices on the circumcircle
+        let apothem = 3. * std::f32::consts::FRAC_PI_8.cos();
+        assert!((radius - apothem).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn largest_empty_circle_of_a_flat_rectangle_is_limited_by_its_height() -> Result<(), CustomError>
+    {
+        let rectangle = [(0., 0.), (10., 0.), (10., 4.), (0., 4.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&rectangle, None, &TriangulateOptions::new())?;
+        let (center, radius) = largest_empty_circle(&result);
+        assert!((center.x - 5.).abs() < 1e-4 && (center.y - 2.).abs() < 1e-4);
+        assert!((radius - 2.).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn segment_path_is_contiguous() -> Result<(), CustomError> {
+        let input_points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(4.);
+        let result = triangulate_with_options(&input_points, None, &options)?;
+        let (a, b) = (Vector::new(-4., -2.), Vector::new(2., 5.));
+
+        let path = segment_path(&result, a, b);
+        assert!(path.len() > 2);
+        assert_eq!(path[0].triangle, locate(&result, a).unwrap());
+        assert_eq!(path[0].entry_edge, None);
+        let last = path.last().unwrap();
+        assert_eq!(last.triangle, locate(&result, b).unwrap());
+        assert_eq!(last.exit_edge, None);
+        for pair in path.windows(2) {
+            let exit_edge = pair[0].exit_edge.unwrap();
+            assert_eq!(
+                result.adjacency[pair[0].triangle][exit_edge],
+                Some(pair[1].triangle)
+            );
+            let entry_edge = pair[1].entry_edge.unwrap();
+            assert_eq!(
+                result.adjacency[pair[1].triangle][entry_edge],
+                Some(pair[0].triangle)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn circumcircles_are_empty() -> Result<(), CustomError> {
+        let input_points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&input_points, None, &TriangulateOptions::new())?;
+
+        let circles = circumcircles(&result);
+        assert_eq!(circles.len(), result.len());
+        for (center, radius) in circles {
+            for vertex in &result.vertices {
+                let offset = *vertex - center;
+                assert!(offset.x.hypot(offset.y) >= radius * (1. - 1e-4));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn skinny_triangles_are_slivers() {
+        let height = 3f32.sqrt() / 2.;
+        let equilateral = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(1., 0.),
+            Vector::new(0.5, height),
+        );
+        let skinny = Triangle::new(
+            Vector::new(0., 0.),
+            Vector::new(10., 0.),
+            Vector::new(5., 0.1),
+        );
+        let triangles = [equilateral, skinny, equilateral];
+        assert_eq!(slivers(&triangles, 5.), vec![1]);
+        assert_eq!(slivers(&triangles, 59.), vec![1]);
+        assert_eq!(slivers(&triangles, 61.), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn refined_square_pairs_into_quads() -> Result<(), CustomError> {
+        let result = refined_square(1.)?;
+
+        let pieces = pair_quads(&result, 45.);
+        let area = |vertices: &[usize]| {
+            let points: Vec<Vector> = vertices.iter().map(|v| result.vertices[*v]).collect();
+            (1..points.len() - 1)
+                .map(|i| (points[i] - points[0]).cross_product(points[i + 1] - points[0]) / 2.)
+                .sum::<f32>()
+        };
+        let mut covered_triangles = 0;
+        let mut covered_area = 0.;
+        let mut quads = 0;
+        for piece in &pieces {
+            match piece {
+                QuadOrTri::Quad(quad) => {
+                    quads += 1;
+                    covered_triangles += 2;
+                    assert!(area(quad) > 0.);
+                    covered_area += area(quad);
+                }
+                QuadOrTri::Tri(triangle) => {
+                    covered_triangles += 1;
+                    covered_area += area(triangle);
+                }
+            }
+        }
+        assert_eq!(covered_triangles, result.len());
+        assert!((covered_area - 100.).abs() < 1e-3);
+        // most triangles are merged
+        assert!(quads * 2 > result.len() / 2);
+        Ok(())
+    }
+
+    #[test]
+    fn region_areas_of_square_with_triangular_hole() {
+        let square = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let hole = [(2., 2.), (2., 6.), (5., 2.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let (outer_area, hole_areas) = region_areas(&square, std::slice::from_ref(&hole));
+        assert_eq!(outer_area, 100.);
+        assert_eq!(hole_areas, vec![6.]);
+
+        // the order of the points and points inside of the hull do not matter
+        let mut cloud = square.clone();
+        cloud.extend(&hole);
+        cloud.reverse();
+        cloud.push(Vector::new(10., 5.));
+        let reversed_hole: Vec<Vector> = hole.iter().rev().copied().collect();
+        assert_eq!(region_areas(&cloud, &[reversed_hole]), (100., vec![6.]));
+        assert_eq!(region_areas(&[], &[]), (0., vec![]));
+    }
+
+    #[test]
+    fn only_hole_outline_is_tagged_constrained() -> Result<(), CustomError> {
+        let outline = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let hole = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(4.);
+        let result =
+            triangulate_with_options(&outline, Some(std::slice::from_ref(&hole)), &options)?;
+
+        let edges = tagged_edges(&result);
+        let is_close = |a: Vector, b: Vector| (a - b).x.hypot((a - b).y) < 1e-4;
+        let constrained: Vec<(Vector, Vector)> = edges
+            .iter()
+            .filter(|(_, _, is_constrained)| *is_constrained)
+            .map(|(start, end, _)| (*start, *end))
+            .collect();
+        assert_eq!(constrained.len(), hole.len());
+        for i in 0..hole.len() {
+            let (a, b) = (hole[i], hole[(i + 1) % hole.len()]);
+            assert!(constrained.iter().any(|(start, end)| {
+                (is_close(*start, a) && is_close(*end, b))
+                    || (is_close(*start, b) && is_close(*end, a))
+            }));
+        }
+        // every edge of the mesh shows up once
+        let interior_edges = result.adjacency.iter().flatten().flatten().count() / 2;
+        let border_edges = result
+            .adjacency
+            .iter()
+            .flatten()
+            .filter(|a| a.is_none())
+            .count();
+        assert_eq!(edges.len(), interior_edges + border_edges);
+        Ok(())
+    }
+
+    #[test]
+    fn hinted_location_matches_cold_location() -> Result<(), CustomError> {
+        let outline = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let hole = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(1.);
+        let result = triangulate_with_options(&outline, Some(&[hole]), &options)?;
+
+        // a row of pixel centers, crossing the hole and leaving the mesh at the end
+        let mut hint = 0;
+        let mut located = 0;
+        for x in 0..64 {
+            let point = Vector::new(x as f32 * 0.171 + 0.013, 4.93);
+            let cold = locate(&result, point);
+            let hinted = locate_from(&result, point, hint);
+            assert_eq!(hinted, cold, "at {point}");
+            if let Some(triangle) = hinted {
+                hint = triangle;
+                located += 1;
+            }
+        }
+        assert!(located > 40);
+        Ok(())
+    }
+
+    #[test]
+    fn docs_example_has_an_outer_and_a_hole_loop() -> Result<(), CustomError> {
+        let points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let bighole = [(-4., 4.), (0., -2.), (4., 4.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result =
+            triangulate_with_options(&points, Some(&[bighole]), &TriangulateOptions::new())?;
+
+        let loops = boundary_loops(&result);
+        assert_eq!(loops.len(), 2);
+        // the convex hull of the points, counter-clockwise
+        assert_eq!(loops[0].len(), 5);
+        assert!(polygon_area(&loops[0]) > 0.);
+        // the hole, clockwise
+        assert_eq!(loops[1].len(), 3);
+        assert!(polygon_area(&loops[1]) < 0.);
+        // every boundary edge is in exactly one loop
+        let border_edges = result
+            .adjacency
+            .iter()
+            .flatten()
+            .filter(|a| a.is_none())
+            .count();
+        assert_eq!(loops.iter().map(Vec::len).sum::<usize>(), border_edges);
+        Ok(())
+    }
+
+    #[test]
+    fn docs_example_vertex_markers() -> Result<(), CustomError> {
+        let points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let minihole = [(-1.5, 3.5), (-0.5, 3.5), (-1., 2.5)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let bighole = [(-4., 4.), (0., -2.), (4., 4.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let count = |result: &TriangulationResult, marker: VertexMarker| {
+            vertex_markers(result)
+                .iter()
+                .filter(|vertex_marker| **vertex_marker == marker)
+                .count()
+        };
+
+        let holes = [minihole.clone(), bighole.clone()];
+        let result = triangulate_with_options(&points, Some(&holes), &TriangulateOptions::new())?;
+        // the clockwise minihole removes everything around it, only its inside with the point (-1, 3) is left
+        assert_eq!(result.vertices.len(), 15);
+        assert_eq!(count(&result, VertexMarker::HoleBoundary(0)), 3);
+        assert_eq!(count(&result, VertexMarker::HoleBoundary(1)), 3);
+        assert_eq!(count(&result, VertexMarker::Hull), 0);
+        assert_eq!(count(&result, VertexMarker::Interior), 9);
+
+        // counter-clockwise, the minihole is inside of the big hole and the outline is the hull of the points
+        let holes = [minihole.into_iter().rev().collect(), bighole];
+        let result = triangulate_with_options(&points, Some(&holes), &TriangulateOptions::new())?;
+        assert_eq!(count(&result, VertexMarker::HoleBoundary(0)), 3);
+        assert_eq!(count(&result, VertexMarker::HoleBoundary(1)), 3);
+        assert_eq!(count(&result, VertexMarker::Hull), 5);
+        // (3, 1), (-4, -1) and (1, -2) are inside of the mesh, (-1, 3) is inside of the big hole
+        assert_eq!(count(&result, VertexMarker::Interior), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn segment_path_ends_at_a_hole() -> Result<(), CustomError> {
+        let outline = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let hole = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&outline, Some(&[hole]), &TriangulateOptions::new())?;
+
+        let path = segment_path(&result, Vector::new(1., 5.), Vector::new(9., 5.));
+        let last = path.last().unwrap();
+        let exit_edge = last.exit_edge.unwrap();
+        assert_eq!(result.adjacency[last.triangle][exit_edge], None);
+        assert!(segment_path(&result, Vector::new(5., 5.), Vector::new(9., 5.)).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn edge_faces_of_a_mesh_with_a_hole() -> Result<(), CustomError> {
+        let points = [
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            (2., 7.),
+            (8., 2.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let hole = [(4., 4.), (6., 4.), (5., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let result = triangulate_with_options(&points, Some(&[hole]), &TriangulateOptions::new())?;
+        let faces = edge_faces(&result);
+
+        // counting the triangles of every edge gives one for the outlines and two inside
+        let mut triangles_of_edge = HashMap::<(usize, usize), Vec<usize>>::new();
+        for (index, triangle) in result.triangles.iter().enumerate() {
+            for edge in 0..3 {
+                let (start, end) = (triangle[edge], triangle[(edge + 1) % 3]);
+                triangles_of_edge
+                    .entry((start.min(end), start.max(end)))
+                    .or_default()
+                    .push(index);
+            }
+        }
+        assert_eq!(faces.len(), triangles_of_edge.len());
+        for (edge, triangles) in triangles_of_edge {
+            let expected = match triangles[..] {
+                [only] => (only, None),
+                [first, second] => (first, Some(second)),
+                _ => panic!("{edge:?} has {triangles:?}"),
+            };
+            assert_eq!(faces[&edge], expected);
+        }
+        // the square and the triangle of the hole
+        assert_eq!(
+            faces
+                .values()
+                .filter(|(_, second)| second.is_none())
+                .count(),
+            7
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn gabriel_graph_drops_edges_seen_at_obtuse_angles() -> Result<(), CustomError> {
+        // (2, 1) is inside of the triangle and sees all of its sides at an obtuse angle
+        let points: Vec<Vector> = [(0., 0.), (4., 0.), (2., 5.), (2., 1.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        let as_points = |edges: Vec<(usize, usize)>| -> Vec<(Vector, Vector)> {
+            edges
+                .into_iter()
+                .map(|(start, end)| (result.vertices[start], result.vertices[end]))
+                .collect()
+        };
+
+        let edges = as_points(edges_iter(&result).collect());
+        assert_eq!(edges.len(), 6);
+        assert!(edges_iter(&result).all(|(start, end)| start < end));
+
+        let gabriel = as_points(gabriel_graph(&result));
+        assert_eq!(gabriel.len(), 3);
+        // only the spokes to the inner point are left
+        assert!(gabriel
+            .iter()
+            .all(|(start, end)| *start == points[3] || *end == points[3]));
+        Ok(())
+    }
+
+    #[test]
+    fn flipped_triangles_and_wrong_neighbours_are_not_manifold() -> Result<(), CustomError> {
+        let holes = [vec![
+            Vector::new(1., 1.),
+            Vector::new(1., 2.),
+            Vector::new(2., 2.),
+            Vector::new(2., 1.),
+        ]];
+        let square = [(0., 0.), (3., 0.), (3., 3.), (0., 3.)].map(|p| Vector::new(p.0, p.1));
+        let result = triangulate_with_options(&square, Some(&holes), &TriangulateOptions::new())?;
+        assert!(is_manifold(&result));
+
+        let mut flipped = result.clone();
+        flipped.triangles[0].swap(1, 2);
+        assert!(!is_manifold(&flipped));
+
+        let mut unlinked = result;
+        let edge = (0..3)
+            .find(|edge| unlinked.adjacency[0][*edge].is_some())
+            .unwrap();
+        unlinked.adjacency[0][edge] = None;
+        assert!(!is_manifold(&unlinked));
+        Ok(())
+    }
+
+    #[test]
+    fn unit_square_has_a_boundary_of_four() -> Result<(), CustomError> {
+        let square =
+            [(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0.5, 0.5)].map(|p| Vector::new(p.0, p.1));
+        let result = triangulate_with_options(&square, None, &TriangulateOptions::new())?;
+        assert_eq!(boundary_length(&result), 4.);
+
+        // tesselation splits the outline, but does not change its length
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.01);
+        let tesselated = triangulate_with_options(&square, None, &options)?;
+        assert!(tesselated.len() > 50);
+        assert!((boundary_length(&tesselated) - 4.).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn points_are_classified_inside_in_holes_on_the_boundary_and_outside() -> Result<(), CustomError>
+    {
+        let square = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)].map(|p| Vector::new(p.0, p.1));
+        let holes = [
+            [(2., 2.), (4., 2.), (4., 4.), (2., 4.)]
+                .map(|p| Vector::new(p.0, p.1))
+                .to_vec(),
+            [(6., 6.), (8., 6.), (7., 8.)]
+                .map(|p| Vector::new(p.0, p.1))
+                .to_vec(),
+        ];
+        let result = triangulate_with_options(&square, Some(&holes), &TriangulateOptions::new())?;
+        let boundary_edge = |point: Vector| match classify_point(&result, point) {
+            PointClass::OnBoundary((start, end)) => (result.vertices[start], result.vertices[end]),
+            other => panic!("{point} should be on the boundary, but is {other:?}"),
+        };
+
+        match classify_point(&result, Vector::new(1., 5.)) {
+            PointClass::Inside(triangle) => {
+                assert_eq!(locate(&result, Vector::new(1., 5.)), Some(triangle))
+            }
+            other => panic!("expected a triangle, got {other:?}"),
+        }
+        assert_eq!(
+            classify_point(&result, Vector::new(3., 3.)),
+            PointClass::InHole(0)
+        );
+        assert_eq!(
+            classify_point(&result, Vector::new(7., 6.5)),
+            PointClass::InHole(1)
+        );
+
+        let hull_edge = boundary_edge(Vector::new(5., 0.));
+        assert!(hull_edge.0.y == 0. && hull_edge.1.y == 0.);
+        let hole_edge = boundary_edge(Vector::new(4., 3.));
+        assert!(hole_edge.0.x == 4. && hole_edge.1.x == 4.);
+        // exactly in the middle of a slanted hole edge
+        let slanted_edge = boundary_edge(Vector::new(7.5, 7.));
+        assert!([Vector::new(8., 6.), Vector::new(7., 8.)].contains(&slanted_edge.0));
+        assert!([Vector::new(8., 6.), Vector::new(7., 8.)].contains(&slanted_edge.1));
+        boundary_edge(Vector::new(10., 10.));
+
+        assert_eq!(
+            classify_point(&result, Vector::new(20., 20.)),
+            PointClass::Outside
+        );
+        assert_eq!(
+            classify_point(&result, Vector::new(-1., 5.)),
+            PointClass::Outside
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn winding_number_is_one_in_the_mesh_and_zero_in_holes_and_outside() -> Result<(), CustomError>
+    {
+        let square = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)].map(|p| Vector::new(p.0, p.1));
+        let holes = [
+            [(2., 2.), (4., 2.), (4., 4.), (2., 4.)]
+                .map(|p| Vector::new(p.0, p.1))
+                .to_vec(),
+            // a concave hole, the notch at (7, 7) is part of the mesh
+            [(6., 6.), (9., 6.), (9., 9.), (7., 7.), (6., 9.)]
+                .map(|p| Vector::new(p.0, p.1))
+                .to_vec(),
+        ];
+        let result = triangulate_with_options(&square, Some(&holes), &TriangulateOptions::new())?;
+        assert_eq!(winding_number(&result, Vector::new(1., 5.)), 1);
+        assert_eq!(winding_number(&result, Vector::new(7., 8.5)), 1);
+        assert_eq!(winding_number(&result, Vector::new(3., 3.)), 0);
+        assert_eq!(winding_number(&result, Vector::new(7., 6.5)), 0);
+        assert_eq!(winding_number(&result, Vector::new(20., 5.)), 0);
+        assert_eq!(winding_number(&result, Vector::new(-1., -1.)), 0);
+
+        // away from the boundary, the winding number agrees with the classification
+        for x in 0..40 {
+            for y in 0..40 {
+                let point = Vector::new(x as f32 * 0.3 - 0.85, y as f32 * 0.3 - 0.85);
+                let expected = match classify_point(&result, point) {
+                    PointClass::Inside(_) => 1,
+                    PointClass::OnBoundary(_) => continue,
+                    PointClass::InHole(_) | PointClass::Outside => 0,
+                };
+                assert_eq!(winding_number(&result, point), expected, "at {point}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn triangles_bordering_two_holes_touch_only_their_own_outline() -> Result<(), CustomError> {
+        let polygon = |points: &[(f32, f32)]| points.iter().map(Vector::from).collect::<Vec<_>>();
+        let square = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let holes = [
+            polygon(&[(2., 2.), (4., 2.), (4., 4.), (2., 4.)]),
+            polygon(&[(6., 5.), (8., 5.), (7., 8.)]),
+        ];
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.5);
+        let result = triangulate_with_options(&square, Some(&holes), &options)?;
+
+        let bordering = [0, 1].map(|hole| triangles_bordering_hole(&result, hole));
+        assert!(bordering.iter().all(|triangles| !triangles.is_empty()));
+        assert!(bordering[0]
+            .iter()
+            .all(|triangle| !bordering[1].contains(triangle)));
+        for (hole, triangles) in holes.iter().zip(&bordering) {
+            // the triangles with an edge whose midpoint is on the outline of the hole
+            let is_on_outline = |point: Vector| {
+                (0..hole.len()).any(|i| {
+                    let (start, end) = (hole[i], hole[(i + 1) % hole.len()]);
+                    let offset = point - start;
+                    let direction = end - start;
+                    let along = (offset.x * direction.x + offset.y * direction.y)
+                        / (direction.x * direction.x + direction.y * direction.y);
+                    direction.cross_product(offset).abs() < 1e-4 && (0. ..=1.).contains(&along)
+                })
+            };
+            let expected: Vec<usize> = (0..result.len())
+                .filter(|index| {
+                    let triangle = result.triangle(*index);
+                    (0..3).any(|edge| {
+                        is_on_outline((triangle.p(edge) + triangle.p((edge + 1) % 3)) / 2.)
+                    })
+                })
+                .collect();
+            assert_eq!(*triangles, expected);
+        }
+        // every edge of the hole outlines borders exactly one kept triangle
+        let outline_edges: usize = result
+            .constrained_edges
+            .iter()
+            .map(|trace| trace.vertices.len() - 1)
+            .sum();
+        let bordering_edges: usize = bordering
+            .iter()
+            .flatten()
+            .map(|triangle| {
+                let vertices = result.triangles[*triangle];
+                (0..3)
+                    .filter(|edge| result.adjacency[*triangle][*edge].is_none())
+                    .filter(|edge| {
+                        let (start, end) = (vertices[*edge], vertices[(edge + 1) % 3]);
+                        result.constrained_edges.iter().any(|trace| {
+                            trace.vertices.windows(2).any(|pair| {
+                                (pair[0], pair[1]) == (start, end)
+                                    || (pair[1], pair[0]) == (start, end)
+                            })
+                        })
+                    })
+                    .count()
+            })
+            .sum();
+        assert_eq!(bordering_edges, outline_edges);
+        Ok(())
+    }
+
+    #[test]
+    fn triangles_in_polygon_match_a_brute_force_search() -> Result<(), CustomError> {
+        let polygon = |points: &[(f32, f32)]| points.iter().map(Vector::from).collect::<Vec<_>>();
+        let square = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let hole = polygon(&[(3., 3.), (7., 3.), (7., 7.), (3., 7.)]);
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.2);
+        let result = triangulate_with_options(&square, Some(&[hole]), &options)?;
+
+        // a concave star around the hole, a strip across the hole whose vertices are all outside of the mesh,
+        // a polygon around everything, a window inside of the hole and one touching a corner from outside
+        let star: Vec<Vector> = (0..10)
+            .map(|i| {
+                let radius = if i % 2 == 0 { 4.5 } else { 1.5 };
+                Vector::new(5., 5.)
+                    + Vector::from_polar(radius, i as f32 * std::f32::consts::PI / 5.)
+            })
+            .collect();
+        let polygons = [
+            star,
+            polygon(&[(-1., 4.8), (11., 4.8), (11., 5.2), (-1., 5.2)]),
+            polygon(&[(-1., -1.), (11., -1.), (11., 11.), (-1., 11.)]),
+            polygon(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)]),
+            polygon(&[(10., 10.), (12., 10.), (12., 12.)]),
+            polygon(&[(0.2, 0.2), (2., 0.5), (0.5, 2.)]),
+        ];
+        for polygon in &polygons {
+            let expected: Vec<usize> = (0..result.len())
+                .filter(|index| does_triangle_intersect_polygon(&result.triangle(*index), polygon))
+                .collect();
+            assert_eq!(triangles_in_polygon_query(&result, polygon), expected);
+        }
+        assert_eq!(
+            triangles_in_polygon_query(&result, &polygons[2]).len(),
+            result.len()
+        );
+        assert!(triangles_in_polygon_query(&result, &polygons[3]).is_empty());
+        // the corner is shared by all triangles around it
+        let corner = triangles_in_polygon_query(&result, &polygons[4]);
+        assert!(!corner.is_empty());
+        assert!(corner.iter().all(|index| {
+            let triangle = result.triangle(*index);
+            (0..3).any(|i| triangle.p(i) == Vector::new(10., 10.))
+        }));
+        // the strip reaches the mesh on both sides of the hole
+        let strip = triangles_in_polygon_query(&result, &polygons[1]);
+        assert!(strip
+            .iter()
+            .any(|index| result.triangle(*index).p(0).x < 3.));
+        assert!(strip
+            .iter()
+            .any(|index| result.triangle(*index).p(0).x > 7.));
+        Ok(())
+    }
+
+    #[test]
+    fn hole_boundaries_are_closed_counter_clockwise_loops_around_the_holes(
+    ) -> Result<(), CustomError> {
+        let polygon = |points: &[(f32, f32)]| points.iter().map(Vector::from).collect::<Vec<_>>();
+        // the points on the edges of the holes split them
+        let points = polygon(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            (3., 2.),
+            (7.5, 6.5),
+        ]);
+        let holes = [
+            polygon(&[(2., 2.), (4., 2.), (4., 4.), (2., 4.)]),
+            polygon(&[(6., 5.), (8., 5.), (7., 8.)]),
+        ];
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.5);
+        let result = triangulate_with_options(&points, Some(&holes), &options)?;
+        let boundaries = hole_boundaries(&result);
+        assert_eq!(boundaries.len(), holes.len());
+
+        let edges = edge_faces(&result);
+        for (hole, boundary) in holes.iter().zip(&boundaries) {
+            assert!(boundary.len() > hole.len());
+            // every edge of the loop, also the one back to the start, is an edge of the mesh with the hole on its left
+            for i in 0..boundary.len() {
+                let (start, end) = (boundary[i], boundary[(i + 1) % boundary.len()]);
+                let (triangle, neighbor) = edges[&(start.min(end), start.max(end))];
+                assert_eq!(neighbor, None);
+                assert!(!(0..3).any(|edge| {
+                    let vertices = result.triangles[triangle];
+                    (vertices[edge], vertices[(edge + 1) % 3]) == (start, end)
+                }));
+            }
+            let outline: Vec<Vector> = boundary
+                .iter()
+                .map(|vertex| result.vertices[*vertex])
+                .collect();
+            assert!(polygon_area(&outline) > 0.);
+            assert!((polygon_area(&outline) - polygon_area(hole)).abs() < 1e-4);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn euclidean_mst_of_a_small_point_set() -> Result<(), CustomError> {
+        let points: Vec<Vector> = [(0., 0.), (2., 0.), (2., 1.), (5., 1.), (0., 3.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        let tree: Vec<(Vector, Vector)> = euclidean_mst(&result)
+            .into_iter()
+            .map(|(start, end)| (result.vertices[start], result.vertices[end]))
+            .collect();
+        // by hand: the edges of length 1, 2, 2.83 and 3, but not the other edge of length 3,
+        // from (0, 0) to (0, 3), which closes a cycle
+        let expected = [
+            ((2., 0.), (2., 1.)),
+            ((0., 0.), (2., 0.)),
+            ((2., 1.), (0., 3.)),
+            ((2., 1.), (5., 1.)),
+        ];
+        assert_eq!(tree.len(), expected.len());
+        for ((start, end), (expected_start, expected_end)) in tree.iter().zip(expected) {
+            let expected = (Vector::from(&expected_start), Vector::from(&expected_end));
+            assert!(
+                (*start, *end) == expected || (*end, *start) == expected,
+                "{tree:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn vertices_inside_of_a_triangular_lattice_have_six_neighbors() -> Result<(), CustomError> {
+        // every other row is shifted by half a step, so that the triangles are equilateral
+        let (columns, rows) = (8, 8);
+        let height = 3f32.sqrt() / 2.;
+        let position = |column: usize, row: usize| {
+            Vector::new(column as f32 + (row % 2) as f32 * 0.5, row as f32 * height)
+        };
+        let points: Vec<Vector> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| position(column, row)))
+            .collect();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        // the vertices went through the normalization, so they are found by their position
+        let index = |point: Vector| nearest_vertex(&result, point);
+
+        for row in 1..rows - 1 {
+            for column in 1..columns - 1 {
+                let vertex = index(position(column, row)).unwrap();
+                let ring = vertex_ring(&result, vertex).unwrap();
+                assert!(ring.is_closed);
+                assert_eq!(ring.degree(), 6);
+                // the neighbors are one step away and turn counter-clockwise
+                let center = result.vertices[vertex];
+                for (i, neighbor) in ring.vertices.iter().enumerate() {
+                    let next = ring.vertices[(i + 1) % ring.degree()];
+                    let (to_neighbor, to_next) = (
+                        result.vertices[*neighbor] - center,
+                        result.vertices[next] - center,
+                    );
+                    assert!((to_neighbor.x.hypot(to_neighbor.y) - 1.).abs() < 1e-4);
+                    assert!(to_neighbor.cross_product(to_next) > 0.);
+                }
+            }
+        }
+        // a corner of the lattice is on the outline, so its ring is open: it runs from the outline edge along
+        // the first row to the one up to the third row, because the second row is shifted inwards
+        let corner = vertex_ring(&result, index(position(0, 0)).unwrap()).unwrap();
+        assert!(!corner.is_closed);
+        assert_eq!(
+            [corner.vertices[0], corner.vertices[corner.degree() - 1]],
+            [
+                index(position(1, 0)).unwrap(),
+                index(position(0, 2)).unwrap()
+            ]
+        );
+        assert_eq!(vertex_ring(&result, result.vertices.len()), None);
+        Ok(())
+    }
+
+    #[test]
+    fn points_on_edges_and_vertices_are_located_in_the_lowest_triangle_from_every_hint(
+    ) -> Result<(), CustomError> {
+        let points: Vec<Vector> = (0..49)
+            .map(|i| Vector::new((i % 7) as f32, (i / 7) as f32))
+            .collect();
+        let hole: Vec<Vector> = [(2.5, 2.5), (3.5, 2.5), (3.5, 3.5), (2.5, 3.5)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let result = triangulate_with_options(&points, Some(&[hole]), &TriangulateOptions::new())?;
+        let containing = |point: Vector| {
+            (0..result.len())
+                .filter(|index| is_point_inside_triangle(&result.triangle(*index), &point))
+                .collect::<Vec<_>>()
+        };
+
+        let vertices = result.vertices.clone();
+        let midpoints = edges_iter(&result)
+            .map(|(start, end)| (result.vertices[start] + result.vertices[end]) / 2.);
+        let mut shared = 0;
+        for point in vertices.into_iter().chain(midpoints) {
+            let candidates = containing(point);
+            if candidates.len() > 1 {
+                shared += 1;
+            }
+            let expected = candidates.first().copied();
+            assert_eq!(locate(&result, point), expected);
+            for hint in (0..result.len()).step_by(5) {
+                assert_eq!(
+                    locate_from(&result, point, hint),
+                    expected,
+                    "{point:?} from {hint}"
+                );
+            }
+        }
+        assert!(shared > result.len());
+        // the corners of the hole are on its rim, not inside of the triangles around them
+        assert!(matches!(
+            classify_point(&result, Vector::new(2.5, 2.5)),
+            PointClass::OnBoundary(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_points_are_in_no_triangle() -> Result<(), CustomError> {
+        let square = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)].map(|p| Vector::new(p.0, p.1));
+        let hole = [(1., 1.), (3., 1.), (3., 3.), (1., 3.)]
+            .map(|p| Vector::new(p.0, p.1))
+            .to_vec();
+        let result = triangulate_with_options(&square, Some(&[hole]), &TriangulateOptions::new())?;
+        for point in [
+            Vector::new(f32::NAN, 0.5),
+            Vector::new(0.5, f32::NAN),
+            Vector::new(f32::INFINITY, 2.),
+            Vector::new(2., f32::NEG_INFINITY),
+        ] {
+            assert_eq!(locate(&result, point), None, "{point}");
+            for hint in 0..result.len() {
+                assert_eq!(
+                    locate_from(&result, point, hint),
+                    None,
+                    "{point} from {hint}"
+                );
+            }
+            assert_eq!(
+                classify_point(&result, point),
+                PointClass::Outside,
+                "{point}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn points_in_small_triangles_are_located_in_them() -> Result<(), CustomError> {
+        // the triangles are so small, that their centers are closer to the edges of the neighbors
+        // than an absolute tolerance on the orientation determinant
+        let points: Vec<Vector> = random_points(200, 8)
+            .into_iter()
+            .map(|point| Vector::new(0.5, 0.5) + point * 0.001)
+            .collect();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        for (index, triangle) in result.iter().enumerate() {
+            let center = (triangle.points[0] + triangle.points[1] + triangle.points[2]) * (1. / 3.);
+            assert_eq!(locate(&result, center), Some(index), "{center}");
+            assert_eq!(locate_from(&result, center, 0), Some(index), "{center}");
+            assert_eq!(
+                classify_point(&result, center),
+                PointClass::Inside(index),
+                "{center}"
+            );
+        }
+        Ok(())
+    }
+}
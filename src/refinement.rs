@@ -0,0 +1,401 @@
+use crate::{
+    data_structures::{
+        error::CustomError,
+        found_or_added::FoundOrAdded,
+        ids::TriangleId,
+        triangle_set::TriangleSet,
+        triangulate_options::{validate_positive, TriangulateOptions},
+        triangulation_result::TriangulationResult,
+        vector::Vector,
+        warning::Warning,
+    },
+    math_utils::calculate_triangle_area,
+    triangulation::insert_point,
+};
+
+/// How many points a refinement may add.
+#[derive(Clone, Copy, Debug)]
+pub struct PointBudget {
+    /// The refinement adds at most this many points.
+    pub max_points: usize,
+    /// Whether needing more than `max_points` is an error, instead of stopping there.
+    pub strict: bool,
+}
+
+impl PointBudget {
+    pub fn from_options(options: &TriangulateOptions) -> Self {
+        PointBudget {
+            max_points: options.steiner_point_limit,
+            strict: options.strict_steiner_point_limit,
+        }
+    }
+
+    fn with_max_points(max_points: usize) -> Self {
+        PointBudget {
+            max_points,
+            strict: false,
+        }
+    }
+}
+
+/// How a refinement ended.
+#[derive(Clone, Copy, Debug)]
+pub struct Refinement {
+    /// The number of points the refinement added.
+    pub inserted: usize,
+    /// Whether the refinement stopped, because it used up `max_points` of its budget.
+    pub budget_exhausted: bool,
+}
+
+impl Refinement {
+    /// Adds a warning to the result, if the budget was used up before every triangle was small enough.
+    pub fn report(&self, result: &mut TriangulationResult, maximum_triangle_area: f32) {
+        if !self.budget_exhausted {
+            return;
+        }
+        let remaining_bad_triangles = result
+            .to_triangles()
+            .iter()
+            .filter(|triangle| calculate_triangle_area(triangle) > maximum_triangle_area)
+            .count();
+        result.warnings.push(Warning::RefinementBudgetExhausted {
+            inserted: self.inserted,
+            remaining_bad_triangles,
+        });
+    }
+}
+
+/// Splits every triangle inside of the point cloud that is bigger than the maximum area (in normalized space).
+///
+/// With the `rayon` feature, independent regions of the mesh are split on several threads,
+/// see [`TriangulateOptions::deterministic`].
+pub fn tesselate(
+    triangle_set: &mut TriangleSet,
+    maximum_triangle_area: f32,
+    options: &TriangulateOptions,
+) -> Result<Refinement, CustomError> {
+    // Skips triangles sharing vertices with the Supertriangle
+    let is_refinable = |triangle_set: &TriangleSet, triangle_index: TriangleId| {
+        !triangle_set.is_supertriangle_triangle(triangle_index)
+    };
+    let budget = PointBudget::from_options(options);
+    #[cfg(feature = "rayon")]
+    if options.tesselates_in_parallel() {
+        return crate::parallel_refinement::tesselate_in_parallel(
+            triangle_set,
+            maximum_triangle_area,
+            budget,
+            &is_refinable,
+        );
+    }
+    let candidates = (0..triangle_set.triangle_count()).map(TriangleId).collect();
+    refine_triangles(
+        triangle_set,
+        candidates,
+        maximum_triangle_area,
+        budget,
+        &is_refinable,
+        &|_, _| true,
+    )
+}
+
+/// Tesselates an existing triangulation further, until none of its triangles is bigger than `maximum_triangle_area`.
+///
+/// Holes, the outline of the mesh and the constrained edges stay exactly as they are,
+/// only the triangles of the result are split. The result is updated in place, and stays unchanged on an error.
+///
+/// At most `max_steiner_points` points are inserted. If that is not enough, the refinement stops early,
+/// still returns `Ok` and adds a [`Warning::RefinementBudgetExhausted`] to the result.
+/// The budget warnings of earlier refinements are replaced, so the warnings describe the mesh as it is now.
+/// ```
+/// use constrained_denaulay_triangulation::{refine, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let mut result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(result.len(), 2);
+/// refine(&mut result, 0.1, 1000).unwrap();
+/// assert!(result.len() > 10);
+/// assert!(result.warnings.is_empty());
+/// ```
+pub fn refine(
+    result: &mut TriangulationResult,
+    maximum_triangle_area: f32,
+    max_steiner_points: usize,
+) -> Result<(), CustomError> {
+    validate_positive("maximum_triangle_area", maximum_triangle_area)?;
+    let removed_triangles = result.removed_triangles.clone();
+    let is_kept =
+        |triangle_index: TriangleId| removed_triangles.binary_search(&triangle_index).is_err();
+
+    // a refinement that fails halfway leaves the result as it was
+    let mut triangle_set = result.triangle_set.clone();
+    let refinement = refine_triangles(
+        &mut triangle_set,
+        result.triangle_ids.clone(),
+        result.bounds.normalize_area(maximum_triangle_area),
+        PointBudget::with_max_points(max_steiner_points),
+        &|_, triangle_index| is_kept(triangle_index),
+        // Swapping with a removed triangle would move the outline or a hole
+        &|current, adjacent| is_kept(current) && is_kept(adjacent),
+    )?;
+    result.triangle_set = triangle_set;
+    result.update_output();
+    result
+        .warnings
+        .retain(|warning| !matches!(warning, Warning::RefinementBudgetExhausted { .. }));
+    refinement.report(result, maximum_triangle_area);
+    Ok(())
+}
+
+/// Splits the refinable triangles of the queue that are too big, until no triangle changes anymore
+/// or the budget is used up.
+///
+/// Edges to triangles that can not be swapped are never split, because their midpoint would end up in those triangles.
+/// Triangles that only have such edges are split at their centroid instead.
+pub fn refine_triangles(
+    triangle_set: &mut TriangleSet,
+    mut queue: Vec<TriangleId>,
+    maximum_triangle_area: f32,
+    budget: PointBudget,
+    is_refinable: &dyn Fn(&TriangleSet, TriangleId) -> bool,
+    can_swap: &dyn Fn(TriangleId, TriangleId) -> bool,
+) -> Result<Refinement, CustomError> {
+    let mut added_points = 0;
+    let mut changed_triangles = Vec::new();
+
+    while let Some(triangle_index) = queue.pop() {
+        if !is_refinable(triangle_set, triangle_index) {
+            continue;
+        }
+        let triangle = triangle_set.get_triangle(triangle_index);
+        if calculate_triangle_area(&triangle) <= maximum_triangle_area {
+            continue;
+        }
+
+        let triangle_info = triangle_set.get_triangle_info(triangle_index);
+        let mut new_points: Vec<Vector> = (0..3)
+            .filter(|&i| {
+                triangle_info.adjacent_triangle_indices[i].is_some_and(|adjacent| {
+                    can_swap(triangle_index, adjacent)
+                        && !triangle_set.is_constrained_edge(
+                            triangle_info.vertex_indices[i],
+                            triangle_info.vertex_indices[(i + 1) % 3],
+                        )
+                })
+            })
+            .map(|i| triangle.p(i) + (triangle.p((i + 1) % 3) - triangle.p(i)) * 0.5)
+            .collect();
+        if new_points.is_empty() {
+            new_points.push((triangle.p(0) + triangle.p(1) + triangle.p(2)) / 3.);
+        }
+
+        for new_point in new_points {
+            if added_points == budget.max_points {
+                return if budget.strict {
+                    Err(CustomError::SteinerPointLimitExceeded(budget.max_points))
+                } else {
+                    Ok(Refinement {
+                        inserted: added_points,
+                        budget_exhausted: true,
+                    })
+                };
+            }
+            match insert_point(
+                triangle_set,
+                new_point,
+                triangle_index,
+                can_swap,
+                &mut changed_triangles,
+            ) {
+                Ok(FoundOrAdded::Added(_)) => added_points += 1,
+                Ok(FoundOrAdded::Found(_)) => (),
+                Err(_) => return Err(CustomError::TesselationFailed),
+            }
+            queue.append(&mut changed_triangles);
+        }
+    }
+    Ok(Refinement {
+        inserted: added_points,
+        budget_exhausted: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{refine, tesselate};
+    use crate::{
+        math_utils::calculate_triangle_area, test_util::square_with_hole, triangulate_with_options,
+        triangulation::insert_vertex, CustomError, Triangle, TriangleId, TriangleSet,
+        TriangulateOptions, TriangulationResult, Vector, Warning, DEFAULT_STEINER_POINT_LIMIT,
+    };
+
+    fn has_edge(result: &TriangulationResult, a: Vector, b: Vector) -> bool {
+        result.triangles.iter().any(|triangle| {
+            (0..3).any(|i| {
+                let (p, q) = (
+                    result.vertices[triangle[i]],
+                    result.vertices[triangle[(i + 1) % 3]],
+                );
+                (p == a && q == b) || (p == b && q == a)
+            })
+        })
+    }
+
+    #[test]
+    fn refining_to_smaller_areas_adds_triangles() -> Result<(), CustomError> {
+        let base_count = square_with_hole()?.len();
+
+        let mut coarse = square_with_hole()?;
+        refine(&mut coarse, 10., DEFAULT_STEINER_POINT_LIMIT)?;
+        let mut fine = square_with_hole()?;
+        refine(&mut fine, 1., DEFAULT_STEINER_POINT_LIMIT)?;
+
+        assert!(base_count < coarse.len());
+        assert!(coarse.len() < fine.len());
+        for (result, maximum_area) in [(&coarse, 10.), (&fine, 1.)] {
+            for triangle in result.to_triangles() {
+                assert!(calculate_triangle_area(&triangle) <= maximum_area * 1.001);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn refining_keeps_the_hole() -> Result<(), CustomError> {
+        let mut result = square_with_hole()?;
+        refine(&mut result, 1., DEFAULT_STEINER_POINT_LIMIT)?;
+
+        let corners = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)].map(|corner| Vector::from(&corner));
+        for i in 0..4 {
+            assert!(has_edge(&result, corners[i], corners[(i + 1) % 4]));
+        }
+        let hole_area = 4.;
+        let total_area: f32 = result
+            .to_triangles()
+            .iter()
+            .map(calculate_triangle_area)
+            .sum();
+        assert!((total_area - (100. - hole_area)).abs() < 0.01);
+        Ok(())
+    }
+
+    #[test]
+    fn refining_a_needle_stops_at_the_budget() -> Result<(), CustomError> {
+        let needle = [(0., 0.), (100., 0.), (50., 0.5)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let mut result = triangulate_with_options(&needle, None, &TriangulateOptions::new())?;
+        refine(&mut result, 0.01, 5)?;
+
+        assert_eq!(result.vertices.len(), needle.len() + 5);
+        match result.warnings.as_slice() {
+            [Warning::RefinementBudgetExhausted {
+                inserted,
+                remaining_bad_triangles,
+            }] => {
+                assert_eq!(*inserted, 5);
+                assert!(*remaining_bad_triangles > 0);
+                assert!(*remaining_bad_triangles <= result.len());
+            }
+            other => panic!("expected a budget warning, got {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn refining_again_replaces_the_budget_warning() -> Result<(), CustomError> {
+        let mut result = square_with_hole()?;
+        let budget_warnings = |result: &TriangulationResult| -> Vec<Warning> {
+            result
+                .warnings
+                .iter()
+                .filter(|warning| matches!(warning, Warning::RefinementBudgetExhausted { .. }))
+                .cloned()
+                .collect()
+        };
+        refine(&mut result, 0.1, 3)?;
+        let first = budget_warnings(&result);
+        refine(&mut result, 0.1, 3)?;
+        let second = budget_warnings(&result);
+        assert_eq!(second.len(), 1);
+        assert_ne!(first, second);
+
+        // the mesh is fine enough for this area already
+        refine(&mut result, 100., 3)?;
+        assert!(budget_warnings(&result).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn refining_rejects_invalid_area() -> Result<(), CustomError> {
+        let mut result = square_with_hole()?;
+        assert!(matches!(
+            refine(&mut result, -1., DEFAULT_STEINER_POINT_LIMIT),
+            Err(CustomError::InvalidParameter("maximum_triangle_area", _))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn tesselation_skips_the_supertriangle_after_points_were_added_before_it(
+    ) -> Result<(), CustomError> {
+        let mut triangle_set = TriangleSet::new(8);
+        // the seeded points take the first vertex ids, which used to belong to the supertriangle
+        let seeded: Vec<_> = [(0.2, 0.2), (0.8, 0.3)]
+            .iter()
+            .map(|point| triangle_set.add_point(Vector::from(point)).vertex())
+            .collect();
+        triangle_set.add_supertriangle(&Triangle::new(
+            Vector::new(-100., -100.),
+            Vector::new(100., -100.),
+            Vector::new(0., 100.),
+        ));
+        for vertex in &seeded {
+            let start = TriangleId(triangle_set.triangle_count() - 1);
+            insert_vertex(
+                &mut triangle_set,
+                *vertex,
+                start,
+                &|_, _| true,
+                &mut Vec::new(),
+            )?;
+        }
+        for point in [(0.7, 0.9), (0.1, 0.8)] {
+            triangle_set.insert_point(Vector::from(&point))?;
+        }
+
+        let maximum_triangle_area = 0.01;
+        tesselate(
+            &mut triangle_set,
+            maximum_triangle_area,
+            &TriangulateOptions::new(),
+        )?;
+        let triangles = (0..triangle_set.triangle_count()).map(TriangleId);
+        let (outside, inside): (Vec<_>, Vec<_>) =
+            triangles.partition(|triangle| triangle_set.is_supertriangle_triangle(*triangle));
+        let area =
+            |triangle: &TriangleId| calculate_triangle_area(&triangle_set.get_triangle(*triangle));
+        assert!(inside.len() > 10);
+        assert!(inside
+            .iter()
+            .all(|triangle| area(triangle) <= maximum_triangle_area));
+        assert!(outside
+            .iter()
+            .any(|triangle| area(triangle) > maximum_triangle_area));
+        for vertex in seeded {
+            assert!(!triangle_set.is_supertriangle_vertex(vertex));
+            assert!(inside.iter().any(|triangle| {
+                triangle_set.triangle_infos[triangle.0]
+                    .vertex_indices
+                    .contains(&vertex)
+            }));
+        }
+        Ok(())
+    }
+}
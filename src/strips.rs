@@ -0,0 +1,257 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::data_structures::triangulation_result::TriangulationResult;
+
+/// Groups the triangles of the result into triangle strips, as indices into [`TriangulationResult::vertices`].
+///
+/// Triangle `k` of a strip is made of the strip vertices `k`, `k + 1` and `k + 2`. Every second triangle is reversed,
+/// so that all of them are counter-clockwise, like with `GL_TRIANGLE_STRIP`.
+/// Every triangle is in exactly one strip. The strips are grown greedily across the adjacency of the triangles,
+/// beginning at the first triangle that is not in a strip yet and in the direction that gives the longest strip.
+/// This is fast, but does not find the fewest strips.
+/// ```
+/// use constrained_denaulay_triangulation::{to_triangle_strips, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// let strips = to_triangle_strips(&result);
+/// // both triangles of the square in one strip
+/// assert_eq!(strips.len(), 1);
+/// assert_eq!(strips[0].len(), 4);
+/// ```
+pub fn to_triangle_strips(result: &TriangulationResult) -> Vec<Vec<usize>> {
+    let mut is_in_strip = vec![false; result.len()];
+    let mut strips = Vec::new();
+    for start in 0..result.len() {
+        if is_in_strip[start] {
+            continue;
+        }
+        let [a, b, c] = result.triangles[start];
+        let (strip, triangles) = [[a, b, c], [b, c, a], [c, a, b]]
+            .into_iter()
+            .map(|first_vertices| grow_strip(result, start, first_vertices, &is_in_strip))
+            .max_by_key(|(_, triangles)| triangles.len())
+            .expect("there are three directions to try");
+        for triangle in triangles {
+            is_in_strip[triangle] = true;
+        }
+        strips.push(strip);
+    }
+    strips
+}
+
+/// Groups the triangles of the result into triangle strips like [`to_triangle_strips`], but chooses where a strip begins
+/// like the SGI stripifier: at the triangle with the fewest neighbors that are not in a strip yet.
+///
+/// Triangles at the border and in corners are taken first, before they are cut off from the rest,
+/// which leaves fewer single triangles and gives longer strips. Use [`join_strips`] to send them as one strip.
+/// ```
+/// use constrained_denaulay_triangulation::{stripify, triangulate_with_options, TriangulateOptions, Vector};
+///
+/// let square = vec![
+///     Vector::new(0., 0.),
+///     Vector::new(1., 0.),
+///     Vector::new(1., 1.),
+///     Vector::new(0., 1.),
+/// ];
+/// let result = triangulate_with_options(&square, None, &TriangulateOptions::new()).unwrap();
+/// assert_eq!(stripify(&result), vec![vec![2, 3, 1, 0]]);
+/// ```
+pub fn stripify(result: &TriangulationResult) -> Vec<Vec<usize>> {
+    let free_neighbors = |triangle: usize, is_in_strip: &[bool]| {
+        result.adjacency[triangle]
+            .iter()
+            .flatten()
+            .filter(|adjacent| !is_in_strip[**adjacent])
+            .count()
+    };
+    let mut is_in_strip = vec![false; result.len()];
+    // the triangles that are not in a strip yet, by their number of such neighbors
+    let mut by_free_neighbors: BTreeSet<(usize, usize)> = (0..result.len())
+        .map(|triangle| (free_neighbors(triangle, &is_in_strip), triangle))
+        .collect();
+    let mut strips = Vec::new();
+    while let Some((_, start)) = by_free_neighbors.pop_first() {
+        let [a, b, c] = result.triangles[start];
+        let (strip, triangles) = [[a, b, c], [b, c, a], [c, a, b]]
+            .into_iter()
+            .map(|first_vertices| grow_strip(result, start, first_vertices, &is_in_strip))
+            .max_by_key(|(_, triangles)| triangles.len())
+            .expect("there are three directions to try");
+
+        // the neighbors of the new strip lose a free neighbor, so their keys are updated
+        let mut neighbors: Vec<usize> = triangles
+            .iter()
+            .flat_map(|triangle| result.adjacency[*triangle].into_iter().flatten())
+            .filter(|adjacent| !is_in_strip[*adjacent])
+            .collect();
+        neighbors.sort();
+        neighbors.dedup();
+        for neighbor in &neighbors {
+            by_free_neighbors.remove(&(free_neighbors(*neighbor, &is_in_strip), *neighbor));
+        }
+        for triangle in triangles {
+            is_in_strip[triangle] = true;
+        }
+        for neighbor in neighbors {
+            if !is_in_strip[neighbor] {
+                by_free_neighbors.insert((free_neighbors(neighbor, &is_in_strip), neighbor));
+            }
+        }
+        strips.push(strip);
+    }
+    strips
+}
+
+/// Joins the strips into a single strip, by repeating the last vertex of a strip and the first vertex of the next one.
+///
+/// The triangles in between have two equal vertices, so they have no area and are not drawn.
+/// A vertex is repeated once more where needed, so that the triangles of every strip keep their winding.
+/// ```
+/// use constrained_denaulay_triangulation::join_strips;
+///
+/// let strip = join_strips(&[vec![0, 1, 2, 3], vec![4, 5, 6]]);
+/// assert_eq!(strip, vec![0, 1, 2, 3, 3, 4, 4, 5, 6]);
+/// ```
+pub fn join_strips(strips: &[Vec<usize>]) -> Vec<usize> {
+    let mut joined: Vec<usize> = Vec::new();
+    for strip in strips.iter().filter(|strip| !strip.is_empty()) {
+        if let Some(last) = joined.last().copied() {
+            // the first triangle of the strip has to start at an even position to be counter-clockwise
+            if joined.len() % 2 == 1 {
+                joined.push(last);
+            }
+            joined.push(last);
+            joined.push(strip[0]);
+        }
+        joined.extend_from_slice(strip);
+    }
+    joined
+}
+
+/// Grows a strip beginning with the counter-clockwise vertices of the `start` triangle,
+/// as long as the triangle across its last edge is not in a strip yet.
+/// Returns the vertices and the triangles of the strip.
+fn grow_strip(
+    result: &TriangulationResult,
+    start: usize,
+    first_vertices: [usize; 3],
+    is_in_strip: &[bool],
+) -> (Vec<usize>, Vec<usize>) {
+    let mut strip = first_vertices.to_vec();
+    let mut triangles = vec![start];
+    // a strip may wind around a vertex back to one of its own triangles
+    let mut is_in_this_strip = HashSet::from([start]);
+    let mut current = start;
+    loop {
+        // the next triangle shares the edge between the last two vertices of the strip
+        let (first, second) = (strip[strip.len() - 2], strip[strip.len() - 1]);
+        let triangle = result.triangles[current];
+        let Some(edge) = (0..3).find(|edge| {
+            let (start, end) = (triangle[*edge], triangle[(edge + 1) % 3]);
+            (start, end) == (first, second) || (start, end) == (second, first)
+        }) else {
+            break;
+        };
+        let Some(next) = result.adjacency[current][edge] else {
+            break;
+        };
+        if is_in_strip[next] || !is_in_this_strip.insert(next) {
+            break;
+        }
+        let third = result.triangles[next]
+            .into_iter()
+            .find(|vertex| *vertex != first && *vertex != second)
+            .expect("adjacent triangles share two vertices");
+        strip.push(third);
+        triangles.push(next);
+        current = next;
+    }
+    (strip, triangles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{join_strips, stripify, to_triangle_strips};
+    use crate::{
+        test_util::canonical_triangles, triangulate_with_options, CustomError, TriangulateOptions,
+        TriangulationResult, Vector,
+    };
+
+    /// Asserts that the strips without their degenerate triangles are the triangles of the result,
+    /// each with its winding.
+    #[track_caller]
+    fn assert_strips_have_every_triangle(result: &TriangulationResult, strips: &[Vec<usize>]) {
+        let strip_triangles: Vec<[usize; 3]> = strips
+            .iter()
+            .flat_map(|strip| {
+                strip.windows(3).enumerate().map(|(k, window)| {
+                    if k % 2 == 0 {
+                        [window[0], window[1], window[2]]
+                    } else {
+                        [window[1], window[0], window[2]]
+                    }
+                })
+            })
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+        assert_eq!(
+            canonical_triangles(&result.vertices, &strip_triangles),
+            canonical_triangles(&result.vertices, &result.triangles)
+        );
+    }
+
+    #[test]
+    fn stripify_and_join_strips_keep_every_triangle_once() -> Result<(), CustomError> {
+        let points = [(0., 0.), (10., 0.), (10., 6.), (0., 6.), (4., 3.)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.5);
+        let result = triangulate_with_options(&points, None, &options)?;
+        let strips = stripify(&result);
+        assert_strips_have_every_triangle(&result, &strips);
+        let joined = join_strips(&strips);
+        assert_strips_have_every_triangle(&result, &[joined]);
+
+        let average_length = |strips: &[Vec<usize>]| result.len() as f32 / strips.len() as f32;
+        let greedy_strips = to_triangle_strips(&result);
+        assert!(average_length(&strips) >= average_length(&greedy_strips));
+        Ok(())
+    }
+
+    #[test]
+    fn strips_contain_every_triangle_once() -> Result<(), CustomError> {
+        let input_points = [
+            (0., 7.),
+            (-5., 5.),
+            (5., 5.),
+            (-1., 3.),
+            (3., 1.),
+            (-4., -1.),
+            (1., -2.),
+            (-6., -4.),
+            (5., -4.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect::<Vec<Vector>>();
+        let hole = [(-1., 2.5), (-0.5, 3.5), (-1.5, 3.5)]
+            .iter()
+            .map(Vector::from)
+            .collect::<Vec<Vector>>();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(1.);
+        let result = triangulate_with_options(&input_points, Some(&[hole]), &options)?;
+        let strips = to_triangle_strips(&result);
+
+        assert_strips_have_every_triangle(&result, &strips);
+        // the strips are useful, not just one triangle each
+        assert!(strips.len() < result.len() / 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Seeded random inputs for tests and fuzzing, so a failing input can be reproduced from its seed,
+//! together with the meshes and the mesh comparison that the tests of several modules share.
+//!
+//! Only available in the tests of this crate and with the `test-util` feature.
+use std::f32::consts::TAU;
+
+use crate::{
+    random::SplitMix64, refine, triangulate_with_options, CustomError, TriangulateOptions,
+    TriangulationResult, Vector, DEFAULT_STEINER_POINT_LIMIT,
+};
+
+/// `n` points spread uniformly over the unit square from `(0, 0)` to `(1, 1)`.
+/// The same seed always gives the same points.
+pub fn random_points(n: usize, seed: u64) -> Vec<Vector> {
+    let mut random = SplitMix64(seed);
+    (0..n)
+        .map(|_| Vector::new(random.next_f32(), random.next_f32()))
+        .collect()
+}
+
+/// `n` points spread uniformly over the disk with radius 1 around the origin.
+/// The same seed always gives the same points.
+pub fn random_points_in_disk(n: usize, seed: u64) -> Vec<Vector> {
+    let mut random = SplitMix64(seed);
+    (0..n)
+        .map(|_| {
+            // the square root keeps the points from bunching up at the center
+            let radius = random.next_f32().sqrt();
+            let angle = random.next_f32() * TAU;
+            Vector::new(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
+
+/// A counterclockwise convex polygon with `n` corners on the circle with radius 1 around the origin,
+/// at random angles. The same seed always gives the same polygon.
+///
+/// Corners at the same angle are only added once, so with large `n` the polygon may have fewer corners.
+pub fn random_convex_polygon(n: usize, seed: u64) -> Vec<Vector> {
+    let mut random = SplitMix64(seed);
+    let mut angles: Vec<f32> = (0..n).map(|_| random.next_f32() * TAU).collect();
+    angles.sort_by(f32::total_cmp);
+    angles.dedup();
+    angles
+        .into_iter()
+        .map(|angle| Vector::new(angle.cos(), angle.sin()))
+        .collect()
+}
+
+/// The triangles as their corner positions, each starting at its smallest corner, in a sorted order.
+///
+/// Two meshes with the same triangles give the same list, whatever the order of their vertices and triangles,
+/// while a triangle with the other winding gives a different one. The positions are compared bit for bit.
+pub fn canonical_triangles(vertices: &[Vector], triangles: &[[usize; 3]]) -> Vec<[(u32, u32); 3]> {
+    let mut canonical: Vec<[(u32, u32); 3]> = triangles
+        .iter()
+        .map(|triangle| {
+            let corners =
+                triangle.map(|vertex| (vertices[vertex].x.to_bits(), vertices[vertex].y.to_bits()));
+            let smallest = (0..3).min_by_key(|corner| corners[*corner]).unwrap_or(0);
+            [0, 1, 2].map(|i| corners[(smallest + i) % 3])
+        })
+        .collect();
+    canonical.sort();
+    canonical
+}
+
+/// Asserts that the two results are the same mesh, see [`canonical_triangles`].
+#[track_caller]
+pub fn assert_same_mesh(a: &TriangulationResult, b: &TriangulationResult) {
+    assert_eq!(
+        canonical_triangles(&a.vertices, &a.triangles),
+        canonical_triangles(&b.vertices, &b.triangles),
+        "the meshes with {} and {} triangles differ",
+        a.len(),
+        b.len()
+    );
+}
+
+/// The square from `(0, 0)` to `(10, 10)` with the square hole from `(4, 4)` to `(6, 6)`, triangulated without tesselation.
+pub fn square_with_hole() -> Result<TriangulationResult, CustomError> {
+    let outline: Vec<Vector> = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+        .iter()
+        .map(Vector::from)
+        .collect();
+    let hole: Vec<Vector> = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+        .iter()
+        .map(Vector::from)
+        .collect();
+    triangulate_with_options(&outline, Some(&[hole]), &TriangulateOptions::new())
+}
+
+/// The square from `(0, 0)` to `(10, 10)`, refined until no triangle is bigger than `maximum_triangle_area`.
+pub fn refined_square(maximum_triangle_area: f32) -> Result<TriangulationResult, CustomError> {
+    let square: Vec<Vector> = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+        .iter()
+        .map(Vector::from)
+        .collect();
+    let mut result = triangulate_with_options(&square, None, &TriangulateOptions::new())?;
+    refine(
+        &mut result,
+        maximum_triangle_area,
+        DEFAULT_STEINER_POINT_LIMIT,
+    )?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonical_triangles, random_convex_polygon, random_points, random_points_in_disk};
+    use crate::Vector;
+
+    #[test]
+    fn the_same_seed_gives_the_same_points() {
+        assert_eq!(random_points(100, 7), random_points(100, 7));
+        assert_eq!(random_points_in_disk(100, 7), random_points_in_disk(100, 7));
+        assert_eq!(random_convex_polygon(20, 7), random_convex_polygon(20, 7));
+        assert_ne!(random_points(100, 7), random_points(100, 8));
+        assert_eq!(random_points(100, 7)[..10], random_points(10, 7));
+    }
+
+    #[test]
+    fn canonical_triangles_ignore_the_order_but_not_the_winding() {
+        let vertices: Vec<Vector> = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let triangles = [[0, 1, 2], [0, 2, 3]];
+        let reordered = [[3, 0, 2], [1, 2, 0]];
+        assert_eq!(
+            canonical_triangles(&vertices, &triangles),
+            canonical_triangles(&vertices, &reordered)
+        );
+        // the same vertices at other indices
+        let reversed: Vec<Vector> = vertices.iter().rev().copied().collect();
+        assert_eq!(
+            canonical_triangles(&vertices, &triangles),
+            canonical_triangles(&reversed, &[[3, 2, 1], [3, 1, 0]])
+        );
+        assert_ne!(
+            canonical_triangles(&vertices, &triangles),
+            canonical_triangles(&vertices, &[[0, 2, 1], [0, 2, 3]])
+        );
+    }
+
+    #[test]
+    fn points_stay_in_their_area() {
+        assert!(random_points(1000, 1)
+            .iter()
+            .all(|point| (0. ..1.).contains(&point.x) && (0. ..1.).contains(&point.y)));
+        assert!(random_points_in_disk(1000, 1)
+            .iter()
+            .all(|point| point.x * point.x + point.y * point.y <= 1. + 1e-6));
+    }
+
+    #[test]
+    fn random_polygons_are_convex() {
+        for seed in 0..20 {
+            let polygon = random_convex_polygon(12, seed);
+            assert!(polygon.len() >= 3);
+            for i in 0..polygon.len() {
+                let (a, b, c) = (
+                    polygon[i],
+                    polygon[(i + 1) % polygon.len()],
+                    polygon[(i + 2) % polygon.len()],
+                );
+                let cross = (b.x - a.x) * (c.y - b.y) - (b.y - a.y) * (c.x - b.x);
+                assert!(cross > 0., "seed {seed}");
+            }
+        }
+    }
+}
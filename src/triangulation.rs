@@ -1,163 +1,625 @@
 //TODO ADD TESTS FOR EVERY FUNCTION (in docs)
+use std::collections::{HashMap, HashSet};
+
 use crate::{
+    bowyer_watson::triangulate_point_bowyer_watson,
     data_structures::{
-        error::CustomError, found_or_added::FoundOrAdded, point_bin_grid::PointBinGrid,
-        triangle::Triangle, triangle_info::TriangleInfo, triangle_set::TriangleSet, vector::Vector,
+        constraint_trace::{ConstraintSource, ConstraintTrace},
+        dedup_summary::DedupSummary,
+        error::CustomError,
+        found_or_added::FoundOrAdded,
+        ids::{TriangleId, VertexId},
+        input_diagnostics::InputDiagnostics,
+        insertion_algorithm::InsertionAlgorithm,
+        insertion_order::InsertionOrder,
+        point_bin_grid::PointBinGrid,
+        triangle::Triangle,
+        triangle_info::TriangleInfo,
+        triangle_set::TriangleSet,
+        triangulate_options::TriangulateOptions,
+        triangulation_algorithm::TriangulationAlgorithm,
+        triangulation_result::TriangulationResult,
+        vector::Vector,
+        warning::Warning,
+    },
+    divide_and_conquer::triangulate_divide_and_conquer,
+    hole_creation::{
+        add_segments, conform_constraints, denormalize_error, find_nearly_touching_holes,
+        get_supertriangle_triangles, get_triangles_outside_of_boundary, insert_holes,
+        insert_split_vertices, remove_holes, remove_seeded_regions, restore_convex_hull,
+        ConstraintSplits,
     },
-    hole_creation::{create_holes, get_supertriangle_triangles},
-    math_utils::is_point_inside_circumcircle,
-    normalize::{denormalize_points, normalize_points},
+    math_utils::{
+        is_point_inside_circumcircle, is_point_strictly_inside_circumcircle, predicate_fallbacks,
+    },
+    normalize::{normalize_points, Bounds},
+    preflight::{check_finite, check_point_count},
+    random::SplitMix64,
+    refinement::tesselate,
 };
 
 pub struct TriangleIndexPair {
-    pub adjacent: usize,
-    pub current: usize,
+    pub adjacent: TriangleId,
+    pub current: TriangleId,
 }
 impl TriangleIndexPair {
-    fn new(adjacent: usize, current: usize) -> Self {
+    pub fn new(adjacent: TriangleId, current: TriangleId) -> Self {
         TriangleIndexPair { adjacent, current }
     }
 }
 
+/// Triangulates the points with the options, which have to be validated already.
+///
+/// This is the core of the public [`triangulate`](crate::triangulate) functions,
+/// which convert the [`TriangulationResult`] into the output they document.
 pub fn triangulate(
-    input_points: &mut Vec<Vector>,
-    holes: Option<&mut Vec<Vec<Vector>>>,
-    maximum_triangle_area: Option<f32>,
-) -> Result<Vec<Triangle>, CustomError> {
-    // Initialize containers
-    let mut triangle_set = TriangleSet::new(input_points.len() - 2);
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<TriangulationResult, CustomError> {
+    triangulate_normalized(input_points, holes, options).map(|(result, _)| result)
+}
 
-    let (normalized_points, bounds) = normalize_points(input_points, None);
+/// Like [`triangulate`], but also returns the normalized input points the triangulation ran on,
+/// after the duplicates were removed if asked for.
+pub fn triangulate_normalized(
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<(TriangulationResult, Vec<Vector>), CustomError> {
+    check_finite(
+        input_points
+            .iter()
+            .chain(holes.unwrap_or_default().iter().flatten()),
+    )?;
+    let bounds = match options.bounds {
+        Some((min, max)) => {
+            let bounds = Bounds::new(min, max);
+            if let Some(point) = input_points.iter().find(|point| !bounds.contains(**point)) {
+                return Err(CustomError::PointOutsideBounds(*point));
+            }
+            Bounds::around_rectangle(min, max, options.pre_transform)
+        }
+        None => Bounds::around(input_points, options.pre_transform),
+    };
+    let original_input_points = input_points;
+    let deduplicated_points;
+    let input_points = if options.dedup_input {
+        deduplicated_points = dedup_points(input_points);
+        &deduplicated_points
+    } else {
+        input_points
+    };
 
-    // 2: Addition of points to the space partitioning grid
-    let mut grid = PointBinGrid::new(
-        //with 100 points that would result in 3 cells per side thus ~10 points per grid
-        // which is the proposed overall_points^1/2 points per grid
-        (input_points.len() as f32).powf(1. / 4.).round() as usize,
-    );
+    let (normalized_points, _) = normalize_points(input_points, Some(bounds));
+    let diagnostics = InputDiagnostics::new(input_points, &bounds);
+    // the duplicates are removed, but their input indices are still wanted
+    let all_normalized_points = options
+        .dedup_input
+        .then(|| normalize_points(original_input_points, Some(bounds)).0);
+    triangulate_normalized_points(
+        normalized_points,
+        all_normalized_points,
+        bounds,
+        diagnostics,
+        holes,
+        options,
+    )
+}
 
-    for point in &normalized_points {
-        grid.add_point(*point);
+/// Like [`triangulate`], but reads the points from an iterator.
+///
+/// With [`TriangulateOptions::bounds`] every point is normalized as soon as it is read,
+/// otherwise the points are collected first to measure their bounds.
+pub fn triangulate_from_iter(
+    input_points: impl ExactSizeIterator<Item = Vector>,
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<TriangulationResult, CustomError> {
+    let Some((min, max)) = options.bounds else {
+        let input_points: Vec<Vector> = input_points.collect();
+        return triangulate(&input_points, holes, options);
+    };
+    let rectangle = Bounds::new(min, max);
+    let bounds = Bounds::around_rectangle(min, max, options.pre_transform);
+    let mut normalized_points = Vec::with_capacity(input_points.len());
+    check_finite(holes.unwrap_or_default().iter().flatten())?;
+    for point in input_points {
+        check_finite([&point])?;
+        if !rectangle.contains(point) {
+            return Err(CustomError::PointOutsideBounds(point));
+        }
+        normalized_points.push(bounds.normalize(point));
+    }
+    let diagnostics = InputDiagnostics::from_normalized(&normalized_points, &bounds);
+    let (normalized_points, all_normalized_points) = if options.dedup_input {
+        (dedup_points(&normalized_points), Some(normalized_points))
+    } else {
+        (normalized_points, None)
+    };
+    triangulate_normalized_points(
+        normalized_points,
+        all_normalized_points,
+        bounds,
+        diagnostics,
+        holes,
+        options,
+    )
+    .map(|(result, _)| result)
+}
+
+/// Triangulates points that are already normalized with the bounds.
+/// `all_normalized_points` are the normalized input points with their duplicates, if `dedup_input` removed some.
+fn triangulate_normalized_points(
+    mut normalized_points: Vec<Vector>,
+    all_normalized_points: Option<Vec<Vector>>,
+    bounds: Bounds,
+    diagnostics: InputDiagnostics,
+    holes: Option<&[Vec<Vector>]>,
+    options: &TriangulateOptions,
+) -> Result<(TriangulationResult, Vec<Vector>), CustomError> {
+    // also after the duplicates were removed
+    check_point_count(normalized_points.len())?;
+    let input_points_len = all_normalized_points
+        .as_ref()
+        .map_or(normalized_points.len(), Vec::len);
+    if let Some((start, end)) =
+        options.segments.iter().copied().find(|(start, end)| {
+            start == end || *start >= input_points_len || *end >= input_points_len
+        })
+    {
+        return Err(CustomError::InvalidSegment(start, end));
     }
 
-    // 3: Supertriangle initialization
+    // the segments are looked up before max_vertices may reorder the points
+    let segment_points: Vec<(Vector, Vector)> = {
+        let input_points = all_normalized_points
+            .as_deref()
+            .unwrap_or(&normalized_points);
+        options
+            .segments
+            .iter()
+            .map(|(start, end)| (input_points[*start], input_points[*end]))
+            .collect()
+    };
+
+    let fallbacks_before = predicate_fallbacks();
+    // the vertices of the holes are always inserted, so the input points get what they leave over
+    let hole_points: usize = holes.map_or(0, |holes| holes.iter().map(Vec::len).sum());
+    let inserted_len = match options.max_vertices {
+        Some(max_vertices) if max_vertices < hole_points + 3 => {
+            return Err(CustomError::InvalidParameter(
+                "max_vertices",
+                max_vertices as f32,
+            ))
+        }
+        Some(max_vertices) => normalized_points.len().min(max_vertices - hole_points),
+        None => normalized_points.len(),
+    };
+    if inserted_len < normalized_points.len() {
+        // the boundary and the segments need all of their points, so those are kept ahead of the cut
+        let endpoints: HashSet<(u32, u32)> = segment_points
+            .iter()
+            .flat_map(|(start, end)| [start, end])
+            .map(|point| (point.x.to_bits(), point.y.to_bits()))
+            .collect();
+        if options.assume_closed_boundary || endpoints.len() > inserted_len {
+            return Err(CustomError::InvalidParameter(
+                "max_vertices",
+                options.max_vertices.unwrap_or_default() as f32,
+            ));
+        }
+        let (mut kept, rest): (Vec<Vector>, Vec<Vector>) = normalized_points
+            .iter()
+            .partition(|point| endpoints.contains(&(point.x.to_bits(), point.y.to_bits())));
+        kept.extend(rest);
+        normalized_points = kept;
+    }
+    let inserted_points = &normalized_points[..inserted_len];
+
     let supertriangle = Triangle::new(
         Vector::new(-100.0, -100.0),
         Vector::new(100.0, -100.0),
         Vector::new(0.0, 100.0),
     );
-    triangle_set.add_triangle(&supertriangle);
-
-    // 4: (loop over each point) For each point P in the list of sorted points, do steps 5-7
-    // Points are added one at a time, and points that are close together are inserted together because they are sorted in the grid,
-    // so a later step for finding their containing triangle is faster
-    for cell in grid.cells().iter() {
-        for point in cell {
-            // All the points in the bin are added together, one by one
-            match triangulate_point(&mut triangle_set, *point) {
-                Ok(_) => (),
-                Err(e) => {
-                    return Err(e);
+    let mut triangle_set = match options.algorithm {
+        TriangulationAlgorithm::Incremental => {
+            triangulate_incrementally(inserted_points, &supertriangle, options)?
+        }
+        TriangulationAlgorithm::DivideAndConquer => {
+            triangulate_divide_and_conquer(inserted_points, [0, 1, 2].map(|i| supertriangle.p(i)))
+        }
+    };
+    let statistics = triangle_set.statistics;
+    let mut dedup_summary = DedupSummary::new(
+        all_normalized_points
+            .as_deref()
+            .unwrap_or(&normalized_points),
+        &triangle_set,
+    );
+    if options.preserve_input_order {
+        // the vertices of the supertriangle stay first, the input points follow in the order of their first occurrence
+        let mut order: Vec<VertexId> = (0..triangle_set.points.len()).map(VertexId).collect();
+        order[3..].sort_by_key(|vertex| dedup_summary.first_occurrence[vertex.0 - 3]);
+        triangle_set.reorder_vertices(&order);
+        dedup_summary.first_occurrence.sort_unstable();
+    }
+    let first_steiner_vertex = VertexId(triangle_set.points.len());
+    let refinement = match options.maximum_triangle_area {
+        Some(maximum_triangle_area) => {
+            let mut tesselation_options = options.clone();
+            if let Some(max_vertices) = options.max_vertices {
+                // the first three points are the vertices of the supertriangle
+                let vertices = triangle_set.points.len() - 3 + hole_points;
+                let remaining = max_vertices.saturating_sub(vertices);
+                if remaining < options.steiner_point_limit {
+                    tesselation_options.steiner_point_limit = remaining;
+                    tesselation_options.strict_steiner_point_limit = false;
                 }
             }
+            Some(tesselate(
+                &mut triangle_set,
+                bounds.normalize_area(maximum_triangle_area),
+                &tesselation_options,
+            )?)
+        }
+        None => None,
+    };
+    restore_convex_hull(&mut triangle_set)?;
+
+    let mut constraint_traces = Vec::new();
+    let mut splits = ConstraintSplits::new();
+    if options.assume_closed_boundary {
+        // the points are already inserted, so this only looks up their vertices
+        let mut boundary = Vec::with_capacity(inserted_points.len());
+        for point in inserted_points {
+            boundary.push(triangulate_point(&mut triangle_set, *point)?.vertex());
         }
+        let segments: Vec<(VertexId, VertexId)> = (0..boundary.len())
+            .map(|i| (boundary[i], boundary[(i + 1) % boundary.len()]))
+            .collect();
+        let chains = add_segments(
+            &mut triangle_set,
+            &segments,
+            options.constraint_intersection,
+            &mut splits,
+        )
+        .map_err(|error| denormalize_error(error, bounds))?;
+        constraint_traces.extend(
+            chains
+                .into_iter()
+                .enumerate()
+                .map(|(i, chain)| ConstraintTrace::new(ConstraintSource::Boundary(i), chain)),
+        );
     }
-    if let Some(maximum_triangle_area) = maximum_triangle_area {
-        tesselate(&mut triangle_set, maximum_triangle_area)?;
+
+    if !segment_points.is_empty() {
+        let mut segments = Vec::with_capacity(segment_points.len());
+        for (start, end) in &segment_points {
+            // the points are already inserted, so this only looks up their vertices
+            segments.push((
+                triangulate_point(&mut triangle_set, *start)?.vertex(),
+                triangulate_point(&mut triangle_set, *end)?.vertex(),
+            ));
+        }
+        let chains = add_segments(
+            &mut triangle_set,
+            &segments,
+            options.constraint_intersection,
+            &mut splits,
+        )
+        .map_err(|error| denormalize_error(error, bounds))?;
+        constraint_traces.extend(
+            chains
+                .into_iter()
+                .enumerate()
+                .map(|(i, chain)| ConstraintTrace::new(ConstraintSource::Segment(i), chain)),
+        );
     }
 
-    let triangles;
-    if let Some(holes) = holes {
-        let triangles_to_remove = create_holes(&mut triangle_set, holes, bounds)?;
-        triangle_set.points = denormalize_points(&mut triangle_set.points, &bounds);
-        triangles = get_triangles_discarding_holes(&triangle_set, triangles_to_remove);
+    let mut hole_warnings = Vec::new();
+    let mut hole_reports = match holes {
+        Some(holes) => {
+            hole_warnings = find_nearly_touching_holes(holes, bounds);
+            let (hole_reports, hole_traces) = insert_holes(
+                &mut triangle_set,
+                holes,
+                bounds,
+                options,
+                first_steiner_vertex,
+                &mut splits,
+            )?;
+            constraint_traces.extend(hole_traces);
+            hole_reports
+        }
+        None => Vec::new(),
+    };
+    if options.conforming {
+        conform_constraints(
+            &mut triangle_set,
+            options.constraint_intersection,
+            &mut splits,
+            options.steiner_point_limit,
+        )
+        .map_err(|error| denormalize_error(error, bounds))?;
+    }
+    remove_holes(&triangle_set, &mut hole_reports, options.hole_rule, &splits)?;
+    for constraint_trace in &mut constraint_traces {
+        constraint_trace.vertex_ids =
+            insert_split_vertices(&constraint_trace.vertex_ids, false, &splits);
+    }
+    let constraint_warnings: Vec<Warning> = constraint_traces
+        .iter()
+        .filter(|constraint_trace| constraint_trace.vertex_ids.len() < 2)
+        .map(|constraint_trace| Warning::ZeroLengthConstraint {
+            source: constraint_trace.source,
+        })
+        .collect();
+    let outside_triangles = if options.assume_closed_boundary {
+        get_triangles_outside_of_boundary(&triangle_set)
     } else {
-        let mut triangles_to_remove = Vec::new();
-        get_supertriangle_triangles(&mut triangle_set, &mut triangles_to_remove);
-        triangle_set.points = denormalize_points(&mut triangle_set.points, &bounds);
-        triangles_to_remove.sort();
-        triangles = get_triangles_discarding_holes(&triangle_set, triangles_to_remove);
+        Vec::new()
+    };
+    let mut seed_warnings = Vec::new();
+    if !options.hole_seeds.is_empty() {
+        let (normalized_seeds, _) = normalize_points(&options.hole_seeds, Some(bounds));
+        let seed_reports;
+        (seed_reports, seed_warnings) = remove_seeded_regions(
+            &triangle_set,
+            &normalized_seeds,
+            &outside_triangles,
+            &hole_reports,
+        )?;
+        hole_reports.extend(seed_reports);
     }
+    let mut supertriangle_triangles = Vec::new();
+    get_supertriangle_triangles(&mut triangle_set, &mut supertriangle_triangles);
 
-    return Ok(triangles);
+    let mut result = TriangulationResult::new(
+        triangle_set,
+        bounds,
+        supertriangle_triangles,
+        outside_triangles,
+        diagnostics,
+        hole_reports,
+        constraint_traces,
+    );
+    if let (Some(refinement), Some(maximum_triangle_area)) =
+        (refinement, options.maximum_triangle_area)
+    {
+        refinement.report(&mut result, maximum_triangle_area);
+    }
+    result.warnings.extend(hole_warnings);
+    result.warnings.extend(constraint_warnings);
+    result.warnings.extend(seed_warnings);
+    result.removed_duplicates = all_normalized_points.map_or(0, |all_normalized_points| {
+        all_normalized_points.len() - normalized_points.len()
+    });
+    result.dedup_summary = dedup_summary;
+    result.statistics = statistics;
+    let fallbacks_after = predicate_fallbacks();
+    result.statistics.orientation_fallbacks = fallbacks_after.0 - fallbacks_before.0;
+    result.statistics.circumcircle_fallbacks = fallbacks_after.1 - fallbacks_before.1;
+    if inserted_len < normalized_points.len() {
+        result.warnings.push(Warning::VertexLimitReached {
+            skipped_points: normalized_points.len() - inserted_len,
+        });
+        normalized_points.truncate(inserted_len);
+    }
+    Ok((result, normalized_points))
 }
 
-fn tesselate(
-    mut triangle_set: &mut TriangleSet,
-    maximum_triangle_area: f32,
-) -> Result<(), CustomError> {
-    // skip Supertriangle
-    let mut triangle_index = 2;
-    while triangle_index < triangle_set.triangle_count() {
-        // Skips  triangles sharing vertices with the Supertriangle
-        let mut is_supertriangle = false;
-        let triangle_info = triangle_set.get_triangle_info(triangle_index);
-
-        for j in 0..3 {
-            if triangle_info.vertex_indices[j] == 0
-                || triangle_info.vertex_indices[j] == 1
-                || triangle_info.vertex_indices[j] == 2
-            {
-                // 0, 1 and 2 are vertices of the supertriangle
-                is_supertriangle = true;
-                break;
-            }
-        }
+/// Triangulates a planar straight-line graph: the vertices, with every segment as a constrained edge
+/// and without the regions around the hole seed points.
+pub fn triangulate_pslg(
+    vertices: &[Vector],
+    segments: &[(usize, usize)],
+    hole_seeds: &[Vector],
+) -> Result<TriangulationResult, CustomError> {
+    let options = TriangulateOptions::new()
+        .with_segments(segments.to_vec())
+        .with_hole_seeds(hole_seeds.to_vec());
+    triangulate(vertices, None, &options)
+}
 
-        if is_supertriangle {
-            continue;
-        }
+/// Inserts the normalized points one by one into the supertriangle.
+fn triangulate_incrementally(
+    normalized_points: &[Vector],
+    supertriangle: &Triangle,
+    options: &TriangulateOptions,
+) -> Result<TriangleSet, CustomError> {
+    // Initialize containers
+    let mut triangle_set = TriangleSet::new(normalized_points.len() - 2);
 
-        let triangle = triangle_set.get_triangle(triangle_index);
-        let triangle_area = crate::math_utils::calculate_triangle_area(&triangle);
+    // 2: Addition of points to the space partitioning grid
+    let sorted_points = sort_for_insertion(
+        normalized_points,
+        options.insertion_order,
+        options.adaptive_binning,
+    );
 
-        if triangle_area > maximum_triangle_area {
-            if let Err(_) = triangulate_point(
-                &mut triangle_set,
-                triangle.p(0) + (triangle.p(1) - triangle.p(0)) * 0.5,
-            ) {
-                return Err(CustomError::TesselationFailed);
-            }
+    // 3: Supertriangle initialization
+    triangle_set.add_supertriangle(supertriangle);
 
-            if let Err(_) = triangulate_point(
-                &mut triangle_set,
-                triangle.p(1) + (triangle.p(2) - triangle.p(1)) * 0.5,
-            ) {
-                return Err(CustomError::TesselationFailed);
+    // 4: (loop over each point) For each point P in the list of sorted points, do steps 5-7
+    // Points are added one at a time, and points that are close together are inserted together because they are sorted in the grid,
+    // so a later step for finding their containing triangle is faster
+    // All the points in a bin are added together, one by one
+    for point in sorted_points {
+        match options.insertion_algorithm {
+            InsertionAlgorithm::Flips => triangulate_point(&mut triangle_set, point)?,
+            InsertionAlgorithm::BowyerWatson => {
+                triangulate_point_bowyer_watson(&mut triangle_set, point)?
             }
+        };
+    }
+    Ok(triangle_set)
+}
 
-            if let Err(_) = triangulate_point(
-                &mut triangle_set,
-                triangle.p(2) + (triangle.p(0) - triangle.p(2)) * 0.5,
-            ) {
-                return Err(CustomError::TesselationFailed);
+/// Triangulates without tesselation and returns the triangles as indices into the input points,
+/// followed by the vertices of the holes.
+pub fn triangulate_indexed(
+    input_points: &[Vector],
+    holes: Option<&[Vec<Vector>]>,
+) -> Result<Vec<[usize; 3]>, CustomError> {
+    let result = triangulate(input_points, holes, &TriangulateOptions::new())?;
+
+    // the normalization gives the same coordinates as during the triangulation, so the points can be matched exactly
+    let all_points: Vec<Vector> = input_points
+        .iter()
+        .chain(holes.unwrap_or_default().iter().flatten())
+        .copied()
+        .collect();
+    let (normalized_points, _) = normalize_points(&all_points, Some(result.bounds));
+    let mut input_index = HashMap::with_capacity(normalized_points.len());
+    for (index, point) in normalized_points.iter().enumerate() {
+        input_index
+            .entry((point.x.to_bits(), point.y.to_bits()))
+            .or_insert(index);
+    }
+    let vertex_to_input: Vec<usize> = result
+        .vertex_ids
+        .iter()
+        .map(|vertex| {
+            // without tesselation every vertex is one of the input or hole points
+            let point = result.triangle_set.get_point_from_vertex(*vertex);
+            input_index[&(point.x.to_bits(), point.y.to_bits())]
+        })
+        .collect();
+    Ok(result
+        .triangles
+        .iter()
+        .map(|triangle| triangle.map(|vertex| vertex_to_input[vertex]))
+        .collect())
+}
+
+/// Sorts the normalized points into the order they are inserted in.
+fn sort_for_insertion(
+    points: &[Vector],
+    insertion_order: InsertionOrder,
+    adaptive_binning: bool,
+) -> Vec<Vector> {
+    match insertion_order {
+        InsertionOrder::Spatial => sort_spatially(points, adaptive_binning),
+        InsertionOrder::Brio { seed } => {
+            let mut shuffled = points.to_vec();
+            shuffle(&mut shuffled, seed);
+            // The last round holds half of the points, the one before it a quarter and so on
+            let mut round_starts = vec![shuffled.len()];
+            while let Some(&start) = round_starts.last().filter(|start| **start > 0) {
+                round_starts.push(start / 2);
             }
-            triangle_index = 2; // The tesselation restarts
+            round_starts
+                .windows(2)
+                .rev()
+                .flat_map(|round| sort_spatially(&shuffled[round[1]..round[0]], adaptive_binning))
+                .collect()
         }
-        triangle_index += 1;
     }
-    return Ok(());
+}
+
+/// How many times the average number of points a cell of [`sort_spatially`] may hold before it is split.
+const SPLIT_CELL_FACTOR: usize = 4;
+
+/// Sorts the points along the cells of a grid, so that consecutive points are close to each other.
+/// With `adaptive_binning`, cells with a lot more points than the average are split, so that clusters are sorted as well.
+fn sort_spatially(points: &[Vector], adaptive_binning: bool) -> Vec<Vector> {
+    //with 100 points that would result in 3 cells per side thus ~10 points per grid
+    // which is the proposed overall_points^1/2 points per grid
+    let cells_per_side = (points.len() as f32).powf(1. / 4.).round() as usize;
+    let mut grid = PointBinGrid::new(cells_per_side);
+    if adaptive_binning {
+        let average_points_per_cell = points
+            .len()
+            .div_ceil((cells_per_side * cells_per_side).max(1));
+        grid = grid.with_max_points_per_cell(SPLIT_CELL_FACTOR * average_points_per_cell);
+    }
+    for point in points {
+        grid.add_point(*point);
+    }
+    grid.rebin();
+    grid.iter_points_in_insertion_order().collect()
+}
+
+/// Shuffles the points with the Fisher-Yates shuffle, driven by the splitmix64 generator.
+fn shuffle(points: &mut [Vector], seed: u64) {
+    let mut random = SplitMix64(seed);
+    for i in (1..points.len()).rev() {
+        let j = (random.next_u64() % (i as u64 + 1)) as usize;
+        points.swap(i, j);
+    }
+}
+
+/// Removes exact duplicates from the points, keeping the first occurrence of every point in order.
+fn dedup_points(points: &[Vector]) -> Vec<Vector> {
+    let mut seen = HashSet::with_capacity(points.len());
+    points
+        .iter()
+        // adding zero turns -0 into 0, so both are treated as the same coordinate
+        .filter(|point| seen.insert(((point.x + 0.).to_bits(), (point.y + 0.).to_bits())))
+        .copied()
+        .collect()
 }
 
 pub fn triangulate_point(
     triangle_set: &mut TriangleSet,
     point_to_insert: Vector,
 ) -> Result<FoundOrAdded, CustomError> {
-    // Note: Adjacent triangle, opposite to the inserted point, is always at index 1
-    // Note 2: Adjacent triangles are stored CCW automatically, their index matches the index of the first vertex in every edge, and it is known that vertices are stored CCW
+    // Start at the last added triangle
+    let start_triangle = TriangleId(triangle_set.triangle_count() - 1);
+    insert_point(
+        triangle_set,
+        point_to_insert,
+        start_triangle,
+        &|_, _| true,
+        &mut Vec::new(),
+    )
+}
 
+/// Inserts a point into the triangulation and restores the delaunay constraint around it.
+///
+/// The search for the containing triangle begins at `start_triangle`.
+/// The constrained edges of the triangle set and the edges for which `can_swap(current, adjacent)` returns false are never swapped.
+/// Every triangle that is created or changed is pushed to `changed_triangles`.
+pub fn insert_point(
+    triangle_set: &mut TriangleSet,
+    point_to_insert: Vector,
+    start_triangle: TriangleId,
+    can_swap: &dyn Fn(TriangleId, TriangleId) -> bool,
+    changed_triangles: &mut Vec<TriangleId>,
+) -> Result<FoundOrAdded, CustomError> {
     // 4.1: Check point existence
-    let inserted_point_index;
-    match triangle_set.add_point(point_to_insert) {
+    let inserted_point_index = match triangle_set.add_point(point_to_insert) {
         FoundOrAdded::Found(idx) => return Ok(FoundOrAdded::Found(idx)),
-        FoundOrAdded::Added(idx) => inserted_point_index = idx,
-    }
+        FoundOrAdded::Added(idx) => idx,
+    };
+    insert_vertex(
+        triangle_set,
+        inserted_point_index,
+        start_triangle,
+        can_swap,
+        changed_triangles,
+    )?;
+    Ok(FoundOrAdded::Added(inserted_point_index))
+}
+
+/// Inserts a vertex, whose point is already part of the triangle set but not of any triangle, like [`insert_point`].
+pub fn insert_vertex(
+    triangle_set: &mut TriangleSet,
+    inserted_point_index: VertexId,
+    start_triangle: TriangleId,
+    can_swap: &dyn Fn(TriangleId, TriangleId) -> bool,
+    changed_triangles: &mut Vec<TriangleId>,
+) -> Result<(), CustomError> {
+    // Note: Adjacent triangle, opposite to the inserted point, is always at index 1
+    // Note 2: Adjacent triangles are stored CCW automatically, their index matches the index of the first vertex in every edge, and it is known that vertices are stored CCW
+    let point_to_insert = triangle_set.get_point_from_vertex(inserted_point_index);
 
     // 4.2: Search containing triangle
-    // Start at the last added triangle
-    if let Ok(containing_triangle_index) = triangle_set
-        .find_triangle_that_contains_point(point_to_insert, triangle_set.triangle_count() - 1)
+    if let Ok((containing_triangle_index, walk_steps)) =
+        triangle_set.walk_to_point(point_to_insert, start_triangle)
     {
+        triangle_set.statistics.walk_steps += walk_steps;
         let containing_triangle = triangle_set.get_triangle_info(containing_triangle_index);
 
         // 5. Insert new point in triangulation and create 2 new triangles off of it
@@ -186,7 +648,7 @@ pub fn triangulate_point(
         );
 
         let second_triangle_index = triangle_set.add_triangle_info(second_triangle);
-        triangle_set.triangle_infos[first_triangle_index].adjacent_triangle_indices[0] =
+        triangle_set.triangle_infos[first_triangle_index.0].adjacent_triangle_indices[0] =
             Some(second_triangle_index);
 
         // Sets the adjacency of the triangles that were adjacent to the original containing triangle
@@ -208,12 +670,16 @@ pub fn triangulate_point(
         // 5.1: Transform containing triangle into the third
         // Original triangle is transformed into the third triangle after the point has split the containing triangle into 3
         // using that triangle to keep main, so that the least has to change
-        triangle_set.triangle_infos[containing_triangle_index].vertex_indices[0] =
-            inserted_point_index;
-        triangle_set.triangle_infos[containing_triangle_index].adjacent_triangle_indices[0] =
-            Some(first_triangle_index);
-        triangle_set.triangle_infos[containing_triangle_index].adjacent_triangle_indices[2] =
-            Some(second_triangle_index);
+        let containing_triangle_info =
+            &mut triangle_set.triangle_infos[containing_triangle_index.0];
+        containing_triangle_info.vertex_indices[0] = inserted_point_index;
+        containing_triangle_info.adjacent_triangle_indices[0] = Some(first_triangle_index);
+        containing_triangle_info.adjacent_triangle_indices[2] = Some(second_triangle_index);
+        changed_triangles.extend([
+            containing_triangle_index,
+            first_triangle_index,
+            second_triangle_index,
+        ]);
 
         // TODO there might be a good capacity to choose here
         let mut index_pairs = Vec::<TriangleIndexPair>::new();
@@ -240,6 +706,14 @@ pub fn triangulate_point(
         }
         // 7.1: Check Delaunay constraint
         while let Some(index_pair) = index_pairs.pop() {
+            let current_vertices = triangle_set
+                .get_triangle_info(index_pair.current)
+                .vertex_indices;
+            if triangle_set.is_constrained_edge(current_vertices[1], current_vertices[2])
+                || !can_swap(index_pair.current, index_pair.adjacent)
+            {
+                continue;
+            }
             if is_point_inside_circumcircle(
                 triangle_set.get_triangle(index_pair.adjacent),
                 point_to_insert,
@@ -250,6 +724,8 @@ pub fn triangulate_point(
                     // TODO rewrite to Option<(usize, usize)>
                     swap_edges(&index_pair, triangle_set, 1)
                 {
+                    triangle_set.statistics.flips += 1;
+                    changed_triangles.extend([index_pair.current, index_pair.adjacent]);
                     // 7.3 push new adjacents on stack
                     if let Some(new_oppositve_index) = second_new_adjacent {
                         index_pairs.push(TriangleIndexPair::new(
@@ -268,10 +744,57 @@ pub fn triangulate_point(
                 }
             }
         }
-        return Ok(FoundOrAdded::Added(inserted_point_index));
+        Ok(())
     } else {
-        return Err(CustomError::PointNotInTriangle);
+        Err(CustomError::PointNotInTriangle)
+    }
+}
+
+/// Swaps edges of the queued triangles until every swappable edge between them and their neighbors is delaunay again.
+///
+/// Constrained edges and edges for which `can_swap(triangle_set, current, adjacent)` returns false are never swapped.
+/// Both triangles of every swap are queued again, so the fix propagates as far as necessary.
+pub fn legalize_triangles(
+    triangle_set: &mut TriangleSet,
+    mut queue: Vec<TriangleId>,
+    can_swap: &dyn Fn(&TriangleSet, TriangleId, TriangleId) -> bool,
+) -> Result<(), CustomError> {
+    while let Some(current) = queue.pop() {
+        for edge_index in 0..3 {
+            let current_info = triangle_set.get_triangle_info(current);
+            let Some(adjacent) = current_info.adjacent_triangle_indices[edge_index] else {
+                continue;
+            };
+            if triangle_set.is_constrained_edge(
+                current_info.vertex_indices[edge_index],
+                current_info.vertex_indices[(edge_index + 1) % 3],
+            ) || !can_swap(triangle_set, current, adjacent)
+            {
+                continue;
+            }
+            let not_shared_vertex = current_info.vertex_indices[(edge_index + 2) % 3];
+            if is_point_strictly_inside_circumcircle(
+                triangle_set.get_triangle(adjacent),
+                triangle_set.get_point_from_vertex(not_shared_vertex),
+            ) {
+                swap_edges(
+                    &TriangleIndexPair::new(adjacent, current),
+                    triangle_set,
+                    edge_index,
+                )?;
+                queue.extend([current, adjacent]);
+                break;
+            }
+        }
     }
+    Ok(())
+}
+
+/// Whether the triangle is clockwise, computed in f64 so that rounding does not invert flat triangles.
+/// Triangles without area, from points exactly on an edge, are not inverted.
+fn is_inverted(triangle: &Triangle) -> bool {
+    let [a, b, c] = [0, 1, 2].map(|i| (triangle.p(i).x as f64, triangle.p(i).y as f64));
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0) < 0.
 }
 
 /// This will swap the adjacent edge between two triangles.
@@ -279,41 +802,30 @@ pub fn swap_edges(
     index_pair: &TriangleIndexPair,
     triangle_set: &mut TriangleSet,
     shared_vertex_index: usize,
-) -> Result<(Option<usize>, Option<usize>), CustomError> {
+) -> Result<(Option<TriangleId>, Option<TriangleId>), CustomError> {
     let current_info = triangle_set.get_triangle_info(index_pair.current);
     let adjacent_info = triangle_set.get_triangle_info(index_pair.adjacent);
     let p = current_info.vertex_indices[(shared_vertex_index + 2) % 3];
     let p2 = current_info.vertex_indices[(shared_vertex_index + 1) % 3];
     let shared_vertex = current_info.vertex_indices[shared_vertex_index];
-    let mut adj_shared_vertex_index = 4; // out of bounds
-    for idx in 0..3 {
-        if shared_vertex == adjacent_info.vertex_indices[idx] {
-            adj_shared_vertex_index = idx;
-            break;
-        }
-    }
-    if adj_shared_vertex_index > 2 {
-        return Err(CustomError::TrianglesDontShareIndex);
-    }
+    let adj_shared_vertex_index = adjacent_info
+        .vertex_indices
+        .iter()
+        .position(|vertex| *vertex == shared_vertex)
+        .ok_or(CustomError::TrianglesDontShareIndex)?;
     let first_new_adjacent = adjacent_info.adjacent_triangle_indices[adj_shared_vertex_index];
     let second_new_adjacent =
         adjacent_info.adjacent_triangle_indices[(adj_shared_vertex_index + 1) % 3];
 
     let opposite_vertex = adjacent_info.vertex_indices[(adj_shared_vertex_index + 1) % 3];
     let a2 = current_info.adjacent_triangle_indices[(shared_vertex_index + 1) % 3];
-    let new_adjacent = TriangleInfo::new([
-        p,
-        opposite_vertex,
-        p2,
-    ])
-    .with_adjacent(Some(index_pair.current), second_new_adjacent, a2);
+    let new_adjacent = TriangleInfo::new([p, opposite_vertex, p2]).with_adjacent(
+        Some(index_pair.current),
+        second_new_adjacent,
+        a2,
+    );
     triangle_set.replace_triangle(index_pair.adjacent, &new_adjacent);
-    let new_current = TriangleInfo::new([
-        p,
-        shared_vertex,
-        opposite_vertex,
-    ])
-    .with_adjacent(
+    let new_current = TriangleInfo::new([p, shared_vertex, opposite_vertex]).with_adjacent(
         current_info.adjacent_triangle_indices[(shared_vertex_index + 2) % 3],
         first_new_adjacent,
         Some(index_pair.adjacent),
@@ -335,65 +847,1191 @@ pub fn swap_edges(
             Some(index_pair.adjacent),
         );
     }
+    debug_assert!(
+        [index_pair.current, index_pair.adjacent]
+            .into_iter()
+            .all(|triangle| !is_inverted(&triangle_set.get_triangle(triangle))),
+        "swapping the edge between {:?} and {:?} left a clockwise triangle: {} and {}",
+        index_pair.current,
+        index_pair.adjacent,
+        triangle_set.get_triangle(index_pair.current),
+        triangle_set.get_triangle(index_pair.adjacent),
+    );
     Ok((first_new_adjacent, second_new_adjacent))
 }
 
-fn get_triangles_discarding_holes(
-    triangle_set: &TriangleSet,
-    triangles_to_remove: Vec<usize>,
-) -> Vec<Triangle> {
-    let mut output_triangles = Vec::with_capacity(triangle_set.triangle_count() - 1);
-
-    // Output filtering
-    let mut idxs_i = 0;
-
-    for (idx, triangle_info) in triangle_set.triangle_infos.iter().enumerate() {
-        if !(triangles_to_remove.get(idxs_i) == Some(&idx)) {
-            output_triangles.push(Triangle::new(
-                triangle_set.get_point_from_vertex(triangle_info.vertex_indices[0]),
-                triangle_set.get_point_from_vertex(triangle_info.vertex_indices[1]),
-                triangle_set.get_point_from_vertex(triangle_info.vertex_indices[2]),
-            ));
-        } else {
-            idxs_i += 1;
-        }
-    }
-    output_triangles
-}
-
-
+#[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use crate::{
-        data_structures::{triangle_info::TriangleInfo, triangle_set::TriangleSet},
+        data_structures::{
+            ids::{TriangleId, VertexId},
+            triangle_info::TriangleInfo,
+            triangle_set::TriangleSet,
+        },
+        is_conforming_delaunay, is_delaunay, is_manifold,
+        math_utils::calculate_triangle_area,
+        test_util::{assert_same_mesh, canonical_triangles, random_points},
+        triangulate, triangulate_from_iter, triangulate_generic, triangulate_into,
+        triangulate_pslg, triangulate_with_options,
         triangulation::{swap_edges, TriangleIndexPair},
-        CustomError, Vector,
+        ConstraintSource, ConstraintTrace, CustomError, InsertionAlgorithm, InsertionOrder, Point2,
+        Triangle, TriangulateOptions, TriangulationAlgorithm, TriangulationResult, Vector, Warning,
     };
 
+    fn vectors(points: &[(f32, f32)]) -> Vec<Vector> {
+        points.iter().map(Vector::from).collect()
+    }
+
+    fn unit_square() -> Vec<Vector> {
+        vec![
+            Vector::new(0., 0.),
+            Vector::new(1., 0.),
+            Vector::new(1., 1.),
+            Vector::new(0., 1.),
+        ]
+    }
+
+    fn vertices(indices: [usize; 3]) -> [VertexId; 3] {
+        indices.map(VertexId)
+    }
+
     #[test]
     fn swapping_edges() -> Result<(), CustomError> {
         let mut triangle_set = TriangleSet::new(2);
         triangle_set.add_point(Vector::new(0.0, 0.0) * 10.); //
-        triangle_set.add_point(Vector::new(0., 1.) * 10.); //
         triangle_set.add_point(Vector::new(1., 0.) * 10.); //
+        triangle_set.add_point(Vector::new(0., 1.) * 10.); //
         triangle_set.add_point(Vector::new(1., 1.) * 10.); //
-        let triangle_info_current = TriangleInfo::new([0, 1, 2]).with_adjacent(None, Some(1), None);
+        let triangle_info_current =
+            TriangleInfo::new(vertices([0, 1, 2])).with_adjacent(None, Some(TriangleId(1)), None);
         let triangle_info_adjacent =
-            TriangleInfo::new([3, 2, 1]).with_adjacent(None, Some(0), None);
+            TriangleInfo::new(vertices([3, 2, 1])).with_adjacent(None, Some(TriangleId(0)), None);
         triangle_set.add_triangle_info(triangle_info_current);
         triangle_set.add_triangle_info(triangle_info_adjacent);
         let index_pair = TriangleIndexPair {
-            adjacent: 1,
-            current: 0,
+            adjacent: TriangleId(1),
+            current: TriangleId(0),
         };
         swap_edges(&index_pair, &mut triangle_set, 1)?;
         let expected_triangle_info_current =
-            TriangleInfo::new([0, 1, 3]).with_adjacent(None, None, Some(1));
+            TriangleInfo::new(vertices([0, 1, 3])).with_adjacent(None, None, Some(TriangleId(1)));
         let expected_triangle_info_adjacent =
-            TriangleInfo::new([0, 3, 2]).with_adjacent(Some(0), None, None);
-        let actual_current = triangle_set.get_triangle_info(0);
-        let actual_adjacent = triangle_set.get_triangle_info(1);
+            TriangleInfo::new(vertices([0, 3, 2])).with_adjacent(Some(TriangleId(0)), None, None);
+        let actual_current = triangle_set.get_triangle_info(TriangleId(0));
+        let actual_adjacent = triangle_set.get_triangle_info(TriangleId(1));
         assert_eq!(expected_triangle_info_current, actual_current);
         assert_eq!(expected_triangle_info_adjacent, actual_adjacent);
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn tesselation_respects_maximum_area() -> Result<(), CustomError> {
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.1);
+        let result = triangulate_with_options(&unit_square(), None, &options)?;
+        assert!(result.len() >= 10);
+        for triangle in &result.to_triangles() {
+            assert!(calculate_triangle_area(triangle) <= 0.1 + f32::EPSILON);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_maximum_area_is_rejected_before_triangulating() {
+        for area in [0., -1., f32::NAN, f32::INFINITY] {
+            let options = TriangulateOptions::new().with_maximum_triangle_area(area);
+            assert!(matches!(
+                triangulate_with_options(&unit_square(), None, &options),
+                Err(CustomError::InvalidParameter("maximum_triangle_area", _))
+            ));
+        }
+    }
+
+    #[test]
+    fn tesselation_stops_at_steiner_point_limit() {
+        let options = TriangulateOptions::new()
+            .with_maximum_triangle_area(0.0001)
+            .with_steiner_point_limit(20);
+        assert!(matches!(
+            triangulate_with_options(&unit_square(), None, &options),
+            Err(CustomError::SteinerPointLimitExceeded(20))
+        ));
+    }
+
+    #[test]
+    fn tesselation_stops_at_point_budget() -> Result<(), CustomError> {
+        let options = TriangulateOptions::new()
+            .with_maximum_triangle_area(0.0001)
+            .with_steiner_point_limit(10)
+            .with_strict_steiner_point_limit(false);
+        let result = triangulate_with_options(&unit_square(), None, &options)?;
+        assert_eq!(result.vertices.len(), unit_square().len() + 10);
+        assert!(matches!(
+            result.warnings.as_slice(),
+            [Warning::RefinementBudgetExhausted { inserted: 10, .. }]
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn brio_on_a_spiral_gives_the_same_triangles_with_fewer_flips() -> Result<(), CustomError> {
+        let spiral: Vec<Vector> = (0..1000)
+            .map(|i| {
+                let angle = i as f32 * 0.05;
+                Vector::from_polar(1. + angle, angle)
+            })
+            .collect();
+
+        let spatial = triangulate_with_options(&spiral, None, &TriangulateOptions::new())?;
+        let options =
+            TriangulateOptions::new().with_insertion_order(InsertionOrder::Brio { seed: 7 });
+        let brio = triangulate_with_options(&spiral, None, &options)?;
+
+        assert_same_mesh(&brio, &spatial);
+        assert!(spatial.statistics.walk_steps >= spiral.len());
+        assert!(brio.statistics.flips * 2 < spatial.statistics.flips);
+        Ok(())
+    }
+
+    #[test]
+    fn bowyer_watson_gives_the_same_triangles_as_flips() -> Result<(), CustomError> {
+        // pseudo random points, so that no four of them are on a common circle
+        let mut state = 12345u32;
+        let mut next_coordinate = || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32 * 100.
+        };
+        let points: Vec<Vector> = (0..2000)
+            .map(|_| Vector::new(next_coordinate(), next_coordinate()))
+            .collect();
+
+        for insertion_order in [InsertionOrder::Spatial, InsertionOrder::Brio { seed: 3 }] {
+            let options = TriangulateOptions::new().with_insertion_order(insertion_order);
+            let flips = triangulate_with_options(&points, None, &options)?;
+            let bowyer_watson = triangulate_with_options(
+                &points,
+                None,
+                &options.with_insertion_algorithm(InsertionAlgorithm::BowyerWatson),
+            )?;
+
+            assert_same_mesh(&bowyer_watson, &flips);
+            assert_eq!(bowyer_watson.statistics.flips, 0);
+            assert!(bowyer_watson.statistics.cavity_triangles >= points.len());
+            assert_eq!(flips.statistics.cavity_triangles, 0);
+        }
+        Ok(())
+    }
+
+    #[derive(Clone, Copy)]
+    struct Site {
+        latitude: f64,
+        longitude: f64,
+    }
+
+    impl Point2 for Site {
+        fn x(&self) -> f32 {
+            self.longitude as f32
+        }
+
+        fn y(&self) -> f32 {
+            self.latitude as f32
+        }
+    }
+
+    #[test]
+    fn generic_points_are_triangulated_into_indices() -> Result<(), CustomError> {
+        let site = |longitude: f64, latitude: f64| Site {
+            latitude,
+            longitude,
+        };
+        let sites = [
+            site(0., 0.),
+            site(10., 0.),
+            site(10., 10.),
+            site(0., 10.),
+            site(10., 10.),
+        ];
+        let hole = vec![site(4., 4.), site(6., 4.), site(6., 6.), site(4., 6.)];
+        let triangles = triangulate_generic(&sites, Some(std::slice::from_ref(&hole)))?;
+
+        let all_sites: Vec<Site> = sites.iter().chain(&hole).copied().collect();
+        let to_vector = |site: &Site| Vector::new(site.x(), site.y());
+        let all_vectors: Vec<Vector> = all_sites.iter().map(to_vector).collect();
+        let vectors: Vec<Vector> = sites.iter().map(to_vector).collect();
+        let hole_vectors: Vec<Vector> = hole.iter().map(to_vector).collect();
+        let result =
+            triangulate_with_options(&vectors, Some(&[hole_vectors]), &TriangulateOptions::new())?;
+        assert_eq!(
+            canonical_triangles(&all_vectors, &triangles),
+            canonical_triangles(&result.vertices, &result.triangles)
+        );
+        // the duplicate shares the index of the first point at its position
+        assert!(triangles.iter().flatten().all(|index| *index != 4));
+        assert!(triangles.iter().flatten().any(|index| *index == 2));
+        Ok(())
+    }
+
+    /// Pseudo random points in the box from 0 to 100 and its corners, so that the hull has no flat triangles.
+    fn random_points_in_a_box(count: usize, seed: u32) -> Vec<Vector> {
+        let mut state = seed;
+        let mut next_coordinate = || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32 * 98. + 1.
+        };
+        let mut points: Vec<Vector> = (0..count)
+            .map(|_| Vector::new(next_coordinate(), next_coordinate()))
+            .collect();
+        points.extend(
+            [(0., 0.), (100., 0.), (100., 100.), (0., 100.)].map(|p| Vector::new(p.0, p.1)),
+        );
+        points
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn normalized_points_are_in_the_unit_square_and_denormalize_to_the_input(
+    ) -> Result<(), CustomError> {
+        let points: Vec<Vector> = random_points_in_a_box(200, 11)
+            .iter()
+            .map(|point| Vector::new(point.x * 30. - 1000., point.y * 0.02 + 5.))
+            .collect();
+        let (result, normalized_points, bounds) =
+            crate::triangulate_debug(&points, None, &TriangulateOptions::new())?;
+        assert_eq!(result.diagnostics.min, bounds.min());
+        assert_eq!(normalized_points.len(), points.len());
+        for (normalized, point) in normalized_points.iter().zip(&points) {
+            assert!((0. ..=1.).contains(&normalized.x) && (0. ..=1.).contains(&normalized.y));
+            let difference = bounds.denormalize(*normalized) - *point;
+            assert!(difference.x.abs() < 1e-3 && difference.y.abs() < 1e-5);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sheared_grid_is_triangulated_in_the_unsheared_frame() -> Result<(), CustomError> {
+        // a square grid sheared along x, and the matrix that shears it back
+        let shear = |point: Vector| Vector::new(point.x + 3. * point.y, point.y);
+        let unshear = |point: Vector| Vector::new(point.x - 3. * point.y, point.y);
+        let points: Vec<Vector> = (0..36)
+            .map(|i| shear(Vector::new((i % 6) as f32, (i / 6) as f32)))
+            .collect();
+        let options = TriangulateOptions::new().with_pre_transform([1., -3., 0., 1.]);
+        let result = triangulate_with_options(&points, None, &options)?;
+
+        assert_eq!(result.len(), 2 * 5 * 5);
+        // the output is in the frame of the input
+        for vertex in &result.vertices {
+            assert!(points.iter().any(
+                |point| (*point - *vertex).x.abs() < 1e-4 && (*point - *vertex).y.abs() < 1e-4
+            ));
+        }
+        for triangle in result.iter_triangles() {
+            // counter-clockwise, with the area of half a grid cell, as the shear keeps areas
+            assert!((calculate_triangle_area(&triangle) - 0.5).abs() < 1e-3);
+            for edge in 0..3 {
+                let length =
+                    (unshear(triangle.p(edge)) - unshear(triangle.p((edge + 1) % 3))).length();
+                assert!(length < 1.5, "{triangle:?} is not made of grid cells");
+            }
+        }
+        // in the sheared frame, the delaunay triangles are not halves of the grid cells
+        let sheared = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        assert!(sheared.iter_triangles().any(|triangle| {
+            (0..3).any(|edge| {
+                (unshear(triangle.p(edge)) - unshear(triangle.p((edge + 1) % 3))).length() > 1.5
+            })
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn near_coincident_points_at_a_grid_corner_are_both_inserted() -> Result<(), CustomError> {
+        // 18 points in the unit square give a grid of 2 x 2 cells, whose corner is at 0.5 / 0.99 after the normalization
+        let corner = 0.5 / 0.99;
+        let near_points = [
+            Vector::new(corner - 5e-6, corner - 5e-6),
+            Vector::new(corner + 5e-6, corner + 5e-6),
+        ];
+        let mut points: Vec<Vector> = random_points_in_a_box(12, 7)
+            .iter()
+            .map(|point| *point / 100.)
+            .collect();
+        points.extend(near_points);
+        let mut swapped = points.clone();
+        swapped.swap(points.len() - 2, points.len() - 1);
+
+        for algorithm in [
+            TriangulationAlgorithm::Incremental,
+            TriangulationAlgorithm::DivideAndConquer,
+        ] {
+            let options = TriangulateOptions::new().with_algorithm(algorithm);
+            let result = triangulate_with_options(&points, None, &options)?;
+            assert_eq!(result.vertices.len(), points.len());
+            for point in near_points {
+                assert!(result.vertices.contains(&point));
+            }
+            assert!(is_delaunay(&result));
+            let swapped_result = triangulate_with_options(&swapped, None, &options)?;
+            assert_same_mesh(&result, &swapped_result);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn divide_and_conquer_gives_the_same_triangles_as_incremental() -> Result<(), CustomError> {
+        let divide_and_conquer =
+            TriangulateOptions::new().with_algorithm(TriangulationAlgorithm::DivideAndConquer);
+        for (count, seed) in [(0, 1), (1, 2), (5, 3), (100, 4), (5000, 5)] {
+            let points = random_points_in_a_box(count, seed);
+            let incremental = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+            let merged = triangulate_with_options(&points, None, &divide_and_conquer)?;
+            assert_same_mesh(&merged, &incremental);
+            // no triangle refers to the unused supertriangle
+            assert_eq!(merged.vertices.len(), points.len());
+        }
+
+        // the constrained edges of the holes are forced in the same way
+        let points = random_points_in_a_box(2000, 6);
+        let hole: Vec<Vector> = [(40., 40.), (60., 45.), (55., 60.), (35., 55.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let holes = std::slice::from_ref(&hole);
+        let incremental =
+            triangulate_with_options(&points, Some(holes), &TriangulateOptions::new())?;
+        let merged = triangulate_with_options(&points, Some(holes), &divide_and_conquer)?;
+        assert_same_mesh(&merged, &incremental);
+        Ok(())
+    }
+
+    #[test]
+    fn hole_search_stops_at_the_hull() -> Result<(), CustomError> {
+        let points: Vec<Vector> = [
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            (3., 3.),
+            (7., 2.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect();
+        // clockwise, so the search spreads outwards from the hole and reaches the hull
+        let hole: Vec<Vector> = [(0., 0.), (5., 9.), (9., 5.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let holes = std::slice::from_ref(&hole);
+        let options =
+            TriangulateOptions::new().with_algorithm(TriangulationAlgorithm::DivideAndConquer);
+        let merged = triangulate_with_options(&points, Some(holes), &options)?;
+        let incremental =
+            triangulate_with_options(&points, Some(holes), &TriangulateOptions::new())?;
+        assert_same_mesh(&merged, &incremental);
+        Ok(())
+    }
+
+    #[test]
+    fn divide_and_conquer_keeps_every_corner_of_a_square() -> Result<(), CustomError> {
+        // the corners of the square are co-circular, which rounding used to turn into deleted edges
+        let points: Vec<Vector> = [(0., 0.), (7., 7.), (1., 0.), (0., 1.), (1., 1.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let options =
+            TriangulateOptions::new().with_algorithm(TriangulationAlgorithm::DivideAndConquer);
+        let result = triangulate_with_options(&points, None, &options)?;
+        let area: f32 = result
+            .iter_triangles()
+            .map(|triangle| calculate_triangle_area(&triangle))
+            .sum();
+        assert_eq!(result.len(), 4);
+        assert!((area - 7.).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn tesselation_keeps_the_hull_next_to_a_steiner_point_close_to_it() -> Result<(), CustomError> {
+        // a Steiner point lands just inside of the hull edge from (0, 0) to (70.1, 54.2),
+        // whose flat triangle used to lose against a vertex of the supertriangle
+        let points: Vec<Vector> = [
+            (0., 0.),
+            (100., 100.),
+            (70.14782, 54.19615),
+            (56.43863, 43.906944),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect();
+        let options = TriangulateOptions::new().with_maximum_triangle_area(392.6);
+        let result = triangulate_with_options(&points, None, &options)?;
+        let area: f32 = result
+            .iter_triangles()
+            .map(|triangle| calculate_triangle_area(&triangle))
+            .sum();
+        assert!((area - 797.583).abs() < 1e-2, "{area}");
+        assert!(is_delaunay(&result));
+        Ok(())
+    }
+
+    #[test]
+    fn points_on_a_circle_around_their_center() -> Result<(), CustomError> {
+        let points: Vec<Vector> = [(1., 0.), (0., 1.), (-1., 0.), (0., -1.), (0., 0.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let result = triangulate_with_options(&points, None, &TriangulateOptions::new())?;
+        assert_eq!(result.len(), 4);
+        let center = result
+            .vertices
+            .iter()
+            .position(|vertex| *vertex == Vector::new(0., 0.))
+            .unwrap();
+        assert!(result
+            .triangles
+            .iter()
+            .all(|triangle| triangle.contains(&center)));
+        // the center is on both diagonals and the corners are on one circle
+        assert!(result.statistics.orientation_fallbacks > 0);
+        assert!(result.statistics.circumcircle_fallbacks > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn dedup_input_reports_removed_points() -> Result<(), CustomError> {
+        let mut points = unit_square();
+        points.insert(2, points[0]);
+        points.push(points[1]);
+        points.push(points[0]);
+        points.push(Vector::new(-0., 0.));
+
+        let options = TriangulateOptions::new().with_dedup_input(true);
+        let deduplicated = triangulate_with_options(&points, None, &options)?;
+        let expected = triangulate_with_options(&unit_square(), None, &TriangulateOptions::new())?;
+
+        assert_eq!(deduplicated.removed_duplicates, 4);
+        assert_eq!(expected.removed_duplicates, 0);
+        assert_eq!(deduplicated.vertices, expected.vertices);
+        assert_eq!(deduplicated.triangles, expected.triangles);
+        Ok(())
+    }
+
+    #[test]
+    fn dedup_summary_points_at_the_first_occurrences() -> Result<(), CustomError> {
+        // 0 and 4 are the same, 1, 5 and 6 are the same, 7 is a few ulps away from 3
+        let mut points = unit_square();
+        points.push(points[0]);
+        points.push(points[1]);
+        points.push(points[1]);
+        points.push(Vector::new(1f32.next_down(), 1.));
+
+        for dedup_input in [false, true] {
+            let options = TriangulateOptions::new().with_dedup_input(dedup_input);
+            let result = triangulate_with_options(&points, None, &options)?;
+            let summary = &result.dedup_summary;
+            assert_eq!(summary.input_count, 8);
+            assert_eq!(summary.unique_count, 4);
+            assert_eq!(result.vertices.len(), 4);
+            let mut first_occurrences = summary.first_occurrence.clone();
+            first_occurrences.sort();
+            assert_eq!(first_occurrences, [0, 1, 2, 3]);
+            for (vertex, input_index) in summary.first_occurrence.iter().enumerate() {
+                assert_eq!(result.vertices[vertex], points[*input_index]);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn dedup_summary_only_covers_the_input_points() -> Result<(), CustomError> {
+        let points = unit_square();
+        let hole = vec![
+            Vector::new(0.4, 0.4),
+            Vector::new(0.6, 0.4),
+            Vector::new(0.5, 0.6),
+        ];
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.05);
+        let result = triangulate_with_options(&points, Some(&[hole]), &options)?;
+        assert_eq!(result.dedup_summary.input_count, 4);
+        assert_eq!(result.dedup_summary.unique_count, 4);
+        assert!(result.vertices.len() > 7);
+        for (vertex, input_index) in result.dedup_summary.first_occurrence.iter().enumerate() {
+            assert_eq!(result.vertices[vertex], points[*input_index]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn pslg_keeps_open_segments_and_removes_the_seeded_region() -> Result<(), CustomError> {
+        let vertices: Vec<Vector> = [
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            // a closed square around the hole seed
+            (2., 2.),
+            (4., 2.),
+            (4., 4.),
+            (2., 4.),
+            // an open polyline that is not part of any hole
+            (6., 2.),
+            (8., 5.),
+            (6., 8.),
+            (5., 6.),
+            (7., 3.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect();
+        let segments = [(4, 5), (5, 6), (6, 7), (7, 4), (8, 9), (9, 10)];
+        let result = triangulate_pslg(&vertices, &segments, Some(&[Vector::new(3., 3.)]))?;
+
+        let edges: Vec<[usize; 2]> = result
+            .triangles
+            .iter()
+            .flat_map(|triangle| (0..3).map(|i| [triangle[i], triangle[(i + 1) % 3]]))
+            .collect();
+        let output_index = |input: usize| {
+            result
+                .vertices
+                .iter()
+                .position(|vertex| *vertex == vertices[input])
+                .unwrap()
+        };
+        for (start, end) in segments {
+            let (start, end) = (output_index(start), output_index(end));
+            assert!(edges.contains(&[start, end]) || edges.contains(&[end, start]));
+        }
+
+        let area: f32 = result
+            .to_triangles()
+            .iter()
+            .map(calculate_triangle_area)
+            .sum();
+        assert!((area - 96.).abs() < 1e-3);
+        assert_eq!(result.hole_reports.len(), 1);
+        assert_eq!(result.hole_reports[0].removed_triangles, 2);
+        assert!(is_delaunay(&result));
+        Ok(())
+    }
+
+    #[test]
+    fn pslg_segment_to_a_missing_vertex_is_rejected() {
+        let result = triangulate_pslg(&unit_square(), &[(0, 4)], None);
+        assert!(matches!(result, Err(CustomError::InvalidSegment(0, 4))));
+    }
+
+    #[test]
+    fn iter_triangles_matches_to_triangles() -> Result<(), CustomError> {
+        let points: Vec<Vector> = [
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            (3., 6.),
+            (7., 2.),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect();
+        let hole: Vec<Vector> = [(4., 4.), (6., 4.), (5., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let result = triangulate_with_options(&points, Some(&[hole]), &TriangulateOptions::new())?;
+        assert!(result.hole_reports[0].removed_triangles > 0);
+        let corners = |triangle: Triangle| [0, 1, 2].map(|i| triangle.p(i));
+        let iterated: Vec<[Vector; 3]> = result.iter_triangles().map(corners).collect();
+        let collected: Vec<[Vector; 3]> = result.to_triangles().into_iter().map(corners).collect();
+        assert_eq!(iterated, collected);
+        Ok(())
+    }
+
+    #[test]
+    fn closed_boundary_leaves_out_the_concave_corner() -> Result<(), CustomError> {
+        // an L, which is not closed back to its first point
+        let outline: Vec<Vector> = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let area = |result: &TriangulationResult| -> f32 {
+            result
+                .iter_triangles()
+                .map(|triangle| calculate_triangle_area(&triangle))
+                .sum()
+        };
+
+        let hull = triangulate_with_options(&outline, None, &TriangulateOptions::new())?;
+        assert!((area(&hull) - 3.5).abs() < 1e-5);
+
+        let closed = TriangulateOptions::new().with_assume_closed_boundary(true);
+        for options in [
+            closed.clone(),
+            closed.clone().with_maximum_triangle_area(0.1),
+            closed.with_algorithm(TriangulationAlgorithm::DivideAndConquer),
+        ] {
+            let result = triangulate_with_options(&outline, None, &options)?;
+            assert!((area(&result) - 3.).abs() < 1e-4, "{options:?}");
+            assert!(result.iter_triangles().all(|triangle| {
+                let center = (triangle.p(0) + triangle.p(1) + triangle.p(2)) / 3.;
+                center.x < 1. || center.y < 1.
+            }));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn constraint_traces_reproduce_the_input_edges() -> Result<(), CustomError> {
+        // every coordinate survives the normalization of the 0..8 square exactly
+        let square: Vec<Vector> = [(0., 0.), (8., 0.), (8., 8.), (0., 8.), (4., 2.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        // (4, 2) lies on the first edge of the hole
+        let hole: Vec<Vector> = [(2., 2.), (6., 2.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let holes = [hole.clone()];
+        let result = triangulate_with_options(&square, Some(&holes), &TriangulateOptions::new())?;
+        let is_edge = |a: usize, b: usize| {
+            result
+                .triangles
+                .iter()
+                .any(|triangle| triangle.contains(&a) && triangle.contains(&b))
+        };
+
+        assert_eq!(result.constrained_edges.len(), 3);
+        for (j, trace) in result.constrained_edges.iter().enumerate() {
+            assert_eq!(
+                trace.source,
+                ConstraintSource::HoleEdge { hole: 0, edge: j }
+            );
+            assert_eq!(result.vertices[trace.vertices[0]], hole[j]);
+            assert_eq!(
+                result.vertices[*trace.vertices.last().unwrap()],
+                hole[(j + 1) % 3]
+            );
+            assert!(trace
+                .vertices
+                .windows(2)
+                .all(|pair| is_edge(pair[0], pair[1])));
+        }
+        let split = &result.constrained_edges[0].vertices;
+        assert_eq!(split.len(), 3);
+        assert_eq!(result.vertices[split[1]], Vector::new(4., 2.));
+
+        // a segment through the middle of the square is split at its center point
+        let mut vertices = square.clone();
+        vertices[4] = Vector::new(4., 4.);
+        let result = triangulate_pslg(&vertices, &[(0, 2)], None)?;
+        assert_eq!(result.constrained_edges.len(), 1);
+        let trace = &result.constrained_edges[0];
+        assert_eq!(trace.source, ConstraintSource::Segment(0));
+        let points: Vec<Vector> = trace
+            .vertices
+            .iter()
+            .map(|vertex| result.vertices[*vertex])
+            .collect();
+        assert_eq!(points, [vertices[0], vertices[4], vertices[2]]);
+        Ok(())
+    }
+
+    /// `n` points of a fibonacci lattice in the square from 0 to 1000, generated one after another.
+    fn fibonacci_lattice(n: usize) -> impl ExactSizeIterator<Item = Vector> {
+        let golden_ratio = (1. + 5f64.sqrt()) / 2.;
+        (0..n).map(move |i| {
+            let x = (i as f64 * golden_ratio).fract();
+            let y = (i as f64 + 0.5) / n as f64;
+            Vector::new(x as f32 * 1000., y as f32 * 1000.)
+        })
+    }
+
+    #[test]
+    fn large_iterator_with_bounds_triangulates_like_the_collected_points() -> Result<(), CustomError>
+    {
+        let n = 20_000;
+        let options =
+            TriangulateOptions::new().with_bounds(Vector::new(0., 0.), Vector::new(1000., 1000.));
+        let streamed = triangulate_from_iter(fibonacci_lattice(n), None, &options)?;
+        assert_eq!(streamed.vertices.len(), n);
+        assert!(is_delaunay(&streamed));
+
+        let points: Vec<Vector> = fibonacci_lattice(n).collect();
+        let collected = triangulate_with_options(&points, None, &options)?;
+        assert_eq!(streamed.vertices, collected.vertices);
+        assert_eq!(streamed.triangles, collected.triangles);
+        Ok(())
+    }
+
+    #[test]
+    fn iterator_without_bounds_is_collected() -> Result<(), CustomError> {
+        let points: Vec<Vector> = fibonacci_lattice(500).collect();
+        let options = TriangulateOptions::new().with_dedup_input(true);
+        let streamed = triangulate_from_iter(points.iter().copied(), None, &options)?;
+        let collected = triangulate_with_options(&points, None, &options)?;
+        assert_eq!(streamed.triangles, collected.triangles);
+        assert_eq!(streamed.diagnostics, collected.diagnostics);
+        Ok(())
+    }
+
+    #[test]
+    fn points_outside_of_the_bounds_are_rejected() {
+        let options =
+            TriangulateOptions::new().with_bounds(Vector::new(0., 0.), Vector::new(10., 10.));
+        let mut points = unit_square();
+        points.push(Vector::new(5., 10.5));
+        assert!(matches!(
+            triangulate_from_iter(points.iter().copied(), None, &options),
+            Err(CustomError::PointOutsideBounds(point)) if point == Vector::new(5., 10.5)
+        ));
+        assert!(matches!(
+            triangulate_with_options(&points, None, &options),
+            Err(CustomError::PointOutsideBounds(_))
+        ));
+    }
+
+    #[test]
+    fn too_few_or_non_finite_points_are_rejected_instead_of_panicking() {
+        let options = TriangulateOptions::new();
+        for points in [Vec::new(), vec![Vector::new(1., 1.)]] {
+            assert!(matches!(
+                triangulate_with_options(&points, None, &options),
+                Err(CustomError::TooFewPoints(len)) if len == points.len()
+            ));
+        }
+        // a duplicate only counts once when the duplicates are removed
+        let with_duplicate = [(0., 0.), (0., 0.), (2., 2.)].map(|p| Vector::new(p.0, p.1));
+        assert!(matches!(
+            triangulate_with_options(
+                &with_duplicate,
+                None,
+                &options.clone().with_dedup_input(true)
+            ),
+            Err(CustomError::TooFewPoints(2))
+        ));
+
+        let mut with_nan = unit_square();
+        with_nan.insert(1, Vector::new(f32::NAN, 1.));
+        assert!(matches!(
+            triangulate_with_options(&with_nan, None, &options),
+            Err(CustomError::NonFinitePoint(point)) if point.x.is_nan()
+        ));
+        let hole = vec![
+            Vector::new(0.2, 0.2),
+            Vector::new(f32::INFINITY, 0.2),
+            Vector::new(0.5, 0.8),
+        ];
+        assert!(matches!(
+            triangulate_with_options(&unit_square(), Some(&[hole]), &options),
+            Err(CustomError::NonFinitePoint(point)) if point.x == f32::INFINITY
+        ));
+        let bounded = options.with_bounds(Vector::new(0., 0.), Vector::new(1., 1.));
+        assert!(matches!(
+            triangulate_from_iter(with_nan.iter().copied(), None, &bounded),
+            Err(CustomError::NonFinitePoint(_))
+        ));
+        assert!(matches!(
+            triangulate_from_iter(std::iter::empty(), None, &bounded),
+            Err(CustomError::TooFewPoints(0))
+        ));
+    }
+
+    #[test]
+    fn vertex_count_never_exceeds_max_vertices() -> Result<(), CustomError> {
+        let mut points = unit_square();
+        points.extend(random_points(300, 5));
+        let hole: Vec<Vector> = [(0.4, 0.4), (0.6, 0.4), (0.6, 0.6), (0.4, 0.6)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let holes = [hole];
+        for max_vertices in [7, 50, 200, 303, 400, 2000] {
+            for maximum_triangle_area in [None, Some(0.001)] {
+                let mut options = TriangulateOptions::new().with_max_vertices(max_vertices);
+                options.maximum_triangle_area = maximum_triangle_area;
+                let result = triangulate_with_options(&points, Some(&holes), &options)?;
+                assert!(
+                    result.vertices.len() <= max_vertices,
+                    "{} vertices for a limit of {max_vertices}",
+                    result.vertices.len()
+                );
+                // the first points are kept
+                let kept = (max_vertices - 4).min(points.len());
+                assert!(points[..kept]
+                    .iter()
+                    .all(|point| result.vertices.contains(point)));
+                let skipped = (points.len() + 4).saturating_sub(max_vertices);
+                assert_eq!(
+                    result.warnings.contains(&Warning::VertexLimitReached {
+                        skipped_points: skipped
+                    }),
+                    skipped > 0
+                );
+            }
+        }
+
+        let too_small = TriangulateOptions::new().with_max_vertices(6);
+        assert!(matches!(
+            triangulate_with_options(&points, Some(&holes), &too_small),
+            Err(CustomError::InvalidParameter("max_vertices", _))
+        ));
+
+        // the end points of the segments are kept ahead of the cut, these are left of the hole
+        let mut left_of_hole = (0..points.len()).rev().filter(|i| points[*i].x < 0.4);
+        let segment = (left_of_hole.next().unwrap(), left_of_hole.next().unwrap());
+        let options = TriangulateOptions::new()
+            .with_max_vertices(10)
+            .with_segments(vec![segment]);
+        let result = triangulate_with_options(&points, Some(&holes), &options)?;
+        assert_eq!(result.vertices.len(), 10);
+        assert!([segment.0, segment.1]
+            .iter()
+            .all(|end| result.vertices.contains(&points[*end])));
+        assert!(result
+            .constrained_edges
+            .iter()
+            .any(|trace| trace.source == ConstraintSource::Segment(0)));
+        let too_small = options.with_segments(vec![(4, 5), (6, 7), (8, 9), (10, 11)]);
+        assert!(matches!(
+            triangulate_with_options(&points, Some(&holes), &too_small),
+            Err(CustomError::InvalidParameter("max_vertices", _))
+        ));
+
+        // the outline of a closed boundary is never cut
+        let outline: Vec<Vector> = [
+            (0., 0.),
+            (0.5, 0.),
+            (1., 0.),
+            (1., 0.5),
+            (1., 1.),
+            (0.5, 1.),
+            (0., 1.),
+            (0., 0.5),
+        ]
+        .iter()
+        .map(Vector::from)
+        .collect();
+        let closed = TriangulateOptions::new().with_assume_closed_boundary(true);
+        let result =
+            triangulate_with_options(&outline, None, &closed.clone().with_max_vertices(8))?;
+        assert_eq!(result.vertices.len(), 8);
+        assert!(matches!(
+            triangulate_with_options(&outline, None, &closed.with_max_vertices(5)),
+            Err(CustomError::InvalidParameter("max_vertices", _))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn hole_seed_in_a_square_of_segments_is_the_same_as_a_hole_polygon() -> Result<(), CustomError>
+    {
+        let outline: Vec<Vector> = [(0., 0.), (10., 0.), (10., 10.), (0., 10.), (2., 7.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let square: Vec<Vector> = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let mut points = outline.clone();
+        points.extend(&square);
+        let polygon_hole =
+            triangulate_with_options(&outline, Some(&[square]), &TriangulateOptions::new())?;
+
+        let options = TriangulateOptions::new()
+            .with_segments(vec![(5, 6), (6, 7), (7, 8), (8, 5)])
+            .with_hole_seeds(vec![Vector::new(5., 5.)]);
+        let seeded_hole = triangulate_with_options(&points, None, &options)?;
+
+        assert_same_mesh(&seeded_hole, &polygon_hole);
+        assert_eq!(
+            seeded_hole.hole_reports[0].removed_triangles,
+            polygon_hole.hole_reports[0].removed_triangles
+        );
+        assert!(seeded_hole.warnings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn hole_seeds_outside_of_the_mesh_or_in_a_hole_are_reported() -> Result<(), CustomError> {
+        let square: Vec<Vector> = [(4., 4.), (6., 4.), (6., 6.), (4., 6.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let mut outline = unit_square();
+        outline.iter_mut().for_each(|point| *point = *point * 10.);
+        let options = TriangulateOptions::new().with_hole_seeds(vec![
+            Vector::new(20., 5.),
+            Vector::new(5., 5.),
+            Vector::new(1., 1.),
+            Vector::new(9., 9.),
+        ]);
+        let result = triangulate_with_options(&outline, Some(&[square]), &options)?;
+
+        assert_eq!(
+            result.warnings,
+            [
+                Warning::HoleSeedOutsideMesh { seed: 0 },
+                Warning::HoleSeedInRemovedRegion { seed: 1 },
+                Warning::HoleSeedInRemovedRegion { seed: 3 },
+            ]
+        );
+        // the third seed removes everything around the polygon hole
+        assert_eq!(result.hole_reports.len(), 5);
+        assert!(result.hole_reports[3].removed_triangles > 0);
+        assert!(result.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn preserved_input_order_lists_the_input_points_first() -> Result<(), CustomError> {
+        let mut points: Vec<Vector> = random_points(200, 9)
+            .into_iter()
+            .map(|point| point * 10.)
+            .collect();
+        points.extend(vectors(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]));
+        let holes = [vectors(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)])];
+        for algorithm in [
+            TriangulationAlgorithm::Incremental,
+            TriangulationAlgorithm::DivideAndConquer,
+        ] {
+            let options = TriangulateOptions::new()
+                .with_preserve_input_order(true)
+                .with_algorithm(algorithm)
+                .with_maximum_triangle_area(1.);
+            let result = triangulate_with_options(&points, Some(&holes), &options)?;
+            assert_eq!(result.vertices[..points.len()], points);
+            assert!(result.vertices.len() > points.len() + holes[0].len());
+            assert!(is_manifold(&result));
+            assert!(is_delaunay(&result));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn preserved_input_order_skips_duplicates() -> Result<(), CustomError> {
+        let points = vectors(&[(0., 0.), (3., 1.), (0., 0.), (2., 4.), (3., 1.), (-1., 2.)]);
+        let options = TriangulateOptions::new().with_preserve_input_order(true);
+        let result = triangulate_with_options(&points, None, &options)?;
+        assert_eq!(result.dedup_summary.first_occurrence, [0, 1, 3, 5]);
+        for (vertex, input) in result.dedup_summary.first_occurrence.iter().enumerate() {
+            assert_eq!(result.vertices[vertex], points[*input]);
+        }
+        assert_eq!(result.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn triangulating_into_the_same_buffer_reuses_its_capacity() -> Result<(), CustomError> {
+        let options = TriangulateOptions::new().with_maximum_triangle_area(0.01);
+        let mut triangles = Vec::new();
+        triangulate_into(&random_points(100, 1), None, &options, &mut triangles)?;
+        let capacity = triangles.capacity();
+        let buffer = triangles.as_ptr();
+
+        let points = random_points(50, 2);
+        triangulate_into(&points, None, &options, &mut triangles)?;
+        assert!(triangles.len() <= capacity);
+        assert_eq!(triangles.capacity(), capacity);
+        assert_eq!(triangles.as_ptr(), buffer);
+        let corners = |triangles: &[Triangle]| -> Vec<[Vector; 3]> {
+            triangles
+                .iter()
+                .map(|triangle| [0, 1, 2].map(|i| triangle.p(i)))
+                .collect()
+        };
+        let expected = triangulate_with_options(&points, None, &options)?.to_triangles();
+        assert_eq!(corners(&triangles), corners(&expected));
+
+        assert!(
+            triangulate_into(&points, None, &options.with_max_vertices(2), &mut triangles).is_err()
+        );
+        assert!(triangles.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn triangulate_returns_the_triangles_of_the_result() -> Result<(), CustomError> {
+        let outline = vectors(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let holes = [vectors(&[(4., 4.), (6., 4.), (6., 6.), (4., 6.)])];
+        let triangles = triangulate(&outline, Some(&holes), Some(2.))?;
+        let options = TriangulateOptions::new().with_maximum_triangle_area(2.);
+        let expected = triangulate_with_options(&outline, Some(&holes), &options)?.to_triangles();
+        let corners = |triangles: &[Triangle]| -> Vec<[Vector; 3]> {
+            triangles
+                .iter()
+                .map(|triangle| [0, 1, 2].map(|i| triangle.p(i)))
+                .collect()
+        };
+        assert_eq!(corners(&triangles), corners(&expected));
+        let area: f32 = triangles.iter().map(calculate_triangle_area).sum();
+        assert!((area - 96.).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn clustered_points_are_triangulated_correctly() -> Result<(), CustomError> {
+        // nearly all points fall into one cell of the grid, which is split until its parts are small
+        let mut points: Vec<Vector> = random_points(3000, 4)
+            .into_iter()
+            .map(|point| Vector::new(0.7, 0.2) + point * 0.001)
+            .collect();
+        points.extend(random_points(40, 5));
+        let adaptive = TriangulateOptions::new().with_adaptive_binning(true);
+        let result = triangulate_with_options(&points, None, &adaptive)?;
+        assert_eq!(result.vertices.len(), points.len());
+        assert!(is_manifold(&result));
+        // the same triangles, inserted in other orders
+        let brio = adaptive
+            .clone()
+            .with_insertion_order(InsertionOrder::Brio { seed: 1 });
+        let divide_and_conquer =
+            TriangulateOptions::new().with_algorithm(TriangulationAlgorithm::DivideAndConquer);
+        for options in [TriangulateOptions::new(), brio, divide_and_conquer] {
+            assert_same_mesh(&result, &triangulate_with_options(&points, None, &options)?);
+        }
+        Ok(())
+    }
+
+    /// A dense cluster in the middle of the unit square, whose edges are much shorter than the distances
+    /// that an absolute tolerance on the orientation determinant would count as being on them.
+    fn cluster_in_a_square(count: usize, seed: u64) -> Vec<Vector> {
+        let mut points: Vec<Vector> = random_points(count, seed)
+            .into_iter()
+            .map(|point| Vector::new(0.5, 0.5) + point * 0.001)
+            .collect();
+        points.extend(vectors(&[(0., 0.), (1., 0.), (1., 1.), (0., 1.)]));
+        points
+    }
+
+    #[test]
+    fn walks_through_a_dense_cluster_find_the_right_triangles() -> Result<(), CustomError> {
+        let points = cluster_in_a_square(200, 8);
+        for options in [
+            TriangulateOptions::new(),
+            TriangulateOptions::new().with_insertion_algorithm(InsertionAlgorithm::BowyerWatson),
+        ] {
+            let result = triangulate_with_options(&points, None, &options)?;
+            assert_eq!(result.vertices.len(), points.len());
+            assert!(is_manifold(&result));
+            // a walk that ends in the wrong triangle leaves flipped or flat triangles behind
+            for triangle in &result {
+                let [a, b, c] = triangle
+                    .points
+                    .map(|point| (point.x as f64, point.y as f64));
+                let orientation = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+                assert!(orientation > 0., "triangle {}", triangle.index);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn segments_inside_of_a_dense_cluster_are_inserted() -> Result<(), CustomError> {
+        let mut points = cluster_in_a_square(200, 9);
+        let first = points.len();
+        // a closed chain of segments around a part of the cluster
+        points.extend((0..8).map(|i| {
+            let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+            Vector::new(0.5005 + 0.0002 * angle.cos(), 0.5005 + 0.0002 * angle.sin())
+        }));
+        let segments: Vec<(usize, usize)> = (first..points.len())
+            .map(|start| {
+                (
+                    start,
+                    if start + 1 == points.len() {
+                        first
+                    } else {
+                        start + 1
+                    },
+                )
+            })
+            .collect();
+        let options = TriangulateOptions::new()
+            .with_segments(segments.clone())
+            .with_preserve_input_order(true);
+        let result = triangulate_with_options(&points, None, &options)?;
+        assert!(is_manifold(&result));
+        let edges: HashSet<(usize, usize)> = result
+            .triangles
+            .iter()
+            .flat_map(|triangle| (0..3).map(|i| (triangle[i], triangle[(i + 1) % 3])))
+            .collect();
+        let traces: Vec<&ConstraintTrace> = result
+            .constrained_edges
+            .iter()
+            .filter(|trace| matches!(trace.source, ConstraintSource::Segment(_)))
+            .collect();
+        assert_eq!(traces.len(), segments.len());
+        for trace in traces {
+            let ConstraintSource::Segment(segment) = trace.source else {
+                unreachable!()
+            };
+            let (start, end) = segments[segment];
+            assert_eq!(trace.vertices.first(), Some(&start), "{trace:?}");
+            assert_eq!(trace.vertices.last(), Some(&end), "{trace:?}");
+            for edge in trace.vertices.windows(2) {
+                assert!(
+                    edges.contains(&(edge[0], edge[1])) || edges.contains(&(edge[1], edge[0])),
+                    "{trace:?}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn conforming_triangulation_is_delaunay_across_the_constraints() -> Result<(), CustomError> {
+        let points = vectors(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (0., 10.),
+            (0., 5.),
+            (10., 5.),
+            // close to both sides of the segment, inside of the circumcircles across it
+            (5., 4.6),
+            (5., 5.4),
+            (2., 2.),
+            (8., 2.),
+            (8., 8.),
+            (2., 8.),
+        ]);
+        let holes = [vectors(&[(3., 6.5), (6., 7.), (6., 7.6), (3., 7.1)])];
+        let options = TriangulateOptions::new().with_segments(vec![(4, 5)]);
+        let constrained = triangulate_with_options(&points, Some(&holes), &options)?;
+        assert!(is_delaunay(&constrained));
+        assert!(!is_conforming_delaunay(&constrained));
+
+        let result =
+            triangulate_with_options(&points, Some(&holes), &options.with_conforming(true))?;
+        assert!(is_conforming_delaunay(&result));
+        assert!(is_manifold(&result));
+        assert!(result.vertices.len() > constrained.vertices.len());
+        let area: f32 = result
+            .to_triangles()
+            .iter()
+            .map(calculate_triangle_area)
+            .sum();
+        assert!((area - (100. - 1.8)).abs() < 1e-3);
+        // the segment is split into pieces along it
+        let segment = &result.constrained_edges[0];
+        assert_eq!(segment.source, ConstraintSource::Segment(0));
+        assert!(segment.vertices.len() > 2);
+        let ends = [
+            segment.vertices[0],
+            segment.vertices[segment.vertices.len() - 1],
+        ];
+        assert_eq!(
+            ends.map(|vertex| result.vertices[vertex]),
+            [points[4], points[5]]
+        );
+        for vertex in &segment.vertices {
+            assert!((result.vertices[*vertex].y - 5.).abs() < 1e-4);
+        }
+        Ok(())
+    }
+}
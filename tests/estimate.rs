@@ -0,0 +1,94 @@
+//! Compares the estimate with the real triangulations, measuring the memory with a counting allocator.
+//! The allocator counts for the whole test binary, so this file has only one test.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use constrained_denaulay_triangulation::{
+    estimate, region_areas, triangulate_with_options, CustomError, TriangulateOptions, Vector,
+};
+
+/// The estimate has to be at most this factor above or below the real values.
+const ACCURACY: f32 = 2.;
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT_BYTES.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK_BYTES.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The `index`th number of the van der Corput sequence in `base`, spread evenly over `0.0..1.0`.
+fn van_der_corput(mut index: usize, base: usize) -> f32 {
+    let (mut value, mut scale) = (0., 1.);
+    while index > 0 {
+        scale /= base as f32;
+        value += (index % base) as f32 * scale;
+        index /= base;
+    }
+    value
+}
+
+/// `n` points of the halton sequence in the square from 0 to 10, with the corners of the square.
+fn halton_points(n: usize) -> Vec<Vector> {
+    let mut points: Vec<Vector> = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)]
+        .iter()
+        .map(Vector::from)
+        .collect();
+    points.extend((1..=n).map(|i| Vector::new(van_der_corput(i, 2), van_der_corput(i, 3)) * 10.));
+    points
+}
+
+fn assert_within_accuracy(name: &str, estimated: usize, real: usize) {
+    let ratio = estimated as f32 / real as f32;
+    assert!(
+        (1. / ACCURACY..=ACCURACY).contains(&ratio),
+        "{name}: estimated {estimated}, but was {real}"
+    );
+}
+
+#[test]
+fn estimates_are_close_to_the_real_triangulations() -> Result<(), CustomError> {
+    let cases = [
+        (100, None),
+        (2000, None),
+        (20000, None),
+        (10, Some(1.)),
+        (10, Some(0.05)),
+        (500, Some(0.01)),
+    ];
+    for (n, maximum_triangle_area) in cases {
+        let points = halton_points(n);
+        let mut options = TriangulateOptions::new();
+        if let Some(maximum_triangle_area) = maximum_triangle_area {
+            options = options.with_maximum_triangle_area(maximum_triangle_area);
+        }
+        let (area, _) = region_areas(&points, &[]);
+        let estimate = estimate(points.len(), area, &options);
+
+        let bytes_before = CURRENT_BYTES.load(Ordering::SeqCst);
+        PEAK_BYTES.store(bytes_before, Ordering::SeqCst);
+        let result = triangulate_with_options(&points, None, &options)?;
+        let peak_bytes = PEAK_BYTES.load(Ordering::SeqCst) - bytes_before;
+
+        let name = format!("{n} points with maximum area {maximum_triangle_area:?}");
+        assert_within_accuracy(&name, estimate.triangles, result.len());
+        assert_within_accuracy(&name, estimate.peak_bytes, peak_bytes);
+    }
+    Ok(())
+}
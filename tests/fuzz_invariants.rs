@@ -0,0 +1,335 @@
+//! Property based tests: for random inputs the triangulation either fails with the error that the input calls for,
+//! or returns a manifold, constrained Delaunay mesh that covers the convex hull without the holes.
+//! It never panics and never hangs.
+//!
+//! The points are normalized separately for x and y, and the mesh is only Delaunay for the normalized points.
+//! So every point cloud whose mesh is checked gets two opposite corners of a square around it,
+//! which makes the normalization a uniform scale. The inputs that have to fail get no corners.
+use std::{sync::mpsc, thread, time::Duration};
+
+use constrained_denaulay_triangulation::{
+    is_delaunay, is_manifold, region_areas, triangulate_with_options, CustomError,
+    InsertionAlgorithm, InsertionOrder, TriangulateOptions, TriangulationAlgorithm,
+    TriangulationResult, Vector,
+};
+use proptest::prelude::*;
+
+/// How long a single triangulation may take before it counts as a hang.
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Triangulates on another thread, so that a hang fails the test instead of blocking it.
+/// A panic on that thread is passed on.
+fn triangulate_in_time(
+    points: Vec<Vector>,
+    holes: Vec<Vec<Vector>>,
+    options: TriangulateOptions,
+) -> Result<TriangulationResult, CustomError> {
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        let holes = (!holes.is_empty()).then_some(&holes[..]);
+        let _ = sender.send(triangulate_with_options(&points, holes, &options));
+    });
+    match receiver.recv_timeout(TIMEOUT) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            std::panic::resume_unwind(worker.join().expect_err("the worker sent nothing"))
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => panic!("the triangulation did not finish in time"),
+    }
+}
+
+fn options() -> impl Strategy<Value = TriangulateOptions> {
+    (
+        prop_oneof![
+            Just(TriangulationAlgorithm::Incremental),
+            Just(TriangulationAlgorithm::DivideAndConquer),
+        ],
+        prop_oneof![
+            Just(InsertionAlgorithm::Flips),
+            Just(InsertionAlgorithm::BowyerWatson),
+        ],
+        prop_oneof![
+            Just(InsertionOrder::Spatial),
+            any::<u64>().prop_map(|seed| InsertionOrder::Brio { seed }),
+        ],
+    )
+        .prop_map(|(algorithm, insertion_algorithm, insertion_order)| {
+            TriangulateOptions::new()
+                .with_algorithm(algorithm)
+                .with_insertion_algorithm(insertion_algorithm)
+                .with_insertion_order(insertion_order)
+        })
+}
+
+fn point(range: std::ops::Range<f32>) -> impl Strategy<Value = Vector> {
+    (range.clone(), range).prop_map(|(x, y)| Vector::new(x, y))
+}
+
+/// A coordinate that is often NaN or infinite.
+fn maybe_non_finite_coordinate() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        3 => -10.0f32..10.0,
+        1 => Just(f32::NAN),
+        1 => Just(f32::INFINITY),
+        1 => Just(f32::NEG_INFINITY),
+    ]
+}
+
+/// The points with the lower left and the upper right corner of the square from `min` to `max` in front of them.
+fn in_square(points: Vec<Vector>, min: f32, max: f32) -> Vec<Vector> {
+    [Vector::new(min, min), Vector::new(max, max)]
+        .into_iter()
+        .chain(points)
+        .collect()
+}
+
+/// Points on a small grid, with many duplicates, collinear and co-circular points.
+/// In the square from 0 to 8 they are normalized exactly, so they stay collinear and co-circular.
+fn grid_point() -> impl Strategy<Value = Vector> {
+    (0..=8, 0..=8).prop_map(|(x, y)| Vector::new(x as f32, y as f32))
+}
+
+/// A counter-clockwise convex polygon around `center`, with its corners at the angles on a circle.
+fn convex_polygon(center: Vector, radius: f32, mut angles: Vec<f32>) -> Vec<Vector> {
+    angles.sort_by(f32::total_cmp);
+    angles.dedup();
+    angles
+        .into_iter()
+        .map(|angle| center + Vector::new(angle.cos(), angle.sin()) * radius)
+        .collect()
+}
+
+/// A counter-clockwise rectangle from `min` to `max`.
+fn rectangle(min: Vector, max: Vector) -> Vec<Vector> {
+    vec![
+        min,
+        Vector::new(max.x, min.y),
+        max,
+        Vector::new(min.x, max.y),
+    ]
+}
+
+/// Fails the test case with the error of a triangulation that should have worked.
+fn expect_ok(
+    result: Result<TriangulationResult, CustomError>,
+) -> Result<TriangulationResult, TestCaseError> {
+    result.map_err(|error| TestCaseError::fail(format!("{error:?}")))
+}
+
+/// A counter-clockwise polygon that is star-shaped around `center`, with one corner in each of the equal sectors
+/// around it, at the fraction of the sector and the distance. With at least four sectors the center is inside.
+/// Unlike the corners of [`convex_polygon`], they are not all on one circle,
+/// so the triangulation of its inside does not depend on rounding.
+fn star_polygon(center: Vector, corners: Vec<(f32, f32)>) -> Vec<Vector> {
+    let sector = std::f32::consts::TAU / corners.len() as f32;
+    corners
+        .into_iter()
+        .enumerate()
+        .map(|(i, (fraction, radius))| {
+            let angle = (i as f32 + fraction) * sector;
+            center + Vector::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+/// Checks the invariants of a successful triangulation.
+fn check_mesh(
+    result: &TriangulationResult,
+    points: &[Vector],
+    holes: &[Vec<Vector>],
+) -> Result<(), TestCaseError> {
+    let (_, hole_areas) = region_areas(points, holes);
+    check_mesh_without(result, points, hole_areas.iter().sum())
+}
+
+/// Checks the invariants of a successful triangulation whose holes cover `hole_area` together.
+fn check_mesh_without(
+    result: &TriangulationResult,
+    points: &[Vector],
+    hole_area: f32,
+) -> Result<(), TestCaseError> {
+    prop_assert!(is_manifold(result), "not manifold");
+    prop_assert!(is_delaunay(result), "not delaunay");
+    let mut area = 0.;
+    for triangle in result.iter_triangles() {
+        let (a, b, c) = (triangle.p(0), triangle.p(1), triangle.p(2));
+        let twice_area = (b - a).cross_product(c - a);
+        prop_assert!(twice_area > 0., "flat or clockwise triangle {:?}", triangle);
+        area += twice_area / 2.;
+    }
+    let (hull_area, _) = region_areas(points, &[]);
+    let expected = hull_area - hole_area;
+    prop_assert!(
+        (area - expected).abs() <= hull_area * 1e-3,
+        "the mesh covers {} instead of {}",
+        area,
+        expected
+    );
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn fewer_than_three_points_fail(
+        points in prop::collection::vec(point(-10.0..10.0), 0..3),
+        options in options(),
+    ) {
+        let result = triangulate_in_time(points.clone(), Vec::new(), options);
+        prop_assert!(
+            matches!(result, Err(CustomError::TooFewPoints(len)) if len == points.len()),
+            "{:?}",
+            result.map(|result| result.len())
+        );
+    }
+
+    #[test]
+    fn non_finite_coordinates_fail(
+        points in prop::collection::vec((maybe_non_finite_coordinate(), maybe_non_finite_coordinate()), 0..12),
+        hole in prop::collection::vec((maybe_non_finite_coordinate(), maybe_non_finite_coordinate()), 0..5),
+        options in options(),
+    ) {
+        let points: Vec<Vector> = points.iter().map(Vector::from).collect();
+        let holes: Vec<Vec<Vector>> = Some(hole.iter().map(Vector::from).collect())
+            .filter(|hole: &Vec<Vector>| !hole.is_empty())
+            .into_iter()
+            .collect();
+        let is_finite = |point: &Vector| point.x.is_finite() && point.y.is_finite();
+        let all_finite = points.iter().chain(holes.iter().flatten()).all(is_finite);
+        let result = triangulate_in_time(points, holes, options);
+        if !all_finite {
+            prop_assert!(
+                matches!(result, Err(CustomError::NonFinitePoint(point)) if !is_finite(&point)),
+                "{:?}",
+                result.map(|result| result.len())
+            );
+        }
+    }
+
+    #[test]
+    fn random_point_clouds(
+        points in prop::collection::vec(point(-1000.0..1000.0), 1..80),
+        options in options(),
+    ) {
+        let points = in_square(points, -1000., 1000.);
+        let result = expect_ok(triangulate_in_time(points.clone(), Vec::new(), options))?;
+        check_mesh(&result, &points, &[])?;
+    }
+
+    #[test]
+    fn degenerate_point_clouds(
+        points in prop::collection::vec(grid_point(), 1..40),
+        options in options(),
+    ) {
+        let points = in_square(points, 0., 8.);
+        match triangulate_in_time(points.clone(), Vec::new(), options) {
+            Ok(result) => check_mesh(&result, &points, &[])?,
+            // the corners are on the diagonal, so only points on it too fail
+            Err(CustomError::CollinearPoints) => {
+                prop_assert!(points.iter().all(|point| point.x == point.y));
+            }
+            Err(error) => prop_assert!(false, "{:?}", error),
+        }
+    }
+
+    #[test]
+    fn point_clouds_with_a_convex_hole(
+        inner_points in prop::collection::vec(point(-10.0..10.0), 0..40),
+        center in point(-4.0..4.0),
+        radius in 1.0f32..5.0,
+        angles in prop::collection::vec(0.0f32..std::f32::consts::TAU, 3..10),
+        options in options(),
+    ) {
+        let corners = [(-10., -10.), (10., -10.), (10., 10.), (-10., 10.)];
+        let points: Vec<Vector> = corners
+            .iter()
+            .map(Vector::from)
+            .chain(inner_points)
+            .collect();
+        let hole = convex_polygon(center, radius, angles);
+        prop_assume!(hole.len() >= 3);
+        let holes = vec![hole];
+        let result = expect_ok(triangulate_in_time(points.clone(), holes.clone(), options))?;
+        check_mesh(&result, &points, &holes)?;
+    }
+
+    #[test]
+    fn clockwise_holes_cut_out_the_outside_of_their_polygon(
+        center in point(-4.0..4.0),
+        corners in prop::collection::vec((0.0f32..1.0, 1.0f32..5.0), 4..10),
+        options in options(),
+    ) {
+        let points: Vec<Vector> = [(-10., -10.), (10., -10.), (10., 10.), (-10., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let mut hole = star_polygon(center, corners);
+        let (hull_area, hole_areas) = region_areas(&points, &[hole.clone()]);
+        hole.reverse();
+        // see validate_holes, only the inside of the polygon is left
+        let result = expect_ok(triangulate_in_time(points.clone(), vec![hole], options))?;
+        check_mesh_without(&result, &points, hull_area - hole_areas[0])?;
+    }
+
+    #[test]
+    fn overlapping_holes_remove_their_union(
+        min in point(-9.0..-1.0),
+        size in point(2.0..5.0),
+        offset in (0.1f32..0.9, 0.1f32..0.9),
+        options in options(),
+    ) {
+        let points: Vec<Vector> = [(-10., -10.), (10., -10.), (10., 10.), (-10., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let offset = Vector::new(size.x * offset.0, size.y * offset.1);
+        let holes = vec![
+            rectangle(min, min + size),
+            rectangle(min + offset, min + offset + size),
+        ];
+        let overlap = (size.x - offset.x) * (size.y - offset.y);
+        let result = expect_ok(triangulate_in_time(points.clone(), holes, options))?;
+        check_mesh_without(&result, &points, 2. * size.x * size.y - overlap)?;
+    }
+
+    #[test]
+    fn touching_holes(
+        min in point(-8.0..0.0),
+        sizes in (point(1.0..4.0), point(1.0..4.0)),
+        shares_an_edge in any::<bool>(),
+        options in options(),
+    ) {
+        let points: Vec<Vector> = [(-10., -10.), (10., -10.), (10., 10.), (-10., 10.)]
+            .iter()
+            .map(Vector::from)
+            .collect();
+        let (first_size, second_size) = sizes;
+        let first_max = min + first_size;
+        // the second hole starts at the right edge or at the upper right corner of the first one
+        let second_min = if shares_an_edge {
+            Vector::new(first_max.x, min.y)
+        } else {
+            first_max
+        };
+        let holes = vec![
+            rectangle(min, first_max),
+            rectangle(second_min, second_min + second_size),
+        ];
+        let result = expect_ok(triangulate_in_time(points.clone(), holes.clone(), options))?;
+        check_mesh(&result, &points, &holes)?;
+    }
+
+    #[test]
+    fn tesselated_point_clouds(
+        points in prop::collection::vec(point(0.0..100.0), 1..20),
+        area_fraction in 0.005f32..0.5,
+    ) {
+        let points = in_square(points, 0., 100.);
+        let (hull_area, _) = region_areas(&points, &[]);
+        prop_assume!(hull_area > 1.);
+        let options = TriangulateOptions::new().with_maximum_triangle_area(hull_area * area_fraction);
+        let result = expect_ok(triangulate_in_time(points.clone(), Vec::new(), options))?;
+        check_mesh(&result, &points, &[])?;
+    }
+}